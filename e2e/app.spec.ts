@@ -932,11 +932,13 @@ test.describe("Scroll-to-commit on tag click", () => {
             timestamp: Date.now() / 1000 - i * 3600,
             parent_hashes: i < 99 ? ['h' + String(i + 1).padStart(15, '0')] : [],
             column: 0,
-            lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: i === 0 || i === 99 ? 'from_above' : 'pass_through' }],
+            branch_id: 0,
+            lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: i === 0 || i === 99 ? 'from_above' : 'pass_through', branch_id: 0 }],
             refs: i === 0 ? [{ name: 'main', ref_type: 'branch', is_head: true }]
                  : i === 80 ? [{ name: 'v1.0.0', ref_type: 'tag', is_head: false }]
                  : [],
             is_tip: i === 0,
+            overflow: false,
           });
         }
         return out;
@@ -87,7 +87,10 @@ export const tauriMocks = `
               is_detached: false,
               remotes: [],
               head_hash: null,
-              repo_state: 'clean'
+              repo_state: 'clean',
+              upstream: null,
+              ahead: null,
+              behind: null
             };
           }
           return {
@@ -96,7 +99,10 @@ export const tauriMocks = `
             is_detached: false,
             remotes: ['origin'],
             head_hash: 'abc123def456789',
-            repo_state: window.__MOCK_REPO_STATE__ || 'clean'
+            repo_state: window.__MOCK_REPO_STATE__ || 'clean',
+            upstream: null,
+            ahead: null,
+            behind: null
           };
 
         case 'get_repository_info':
@@ -107,7 +113,10 @@ export const tauriMocks = `
               is_detached: false,
               remotes: [],
               head_hash: null,
-              repo_state: 'clean'
+              repo_state: 'clean',
+              upstream: null,
+              ahead: null,
+              behind: null
             };
           }
           return {
@@ -116,7 +125,10 @@ export const tauriMocks = `
             is_detached: false,
             remotes: ['origin'],
             head_hash: 'abc123def456789',
-            repo_state: window.__MOCK_REPO_STATE__ || 'clean'
+            repo_state: window.__MOCK_REPO_STATE__ || 'clean',
+            upstream: null,
+            ahead: null,
+            behind: null
           };
 
         case 'get_all_commit_graph':
@@ -133,11 +145,13 @@ export const tauriMocks = `
               timestamp: Date.now() / 1000,
               parent_hashes: [],
               column: 0,
+              branch_id: 0,
               lines: [
-                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above' }
+                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above', branch_id: 0 }
               ],
               refs: [{ name: 'main', ref_type: 'branch', is_head: true }],
-              is_tip: true
+              is_tip: true,
+              overflow: false
             },
             {
               hash: 'bbb222ccc333ddd',
@@ -148,13 +162,15 @@ export const tauriMocks = `
               timestamp: Date.now() / 1000 - 3600,
               parent_hashes: ['ccc333ddd444eee', 'fff666ggg777hhh'],
               column: 0,
+              branch_id: 0,
               lines: [
-                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above' },
-                { from_column: 0, to_column: 0, is_merge: false, line_type: 'to_parent' },
-                { from_column: 0, to_column: 1, is_merge: true, line_type: 'to_parent' }
+                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above', branch_id: 0 },
+                { from_column: 0, to_column: 0, is_merge: false, line_type: 'to_parent', branch_id: 0 },
+                { from_column: 0, to_column: 1, is_merge: true, line_type: 'to_parent', branch_id: 1 }
               ],
               refs: [],
-              is_tip: false
+              is_tip: false,
+              overflow: false
             },
             {
               hash: 'fff666ggg777hhh',
@@ -165,13 +181,15 @@ export const tauriMocks = `
               timestamp: Date.now() / 1000 - 5400,
               parent_hashes: ['ddd444eee555fff'],
               column: 1,
+              branch_id: 1,
               lines: [
-                { from_column: 0, to_column: 0, is_merge: false, line_type: 'pass_through' },
-                { from_column: 1, to_column: 1, is_merge: false, line_type: 'from_above' },
-                { from_column: 1, to_column: 1, is_merge: false, line_type: 'to_parent' }
+                { from_column: 0, to_column: 0, is_merge: false, line_type: 'pass_through', branch_id: 0 },
+                { from_column: 1, to_column: 1, is_merge: false, line_type: 'from_above', branch_id: 1 },
+                { from_column: 1, to_column: 1, is_merge: false, line_type: 'to_parent', branch_id: 1 }
               ],
               refs: [{ name: 'feature/test', ref_type: 'branch', is_head: false }],
-              is_tip: true
+              is_tip: true,
+              overflow: false
             },
             {
               hash: 'ccc333ddd444eee',
@@ -182,13 +200,15 @@ export const tauriMocks = `
               timestamp: Date.now() / 1000 - 7200,
               parent_hashes: ['ddd444eee555fff'],
               column: 0,
+              branch_id: 0,
               lines: [
-                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above' },
-                { from_column: 1, to_column: 1, is_merge: false, line_type: 'pass_through' },
-                { from_column: 0, to_column: 0, is_merge: false, line_type: 'to_parent' }
+                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above', branch_id: 0 },
+                { from_column: 1, to_column: 1, is_merge: false, line_type: 'pass_through', branch_id: 1 },
+                { from_column: 0, to_column: 0, is_merge: false, line_type: 'to_parent', branch_id: 0 }
               ],
               refs: [],
-              is_tip: false
+              is_tip: false,
+              overflow: false
             },
             {
               hash: 'ddd444eee555fff',
@@ -199,11 +219,13 @@ export const tauriMocks = `
               timestamp: Date.now() / 1000 - 10800,
               parent_hashes: [],
               column: 0,
+              branch_id: 0,
               lines: [
-                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above' }
+                { from_column: 0, to_column: 0, is_merge: false, line_type: 'from_above', branch_id: 0 }
               ],
               refs: [{ name: 'v1.0.0', ref_type: 'tag', is_head: false }],
-              is_tip: false
+              is_tip: false,
+              overflow: false
             }
           ];
 
@@ -417,7 +439,8 @@ export const tauriMocks = `
               is_annotated: true,
               message: 'First release',
               tagger_name: 'Test User',
-              tagger_time: Math.floor(Date.now() / 1000) - 604800,
+              tagger_email: 'test@example.com',
+              tagged_time: Math.floor(Date.now() / 1000) - 604800,
               last_commit_summary: 'Initial commit'
             },
             {
@@ -426,7 +449,8 @@ export const tauriMocks = `
               is_annotated: false,
               message: null,
               tagger_name: null,
-              tagger_time: null,
+              tagger_email: null,
+              tagged_time: null,
               last_commit_summary: 'Add feature'
             }
           ];
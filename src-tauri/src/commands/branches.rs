@@ -1,4 +1,4 @@
-use git2::{BranchType, Oid};
+use git2::{BranchType, Oid, Repository};
 use serde::Serialize;
 use tauri::State;
 
@@ -26,7 +26,9 @@ pub struct TagInfo {
     pub is_annotated: bool,
     pub message: Option<String>,
     pub tagger_name: Option<String>,
-    pub tagger_time: Option<i64>,
+    pub tagger_email: Option<String>,
+    /// When the annotated tag was created; `None` for lightweight tags.
+    pub tagged_time: Option<i64>,
     pub last_commit_summary: Option<String>,
 }
 
@@ -41,83 +43,197 @@ pub fn list_branches(state: State<AppState>) -> Result<Vec<BranchInfo>, AppError
         .and_then(|h| h.shorthand().ok().map(String::from));
 
     let mut branches = Vec::new();
-
     for branch_result in repo.branches(None)? {
         let (branch, branch_type) = branch_result?;
-        let name = branch.name()?.unwrap_or("").to_string();
-        let is_remote = matches!(branch_type, git2::BranchType::Remote);
-        let is_head = head_name.as_ref() == Some(&name) && !is_remote;
-
-        let tip = branch.get().peel_to_commit().ok();
-        let target_hash = tip.as_ref().map(|c| c.id().to_string()).unwrap_or_default();
-        let last_commit_summary = tip
-            .as_ref()
-            .and_then(|c| c.summary().ok().flatten().map(String::from));
-        let last_commit_author = tip
-            .as_ref()
-            .and_then(|c| c.author().name().ok().map(String::from));
-        let last_commit_time = tip.as_ref().map(|c| c.time().seconds());
-
-        // Upstream tracking + ahead/behind (local branches only)
-        let mut upstream = None;
-        let mut ahead = 0u32;
-        let mut behind = 0u32;
-        if !is_remote {
-            if let Ok(up) = branch.upstream() {
-                if let Ok(Some(up_name)) = up.name() {
-                    upstream = Some(up_name.to_string());
-                }
-                if let (Some(local_oid), Ok(up_oid)) =
-                    (tip.as_ref().map(|c| c.id()), up.get().peel_to_commit())
-                {
-                    if let Ok((a, b)) = repo.graph_ahead_behind(local_oid, up_oid.id()) {
-                        ahead = a.min(u32::MAX as usize) as u32;
-                        behind = b.min(u32::MAX as usize) as u32;
-                    }
+        branches.push(build_branch_info(
+            &repo,
+            &branch,
+            branch_type,
+            head_name.as_deref(),
+        )?);
+    }
+
+    Ok(branches)
+}
+
+fn build_branch_info(
+    repo: &Repository,
+    branch: &git2::Branch,
+    branch_type: BranchType,
+    head_name: Option<&str>,
+) -> Result<BranchInfo, AppError> {
+    let name = branch.name()?.unwrap_or("").to_string();
+    let is_remote = matches!(branch_type, git2::BranchType::Remote);
+    let is_head = head_name == Some(name.as_str()) && !is_remote;
+
+    let tip = branch.get().peel_to_commit().ok();
+    let target_hash = tip.as_ref().map(|c| c.id().to_string()).unwrap_or_default();
+    let last_commit_summary = tip
+        .as_ref()
+        .and_then(|c| c.summary().ok().flatten().map(String::from));
+    let last_commit_author = tip
+        .as_ref()
+        .and_then(|c| c.author().name().ok().map(String::from));
+    let last_commit_time = tip.as_ref().map(|c| c.time().seconds());
+
+    // Upstream tracking + ahead/behind (local branches only)
+    let mut upstream = None;
+    let mut ahead = 0u32;
+    let mut behind = 0u32;
+    if !is_remote {
+        if let Ok(up) = branch.upstream() {
+            if let Ok(Some(up_name)) = up.name() {
+                upstream = Some(up_name.to_string());
+            }
+            if let (Some(local_oid), Ok(up_oid)) =
+                (tip.as_ref().map(|c| c.id()), up.get().peel_to_commit())
+            {
+                if let Ok((a, b)) = repo.graph_ahead_behind(local_oid, up_oid.id()) {
+                    ahead = a.min(u32::MAX as usize) as u32;
+                    behind = b.min(u32::MAX as usize) as u32;
                 }
             }
         }
-
-        branches.push(BranchInfo {
-            name,
-            is_remote,
-            is_head,
-            target_hash,
-            upstream,
-            ahead,
-            behind,
-            last_commit_summary,
-            last_commit_author,
-            last_commit_time,
-        });
     }
 
-    Ok(branches)
+    Ok(BranchInfo {
+        name,
+        is_remote,
+        is_head,
+        target_hash,
+        upstream,
+        ahead,
+        behind,
+        last_commit_summary,
+        last_commit_author,
+        last_commit_time,
+    })
 }
 
+/// `force` discards uncommitted changes that are in the way; otherwise the
+/// checkout fails with `AppError::CheckoutConflict`.
 #[tauri::command]
-pub fn checkout_commit(hash: String, state: State<AppState>) -> Result<(), AppError> {
-    crate::log_cmd!("checkout_commit", hash = hash);
+pub fn checkout_commit(
+    hash: String,
+    force: Option<bool>,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!("checkout_commit", hash = hash, force = force);
     let repo = state.get_repo()?;
 
-    let oid = Oid::from_str(&hash)?;
+    let oid = crate::git::find_revision(&repo, &hash)?.id();
+    checkout_detached(&repo, oid, force.unwrap_or(false))
+}
+
+/// Check out commit `oid` and detach HEAD at it.
+pub fn checkout_detached(repo: &Repository, oid: Oid, force: bool) -> Result<(), AppError> {
     let commit = repo.find_commit(oid)?;
     let tree = commit.tree()?;
 
-    // Checkout the tree
-    repo.checkout_tree(tree.as_object(), None)?;
+    crate::git::repository::checkout_tree_checked(repo, &tree, force)?;
 
-    // Set HEAD to detached state pointing to this commit
-    repo.set_head_detached(oid)?;
+    // Detach HEAD at this commit
+    let message = checkout_reflog_message(repo, &oid.to_string());
+    repo.reference("HEAD", oid, true, &message)?;
 
     Ok(())
 }
 
+/// Reflog message in the git CLI's format: "checkout: moving from X to Y",
+/// where each side is a branch name or, when detached, a commit hash.
+fn checkout_reflog_message(repo: &Repository, to: &str) -> String {
+    let from = match repo.find_reference("HEAD") {
+        Ok(head) => match head.symbolic_target().ok().flatten() {
+            Some(target) => target.trim_start_matches("refs/heads/").to_string(),
+            None => head.target().map(|oid| oid.to_string()).unwrap_or_default(),
+        },
+        Err(_) => String::new(),
+    };
+    format!("checkout: moving from {from} to {to}")
+}
+
+/// Detached checkout of the commit HEAD moved to in HEAD reflog entry
+/// `index` (0 is the most recent). Returns the checked-out hash.
+pub fn checkout_head_reflog_entry(repo: &Repository, index: usize) -> Result<String, AppError> {
+    let reflog = repo.reflog("HEAD")?;
+    let entry = reflog.get(index).ok_or_else(|| {
+        AppError::InvalidArgument(format!(
+            "Reflog entry {index} out of range ({} entries)",
+            reflog.len()
+        ))
+    })?;
+    let oid = entry.id_new();
+    checkout_detached(repo, oid, false)?;
+    Ok(oid.to_string())
+}
+
+#[tauri::command]
+pub fn checkout_reflog_entry(index: usize, state: State<AppState>) -> Result<String, AppError> {
+    crate::log_cmd!("checkout_reflog_entry", index = index);
+    let repo = state.get_repo()?;
+    checkout_head_reflog_entry(&repo, index)
+}
+
+#[tauri::command]
+pub fn branches_containing(hash: String, state: State<AppState>) -> Result<Vec<String>, AppError> {
+    crate::log_cmd_debug!("branches_containing", hash = hash);
+    let repo = state.get_repo()?;
+    find_branches_containing(&repo, &hash)
+}
+
+/// Names of the local and remote-tracking branches whose tip is `hash` or
+/// a descendant of it, e.g. to offer a branch to reattach a detached HEAD
+/// to. Symbolic refs such as `origin/HEAD` are skipped.
+pub fn find_branches_containing(repo: &Repository, hash: &str) -> Result<Vec<String>, AppError> {
+    let target = crate::git::find_revision(repo, hash)?.id();
+    let mut names = Vec::new();
+    for branch_result in repo.branches(None)? {
+        let (branch, _) = branch_result?;
+        if branch.get().symbolic_target().ok().flatten().is_some() {
+            continue;
+        }
+        let Some(tip) = branch.get().target() else {
+            continue;
+        };
+        if tip == target || repo.graph_descendant_of(tip, target)? {
+            if let Some(name) = branch.name()? {
+                names.push(name.to_string());
+            }
+        }
+    }
+    Ok(names)
+}
+
 #[tauri::command]
-pub fn list_tags(state: State<AppState>) -> Result<Vec<TagInfo>, AppError> {
-    crate::log_cmd_debug!("list_tags");
+pub fn count_commits_between(
+    base: String,
+    head: String,
+    state: State<AppState>,
+) -> Result<(usize, usize), AppError> {
+    crate::log_cmd_debug!("count_commits_between", base = base, head = head);
     let repo = state.get_repo()?;
+    ahead_behind(&repo, &base, &head)
+}
+
+/// `(ahead, behind)`: commits reachable from `head` but not `base`, and
+/// the reverse. Both are any revision `find_revision` accepts.
+pub fn ahead_behind(repo: &Repository, base: &str, head: &str) -> Result<(usize, usize), AppError> {
+    let base = crate::git::find_revision(repo, base)?.id();
+    let head = crate::git::find_revision(repo, head)?.id();
+    Ok(repo.graph_ahead_behind(head, base)?)
+}
 
+#[tauri::command]
+pub fn list_tags(sort: Option<String>, state: State<AppState>) -> Result<Vec<TagInfo>, AppError> {
+    crate::log_cmd_debug!("list_tags", sort = sort);
+    let repo = state.get_repo()?;
+
+    collect_tags(&repo, sort.as_deref())
+}
+
+/// All tags, sorted by `sort`: `"name"` (the default) or `"tagged_time"`,
+/// newest first with lightweight tags (which have no tag date) last.
+pub fn collect_tags(repo: &Repository, sort: Option<&str>) -> Result<Vec<TagInfo>, AppError> {
     let mut tags = Vec::new();
 
     repo.tag_foreach(|oid, name_bytes| {
@@ -125,70 +241,194 @@ pub fn list_tags(state: State<AppState>) -> Result<Vec<TagInfo>, AppError> {
             .trim_start_matches("refs/tags/")
             .to_string();
 
-        if let Ok(obj) = repo.find_object(oid, None) {
-            let (target_hash, is_annotated, message, tagger_name, tagger_time) =
-                if let Some(tag) = obj.as_tag() {
-                    let target = tag.target_id().to_string();
-                    let msg = tag.message().ok().flatten().map(|s: &str| s.to_string());
-                    let (t_name, t_time) = tag
-                        .tagger()
-                        .map(|sig| {
-                            (
-                                sig.name().ok().map(String::from),
-                                Some(sig.when().seconds()),
-                            )
-                        })
-                        .unwrap_or((None, None));
-                    (target, true, msg, t_name, t_time)
-                } else {
-                    (oid.to_string(), false, None, None, None)
-                };
-
-            // Last commit summary for the commit the tag points to.
-            let last_commit_summary = Oid::from_str(&target_hash)
-                .ok()
-                .and_then(|o| repo.find_commit(o).ok())
-                .and_then(|c| c.summary().ok().flatten().map(String::from));
-
-            tags.push(TagInfo {
-                name,
-                target_hash,
-                is_annotated,
-                message,
-                tagger_name,
-                tagger_time,
-                last_commit_summary,
-            });
+        if let Some(info) = build_tag_info(repo, oid, name) {
+            tags.push(info);
         }
 
         true
     })?;
 
-    // Sort tags alphabetically
+    // Name order first so equal tag dates fall back to it.
     tags.sort_by(|a, b| a.name.cmp(&b.name));
+    match sort.unwrap_or("name") {
+        "name" => {}
+        "tagged_time" => tags.sort_by_key(|t| std::cmp::Reverse(t.tagged_time)),
+        other => {
+            return Err(AppError::InvalidArgument(format!(
+                "Unknown tag sort: {other}"
+            )));
+        }
+    }
 
     Ok(tags)
 }
 
+/// Build a `TagInfo` for the tag object (or, for lightweight tags, the
+/// commit) at `oid`. Returns `None` if the object can't be found.
+fn build_tag_info(repo: &Repository, oid: Oid, name: String) -> Option<TagInfo> {
+    let obj = repo.find_object(oid, None).ok()?;
+    let (target_hash, is_annotated, message, tagger_name, tagger_email, tagged_time) =
+        if let Some(tag) = obj.as_tag() {
+            let target = tag.target_id().to_string();
+            let msg = tag.message().ok().flatten().map(|s: &str| s.to_string());
+            let (t_name, t_email, t_time) = tag
+                .tagger()
+                .map(|sig| {
+                    (
+                        sig.name().ok().map(String::from),
+                        sig.email().ok().map(String::from),
+                        Some(sig.when().seconds()),
+                    )
+                })
+                .unwrap_or((None, None, None));
+            (target, true, msg, t_name, t_email, t_time)
+        } else {
+            (oid.to_string(), false, None, None, None, None)
+        };
+
+    // Last commit summary for the commit the tag points to.
+    let last_commit_summary = Oid::from_str(&target_hash)
+        .ok()
+        .and_then(|o| repo.find_commit(o).ok())
+        .and_then(|c| c.summary().ok().flatten().map(String::from));
+
+    Some(TagInfo {
+        name,
+        target_hash,
+        is_annotated,
+        message,
+        tagger_name,
+        tagger_email,
+        tagged_time,
+        last_commit_summary,
+    })
+}
+
+/// Create a tag on `target` (a commit hash or ref name). With a `message`
+/// the tag is annotated and tagged by the repository's configured identity;
+/// without one it is lightweight.
+pub fn create_tag_at(
+    repo: &Repository,
+    name: &str,
+    target: &str,
+    message: Option<&str>,
+) -> Result<TagInfo, AppError> {
+    let commit = repo.revparse_single(target)?.peel_to_commit()?;
+
+    let oid = match message {
+        Some(msg) => {
+            let sig = repo.signature()?;
+            repo.tag(name, commit.as_object(), &sig, msg, false)?
+        }
+        None => repo.tag_lightweight(name, commit.as_object(), false)?,
+    };
+
+    build_tag_info(repo, oid, name.to_string())
+        .ok_or_else(|| AppError::Internal(format!("Created tag '{name}' could not be read back")))
+}
+
+#[tauri::command]
+pub fn create_tag(
+    name: String,
+    target: String,
+    message: Option<String>,
+    state: State<AppState>,
+) -> Result<TagInfo, AppError> {
+    crate::log_cmd!(
+        "create_tag",
+        name = name,
+        target = target,
+        annotated = message.is_some()
+    );
+    let repo = state.get_repo()?;
+    create_tag_at(&repo, &name, &target, message.as_deref())
+}
+
+/// Point the existing tag `name` at `target` instead, e.g. to move a release
+/// candidate tag forward. An annotated tag gets a new tag object, keeping its
+/// message unless `message` replaces it; a lightweight tag stays lightweight
+/// unless a `message` is given. Overwriting needs `force`, as with
+/// `git tag -f`.
+pub fn move_tag_to(
+    repo: &Repository,
+    name: &str,
+    target: &str,
+    message: Option<&str>,
+    force: bool,
+) -> Result<TagInfo, AppError> {
+    let existing = repo.find_reference(&format!("refs/tags/{name}"))?;
+    if !force {
+        return Err(git2::Error::new(
+            git2::ErrorCode::Exists,
+            git2::ErrorClass::Tag,
+            format!("tag '{name}' already exists; moving it requires force"),
+        )
+        .into());
+    }
+    let old_message = existing
+        .peel_to_tag()
+        .ok()
+        .map(|tag| tag.message().ok().flatten().unwrap_or_default().to_string());
+    let commit = crate::git::find_revision(repo, target)?;
+
+    let oid = match message.map(String::from).or(old_message) {
+        Some(msg) => {
+            let sig = repo.signature()?;
+            repo.tag(name, commit.as_object(), &sig, &msg, true)?
+        }
+        None => repo.tag_lightweight(name, commit.as_object(), true)?,
+    };
+
+    build_tag_info(repo, oid, name.to_string())
+        .ok_or_else(|| AppError::Internal(format!("Moved tag '{name}' could not be read back")))
+}
+
 #[tauri::command]
-pub fn checkout_branch(branch_name: String, state: State<AppState>) -> Result<(), AppError> {
-    crate::log_cmd!("checkout_branch", branch = branch_name);
+pub fn move_tag(
+    name: String,
+    target: String,
+    force: bool,
+    message: Option<String>,
+    state: State<AppState>,
+) -> Result<TagInfo, AppError> {
+    crate::log_cmd!("move_tag", name = name, target = target, force = force);
     let repo = state.get_repo()?;
+    move_tag_to(&repo, &name, &target, message.as_deref(), force)
+}
 
+/// `force` as for `checkout_commit`.
+#[tauri::command]
+pub fn checkout_branch(
+    branch_name: String,
+    force: Option<bool>,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!("checkout_branch", branch = branch_name, force = force);
+    let repo = state.get_repo()?;
+
+    checkout_local_branch(&repo, &branch_name, force.unwrap_or(false))
+}
+
+/// Check out the tree of a local branch and point HEAD at it.
+pub fn checkout_local_branch(
+    repo: &Repository,
+    branch_name: &str,
+    force: bool,
+) -> Result<(), AppError> {
     // Find the branch
-    let branch = repo.find_branch(&branch_name, BranchType::Local)?;
+    let branch = repo.find_branch(branch_name, BranchType::Local)?;
     let reference = branch.get();
     let commit = reference.peel_to_commit()?;
     let tree = commit.tree()?;
 
-    // Checkout the tree
-    repo.checkout_tree(tree.as_object(), None)?;
+    crate::git::repository::checkout_tree_checked(repo, &tree, force)?;
 
     // Set HEAD to point to the branch
     let refname = reference
         .name()
         .map_err(|_| AppError::Git(git2::Error::from_str("Invalid branch reference name")))?;
-    repo.set_head(refname)?;
+    let message = checkout_reflog_message(repo, branch_name);
+    repo.reference_symbolic("HEAD", refname, true, &message)?;
 
     Ok(())
 }
@@ -207,19 +447,94 @@ pub fn create_branch_and_checkout(
     repo.branch(&branch_name, &head_commit, false)?;
 
     // Check out the newly created branch (same pattern as checkout_branch).
-    let branch = repo.find_branch(&branch_name, BranchType::Local)?;
-    let reference = branch.get();
-    let commit = reference.peel_to_commit()?;
-    let tree = commit.tree()?;
-    repo.checkout_tree(tree.as_object(), None)?;
-    let refname = reference
-        .name()
-        .map_err(|_| AppError::Git(git2::Error::from_str("Invalid branch reference name")))?;
-    repo.set_head(refname)?;
+    checkout_local_branch(&repo, &branch_name, false)
+}
+
+/// Create a local branch at `start_point` (a commit hash or ref name,
+/// defaulting to HEAD) and optionally check it out.
+pub fn create_local_branch(
+    repo: &Repository,
+    name: &str,
+    start_point: Option<&str>,
+    checkout: bool,
+) -> Result<BranchInfo, AppError> {
+    // Same leading-dash rule as `validate_branch_name`.
+    if name.starts_with('-') || !git2::Branch::name_is_valid(name)? {
+        return Err(AppError::InvalidBranchName(name.to_string()));
+    }
+
+    let start_commit = match start_point {
+        Some(spec) => repo.revparse_single(spec)?.peel_to_commit()?,
+        None => repo.head()?.peel_to_commit()?,
+    };
+    // An existing branch fails here with `ErrorCode::Exists`.
+    let mut branch = repo.branch(name, &start_commit, false)?;
+
+    if checkout {
+        if let Err(e) = checkout_local_branch(repo, name, false) {
+            // Don't leave a branch behind for a create that didn't happen.
+            branch.delete()?;
+            return Err(e);
+        }
+    }
+
+    let head_name = repo
+        .head()
+        .ok()
+        .and_then(|h| h.shorthand().ok().map(String::from));
+    let branch = repo.find_branch(name, BranchType::Local)?;
+    build_branch_info(repo, &branch, BranchType::Local, head_name.as_deref())
+}
+
+#[tauri::command]
+pub fn create_branch(
+    name: String,
+    start_point: Option<String>,
+    checkout: bool,
+    state: State<AppState>,
+) -> Result<BranchInfo, AppError> {
+    crate::log_cmd!(
+        "create_branch",
+        name = name,
+        start_point = start_point,
+        checkout = checkout
+    );
+    let repo = state.get_repo()?;
+    create_local_branch(&repo, &name, start_point.as_deref(), checkout)
+}
 
+/// Point local branch `branch_name` at remote-tracking branch `upstream`
+/// (e.g. `origin/main`), or clear its upstream when `None`.
+pub fn set_branch_upstream(
+    repo: &Repository,
+    branch_name: &str,
+    upstream: Option<&str>,
+) -> Result<(), AppError> {
+    let mut branch = repo.find_branch(branch_name, BranchType::Local)?;
+    if let Some(upstream) = upstream {
+        if repo.find_branch(upstream, BranchType::Remote).is_err() {
+            return Err(AppError::InvalidUpstream(upstream.to_string()));
+        }
+    }
+    branch.set_upstream(upstream)?;
     Ok(())
 }
 
+#[tauri::command]
+pub fn set_upstream(
+    branch_name: String,
+    upstream: Option<String>,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!(
+        "set_upstream",
+        branch_name = branch_name,
+        upstream = upstream
+    );
+    let repo = state.get_repo()?;
+    set_branch_upstream(&repo, &branch_name, upstream.as_deref())
+}
+
 #[tauri::command]
 pub fn validate_branch_name(name: String) -> Result<(), AppError> {
     crate::log_cmd!("validate_branch_name", name = name);
@@ -317,7 +632,6 @@ pub fn delete_tag(tag_name: String, state: State<AppState>) -> Result<(), AppErr
 mod tests {
     use super::*;
     use crate::state::AppState;
-    use git2::Repository;
 
     #[test]
     fn test_no_repository_error() {
@@ -172,9 +172,7 @@ pub fn delete_branches_bulk(repo: &Repository, names: &[String]) -> Vec<BulkResu
 ///
 /// Returns the list of remote refs that were pruned.
 pub fn run_remote_prune(repo: &Repository, remote: &str) -> Result<Vec<String>, AppError> {
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| AppError::InvalidPath("No working directory".into()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
 
     let output = std::process::Command::new("git")
         .args(["remote", "prune", remote])
@@ -271,9 +269,7 @@ pub fn find_untracked_files(repo: &Repository) -> Result<Vec<String>, AppError>
 /// inside the repo workdir before removal — paths attempting to escape are
 /// rejected.
 pub fn clean_untracked(repo: &Repository, paths: &[String]) -> Result<Vec<BulkResult>, AppError> {
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| AppError::InvalidPath("No working directory".into()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     let workdir_canon = workdir
         .canonicalize()
         .map_err(|e| AppError::Internal(format!("Failed to canonicalize workdir: {}", e)))?;
@@ -1,44 +1,74 @@
-use git2::Signature;
 use tauri::State;
 
 use crate::error::AppError;
+use crate::git;
 use crate::state::AppState;
 
+// Flat args map straight onto the frontend's invoke() payload.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
-pub fn create_commit(message: String, state: State<AppState>) -> Result<String, AppError> {
-    crate::log_cmd!("create_commit", msg_len = message.len());
+pub fn create_commit(
+    message: String,
+    author_name: Option<String>,
+    author_email: Option<String>,
+    author_time: Option<i64>,
+    sign_commits: Option<bool>,
+    strict: Option<bool>,
+    skip_hooks: Option<bool>,
+    allow_empty: Option<bool>,
+    state: State<AppState>,
+) -> Result<String, AppError> {
+    crate::log_cmd!(
+        "create_commit",
+        msg_len = message.len(),
+        author_override = author_name.is_some() || author_email.is_some() || author_time.is_some(),
+        sign_commits = sign_commits,
+        strict = strict,
+        skip_hooks = skip_hooks,
+        allow_empty = allow_empty
+    );
+    if strict.unwrap_or(false) {
+        git::ensure_valid_commit_message(&message)?;
+    }
     let repo = state.get_repo()?;
 
-    // Get the signature from git config
-    let signature = repo
-        .signature()
-        .unwrap_or_else(|_| Signature::now("Unknown", "unknown@example.com").unwrap());
-
-    // Get the index and write it as a tree
-    let mut index = repo.index()?;
-    let tree_oid = index.write_tree()?;
-    let tree = repo.find_tree(tree_oid)?;
-
-    // Get the parent commit (HEAD)
-    let parent = if let Ok(head) = repo.head() {
-        Some(head.peel_to_commit()?)
-    } else {
-        None
+    let author = git::AuthorOverride {
+        name: author_name,
+        email: author_email,
+        time: author_time,
     };
+    git::create_commit(
+        &repo,
+        &message,
+        &author,
+        sign_commits,
+        skip_hooks.unwrap_or(false),
+        allow_empty.unwrap_or(false),
+    )
+}
 
-    let parents: Vec<&git2::Commit> = parent.iter().collect();
+#[tauri::command]
+pub fn validate_commit_message(message: String) -> Vec<git::CommitMessageIssue> {
+    crate::log_cmd_debug!("validate_commit_message", msg_len = message.len());
+    git::validate_commit_message(&message)
+}
 
-    // Create the commit
-    let commit_oid = repo.commit(
-        Some("HEAD"),
-        &signature,
-        &signature,
-        &message,
-        &tree,
-        &parents,
-    )?;
+#[tauri::command]
+pub fn amend_commit(message: Option<String>, state: State<AppState>) -> Result<String, AppError> {
+    crate::log_cmd!("amend_commit", msg_len = message.as_ref().map(|m| m.len()));
+    let repo = state.get_repo()?;
+    git::amend_commit(&repo, message.as_deref())
+}
 
-    Ok(commit_oid.to_string())
+#[tauri::command]
+pub fn squash_commits(
+    count: usize,
+    new_message: String,
+    state: State<AppState>,
+) -> Result<String, AppError> {
+    crate::log_cmd!("squash_commits", count = count, msg_len = new_message.len());
+    let repo = state.get_repo()?;
+    git::squash_commits(&repo, count, &new_message)
 }
 
 #[cfg(test)]
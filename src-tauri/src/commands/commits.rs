@@ -6,19 +6,26 @@ use crate::state::AppState;
 
 #[tauri::command]
 pub async fn get_all_commit_graph(
+    refs: Option<Vec<String>>,
+    max_columns: Option<usize>,
     state: State<'_, AppState>,
 ) -> Result<Vec<git::GraphCommit>, AppError> {
-    crate::log_cmd_debug!("get_all_commit_graph");
+    crate::log_cmd_debug!(
+        "get_all_commit_graph",
+        refs = refs,
+        max_columns = max_columns
+    );
     // Clone the Arc so the blocking work can own the handle and run off the
     // async runtime without holding the mutex across an .await point.
     let repository = state.repository.clone();
+    let refs_cache = state.refs_cache.clone();
     tokio::task::spawn_blocking(move || {
         let guard = repository.lock();
         let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
 
-        let commits = git::get_all_commits(repo)?;
-        let refs = git::collect_refs(repo)?;
-        let graph = git::build_commit_graph(commits, refs);
+        let commits = git::get_all_commits(repo, refs.as_deref())?;
+        let refs = git::collect_refs_cached(repo, &mut refs_cache.lock())?;
+        let graph = git::build_commit_graph(commits, refs, max_columns.unwrap_or(usize::MAX));
 
         Ok(graph)
     })
@@ -26,6 +33,168 @@ pub async fn get_all_commit_graph(
     .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
 }
 
+/// Same layout as `get_all_commit_graph`, flattened to one edge per parent
+/// link. Edge rows index into `get_all_commit_graph`'s result.
+#[tauri::command]
+pub async fn get_commit_graph_edges(
+    refs: Option<Vec<String>>,
+    max_columns: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<git::GraphEdge>, AppError> {
+    crate::log_cmd_debug!(
+        "get_commit_graph_edges",
+        refs = refs,
+        max_columns = max_columns
+    );
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+
+        let commits = git::get_all_commits(repo, refs.as_deref())?;
+        Ok(git::build_commit_graph_edges(
+            commits,
+            max_columns.unwrap_or(usize::MAX),
+        ))
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+#[tauri::command]
+pub fn refs_at_commit(hash: String, state: State<AppState>) -> Result<Vec<git::RefInfo>, AppError> {
+    crate::log_cmd_debug!("refs_at_commit", hash = hash);
+    let repo = state.get_repo()?;
+
+    git::refs_at_commit(&repo, &hash)
+}
+
+#[tauri::command]
+pub async fn get_commits(
+    skip: usize,
+    limit: usize,
+    refs: Option<Vec<String>>,
+    author_filter: Option<String>,
+    operation_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<git::CommitInfo>, AppError> {
+    crate::log_cmd_debug!(
+        "get_commits",
+        skip = skip,
+        limit = limit,
+        refs = refs,
+        author_filter = author_filter,
+        operation_id = operation_id
+    );
+    let operation = state.begin_operation(operation_id);
+    let cancel = operation.token();
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+
+        git::get_commits_cancellable(
+            repo,
+            skip,
+            limit,
+            refs.as_deref(),
+            author_filter.as_deref(),
+            &cancel,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+#[tauri::command]
+pub async fn search_commits(
+    query: String,
+    skip: usize,
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<git::CommitInfo>, AppError> {
+    crate::log_cmd_debug!("search_commits", query = query, skip = skip, limit = limit);
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+
+        git::search_commits(repo, &query, skip, limit)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+#[tauri::command]
+pub async fn find_commit_in_graph(
+    prefix: String,
+    state: State<'_, AppState>,
+) -> Result<git::CommitLocation, AppError> {
+    crate::log_cmd_debug!("find_commit_in_graph", prefix = prefix);
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+
+        git::find_commit_in_graph(repo, &prefix)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+#[tauri::command]
+pub async fn get_commit_graph_page(
+    skip: usize,
+    limit: usize,
+    refs: Option<Vec<String>>,
+    max_columns: Option<usize>,
+    state: State<'_, AppState>,
+) -> Result<Vec<git::GraphCommit>, AppError> {
+    crate::log_cmd_debug!(
+        "get_commit_graph_page",
+        skip = skip,
+        limit = limit,
+        refs = refs,
+        max_columns = max_columns
+    );
+    let repository = state.repository.clone();
+    let refs_cache = state.refs_cache.clone();
+    let graph_page_cache = state.graph_page_cache.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+
+        git::get_commit_graph_page_cached(
+            repo,
+            skip,
+            limit,
+            refs.as_deref(),
+            max_columns.unwrap_or(usize::MAX),
+            &mut refs_cache.lock(),
+            &mut graph_page_cache.lock(),
+        )
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+/// Rebuild the graph's branch and tag labels. The cache already notices refs
+/// moving; this is for when the frontend wants to be sure.
+#[tauri::command]
+pub fn refresh_refs(state: State<AppState>) -> Result<(), AppError> {
+    crate::log_cmd_debug!("refresh_refs");
+    let repo = state.get_repo()?;
+    git::refresh_refs(&repo, &mut state.refs_cache.lock())
+}
+
+#[tauri::command]
+pub fn resolve_revision(rev: String, state: State<AppState>) -> Result<String, AppError> {
+    crate::log_cmd_debug!("resolve_revision", rev = rev);
+    let repo = state.get_repo()?;
+
+    git::resolve_revision(&repo, &rev)
+}
+
 #[tauri::command]
 pub fn get_commit_details(
     hash: String,
@@ -37,6 +206,18 @@ pub fn get_commit_details(
     git::get_commit_details(&repo, &hash)
 }
 
+#[tauri::command]
+pub fn get_branch_diff_files(
+    base: String,
+    head: String,
+    state: State<AppState>,
+) -> Result<Vec<git::CommitFileChange>, AppError> {
+    crate::log_cmd_debug!("get_branch_diff_files", base = base, head = head);
+    let repo = state.get_repo()?;
+
+    git::get_branch_diff_files(&repo, &base, &head)
+}
+
 #[tauri::command]
 pub fn get_commit_file_diff(
     hash: String,
@@ -44,9 +225,52 @@ pub fn get_commit_file_diff(
     state: State<AppState>,
 ) -> Result<git::FileDiff, AppError> {
     crate::log_cmd_debug!("get_commit_file_diff", hash = hash, file = file_path);
+    let config = *state.diff_config.lock();
     let repo = state.get_repo()?;
 
-    git::get_commit_file_diff(&repo, &hash, &file_path)
+    git::get_commit_file_diff_with_config(&repo, &hash, &file_path, &config)
+}
+
+/// Every file's diff for `hash`, sharing one `DiffConfig` byte budget.
+#[tauri::command]
+pub async fn get_commit_diff(
+    hash: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<git::FileDiff>, AppError> {
+    crate::log_cmd_debug!("get_commit_diff", hash = hash);
+    let config = *state.diff_config.lock();
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::get_commit_diff_with_config(repo, &hash, &config)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+#[tauri::command]
+pub fn get_file_at_commit(
+    hash: String,
+    path: String,
+    state: State<AppState>,
+) -> Result<git::FileContent, AppError> {
+    crate::log_cmd_debug!("get_file_at_commit", hash = hash, path = path);
+    let repo = state.get_repo()?;
+
+    git::get_file_at_commit(&repo, &hash, &path)
+}
+
+#[tauri::command]
+pub fn list_tree(
+    hash: String,
+    dir: Option<String>,
+    state: State<AppState>,
+) -> Result<Vec<git::TreeEntryInfo>, AppError> {
+    crate::log_cmd_debug!("list_tree", hash = hash, dir = dir);
+    let repo = state.get_repo()?;
+
+    git::list_tree(&repo, &hash, dir.as_deref())
 }
 
 #[tauri::command]
@@ -67,6 +291,51 @@ pub fn get_commit_diff_hunk(
     git::get_commit_diff_hunk(&repo, &hash, &file_path, hunk_index)
 }
 
+#[tauri::command]
+pub fn reset_to_commit(hash: String, mode: String, state: State<AppState>) -> Result<(), AppError> {
+    crate::log_cmd!("reset_to_commit", hash = hash, mode = mode);
+    let mode = git::ResetMode::parse(&mode)?;
+    let repo = state.get_repo()?;
+
+    git::reset_to_commit(&repo, &hash, mode)
+}
+
+#[tauri::command]
+pub async fn get_file_history(
+    path: String,
+    skip: usize,
+    limit: usize,
+    state: State<'_, AppState>,
+) -> Result<Vec<git::CommitInfo>, AppError> {
+    crate::log_cmd_debug!("get_file_history", path = path, skip = skip, limit = limit);
+    // Diffs every commit on the walk — keep it off the async runtime.
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::get_file_history(repo, &path, skip, limit)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+#[tauri::command]
+pub async fn get_blame(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<git::BlameLine>, AppError> {
+    crate::log_cmd_debug!("get_blame", path = path);
+    // Blame walks history for every line — keep it off the async runtime.
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::get_blame(repo, &path)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
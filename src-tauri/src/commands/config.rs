@@ -0,0 +1,43 @@
+use tauri::State;
+
+use crate::error::AppError;
+use crate::git;
+use crate::state::AppState;
+
+#[tauri::command]
+pub fn get_config(key: String, state: State<AppState>) -> Result<Option<String>, AppError> {
+    crate::log_cmd_debug!("get_config", key = key);
+    let repo = state.get_repo()?;
+    git::get_config(&repo, &key)
+}
+
+#[tauri::command]
+pub fn set_config(
+    key: String,
+    value: String,
+    scope: String,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!("set_config", key = key, scope = scope);
+    let scope = git::ConfigScope::parse(&scope)?;
+    let repo = state.get_repo()?;
+    git::set_config(&repo, &key, &value, scope)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+    use git2::Repository;
+
+    #[test]
+    fn test_no_repository_error() {
+        let state = AppState::new();
+
+        let repo_lock = state.repository.lock();
+        let result: Result<&Repository, AppError> =
+            repo_lock.as_ref().ok_or(AppError::NoRepository);
+
+        assert!(result.is_err());
+    }
+}
@@ -4,12 +4,19 @@ use crate::error::AppError;
 use crate::git;
 use crate::state::AppState;
 
+/// Without `is_untracked`, an unstaged diff of an untracked path is still
+/// routed to the untracked reader, so its size limits apply. With
+/// `operation_id`, `cancel_operation` can abandon a long diff.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn get_file_diff(
     path: String,
     staged: bool,
     is_untracked: Option<bool>,
     is_conflicted: Option<bool>,
+    ignore_whitespace: Option<bool>,
+    context_lines: Option<u32>,
+    operation_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<git::FileDiff, AppError> {
     crate::log_cmd_debug!(
@@ -17,8 +24,14 @@ pub async fn get_file_diff(
         path = path,
         staged = staged,
         is_untracked = is_untracked,
-        is_conflicted = is_conflicted
+        is_conflicted = is_conflicted,
+        ignore_whitespace = ignore_whitespace,
+        context_lines = context_lines,
+        operation_id = operation_id
     );
+    let config = *state.diff_config.lock();
+    let operation = state.begin_operation(operation_id);
+    let cancel = operation.token();
     let repository = state.repository.clone();
     tokio::task::spawn_blocking(move || {
         let guard = repository.lock();
@@ -29,16 +42,113 @@ pub async fn get_file_diff(
         }
 
         // For untracked files, read the file directly
-        if is_untracked.unwrap_or(false) {
-            return git::get_untracked_file_diff(repo, &path);
+        let is_untracked = match is_untracked {
+            Some(flag) => flag,
+            None => !staged && git::is_untracked(repo, &path)?,
+        };
+        if is_untracked {
+            return git::get_untracked_file_diff_with_config(repo, &path, &config);
         }
 
-        git::get_file_diff(repo, &path, staged)
+        git::get_file_diff_cancellable(
+            repo,
+            &path,
+            staged,
+            &config,
+            ignore_whitespace.unwrap_or(false),
+            context_lines,
+            &cancel,
+        )
     })
     .await
     .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
 }
 
+/// Raw unified diff of `path`, for copying or feeding to `git apply`.
+#[tauri::command]
+pub fn get_file_patch(
+    path: String,
+    staged: bool,
+    state: State<AppState>,
+) -> Result<String, AppError> {
+    crate::log_cmd_debug!("get_file_patch", path = path, staged = staged);
+    let repo = state.get_repo()?;
+    git::get_file_patch(&repo, &path, staged)
+}
+
+#[tauri::command]
+pub async fn get_untracked_file_diff(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<git::FileDiff, AppError> {
+    crate::log_cmd_debug!("get_untracked_file_diff", path = path);
+    let config = *state.diff_config.lock();
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::get_untracked_file_diff_with_config(repo, &path, &config)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+/// Staged and unstaged diffs for `path` in one call, so the two halves can't
+/// straddle an index update.
+#[tauri::command]
+pub async fn get_combined_file_diff(
+    path: String,
+    ignore_whitespace: Option<bool>,
+    context_lines: Option<u32>,
+    state: State<'_, AppState>,
+) -> Result<git::CombinedFileDiff, AppError> {
+    crate::log_cmd_debug!(
+        "get_combined_file_diff",
+        path = path,
+        ignore_whitespace = ignore_whitespace,
+        context_lines = context_lines
+    );
+    let config = *state.diff_config.lock();
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::get_combined_file_diff(
+            repo,
+            &path,
+            &config,
+            ignore_whitespace.unwrap_or(false),
+            context_lines,
+        )
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+/// Base64 before/after bytes of an image, for a visual diff. `hash` selects a
+/// commit; without it, HEAD is compared against the working tree.
+#[tauri::command]
+pub async fn get_image_blobs(
+    hash: Option<String>,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<git::ImageBlobs, AppError> {
+    crate::log_cmd_debug!("get_image_blobs", hash = hash, path = path);
+    let config = *state.diff_config.lock();
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::get_image_blobs(repo, hash.as_deref(), &path, &config)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
+/// `ignore_whitespace` and `context_lines` must match the `get_file_diff`
+/// call the hunk index came from.
+// Flat args map straight onto the frontend's invoke() payload.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub fn get_diff_hunk(
     path: String,
@@ -46,6 +156,8 @@ pub fn get_diff_hunk(
     hunk_index: usize,
     is_untracked: Option<bool>,
     is_conflicted: Option<bool>,
+    ignore_whitespace: Option<bool>,
+    context_lines: Option<u32>,
     state: State<AppState>,
 ) -> Result<git::DiffHunk, AppError> {
     crate::log_cmd_debug!(
@@ -54,7 +166,9 @@ pub fn get_diff_hunk(
         staged = staged,
         hunk = hunk_index,
         is_untracked = is_untracked,
-        is_conflicted = is_conflicted
+        is_conflicted = is_conflicted,
+        ignore_whitespace = ignore_whitespace,
+        context_lines = context_lines
     );
     let repo = state.get_repo()?;
 
@@ -62,11 +176,41 @@ pub fn get_diff_hunk(
         return git::get_conflicted_diff_hunk(&repo, &path, hunk_index);
     }
 
-    if is_untracked.unwrap_or(false) {
+    let is_untracked = match is_untracked {
+        Some(flag) => flag,
+        None => !staged && git::is_untracked(&repo, &path)?,
+    };
+    if is_untracked {
         return git::get_untracked_diff_hunk(&repo, &path, hunk_index);
     }
 
-    git::get_diff_hunk(&repo, &path, staged, hunk_index)
+    git::get_diff_hunk(
+        &repo,
+        &path,
+        staged,
+        hunk_index,
+        ignore_whitespace.unwrap_or(false),
+        context_lines,
+    )
+}
+
+/// Override the diff size limits for the rest of the session.
+#[tauri::command]
+pub fn set_diff_limits(
+    max_diff_bytes: usize,
+    max_file_size: u64,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!(
+        "set_diff_limits",
+        max_diff_bytes = max_diff_bytes,
+        max_file_size = max_file_size
+    );
+    *state.diff_config.lock() = git::DiffConfig {
+        max_diff_bytes,
+        max_file_size,
+    };
+    Ok(())
 }
 
 #[cfg(test)]
@@ -0,0 +1,47 @@
+use tauri::State;
+
+use crate::error::AppError;
+use crate::git;
+use crate::state::AppState;
+
+#[tauri::command]
+pub fn merge_branch(
+    branch_name: String,
+    state: State<AppState>,
+) -> Result<git::MergeResult, AppError> {
+    crate::log_cmd!("merge_branch", branch = branch_name);
+    let repo = state.get_repo()?;
+    git::merge_branch(&repo, &branch_name)
+}
+
+#[tauri::command]
+pub fn merge_base(a: String, b: String, state: State<AppState>) -> Result<String, AppError> {
+    crate::log_cmd_debug!("merge_base", a = a, b = b);
+    let repo = state.get_repo()?;
+    git::merge_base(&repo, &a, &b)
+}
+
+#[tauri::command]
+pub fn merge_base_many(revs: Vec<String>, state: State<AppState>) -> Result<String, AppError> {
+    crate::log_cmd_debug!("merge_base_many", revs = revs);
+    let repo = state.get_repo()?;
+    git::merge_base_many(&repo, &revs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+    use git2::Repository;
+
+    #[test]
+    fn test_no_repository_error() {
+        let state = AppState::new();
+
+        let repo_lock = state.repository.lock();
+        let result: Result<&Repository, AppError> =
+            repo_lock.as_ref().ok_or(AppError::NoRepository);
+
+        assert!(result.is_err());
+    }
+}
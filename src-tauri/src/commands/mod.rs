@@ -2,13 +2,18 @@ pub mod branches;
 pub mod cleanup;
 pub mod commit;
 pub mod commits;
+pub mod config;
 pub mod diff;
 pub mod logging;
+pub mod merge;
 pub mod operations;
+pub mod rebase;
+pub mod remote;
 pub mod repository;
 pub mod settings;
 pub mod staging;
 pub mod stash;
+pub mod submodule;
 pub mod system;
 pub mod terminal;
 pub mod worktree;
@@ -17,13 +22,18 @@ pub use branches::*;
 pub use cleanup::*;
 pub use commit::*;
 pub use commits::*;
+pub use config::*;
 pub use diff::*;
 pub use logging::*;
+pub use merge::*;
 pub use operations::*;
+pub use rebase::*;
+pub use remote::*;
 pub use repository::*;
 pub use settings::*;
 pub use staging::*;
 pub use stash::*;
+pub use submodule::*;
 pub use system::*;
 pub use terminal::*;
 pub use worktree::*;
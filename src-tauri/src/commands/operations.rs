@@ -17,3 +17,35 @@ pub fn continue_operation(state: State<AppState>) -> Result<String, AppError> {
     let repo = state.get_repo()?;
     git::operations::continue_operation(&repo)
 }
+
+#[tauri::command]
+pub fn cherry_pick_range(
+    from: String,
+    to: String,
+    state: State<AppState>,
+) -> Result<git::operations::RangeResult, AppError> {
+    crate::log_cmd!("cherry_pick_range", from = from, to = to);
+    let repo = state.get_repo()?;
+    git::operations::cherry_pick_range(&repo, &from, &to)
+}
+
+#[tauri::command]
+pub fn revert_range(
+    from: String,
+    to: String,
+    state: State<AppState>,
+) -> Result<git::operations::RangeResult, AppError> {
+    crate::log_cmd!("revert_range", from = from, to = to);
+    let repo = state.get_repo()?;
+    git::operations::revert_range(&repo, &from, &to)
+}
+
+/// Stop a running `get_commits` or `get_file_diff` that was started with
+/// `operation_id = id`; it then fails with `cancelled`. Unlike
+/// `abort_operation` this touches no repository state. Returns false when no
+/// such operation is running.
+#[tauri::command]
+pub fn cancel_operation(id: String, state: State<AppState>) -> bool {
+    crate::log_cmd!("cancel_operation", id = id);
+    state.cancel_operation(&id)
+}
@@ -0,0 +1,33 @@
+use tauri::State;
+
+use crate::error::AppError;
+use crate::git;
+use crate::state::AppState;
+
+#[tauri::command]
+pub fn rebase_branch(
+    upstream: String,
+    state: State<AppState>,
+) -> Result<git::RebaseResult, AppError> {
+    crate::log_cmd!("rebase_branch", upstream = upstream);
+    let repo = state.get_repo()?;
+    git::rebase_onto(&repo, &upstream, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+    use git2::Repository;
+
+    #[test]
+    fn test_no_repository_error() {
+        let state = AppState::new();
+
+        let repo_lock = state.repository.lock();
+        let result: Result<&Repository, AppError> =
+            repo_lock.as_ref().ok_or(AppError::NoRepository);
+
+        assert!(result.is_err());
+    }
+}
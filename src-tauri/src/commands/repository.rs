@@ -2,8 +2,9 @@ use std::path::PathBuf;
 use tauri::State;
 
 use crate::error::AppError;
-use crate::git;
+use crate::git::{self, GraphPageCache, HistoryCache, RefsCache};
 use crate::state::AppState;
+use crate::watcher::RepoWatcher;
 
 #[tauri::command]
 pub fn get_current_dir() -> Result<String, AppError> {
@@ -23,33 +24,97 @@ pub fn get_current_dir() -> Result<String, AppError> {
     Ok(cwd.to_string_lossy().to_string())
 }
 
+/// Open the repository containing `path`, which may be a subdirectory of
+/// its working tree. With `strict`, `path` must be the repository itself.
 #[tauri::command]
 pub fn open_repository(
     path: String,
+    strict: Option<bool>,
     state: State<AppState>,
 ) -> Result<git::RepositoryInfo, AppError> {
-    crate::log_cmd!("open_repository", path = path);
+    crate::log_cmd!("open_repository", path = path, strict = strict);
     let path = PathBuf::from(&path);
     let canonical_path = path.canonicalize().map_err(|e| {
         log::error!(target: "yagg::error", "canonicalize failed path={:?} err={e}", path);
         AppError::InvalidPath(path.display().to_string())
     })?;
 
-    let repo = git::open_repo(&canonical_path)?;
+    let repo = if strict.unwrap_or(false) {
+        git::open_repo(&canonical_path)?
+    } else {
+        git::discover_repo(&canonical_path)?
+    };
+    set_repository(&state, repo)
+}
+
+/// Start version control in the existing directory `path` and open the new
+/// repository, as `open_repository` would.
+#[tauri::command]
+pub fn init_repository(
+    path: String,
+    bare: bool,
+    state: State<AppState>,
+) -> Result<git::RepositoryInfo, AppError> {
+    crate::log_cmd!("init_repository", path = path, bare = bare);
+    let path = PathBuf::from(&path);
+    let canonical_path = path.canonicalize().map_err(|e| {
+        log::error!(target: "yagg::error", "canonicalize failed path={:?} err={e}", path);
+        AppError::InvalidPath(path.display().to_string())
+    })?;
+
+    let repo = git::init_repo(&canonical_path, bare)?;
+    set_repository(&state, repo)
+}
+
+/// Make `repo` the open repository and remember it in the recent list.
+fn set_repository(
+    state: &AppState,
+    repo: git2::Repository,
+) -> Result<git::RepositoryInfo, AppError> {
     let info = git::get_repo_info(&repo)?;
+    // Remember the repository root, not the subdirectory it was opened from.
+    let root = repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf();
+    let root = root.canonicalize().unwrap_or(root);
 
     log::info!(
         target: "yagg::lifecycle",
         "repo opened path={:?}",
-        canonical_path
+        root
     );
 
     let mut repo_lock = state.repository.lock();
     *repo_lock = Some(repo);
+    drop(repo_lock);
+    // The old watcher and caches belong to the previous repository.
+    *state.watcher.lock() = None;
+    *state.history_cache.lock() = HistoryCache::default();
+    *state.refs_cache.lock() = RefsCache::default();
+    *state.graph_page_cache.lock() = GraphPageCache::default();
+
+    state.remember_repo(root.to_string_lossy().into_owned());
 
     Ok(info)
 }
 
+/// Start emitting `repo-changed` events for the open repository. Replaces
+/// any existing watch.
+#[tauri::command]
+pub fn watch_repository(app: tauri::AppHandle, state: State<AppState>) -> Result<(), AppError> {
+    crate::log_cmd!("watch_repository");
+    let repo = state.get_repo()?;
+    let watcher = RepoWatcher::start(repo.workdir(), repo.path(), app)?;
+    drop(repo);
+
+    *state.watcher.lock() = Some(watcher);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_recent_repos(state: State<AppState>) -> Vec<String> {
+    crate::log_cmd_debug!("get_recent_repos");
+    state.recent_repos.lock().clone()
+}
+
 #[tauri::command]
 pub fn get_repository_info(state: State<AppState>) -> Result<git::RepositoryInfo, AppError> {
     crate::log_cmd_debug!("get_repository_info");
@@ -58,6 +123,29 @@ pub fn get_repository_info(state: State<AppState>) -> Result<git::RepositoryInfo
     git::get_repo_info(&repo)
 }
 
+#[tauri::command]
+pub fn get_repo_state(state: State<AppState>) -> Result<git::RepoState, AppError> {
+    crate::log_cmd_debug!("get_repo_state");
+    let repo = state.get_repo()?;
+
+    Ok(git::get_repo_state(&repo))
+}
+
+/// Slow on first call for large repositories; see `git::get_repo_stats`.
+#[tauri::command]
+pub async fn get_repo_stats(state: State<'_, AppState>) -> Result<git::RepoStats, AppError> {
+    crate::log_cmd_debug!("get_repo_stats");
+    let repository = state.repository.clone();
+    let history_cache = state.history_cache.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::get_repo_stats(repo, &mut history_cache.lock())
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,19 +41,59 @@ fn resolve_repo_path(workdir: &Path, user_path: &str) -> Result<PathBuf, AppErro
     Ok(resolved)
 }
 
+/// With `line_counts`, each entry carries its addition and deletion counts.
 #[tauri::command]
-pub async fn get_file_statuses(state: State<'_, AppState>) -> Result<git::FileStatuses, AppError> {
-    crate::log_cmd_debug!("get_file_statuses");
+pub async fn get_file_statuses(
+    include_ignored: Option<bool>,
+    line_counts: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<git::FileStatuses, AppError> {
+    crate::log_cmd_debug!(
+        "get_file_statuses",
+        include_ignored = include_ignored,
+        line_counts = line_counts
+    );
     let repository = state.repository.clone();
     tokio::task::spawn_blocking(move || {
         let guard = repository.lock();
         let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
-        git::get_file_statuses(repo)
+        let mut statuses = git::get_file_statuses(repo, include_ignored.unwrap_or(false))?;
+        if line_counts.unwrap_or(false) {
+            git::add_line_counts(repo, &mut statuses)?;
+        }
+        Ok(statuses)
     })
     .await
     .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
 }
 
+#[tauri::command]
+pub fn get_path_status(
+    path: String,
+    state: State<AppState>,
+) -> Result<Option<git::PathStatus>, AppError> {
+    crate::log_cmd_debug!("get_path_status", path = path);
+    let repo = state.get_repo()?;
+
+    git::get_path_status(&repo, &path)
+}
+
+#[tauri::command]
+pub fn is_path_ignored(path: String, state: State<AppState>) -> Result<bool, AppError> {
+    crate::log_cmd_debug!("is_path_ignored", path = path);
+    let repo = state.get_repo()?;
+
+    git::is_path_ignored(&repo, &path)
+}
+
+#[tauri::command]
+pub fn add_to_gitignore(pattern: String, state: State<AppState>) -> Result<Vec<String>, AppError> {
+    crate::log_cmd!("add_to_gitignore", pattern = pattern);
+    let repo = state.get_repo()?;
+
+    git::add_to_gitignore(&repo, &pattern)
+}
+
 #[tauri::command]
 pub fn stage_file(path: String, state: State<AppState>) -> Result<(), AppError> {
     crate::log_cmd!("stage_file", path = path);
@@ -87,23 +127,85 @@ pub fn unstage_files(paths: Vec<String>, state: State<AppState>) -> Result<(), A
 }
 
 #[tauri::command]
-pub fn stage_hunk(path: String, hunk_index: usize, state: State<AppState>) -> Result<(), AppError> {
-    crate::log_cmd!("stage_hunk", path = path, hunk = hunk_index);
+pub fn stage_all(state: State<AppState>) -> Result<(), AppError> {
+    crate::log_cmd!("stage_all");
     let repo = state.get_repo()?;
 
-    git::stage_hunk(&repo, &path, hunk_index)
+    git::stage_all(&repo)
+}
+
+#[tauri::command]
+pub fn stage_untracked(state: State<AppState>) -> Result<usize, AppError> {
+    crate::log_cmd!("stage_untracked");
+    let repo = state.get_repo()?;
+
+    git::stage_untracked(&repo)
+}
+
+#[tauri::command]
+pub fn stage_directory(dir: String, state: State<AppState>) -> Result<usize, AppError> {
+    crate::log_cmd!("stage_directory", dir = dir);
+    let repo = state.get_repo()?;
+
+    git::stage_directory(&repo, &dir)
+}
+
+#[tauri::command]
+pub fn unstage_all(state: State<AppState>) -> Result<(), AppError> {
+    crate::log_cmd!("unstage_all");
+    let repo = state.get_repo()?;
+
+    git::unstage_all(&repo)
+}
+
+/// `expected_header` is the hunk's header from the diff the user acted on;
+/// if the hunk has since changed, this fails with `stale_hunk`. The hunk and
+/// line commands take the `context_lines` that diff was fetched with, since
+/// hunk indices depend on it.
+#[tauri::command]
+pub fn stage_hunk(
+    path: String,
+    hunk_index: usize,
+    expected_header: Option<String>,
+    context_lines: Option<u32>,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!(
+        "stage_hunk",
+        path = path,
+        hunk = hunk_index,
+        context_lines = context_lines
+    );
+    let config = *state.diff_config.lock();
+    let repo = state.get_repo()?;
+
+    git::stage_hunk(
+        &repo,
+        &path,
+        hunk_index,
+        expected_header.as_deref(),
+        context_lines,
+        &config,
+    )
 }
 
 #[tauri::command]
 pub fn unstage_hunk(
     path: String,
     hunk_index: usize,
+    context_lines: Option<u32>,
     state: State<AppState>,
 ) -> Result<(), AppError> {
-    crate::log_cmd!("unstage_hunk", path = path, hunk = hunk_index);
+    crate::log_cmd!(
+        "unstage_hunk",
+        path = path,
+        hunk = hunk_index,
+        context_lines = context_lines
+    );
+    let config = *state.diff_config.lock();
     let repo = state.get_repo()?;
 
-    git::unstage_hunk(&repo, &path, hunk_index)
+    git::unstage_hunk(&repo, &path, hunk_index, context_lines, &config)
 }
 
 #[tauri::command]
@@ -111,17 +213,66 @@ pub fn stage_lines(
     path: String,
     hunk_index: usize,
     line_indices: Vec<usize>,
+    context_lines: Option<u32>,
     state: State<AppState>,
 ) -> Result<(), AppError> {
     crate::log_cmd!(
         "stage_lines",
         path = path,
         hunk = hunk_index,
-        lines = line_indices.len()
+        lines = line_indices.len(),
+        context_lines = context_lines
+    );
+    let config = *state.diff_config.lock();
+    let repo = state.get_repo()?;
+
+    git::stage_lines(
+        &repo,
+        &path,
+        hunk_index,
+        line_indices,
+        context_lines,
+        &config,
+    )
+}
+
+/// Stage lines from several hunks in one call. Each selection is a hunk index
+/// with line indices within it, as for `stage_lines`.
+#[tauri::command]
+pub fn stage_selection(
+    path: String,
+    selections: Vec<(usize, Vec<usize>)>,
+    context_lines: Option<u32>,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!(
+        "stage_selection",
+        path = path,
+        hunks = selections.len(),
+        context_lines = context_lines
+    );
+    let config = *state.diff_config.lock();
+    let repo = state.get_repo()?;
+
+    git::stage_selection(&repo, &path, &selections, context_lines, &config)
+}
+
+/// Apply pasted unified-diff text to the working tree, or to the index when
+/// `to_working_tree` is false. All or nothing.
+#[tauri::command]
+pub fn apply_patch(
+    patch_text: String,
+    to_working_tree: bool,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!(
+        "apply_patch",
+        bytes = patch_text.len(),
+        to_working_tree = to_working_tree
     );
     let repo = state.get_repo()?;
 
-    git::stage_lines(&repo, &path, hunk_index, line_indices)
+    git::apply_patch(&repo, &patch_text, to_working_tree)
 }
 
 #[tauri::command]
@@ -129,17 +280,27 @@ pub fn discard_hunk(
     path: String,
     hunk_index: usize,
     line_indices: Option<Vec<usize>>,
+    context_lines: Option<u32>,
     state: State<AppState>,
 ) -> Result<(), AppError> {
     crate::log_cmd!(
         "discard_hunk",
         path = path,
         hunk = hunk_index,
-        lines = line_indices.as_ref().map(|v| v.len())
+        lines = line_indices.as_ref().map(|v| v.len()),
+        context_lines = context_lines
     );
+    let config = *state.diff_config.lock();
     let repo = state.get_repo()?;
 
-    git::discard_hunk(&repo, &path, hunk_index, line_indices)
+    git::discard_hunk(
+        &repo,
+        &path,
+        hunk_index,
+        line_indices,
+        context_lines,
+        &config,
+    )
 }
 
 #[tauri::command]
@@ -161,11 +322,42 @@ pub fn revert_file(path: String, state: State<AppState>) -> Result<(), AppError>
 }
 
 #[tauri::command]
-pub fn revert_commit(hash: String, state: State<AppState>) -> Result<(), AppError> {
-    crate::log_cmd!("revert_commit", hash = hash);
+pub fn discard_file(
+    path: String,
+    state: State<AppState>,
+) -> Result<Option<git::PathStatus>, AppError> {
+    crate::log_cmd!("discard_file", path = path);
+    let repo = state.get_repo()?;
+
+    git::discard_file(&repo, &path)
+}
+
+#[tauri::command]
+pub fn discard_all_changes(
+    include_untracked: Option<bool>,
+    state: State<AppState>,
+) -> Result<(), AppError> {
+    crate::log_cmd!("discard_all_changes", include_untracked = include_untracked);
+    let repo = state.get_repo()?;
+
+    git::discard_all_changes(&repo, include_untracked.unwrap_or(false))
+}
+
+#[tauri::command]
+pub fn revert_commit(
+    hash: String,
+    commit: Option<bool>,
+    state: State<AppState>,
+) -> Result<Option<String>, AppError> {
+    crate::log_cmd!("revert_commit", hash = hash, commit = commit);
     let repo = state.get_repo()?;
 
-    git::revert_commit(&repo, &hash)
+    if commit.unwrap_or(false) {
+        git::revert_commit_and_commit(&repo, &hash)
+    } else {
+        git::revert_commit(&repo, &hash)?;
+        Ok(None)
+    }
 }
 
 #[tauri::command]
@@ -205,9 +397,7 @@ pub fn delete_file(path: String, state: State<AppState>) -> Result<(), AppError>
     crate::log_cmd!("delete_file", path = path);
     let repo = state.get_repo()?;
 
-    let workdir = repo
-        .workdir()
-        .ok_or(AppError::InvalidPath("No working directory".to_string()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     let file_path = resolve_repo_path(workdir, &path)?;
     std::fs::remove_file(file_path)?;
     Ok(())
@@ -218,9 +408,7 @@ pub fn delete_files(paths: Vec<String>, state: State<AppState>) -> Result<(), Ap
     crate::log_cmd!("delete_files", count = paths.len());
     let repo = state.get_repo()?;
 
-    let workdir = repo
-        .workdir()
-        .ok_or(AppError::InvalidPath("No working directory".to_string()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     for path in &paths {
         let resolved = resolve_repo_path(workdir, path)?;
         std::fs::remove_file(resolved)?;
@@ -228,15 +416,37 @@ pub fn delete_files(paths: Vec<String>, state: State<AppState>) -> Result<(), Ap
     Ok(())
 }
 
+#[tauri::command]
+pub fn get_conflict(path: String, state: State<AppState>) -> Result<git::ConflictInfo, AppError> {
+    crate::log_cmd_debug!("get_conflict", path = path);
+    let repo = state.get_repo()?;
+    git::get_conflict(&repo, &path)
+}
+
+/// Resolve a conflicted file either with explicit `content` (from a merge
+/// editor) or by applying a marker `strategy` ("ours", "theirs", "both").
+/// `content` wins when both are given.
 #[tauri::command]
 pub fn resolve_conflict(
     path: String,
-    strategy: String,
+    strategy: Option<String>,
+    content: Option<String>,
     state: State<AppState>,
 ) -> Result<(), AppError> {
-    crate::log_cmd!("resolve_conflict", path = path, strategy = strategy);
+    crate::log_cmd!(
+        "resolve_conflict",
+        path = path,
+        strategy = strategy,
+        has_content = content.is_some()
+    );
     let repo = state.get_repo()?;
-    git::resolve_conflict(&repo, &path, &strategy)
+    match (content, strategy) {
+        (Some(content), _) => git::resolve_conflict_with_content(&repo, &path, &content),
+        (None, Some(strategy)) => git::resolve_conflict(&repo, &path, &strategy),
+        (None, None) => Err(AppError::InvalidArgument(
+            "resolve_conflict needs either content or a strategy".into(),
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -277,9 +487,7 @@ mod tests {
     /// Mirror of `delete_files` (the production fn at line 149) for tests
     /// that don't have an `AppState`. Must stay in sync.
     fn delete_files_logic(repo: &Repository, paths: &[String]) -> Result<(), AppError> {
-        let workdir = repo
-            .workdir()
-            .ok_or(AppError::InvalidPath("No working directory".to_string()))?;
+        let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
         for path in paths {
             let resolved = resolve_repo_path(workdir, path)?;
             std::fs::remove_file(resolved)?;
@@ -375,9 +583,7 @@ mod tests {
     /// Mirror of `delete_file` (the production fn at line 138) for tests
     /// that don't have an `AppState`. Must stay in sync.
     fn delete_file_logic(repo: &Repository, path: &str) -> Result<(), AppError> {
-        let workdir = repo
-            .workdir()
-            .ok_or(AppError::InvalidPath("No working directory".to_string()))?;
+        let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
         let file_path = resolve_repo_path(workdir, path)?;
         std::fs::remove_file(file_path)?;
         Ok(())
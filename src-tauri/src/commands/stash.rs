@@ -1,4 +1,6 @@
-use tauri::State;
+use git2::StashApplyProgress;
+use serde::Serialize;
+use tauri::{Emitter, State};
 
 use crate::error::AppError;
 use crate::git;
@@ -24,11 +26,72 @@ pub fn get_stash_details(
 }
 
 #[tauri::command]
-pub fn apply_stash(index: usize, state: State<AppState>) -> Result<(), AppError> {
+pub fn create_stash(
+    message: Option<String>,
+    include_untracked: bool,
+    keep_index: bool,
+    state: State<AppState>,
+) -> Result<git::StashInfo, AppError> {
+    crate::log_cmd!(
+        "create_stash",
+        include_untracked = include_untracked,
+        keep_index = keep_index
+    );
+    let mut repo = state.get_repo()?;
+
+    git::create_stash(&mut repo, message.as_deref(), include_untracked, keep_index)
+}
+
+#[tauri::command]
+pub fn stash_paths(
+    paths: Vec<String>,
+    message: Option<String>,
+    state: State<AppState>,
+) -> Result<git::StashInfo, AppError> {
+    crate::log_cmd!("stash_paths", count = paths.len());
+    let mut repo = state.get_repo()?;
+
+    git::stash_paths(&mut repo, &paths, message.as_deref())
+}
+
+/// Emitted with a `StashApplyProgressPayload` at each stage of `apply_stash`.
+pub const STASH_APPLY_PROGRESS_EVENT: &str = "stash-apply-progress";
+
+#[derive(Serialize, Clone)]
+struct StashApplyProgressPayload {
+    index: usize,
+    stage: &'static str,
+}
+
+fn stash_apply_stage(stage: StashApplyProgress) -> &'static str {
+    match stage {
+        StashApplyProgress::None => "none",
+        StashApplyProgress::LoadingStash => "loading_stash",
+        StashApplyProgress::AnalyzeIndex => "analyze_index",
+        StashApplyProgress::AnalyzeModified => "analyze_modified",
+        StashApplyProgress::AnalyzeUntracked => "analyze_untracked",
+        StashApplyProgress::CheckoutUntracked => "checkout_untracked",
+        StashApplyProgress::CheckoutModified => "checkout_modified",
+        StashApplyProgress::Done => "done",
+    }
+}
+
+#[tauri::command]
+pub fn apply_stash(
+    index: usize,
+    app: tauri::AppHandle,
+    state: State<AppState>,
+) -> Result<(), AppError> {
     crate::log_cmd!("apply_stash", index = index);
     let mut repo = state.get_repo()?;
 
-    git::apply_stash(&mut repo, index)
+    git::apply_stash_with_progress(&mut repo, index, |stage| {
+        let stage = stash_apply_stage(stage);
+        let _ = app.emit(
+            STASH_APPLY_PROGRESS_EVENT,
+            StashApplyProgressPayload { index, stage },
+        );
+    })
 }
 
 #[tauri::command]
@@ -39,6 +102,14 @@ pub fn drop_stash(index: usize, state: State<AppState>) -> Result<(), AppError>
     git::drop_stash(&mut repo, index)
 }
 
+#[tauri::command]
+pub fn clear_stashes(state: State<AppState>) -> Result<usize, AppError> {
+    crate::log_cmd!("clear_stashes");
+    let mut repo = state.get_repo()?;
+
+    git::clear_stashes(&mut repo)
+}
+
 #[tauri::command]
 pub fn get_stash_file_diff(
     index: usize,
@@ -67,4 +138,17 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_stash_apply_stage_names() {
+        assert_eq!(
+            stash_apply_stage(StashApplyProgress::LoadingStash),
+            "loading_stash"
+        );
+        assert_eq!(
+            stash_apply_stage(StashApplyProgress::CheckoutModified),
+            "checkout_modified"
+        );
+        assert_eq!(stash_apply_stage(StashApplyProgress::Done), "done");
+    }
 }
@@ -0,0 +1,13 @@
+use tauri::State;
+
+use crate::error::AppError;
+use crate::git;
+use crate::state::AppState;
+
+#[tauri::command]
+pub fn list_submodules(state: State<AppState>) -> Result<Vec<git::SubmoduleInfo>, AppError> {
+    crate::log_cmd_debug!("list_submodules");
+    let repo = state.get_repo()?;
+
+    git::list_submodules(&repo)
+}
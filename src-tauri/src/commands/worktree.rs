@@ -74,6 +74,19 @@ pub async fn remove_worktree(
     .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
 }
 
+#[tauri::command]
+pub async fn prune_worktrees(state: State<'_, AppState>) -> Result<Vec<String>, AppError> {
+    crate::log_cmd!("prune_worktrees");
+    let repository = state.repository.clone();
+    tokio::task::spawn_blocking(move || {
+        let guard = repository.lock();
+        let repo = guard.as_ref().ok_or(AppError::NoRepository)?;
+        git::prune_worktrees(repo)
+    })
+    .await
+    .map_err(|e| AppError::Internal(format!("spawn_blocking join error: {e}")))?
+}
+
 #[tauri::command]
 pub async fn move_worktree(
     name: String,
@@ -1,3 +1,4 @@
+use serde::ser::SerializeStruct;
 use serde::Serialize;
 use thiserror::Error;
 
@@ -12,15 +13,95 @@ pub enum AppError {
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
+    #[error("Repository has no working directory")]
+    NoWorkdir,
+
+    #[error("Hunk index {0} out of range")]
+    HunkOutOfRange(usize),
+
+    /// The hunk at this index is no longer the one the caller saw.
+    #[error("Hunk {0} has changed since the diff was loaded")]
+    StaleHunk(usize),
+
+    #[error("File I/O error: {0}")]
+    FileIo(String),
+
+    #[error("Invalid branch name: {0}")]
+    InvalidBranchName(String),
+
+    #[error("Invalid remote name: {0}")]
+    InvalidRemoteName(String),
+
+    #[error("Invalid tag name: {0}")]
+    InvalidTagName(String),
+
+    #[error("Invalid email address: {0}")]
+    InvalidEmail(String),
+
+    #[error("Invalid commit message: {0}")]
+    InvalidCommitMessage(String),
+
+    /// A command argument outside what it accepts, e.g. an unknown mode
+    /// name or an out-of-range count.
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    #[error("Nothing to commit")]
+    NothingToCommit,
+
+    #[error("No local changes to stash")]
+    NothingToStash,
+
+    #[error("Hook failed: {0}")]
+    HookFailed(String),
+
+    #[error("Commit signing failed: {0}")]
+    SigningFailed(String),
+
+    #[error("Invalid upstream: {0}")]
+    InvalidUpstream(String),
+
+    #[error("Ambiguous commit prefix, could be: {0:?}")]
+    AmbiguousCommit(Vec<String>),
+
     #[error("Revert conflict: {0}")]
     RevertConflict(String),
 
-    #[error("No rebase, cherry-pick, or revert in progress")]
+    #[error("No merge, rebase, cherry-pick, or revert in progress")]
     NoOperationInProgress,
 
     #[error("Resolve conflicts in: {0:?}")]
     ConflictsRemaining(Vec<String>),
 
+    #[error("Not conflicted: {0}")]
+    NotConflicted(String),
+
+    /// `step` is set when a rebase stopped: the index of the commit being
+    /// replayed.
+    #[error(
+        "Merge conflict in: {paths:?}{}",
+        .step.map(|s| format!(" (rebase step {s})")).unwrap_or_default()
+    )]
+    MergeConflict {
+        paths: Vec<String>,
+        step: Option<usize>,
+    },
+
+    #[error("Checkout would overwrite local changes in: {0:?}")]
+    CheckoutConflict(Vec<String>),
+
+    #[error("Patch failed: {0}")]
+    PatchFailed(String),
+
+    #[error("Operation cancelled")]
+    Cancelled,
+
+    #[error("Authentication failed: {0}")]
+    AuthFailed(String),
+
+    #[error("Push rejected: {0}")]
+    PushRejected(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -31,6 +112,67 @@ pub enum AppError {
     Internal(String),
 }
 
+impl AppError {
+    /// Stable snake_case identifier for the kind of failure, so the frontend
+    /// can react to it without parsing the message. `Git` errors are split by
+    /// libgit2's error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Git(e) => git_error_code(e),
+            AppError::NoRepository => "no_repository",
+            AppError::InvalidPath(_) => "invalid_path",
+            AppError::NoWorkdir => "no_workdir",
+            AppError::HunkOutOfRange(_) => "hunk_out_of_range",
+            AppError::StaleHunk(_) => "stale_hunk",
+            AppError::FileIo(_) => "file_io",
+            AppError::InvalidBranchName(_) => "invalid_branch_name",
+            AppError::InvalidRemoteName(_) => "invalid_remote_name",
+            AppError::InvalidTagName(_) => "invalid_tag_name",
+            AppError::InvalidEmail(_) => "invalid_email",
+            AppError::InvalidCommitMessage(_) => "invalid_commit_message",
+            AppError::InvalidArgument(_) => "invalid_argument",
+            AppError::NothingToCommit => "nothing_to_commit",
+            AppError::NothingToStash => "nothing_to_stash",
+            AppError::HookFailed(_) => "hook_failed",
+            AppError::SigningFailed(_) => "signing_failed",
+            AppError::InvalidUpstream(_) => "invalid_upstream",
+            AppError::AmbiguousCommit(_) => "ambiguous_commit",
+            AppError::RevertConflict(_) => "revert_conflict",
+            AppError::NoOperationInProgress => "no_operation_in_progress",
+            AppError::ConflictsRemaining(_) => "conflicts_remaining",
+            AppError::NotConflicted(_) => "not_conflicted",
+            AppError::MergeConflict { .. } => "merge_conflict",
+            AppError::CheckoutConflict(_) => "checkout_conflict",
+            AppError::PatchFailed(_) => "patch_failed",
+            AppError::Cancelled => "cancelled",
+            AppError::AuthFailed(_) => "auth_failed",
+            AppError::PushRejected(_) => "push_rejected",
+            AppError::Io(_) => "io",
+            AppError::Terminal(_) => "terminal",
+            AppError::Internal(_) => "internal",
+        }
+    }
+}
+
+fn git_error_code(e: &git2::Error) -> &'static str {
+    use git2::ErrorCode;
+    match e.code() {
+        ErrorCode::NotFound => "git_not_found",
+        ErrorCode::Exists => "git_exists",
+        ErrorCode::Ambiguous => "git_ambiguous",
+        ErrorCode::BareRepo => "git_bare_repo",
+        ErrorCode::UnbornBranch => "git_unborn_branch",
+        ErrorCode::NotFastForward => "git_not_fast_forward",
+        ErrorCode::InvalidSpec => "git_invalid_spec",
+        ErrorCode::Conflict | ErrorCode::MergeConflict | ErrorCode::Unmerged => "git_conflict",
+        ErrorCode::Locked => "git_locked",
+        ErrorCode::Auth => "git_auth",
+        ErrorCode::Uncommitted | ErrorCode::IndexDirty => "git_uncommitted",
+        _ => "git",
+    }
+}
+
+/// Sent to the frontend as `{ code, message }`.
 impl Serialize for AppError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -38,7 +180,10 @@ impl Serialize for AppError {
     {
         let s = self.to_string();
         log::error!(target: "yagg::error", "{s}");
-        serializer.serialize_str(&s)
+        let mut state = serializer.serialize_struct("AppError", 2)?;
+        state.serialize_field("code", self.code())?;
+        state.serialize_field("message", &s)?;
+        state.end()
     }
 }
 
@@ -58,6 +203,14 @@ mod tests {
         assert_eq!(error.to_string(), "Invalid path: /bad/path");
     }
 
+    #[test]
+    fn test_nothing_to_stash_error_display() {
+        assert_eq!(
+            AppError::NothingToStash.to_string(),
+            "No local changes to stash"
+        );
+    }
+
     #[test]
     fn test_io_error_conversion() {
         let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
@@ -69,14 +222,20 @@ mod tests {
     fn test_serialize_no_repository() {
         let error = AppError::NoRepository;
         let json = serde_json::to_string(&error).unwrap();
-        assert_eq!(json, "\"No repository open\"");
+        assert_eq!(
+            json,
+            r#"{"code":"no_repository","message":"No repository open"}"#
+        );
     }
 
     #[test]
     fn test_serialize_invalid_path() {
         let error = AppError::InvalidPath("/test/path".to_string());
         let json = serde_json::to_string(&error).unwrap();
-        assert_eq!(json, "\"Invalid path: /test/path\"");
+        assert_eq!(
+            json,
+            r#"{"code":"invalid_path","message":"Invalid path: /test/path"}"#
+        );
     }
 
     #[test]
@@ -84,6 +243,7 @@ mod tests {
         let io_error = std::io::Error::new(std::io::ErrorKind::PermissionDenied, "access denied");
         let error: AppError = io_error.into();
         let json = serde_json::to_string(&error).unwrap();
+        assert!(json.contains(r#""code":"io""#));
         assert!(json.contains("access denied"));
     }
 
@@ -99,10 +259,200 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_invalid_branch_name_error_display() {
+        let error = AppError::InvalidBranchName("bad name".to_string());
+        assert_eq!(error.to_string(), "Invalid branch name: bad name");
+    }
+
+    #[test]
+    fn test_invalid_remote_name_error_display() {
+        let error = AppError::InvalidRemoteName("a b".to_string());
+        assert_eq!(error.to_string(), "Invalid remote name: a b");
+    }
+
+    #[test]
+    fn test_invalid_tag_name_error_display() {
+        let error = AppError::InvalidTagName("v1:v2".to_string());
+        assert_eq!(error.to_string(), "Invalid tag name: v1:v2");
+    }
+
+    #[test]
+    fn test_invalid_email_error_display() {
+        let error = AppError::InvalidEmail("nope".to_string());
+        assert_eq!(error.to_string(), "Invalid email address: nope");
+    }
+
+    #[test]
+    fn test_invalid_commit_message_error_display() {
+        let error = AppError::InvalidCommitMessage("empty".to_string());
+        assert_eq!(error.to_string(), "Invalid commit message: empty");
+    }
+
+    #[test]
+    fn test_invalid_argument_error_display() {
+        let error = AppError::InvalidArgument("count must be at least 1".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Invalid argument: count must be at least 1"
+        );
+        assert_eq!(error.code(), "invalid_argument");
+    }
+
+    #[test]
+    fn test_hook_failed_error_display() {
+        let error = AppError::HookFailed("lint errors".to_string());
+        assert_eq!(error.to_string(), "Hook failed: lint errors");
+    }
+
+    #[test]
+    fn test_signing_failed_error_display() {
+        let error = AppError::SigningFailed("no secret key".to_string());
+        assert_eq!(error.to_string(), "Commit signing failed: no secret key");
+    }
+
+    #[test]
+    fn test_invalid_upstream_error_display() {
+        let error = AppError::InvalidUpstream("origin/nope".to_string());
+        assert_eq!(error.to_string(), "Invalid upstream: origin/nope");
+    }
+
+    #[test]
+    fn test_ambiguous_commit_error_display() {
+        let error = AppError::AmbiguousCommit(vec!["abc1".to_string(), "abc2".to_string()]);
+        assert_eq!(
+            error.to_string(),
+            "Ambiguous commit prefix, could be: [\"abc1\", \"abc2\"]"
+        );
+    }
+
+    #[test]
+    fn test_merge_conflict_error_display() {
+        let error = AppError::MergeConflict {
+            paths: vec!["a.txt".to_string()],
+            step: None,
+        };
+        assert_eq!(error.to_string(), "Merge conflict in: [\"a.txt\"]");
+    }
+
+    #[test]
+    fn test_rebase_conflict_error_display() {
+        let error = AppError::MergeConflict {
+            paths: vec!["a.txt".to_string()],
+            step: Some(2),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Merge conflict in: [\"a.txt\"] (rebase step 2)"
+        );
+    }
+
+    #[test]
+    fn test_not_conflicted_error_display() {
+        let error = AppError::NotConflicted("a.txt".to_string());
+        assert_eq!(error.to_string(), "Not conflicted: a.txt");
+        assert_eq!(error.code(), "not_conflicted");
+    }
+
+    #[test]
+    fn test_checkout_conflict_error_display() {
+        let error = AppError::CheckoutConflict(vec!["a.txt".to_string()]);
+        assert_eq!(
+            error.to_string(),
+            "Checkout would overwrite local changes in: [\"a.txt\"]"
+        );
+    }
+
+    #[test]
+    fn test_patch_failed_error_display() {
+        let error = AppError::PatchFailed("hunk at line 1 did not apply".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Patch failed: hunk at line 1 did not apply"
+        );
+    }
+
+    #[test]
+    fn test_cancelled_error_display() {
+        assert_eq!(AppError::Cancelled.to_string(), "Operation cancelled");
+    }
+
+    #[test]
+    fn test_auth_failed_error_display() {
+        let error = AppError::AuthFailed("invalid credentials".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Authentication failed: invalid credentials"
+        );
+    }
+
+    #[test]
+    fn test_push_rejected_error_display() {
+        let error = AppError::PushRejected("refs/heads/main: non-fast-forward".to_string());
+        assert_eq!(
+            error.to_string(),
+            "Push rejected: refs/heads/main: non-fast-forward"
+        );
+    }
+
     #[test]
     fn test_serialize_revert_conflict() {
         let error = AppError::RevertConflict("conflict details".to_string());
         let json = serde_json::to_string(&error).unwrap();
-        assert_eq!(json, "\"Revert conflict: conflict details\"");
+        assert_eq!(
+            json,
+            r#"{"code":"revert_conflict","message":"Revert conflict: conflict details"}"#
+        );
+    }
+
+    #[test]
+    fn test_no_workdir_error_display() {
+        let error = AppError::NoWorkdir;
+        assert_eq!(error.to_string(), "Repository has no working directory");
+        assert_eq!(error.code(), "no_workdir");
+    }
+
+    #[test]
+    fn test_hunk_out_of_range_error_display() {
+        let error = AppError::HunkOutOfRange(3);
+        assert_eq!(error.to_string(), "Hunk index 3 out of range");
+        assert_eq!(error.code(), "hunk_out_of_range");
+    }
+
+    #[test]
+    fn test_stale_hunk_error_display() {
+        let error = AppError::StaleHunk(1);
+        assert_eq!(
+            error.to_string(),
+            "Hunk 1 has changed since the diff was loaded"
+        );
+        assert_eq!(error.code(), "stale_hunk");
+    }
+
+    #[test]
+    fn test_file_io_error_display() {
+        let error = AppError::FileIo("failed to read a.txt: denied".to_string());
+        assert_eq!(
+            error.to_string(),
+            "File I/O error: failed to read a.txt: denied"
+        );
+        assert_eq!(error.code(), "file_io");
+    }
+
+    #[test]
+    fn test_git_error_code_follows_libgit2_code() {
+        let not_found = git2::Error::new(
+            git2::ErrorCode::NotFound,
+            git2::ErrorClass::Reference,
+            "no such ref",
+        );
+        assert_eq!(AppError::Git(not_found).code(), "git_not_found");
+        let locked = git2::Error::new(
+            git2::ErrorCode::Locked,
+            git2::ErrorClass::Index,
+            "index.lock exists",
+        );
+        assert_eq!(AppError::Git(locked).code(), "git_locked");
+        assert_eq!(AppError::Git(git2::Error::from_str("boom")).code(), "git");
     }
 }
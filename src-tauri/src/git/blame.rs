@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use git2::{ErrorCode, Repository};
+use serde::Serialize;
+
+use crate::error::AppError;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct BlameLine {
+    /// 1-based line number in the file as of HEAD.
+    pub line_number: usize,
+    pub commit_hash: String,
+    pub short_hash: String,
+    pub author_name: String,
+    pub timestamp: i64,
+}
+
+/// Blame every line of `path` as of HEAD. Files that aren't in HEAD (new or
+/// untracked) have nothing to attribute and return `AppError::InvalidPath`.
+pub fn get_blame(repo: &Repository, path: &str) -> Result<Vec<BlameLine>, AppError> {
+    crate::log_git_op_debug!("get_blame", path = path);
+    let blame = repo.blame_file(Path::new(path), None).map_err(|e| {
+        if e.code() == ErrorCode::NotFound {
+            AppError::InvalidPath(format!("File has no committed history: {path}"))
+        } else {
+            AppError::Git(e)
+        }
+    })?;
+
+    let mut lines = Vec::new();
+    for hunk in blame.iter() {
+        let commit_hash = hunk.final_commit_id().to_string();
+        let short_hash = commit_hash[..7.min(commit_hash.len())].to_string();
+        let signature = hunk.final_signature();
+        let author_name = signature
+            .as_ref()
+            .and_then(|sig| sig.name().ok())
+            .unwrap_or("")
+            .to_string();
+        let timestamp = signature.map(|sig| sig.when().seconds()).unwrap_or(0);
+
+        let start = hunk.final_start_line();
+        for line_number in start..start + hunk.lines_in_hunk() {
+            lines.push(BlameLine {
+                line_number,
+                commit_hash: commit_hash.clone(),
+                short_hash: short_hash.clone(),
+                author_name: author_name.clone(),
+                timestamp,
+            });
+        }
+    }
+
+    Ok(lines)
+}
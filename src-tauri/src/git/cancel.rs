@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::AppError;
+
+/// Shared flag a long-running walk or diff polls between steps. Clones share
+/// the flag, so the command can hand one to the blocking task and keep
+/// another for `cancel_operation`.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    /// Polls left before the token cancels itself, so tests can cancel work
+    /// at a known point after it has started.
+    #[cfg(test)]
+    polls_left: Option<Arc<std::sync::atomic::AtomicUsize>>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        #[cfg(test)]
+        if let Some(left) = &self.polls_left {
+            if left
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+                .is_err()
+            {
+                self.cancel();
+            }
+        }
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// `Err(AppError::Cancelled)` once `cancel` has been called.
+    pub fn check(&self) -> Result<(), AppError> {
+        if self.is_cancelled() {
+            Err(AppError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+
+    pub(crate) fn same_as(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+
+    /// A token that reads as not cancelled for `polls` polls, then cancels.
+    #[cfg(test)]
+    fn cancelled_after(polls: usize) -> Self {
+        Self {
+            polls_left: Some(Arc::new(polls.into())),
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    // Behavior tests for a token cancelled up front live in tests/commits.rs;
+    // these need `cancelled_after` to cancel once the work is underway.
+    fn repo_with_commits(count: usize) -> (TempDir, git2::Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp_dir.path()).unwrap();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test User").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+        let sig = repo.signature().unwrap();
+        for i in 0..count {
+            let name = format!("f{i}.txt");
+            fs::write(temp_dir.path().join(&name), "x\n").unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(Path::new(&name)).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+            let parents: Vec<&git2::Commit> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, &name, &tree, &parents)
+                .unwrap();
+        }
+        (temp_dir, repo)
+    }
+
+    #[test]
+    fn test_get_commits_cancelled_mid_walk() {
+        let (_temp_dir, repo) = repo_with_commits(20);
+
+        // The walk polls once per commit, so this cancels partway through a
+        // filtered walk that would otherwise visit all 20.
+        let token = CancelToken::cancelled_after(5);
+        let result =
+            crate::git::get_commits_cancellable(&repo, 0, 100, None, Some("nobody"), &token);
+        assert!(matches!(result, Err(AppError::Cancelled)));
+    }
+
+    #[test]
+    fn test_get_file_diff_cancelled_while_printing() {
+        let (temp_dir, repo) = repo_with_commits(1);
+        let content: String = (0..5000).map(|i| format!("line {i}\n")).collect();
+        fs::write(temp_dir.path().join("f0.txt"), content).unwrap();
+
+        // One poll before diffing, then one per printed line: this cancels
+        // inside the `diff.print` callback, whose abort libgit2 reports as
+        // its own error.
+        let token = CancelToken::cancelled_after(10);
+        let result = crate::git::get_file_diff_cancellable(
+            &repo,
+            "f0.txt",
+            false,
+            &crate::git::DiffConfig::default(),
+            false,
+            None,
+            &token,
+        );
+        assert!(matches!(result, Err(AppError::Cancelled)));
+    }
+}
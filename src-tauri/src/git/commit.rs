@@ -1,6 +1,7 @@
-use git2::{Oid, Repository, Sort};
+use git2::{Delta, DiffFindOptions, DiffOptions, Oid, Repository, Signature, Sort};
 use serde::Serialize;
 
+use super::cancel::CancelToken;
 use crate::error::AppError;
 
 #[derive(Debug, Serialize, Clone)]
@@ -10,7 +11,19 @@ pub struct CommitInfo {
     pub message: String,
     pub author_name: String,
     pub author_email: String,
+    /// Gravatar-style hash of `author_email`; see [`email_hash`].
+    pub author_email_hash: String,
+    /// Committer time; same as `commit_time`.
     pub timestamp: i64,
+    /// When the change was written, in seconds. Survives rebase and amend,
+    /// unlike `commit_time`.
+    pub author_time: i64,
+    /// Author's UTC offset in minutes.
+    pub author_offset_minutes: i32,
+    /// When the commit object was created, in seconds.
+    pub commit_time: i64,
+    /// Committer's UTC offset in minutes.
+    pub commit_offset_minutes: i32,
     pub parent_hashes: Vec<String>,
 }
 
@@ -19,6 +32,16 @@ pub struct CommitFileChange {
     pub path: String,
     pub status: String,
     pub old_path: Option<String>,
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Totals across every file in a commit's diff.
+#[derive(Debug, Serialize, Clone)]
+pub struct CommitStats {
+    pub files_changed: usize,
+    pub insertions: usize,
+    pub deletions: usize,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -29,20 +52,37 @@ pub struct CommitDetails {
     pub author_email: String,
     pub committer_name: String,
     pub committer_email: String,
+    /// Committer time; same as `commit_time`.
     pub timestamp: i64,
+    pub author_time: i64,
+    pub author_offset_minutes: i32,
+    pub commit_time: i64,
+    pub commit_offset_minutes: i32,
     pub parent_hashes: Vec<String>,
+    /// First message line of each parent, in `parent_hashes` order.
+    pub parent_subjects: Vec<String>,
     pub files_changed: Vec<CommitFileChange>,
+    pub stats: CommitStats,
 }
 
-pub fn get_commits(
-    repo: &Repository,
-    skip: usize,
-    limit: usize,
-) -> Result<Vec<CommitInfo>, AppError> {
-    crate::log_git_op_debug!("get_commits", skip = skip, limit = limit);
+/// Revwalk in the order the history views use. With `refs` the walk starts
+/// only from those revisions (branch names, tags, hashes); otherwise from HEAD
+/// and every branch tip.
+pub(super) fn history_revwalk<'r>(
+    repo: &'r Repository,
+    refs: Option<&[String]>,
+) -> Result<git2::Revwalk<'r>, AppError> {
     let mut revwalk = repo.revwalk()?;
     revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
 
+    if let Some(refs) = refs {
+        for spec in refs {
+            let commit = repo.revparse_single(spec)?.peel_to_commit()?;
+            revwalk.push(commit.id())?;
+        }
+        return Ok(revwalk);
+    }
+
     // Start from HEAD
     if let Ok(head) = repo.head() {
         if let Some(target) = head.target() {
@@ -57,37 +97,166 @@ pub fn get_commits(
         }
     }
 
+    Ok(revwalk)
+}
+
+/// A page of history. With `author_filter`, only commits whose author name
+/// or email contains it (case-insensitively) are kept, and `skip`/`limit`
+/// count matching commits only.
+pub fn get_commits(
+    repo: &Repository,
+    skip: usize,
+    limit: usize,
+    refs: Option<&[String]>,
+    author_filter: Option<&str>,
+) -> Result<Vec<CommitInfo>, AppError> {
+    // Logged by `get_commits_cancellable`.
+    get_commits_cancellable(repo, skip, limit, refs, author_filter, &CancelToken::new())
+}
+
+/// `get_commits`, stopping with `AppError::Cancelled` once `cancel` is set.
+/// A filtered walk over a large history can visit every commit.
+pub fn get_commits_cancellable(
+    repo: &Repository,
+    skip: usize,
+    limit: usize,
+    refs: Option<&[String]>,
+    author_filter: Option<&str>,
+    cancel: &CancelToken,
+) -> Result<Vec<CommitInfo>, AppError> {
+    crate::log_git_op_debug!(
+        "get_commits_cancellable",
+        skip = skip,
+        limit = limit,
+        refs = refs,
+        author_filter = author_filter
+    );
+    let revwalk = history_revwalk(repo, refs)?;
+    let needle = author_filter.map(str::to_lowercase);
+
     let commits: Vec<CommitInfo> = revwalk
+        .take_while(|_| !cancel.is_cancelled())
+        .filter_map(|oid| oid.ok())
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        .filter(|commit| {
+            needle
+                .as_deref()
+                .is_none_or(|needle| author_matches(&commit.author(), needle))
+        })
         .skip(skip)
         .take(limit)
+        .map(|commit| commit_to_info(&commit))
+        .collect();
+    cancel.check()?;
+
+    Ok(commits)
+}
+
+fn author_matches(author: &git2::Signature, needle: &str) -> bool {
+    [author.name(), author.email()]
+        .into_iter()
+        .flatten()
+        .any(|field| field.to_lowercase().contains(needle))
+}
+
+/// Commits whose full message (not just the summary line shown in the log)
+/// contains `query`, case-insensitively. `skip`/`limit` count matches.
+pub fn search_commits(
+    repo: &Repository,
+    query: &str,
+    skip: usize,
+    limit: usize,
+) -> Result<Vec<CommitInfo>, AppError> {
+    crate::log_git_op_debug!("search_commits", query = query, skip = skip, limit = limit);
+    let revwalk = history_revwalk(repo, None)?;
+    let needle = query.to_lowercase();
+
+    let commits: Vec<CommitInfo> = revwalk
         .filter_map(|oid| oid.ok())
-        .filter_map(|oid| {
-            let commit = repo.find_commit(oid).ok()?;
-            Some(commit_to_info(&commit))
+        .filter_map(|oid| repo.find_commit(oid).ok())
+        .filter(|commit| {
+            String::from_utf8_lossy(commit.message_bytes())
+                .to_lowercase()
+                .contains(&needle)
         })
+        .skip(skip)
+        .take(limit)
+        .map(|commit| commit_to_info(&commit))
         .collect();
 
     Ok(commits)
 }
 
-pub fn get_all_commits(repo: &Repository) -> Result<Vec<CommitInfo>, AppError> {
-    crate::log_git_op_debug!("get_all_commits");
-    let mut revwalk = repo.revwalk()?;
-    revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
+/// A commit plus the row it occupies in the default log ordering (the one
+/// `get_all_commits` uses), or `None` when HEAD and the branches don't reach it.
+#[derive(Debug, Serialize, Clone)]
+pub struct CommitLocation {
+    pub commit: CommitInfo,
+    pub index: Option<usize>,
+}
 
-    // Start from HEAD
-    if let Ok(head) = repo.head() {
-        if let Some(target) = head.target() {
-            revwalk.push(target)?;
+/// Resolve a short hash (or any revspec) for "jump to commit". A hash prefix
+/// shared by several commits fails with `AmbiguousCommit` listing them.
+pub fn find_commit_in_graph(repo: &Repository, prefix: &str) -> Result<CommitLocation, AppError> {
+    crate::log_git_op_debug!("find_commit_in_graph", prefix = prefix);
+    let prefix = prefix.trim();
+    let commit = match repo.revparse_single(prefix) {
+        Ok(object) => object.peel_to_commit()?,
+        Err(e) if e.code() == git2::ErrorCode::Ambiguous => {
+            // The prefix may also match trees or blobs; only commits count.
+            let candidates = commits_with_prefix(repo, prefix)?;
+            match candidates.as_slice() {
+                [] => return Err(e.into()),
+                [only] => repo.find_commit(*only)?,
+                _ => {
+                    return Err(AppError::AmbiguousCommit(
+                        candidates.iter().map(Oid::to_string).collect(),
+                    ))
+                }
+            }
         }
-    }
+        Err(e) => return Err(e.into()),
+    };
 
-    // Also include all branch tips
-    for (branch, _) in repo.branches(None)?.flatten() {
-        if let Some(target) = branch.get().target() {
-            let _ = revwalk.push(target);
+    let index = history_revwalk(repo, None)?
+        .filter_map(|oid| oid.ok())
+        .position(|oid| oid == commit.id());
+    Ok(CommitLocation {
+        commit: commit_to_info(&commit),
+        index,
+    })
+}
+
+/// Every commit object in the odb whose hash starts with `prefix`, sorted.
+fn commits_with_prefix(repo: &Repository, prefix: &str) -> Result<Vec<Oid>, AppError> {
+    let prefix = prefix.to_ascii_lowercase();
+    let odb = repo.odb()?;
+    let mut matches = Vec::new();
+    odb.foreach(|oid| {
+        if oid.to_string().starts_with(&prefix) {
+            matches.push(*oid);
         }
-    }
+        true
+    })?;
+
+    let mut commits: Vec<Oid> = matches
+        .into_iter()
+        .filter(|oid| {
+            odb.read_header(*oid)
+                .is_ok_and(|(_, kind)| kind == git2::ObjectType::Commit)
+        })
+        .collect();
+    commits.sort();
+    commits.dedup();
+    Ok(commits)
+}
+
+pub fn get_all_commits(
+    repo: &Repository,
+    refs: Option<&[String]>,
+) -> Result<Vec<CommitInfo>, AppError> {
+    crate::log_git_op_debug!("get_all_commits", refs = refs);
+    let revwalk = history_revwalk(repo, refs)?;
 
     let commits: Vec<CommitInfo> = revwalk
         .filter_map(|oid| oid.ok())
@@ -100,12 +269,99 @@ pub fn get_all_commits(repo: &Repository) -> Result<Vec<CommitInfo>, AppError> {
     Ok(commits)
 }
 
+/// Commits reachable from HEAD that changed `path` relative to their first
+/// parent, newest first. When a commit turns out to have created `path` by
+/// renaming another file, the walk continues under the old name so history
+/// doesn't stop at the rename.
+pub fn get_file_history(
+    repo: &Repository,
+    path: &str,
+    skip: usize,
+    limit: usize,
+) -> Result<Vec<CommitInfo>, AppError> {
+    crate::log_git_op_debug!("get_file_history", path = path, skip = skip, limit = limit);
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(Sort::TIME | Sort::TOPOLOGICAL)?;
+    match repo.head() {
+        Ok(head) => match head.target() {
+            Some(target) => revwalk.push(target)?,
+            None => return Ok(Vec::new()),
+        },
+        // Unborn HEAD: no history yet.
+        Err(_) => return Ok(Vec::new()),
+    }
+
+    let mut current_path = path.to_string();
+    let mut matched = Vec::new();
+
+    for oid in revwalk {
+        let commit = repo.find_commit(oid?)?;
+        let tree = commit.tree()?;
+        let parent_tree = if commit.parent_count() > 0 {
+            Some(commit.parent(0)?.tree()?)
+        } else {
+            None
+        };
+
+        let mut opts = DiffOptions::new();
+        opts.pathspec(&current_path).disable_pathspec_match(true);
+        let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))?;
+        if diff.deltas().len() == 0 {
+            continue;
+        }
+
+        // An "added" path with a parent may really be the target of a rename.
+        // Re-diff without the pathspec so rename detection can see the source.
+        let mut renamed_from = None;
+        let added = diff.deltas().any(|d| d.status() == Delta::Added);
+        if added && parent_tree.is_some() {
+            let mut full = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+            full.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+            renamed_from = full
+                .deltas()
+                .filter(|d| d.status() == Delta::Renamed)
+                .find(|d| d.new_file().path() == Some(std::path::Path::new(&current_path)))
+                .and_then(|d| d.old_file().path())
+                .map(|p| p.to_string_lossy().into_owned());
+        }
+
+        matched.push(commit_to_info(&commit));
+        if matched.len() >= skip + limit {
+            break;
+        }
+        if let Some(old_path) = renamed_from {
+            current_path = old_path;
+        }
+    }
+
+    Ok(matched.into_iter().skip(skip).take(limit).collect())
+}
+
+/// Resolve any revspec git understands (`HEAD~2`, `main^`, `v1.0`, a short
+/// hash) to the full hash of the commit it names.
+pub fn resolve_revision(repo: &Repository, rev: &str) -> Result<String, AppError> {
+    crate::log_git_op_debug!("resolve_revision", rev = rev);
+    Ok(find_revision(repo, rev)?.id().to_string())
+}
+
+/// The commit `rev` names. Commands that take a `hash` go through this so
+/// they accept any revspec, not just a full hash.
+pub(crate) fn find_revision<'r>(
+    repo: &'r Repository,
+    rev: &str,
+) -> Result<git2::Commit<'r>, AppError> {
+    Ok(repo.revparse_single(rev.trim())?.peel_to_commit()?)
+}
+
 pub fn get_commit_details(repo: &Repository, hash: &str) -> Result<CommitDetails, AppError> {
     crate::log_git_op_debug!("get_commit_details", hash = hash);
-    let oid = Oid::from_str(hash)?;
-    let commit = repo.find_commit(oid)?;
+    let commit = find_revision(repo, hash)?;
 
     let parent_hashes: Vec<String> = commit.parent_ids().map(|id| id.to_string()).collect();
+    let parent_subjects: Vec<String> = commit
+        .parents()
+        .map(|p| p.summary().ok().flatten().unwrap_or("").to_string())
+        .collect();
 
     // Extract author/committer info before borrowing for tree operations
     let commit_hash = commit.id().to_string();
@@ -117,6 +373,8 @@ pub fn get_commit_details(repo: &Repository, hash: &str) -> Result<CommitDetails
     let committer_name = committer.name().unwrap_or("").to_string();
     let committer_email = committer.email().unwrap_or("").to_string();
     let timestamp = commit.time().seconds();
+    let author_when = author.when();
+    let commit_when = committer.when();
 
     // Get changed files
     let tree = commit.tree()?;
@@ -126,40 +384,14 @@ pub fn get_commit_details(repo: &Repository, hash: &str) -> Result<CommitDetails
         None
     };
 
-    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
-    let files_changed: Vec<CommitFileChange> = diff
-        .deltas()
-        .filter_map(|delta| {
-            let path = delta
-                .new_file()
-                .path()
-                .map(|p| p.to_string_lossy().to_string())?;
-            let status = match delta.status() {
-                git2::Delta::Added => "added",
-                git2::Delta::Deleted => "deleted",
-                git2::Delta::Modified => "modified",
-                git2::Delta::Renamed => "renamed",
-                git2::Delta::Copied => "copied",
-                _ => "modified",
-            }
-            .to_string();
-            let old_path = if delta.status() == git2::Delta::Renamed
-                || delta.status() == git2::Delta::Copied
-            {
-                delta
-                    .old_file()
-                    .path()
-                    .map(|p| p.to_string_lossy().to_string())
-            } else {
-                None
-            };
-            Some(CommitFileChange {
-                path,
-                status,
-                old_path,
-            })
-        })
-        .collect();
+    let diff = diff_trees_with_renames(repo, parent_tree.as_ref(), Some(&tree))?;
+    let files_changed = diff_file_changes(&diff)?;
+    let diff_stats = diff.stats()?;
+    let stats = CommitStats {
+        files_changed: diff_stats.files_changed(),
+        insertions: diff_stats.insertions(),
+        deletions: diff_stats.deletions(),
+    };
 
     Ok(CommitDetails {
         hash: commit_hash,
@@ -169,14 +401,427 @@ pub fn get_commit_details(repo: &Repository, hash: &str) -> Result<CommitDetails
         committer_name,
         committer_email,
         timestamp,
+        author_time: author_when.seconds(),
+        author_offset_minutes: author_when.offset_minutes(),
+        commit_time: commit_when.seconds(),
+        commit_offset_minutes: commit_when.offset_minutes(),
         parent_hashes,
+        parent_subjects,
         files_changed,
+        stats,
     })
 }
 
-fn commit_to_info(commit: &git2::Commit) -> CommitInfo {
+/// Files `head` changes since it diverged from `base`: the diff from their
+/// merge base to `head`, so commits only on `base` don't show up. Both are
+/// any revision `find_revision` accepts.
+pub fn get_branch_diff_files(
+    repo: &Repository,
+    base: &str,
+    head: &str,
+) -> Result<Vec<CommitFileChange>, AppError> {
+    crate::log_git_op_debug!("get_branch_diff_files", base = base, head = head);
+    let base = find_revision(repo, base)?;
+    let head = find_revision(repo, head)?;
+    let merge_base = repo
+        .merge_base(base.id(), head.id())
+        .map_err(super::merge::unrelated_histories)?;
+    let merge_base = repo.find_commit(merge_base)?;
+
+    let diff = diff_trees_with_renames(repo, Some(&merge_base.tree()?), Some(&head.tree()?))?;
+    diff_file_changes(&diff)
+}
+
+/// Diff `old` against `new` with renames detected; without `find_similar` a
+/// rename is reported as a delete plus an add.
+pub(crate) fn diff_trees_with_renames<'r>(
+    repo: &'r Repository,
+    old: Option<&git2::Tree>,
+    new: Option<&git2::Tree>,
+) -> Result<git2::Diff<'r>, AppError> {
+    let mut diff = repo.diff_tree_to_tree(old, new, None)?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+    Ok(diff)
+}
+
+/// One `CommitFileChange` per delta, with per-file line counts. Binary files
+/// report zero additions and deletions.
+pub(crate) fn diff_file_changes(diff: &git2::Diff) -> Result<Vec<CommitFileChange>, AppError> {
+    let mut changes = Vec::with_capacity(diff.deltas().len());
+    for (idx, delta) in diff.deltas().enumerate() {
+        let Some(path) = delta
+            .new_file()
+            .path()
+            .map(|p| p.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let status = match delta.status() {
+            git2::Delta::Added => "added",
+            git2::Delta::Deleted => "deleted",
+            git2::Delta::Modified => "modified",
+            git2::Delta::Renamed => "renamed",
+            git2::Delta::Copied => "copied",
+            _ => "modified",
+        }
+        .to_string();
+        let old_path =
+            if delta.status() == git2::Delta::Renamed || delta.status() == git2::Delta::Copied {
+                delta
+                    .old_file()
+                    .path()
+                    .map(|p| p.to_string_lossy().to_string())
+            } else {
+                None
+            };
+        // No patch is produced for binary (or unchanged) files.
+        let (additions, deletions) = match git2::Patch::from_diff(diff, idx)? {
+            Some(patch) if !patch.delta().flags().is_binary() => {
+                let (_, additions, deletions) = patch.line_stats()?;
+                (additions, deletions)
+            }
+            _ => (0, 0),
+        };
+        changes.push(CommitFileChange {
+            path,
+            status,
+            old_path,
+            additions,
+            deletions,
+        });
+    }
+    Ok(changes)
+}
+
+const SUBJECT_MAX_LEN: usize = 50;
+const BODY_LINE_MAX_LEN: usize = 72;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageIssueKind {
+    Empty,
+    SubjectTooLong,
+    MissingBlankLine,
+    BodyLineTooLong,
+}
+
+/// Errors block a `strict` commit; warnings are advisory.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageIssueLevel {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+pub struct CommitMessageIssue {
+    pub kind: MessageIssueKind,
+    pub level: MessageIssueLevel,
+    /// 1-based line the issue refers to, if any.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Lint a commit message against the usual conventions: non-empty, a subject
+/// of at most 50 chars, a blank line before the body, body lines of at most
+/// 72 chars. Lengths count chars, not bytes.
+pub fn validate_commit_message(message: &str) -> Vec<CommitMessageIssue> {
+    let mut issues = Vec::new();
+    if message.trim().is_empty() {
+        issues.push(CommitMessageIssue {
+            kind: MessageIssueKind::Empty,
+            level: MessageIssueLevel::Error,
+            line: None,
+            message: "Commit message is empty".to_string(),
+        });
+        return issues;
+    }
+
+    let lines: Vec<&str> = message.trim_end().lines().collect();
+    let subject_len = lines[0].chars().count();
+    if subject_len > SUBJECT_MAX_LEN {
+        issues.push(CommitMessageIssue {
+            kind: MessageIssueKind::SubjectTooLong,
+            level: MessageIssueLevel::Warning,
+            line: Some(1),
+            message: format!("Subject is {subject_len} characters (max {SUBJECT_MAX_LEN})"),
+        });
+    }
+    if lines.get(1).is_some_and(|line| !line.trim().is_empty()) {
+        issues.push(CommitMessageIssue {
+            kind: MessageIssueKind::MissingBlankLine,
+            level: MessageIssueLevel::Warning,
+            line: Some(2),
+            message: "Separate the subject from the body with a blank line".to_string(),
+        });
+    }
+    for (index, line) in lines.iter().enumerate().skip(1) {
+        let len = line.chars().count();
+        if len > BODY_LINE_MAX_LEN {
+            issues.push(CommitMessageIssue {
+                kind: MessageIssueKind::BodyLineTooLong,
+                level: MessageIssueLevel::Warning,
+                line: Some(index + 1),
+                message: format!(
+                    "Line {} is {len} characters (max {BODY_LINE_MAX_LEN})",
+                    index + 1
+                ),
+            });
+        }
+    }
+    issues
+}
+
+/// Fails with the first error-level issue from [`validate_commit_message`].
+pub fn ensure_valid_commit_message(message: &str) -> Result<(), AppError> {
+    match validate_commit_message(message)
+        .into_iter()
+        .find(|issue| issue.level == MessageIssueLevel::Error)
+    {
+        Some(issue) => Err(AppError::InvalidCommitMessage(issue.message)),
+        None => Ok(()),
+    }
+}
+
+/// Optional overrides for the author of a new commit. Unset fields fall back
+/// to the committer identity and the current time.
+#[derive(Debug, Default, Clone)]
+pub struct AuthorOverride {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub time: Option<i64>,
+}
+
+/// Loose sanity check: something@something, no whitespace.
+fn looks_like_email(email: &str) -> bool {
+    match email.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.is_empty()
+                && !domain.contains('@')
+                && !email.chars().any(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+/// Commit the current index on top of HEAD (or as a root commit). The
+/// committer comes from the repo config; `author` can attribute the commit
+/// to someone else. `sign` overrides `commit.gpgsign`; `skip_hooks` is
+/// `--no-verify`. Unless `allow_empty`, an index that matches HEAD fails
+/// with `AppError::NothingToCommit`.
+pub fn create_commit(
+    repo: &Repository,
+    message: &str,
+    author: &AuthorOverride,
+    sign: Option<bool>,
+    skip_hooks: bool,
+    allow_empty: bool,
+) -> Result<String, AppError> {
+    crate::log_git_op!(
+        "create_commit",
+        msg_len = message.len(),
+        author_override = author.name.is_some() || author.email.is_some() || author.time.is_some()
+    );
+    if let Some(email) = author.email.as_deref() {
+        if !looks_like_email(email) {
+            return Err(AppError::InvalidEmail(email.to_string()));
+        }
+    }
+
+    let committer = repo
+        .signature()
+        .unwrap_or_else(|_| Signature::now("Unknown", "unknown@example.com").unwrap());
+    let author_name = author
+        .name
+        .as_deref()
+        .unwrap_or_else(|| committer.name().unwrap_or("Unknown"));
+    let author_email = author
+        .email
+        .as_deref()
+        .unwrap_or_else(|| committer.email().unwrap_or("unknown@example.com"));
+    let author_sig = match author.time {
+        Some(seconds) => Signature::new(
+            author_name,
+            author_email,
+            &git2::Time::new(seconds, committer.when().offset_minutes()),
+        )?,
+        None => Signature::new(author_name, author_email, &committer.when())?,
+    };
+
+    let mut index = repo.index()?;
+    if !skip_hooks {
+        super::run_hook(repo, "pre-commit")?;
+        // The hook may have restaged files (e.g. after formatting).
+        index.read(true)?;
+    }
+    let tree_oid = index.write_tree()?;
+    let tree = repo.find_tree(tree_oid)?;
+
+    let parent = if let Ok(head) = repo.head() {
+        Some(head.peel_to_commit()?)
+    } else {
+        None
+    };
+    let unchanged = match &parent {
+        Some(parent) => parent.tree_id() == tree_oid,
+        None => index.is_empty(),
+    };
+    if unchanged && !allow_empty {
+        return Err(AppError::NothingToCommit);
+    }
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+
+    let commit_oid = match super::signing_config(repo, sign)? {
+        Some(config) => {
+            let oid = super::create_signed_commit(
+                repo,
+                &config,
+                &author_sig,
+                &committer,
+                message,
+                &tree,
+                &parents,
+            )?;
+            // commit_signed only writes the object; move HEAD like
+            // repo.commit(Some("HEAD"), ..) would. Updating through HEAD
+            // logs the commit in HEAD's reflog as well as the branch's.
+            let summary = message.lines().next().unwrap_or("");
+            let reflog = if parents.is_empty() {
+                format!("commit (initial): {summary}")
+            } else {
+                format!("commit: {summary}")
+            };
+            match repo.head() {
+                Ok(mut head) => {
+                    head.set_target(oid, &reflog)?;
+                }
+                Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+                    let head = repo.find_reference("HEAD")?;
+                    let branch_ref = head.symbolic_target()?.ok_or(e)?;
+                    repo.reference(branch_ref, oid, false, &reflog)?;
+                }
+                Err(e) => return Err(e.into()),
+            }
+            oid
+        }
+        None => repo.commit(
+            Some("HEAD"),
+            &author_sig,
+            &committer,
+            message,
+            &tree,
+            &parents,
+        )?,
+    };
+
+    Ok(commit_oid.to_string())
+}
+
+/// Replace HEAD with a commit built from the current index. The original
+/// author is kept and the committer is refreshed from the repo config. With
+/// `message` of `None` the original message is reused.
+pub fn amend_commit(repo: &Repository, message: Option<&str>) -> Result<String, AppError> {
+    crate::log_git_op!("amend_commit", new_message = message.is_some());
+    let head_commit = match repo.head() {
+        Ok(head) => head.peel_to_commit()?,
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+            return Err(AppError::Git(git2::Error::new(
+                git2::ErrorCode::UnbornBranch,
+                git2::ErrorClass::Reference,
+                "No commit to amend",
+            )))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut index = repo.index()?;
+    let tree_oid = index.write_tree()?;
+    let tree = repo.find_tree(tree_oid)?;
+    let committer = repo.signature()?;
+
+    // Passing `None` for author keeps the original author signature.
+    let new_oid = head_commit.amend(
+        Some("HEAD"),
+        None,
+        Some(&committer),
+        None,
+        message,
+        Some(&tree),
+    )?;
+
+    Ok(new_oid.to_string())
+}
+
+/// Squash the last `count` commits (following first parents) into one with
+/// `message`: a commit of HEAD's tree on top of `HEAD~count`, which HEAD
+/// then moves to. The oldest squashed commit's author is kept. Staged
+/// changes stay staged rather than being folded in. Refuses if `HEAD~count` doesn't exist,
+/// i.e. when the squash would have to include a root commit.
+pub fn squash_commits(repo: &Repository, count: usize, message: &str) -> Result<String, AppError> {
+    crate::log_git_op!("squash_commits", count = count, msg_len = message.len());
+    if count == 0 {
+        return Err(AppError::InvalidArgument(
+            "Nothing to squash: count must be at least 1".to_string(),
+        ));
+    }
+    if message.trim().is_empty() {
+        return Err(AppError::InvalidCommitMessage(
+            "Commit message is empty".to_string(),
+        ));
+    }
+    let head = match repo.head() {
+        Ok(head) => head.peel_to_commit()?,
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {
+            return Err(AppError::Git(git2::Error::new(
+                git2::ErrorCode::UnbornBranch,
+                git2::ErrorClass::Reference,
+                "No commits to squash",
+            )))
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut oldest = head.clone();
+    for depth in 1..count {
+        oldest = oldest.parent(0).map_err(|_| {
+            AppError::InvalidArgument(format!(
+                "Cannot squash {count} commits: history has only {depth}"
+            ))
+        })?;
+    }
+    let base = oldest.parent(0).map_err(|_| {
+        AppError::InvalidArgument(format!(
+            "Cannot squash {count} commits: that would include the root commit"
+        ))
+    })?;
+
+    let tree = head.tree()?;
+    let author = oldest.author();
+    let committer = repo.signature()?;
+    // Write the commit first and move HEAD once, so a failure leaves HEAD
+    // where it was.
+    let oid = repo.commit(None, &author, &committer, message, &tree, &[&base])?;
+    let summary = message.lines().next().unwrap_or("");
+    repo.head()?
+        .set_target(oid, &format!("squash {count} commits: {summary}"))?;
+
+    Ok(oid.to_string())
+}
+
+/// Hex MD5 of the trimmed, lowercased email: the key Gravatar and similar
+/// avatar services look addresses up by.
+pub fn email_hash(email: &str) -> String {
+    format!("{:x}", md5::compute(email.trim().to_lowercase()))
+}
+
+pub(super) fn commit_to_info(commit: &git2::Commit) -> CommitInfo {
     let hash = commit.id().to_string();
     let short_hash = hash[..7.min(hash.len())].to_string();
+    let author = commit.author();
+    let author_when = author.when();
+    let commit_when = commit.committer().when();
 
     CommitInfo {
         hash,
@@ -188,9 +833,14 @@ fn commit_to_info(commit: &git2::Commit) -> CommitInfo {
             .next()
             .unwrap_or("")
             .to_string(),
-        author_name: commit.author().name().unwrap_or("").to_string(),
-        author_email: commit.author().email().unwrap_or("").to_string(),
+        author_name: author.name().unwrap_or("").to_string(),
+        author_email: author.email().unwrap_or("").to_string(),
+        author_email_hash: email_hash(author.email().unwrap_or("")),
         timestamp: commit.time().seconds(),
+        author_time: author_when.seconds(),
+        author_offset_minutes: author_when.offset_minutes(),
+        commit_time: commit_when.seconds(),
+        commit_offset_minutes: commit_when.offset_minutes(),
         parent_hashes: commit.parent_ids().map(|id| id.to_string()).collect(),
     }
 }
@@ -202,6 +852,85 @@ mod tests {
     use std::path::Path;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_email_hash_matches_gravatar() {
+        // Example from Gravatar's documentation.
+        assert_eq!(
+            email_hash(" MyEmailAddress@example.com "),
+            "0bc83cb571cd1c50ba6f3e8a78ef1346"
+        );
+        assert_eq!(email_hash("a@b.c"), email_hash("A@B.C"));
+    }
+
+    #[test]
+    fn test_looks_like_email() {
+        assert!(looks_like_email("a@b.c"));
+        assert!(looks_like_email("pair+dev@example.com"));
+        for bad in ["", "plain", "@example.com", "user@", "a@b@c", "a b@c.d"] {
+            assert!(!looks_like_email(bad), "{bad:?}");
+        }
+    }
+
+    fn kinds(message: &str) -> Vec<MessageIssueKind> {
+        validate_commit_message(message)
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_commit_message_clean() {
+        assert!(kinds("Fix the thing").is_empty());
+        assert!(kinds("Fix the thing\n\nLonger explanation.\n").is_empty());
+    }
+
+    #[test]
+    fn test_validate_commit_message_empty() {
+        for message in ["", "  \n\n\t"] {
+            let issues = validate_commit_message(message);
+            assert_eq!(issues.len(), 1);
+            assert_eq!(issues[0].kind, MessageIssueKind::Empty);
+            assert_eq!(issues[0].level, MessageIssueLevel::Error);
+        }
+    }
+
+    #[test]
+    fn test_validate_commit_message_subject_too_long() {
+        assert!(kinds(&"a".repeat(50)).is_empty());
+        let issues = validate_commit_message(&"a".repeat(51));
+        assert_eq!(issues[0].kind, MessageIssueKind::SubjectTooLong);
+        assert_eq!(issues[0].level, MessageIssueLevel::Warning);
+        assert_eq!(issues[0].line, Some(1));
+        // Counted in chars, so multi-byte subjects aren't penalised.
+        assert!(kinds(&"é".repeat(50)).is_empty());
+    }
+
+    #[test]
+    fn test_validate_commit_message_missing_blank_line() {
+        let issues = validate_commit_message("Subject\nBody straight away");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, MessageIssueKind::MissingBlankLine);
+        assert_eq!(issues[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_validate_commit_message_body_line_too_long() {
+        let message = format!("Subject\n\n{}\n{}", "b".repeat(72), "b".repeat(73));
+        let issues = validate_commit_message(&message);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, MessageIssueKind::BodyLineTooLong);
+        assert_eq!(issues[0].line, Some(4));
+    }
+
+    #[test]
+    fn test_ensure_valid_commit_message_only_rejects_errors() {
+        assert!(ensure_valid_commit_message(&"a".repeat(80)).is_ok());
+        assert!(matches!(
+            ensure_valid_commit_message(""),
+            Err(AppError::InvalidCommitMessage(_))
+        ));
+    }
+
     // Only test that genuinely needs private access — exercises commit_to_info
     // (private helper). Behavior tests for `get_commits` / `get_commit_details`
     // live in tests/commits.rs. The setup is inlined here (rather than using
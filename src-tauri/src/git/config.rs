@@ -0,0 +1,81 @@
+use git2::{Config, ErrorCode, Repository};
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigScope {
+    /// The repository's own `.git/config`.
+    Local,
+    /// The user's `~/.gitconfig` (or the XDG file if that's what git uses).
+    Global,
+}
+
+impl ConfigScope {
+    pub fn parse(scope: &str) -> Result<Self, AppError> {
+        match scope {
+            "local" => Ok(ConfigScope::Local),
+            "global" => Ok(ConfigScope::Global),
+            other => Err(AppError::InvalidArgument(format!(
+                "Unknown config scope: {other} (expected local or global)"
+            ))),
+        }
+    }
+}
+
+/// Effective value of `key` (e.g. `user.email`) as git sees it for this
+/// repository, local settings winning over global and system ones. `None`
+/// if it isn't set anywhere.
+pub fn get_config(repo: &Repository, key: &str) -> Result<Option<String>, AppError> {
+    crate::log_git_op_debug!("get_config", key = key);
+    match repo.config()?.get_string(key) {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.code() == ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Write `key = value` at `scope`. Commits pick the change up straight away:
+/// `create_commit` and friends read `user.name`/`user.email` through
+/// `repo.signature()`, which consults the config on every call.
+pub fn set_config(
+    repo: &Repository,
+    key: &str,
+    value: &str,
+    scope: ConfigScope,
+) -> Result<(), AppError> {
+    crate::log_git_op!("set_config", key = key, scope = scope);
+    let mut config = match scope {
+        ConfigScope::Local => repo.config()?.open_level(git2::ConfigLevel::Local)?,
+        ConfigScope::Global => {
+            // find_global fails when the file doesn't exist yet; writing to
+            // ~/.gitconfig creates it, as `git config --global` would.
+            let path = match Config::find_global() {
+                Ok(path) => path,
+                Err(_) => dirs::home_dir()
+                    .ok_or_else(|| {
+                        AppError::Internal("Cannot locate the home directory".to_string())
+                    })?
+                    .join(".gitconfig"),
+            };
+            Config::open(&path)?
+        }
+    };
+    config.set_str(key, value)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_scope_parse() {
+        assert_eq!(ConfigScope::parse("local").unwrap(), ConfigScope::Local);
+        assert_eq!(ConfigScope::parse("global").unwrap(), ConfigScope::Global);
+        assert!(matches!(
+            ConfigScope::parse("system"),
+            Err(AppError::InvalidArgument(_))
+        ));
+        assert!(ConfigScope::parse("LOCAL").is_err());
+    }
+}
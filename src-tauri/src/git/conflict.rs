@@ -0,0 +1,110 @@
+use std::path::Path;
+
+use git2::{Index, IndexConflict, IndexEntry, Repository};
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// One side of a conflict, read from an index stage.
+#[derive(Debug, Serialize, Clone)]
+pub struct ConflictStage {
+    /// Blob content, or `None` when the blob is binary.
+    pub content: Option<String>,
+    /// Git filemode, e.g. `0o100644` or `0o100755`.
+    pub filemode: u32,
+    pub is_binary: bool,
+}
+
+/// The three versions of a conflicted file. A side is `None` when that stage
+/// is absent, e.g. no ancestor for an add/add conflict or no `theirs` when
+/// the other branch deleted the file.
+#[derive(Debug, Serialize, Clone)]
+pub struct ConflictInfo {
+    pub path: String,
+    pub ancestor: Option<ConflictStage>,
+    pub ours: Option<ConflictStage>,
+    pub theirs: Option<ConflictStage>,
+}
+
+fn read_stage(
+    repo: &Repository,
+    entry: Option<IndexEntry>,
+) -> Result<Option<ConflictStage>, AppError> {
+    let Some(entry) = entry else {
+        return Ok(None);
+    };
+    let blob = repo.find_blob(entry.id)?;
+    // libgit2's heuristic catches NUL bytes; invalid UTF-8 is binary to us too
+    // since returning it lossily would corrupt the content on resolve.
+    let content = if blob.is_binary() {
+        None
+    } else {
+        std::str::from_utf8(blob.content()).ok().map(String::from)
+    };
+    Ok(Some(ConflictStage {
+        is_binary: content.is_none(),
+        content,
+        filemode: entry.mode,
+    }))
+}
+
+fn conflict_touches(conflict: &IndexConflict, path: &str) -> bool {
+    [&conflict.ancestor, &conflict.our, &conflict.their]
+        .iter()
+        .any(|e| e.as_ref().is_some_and(|e| e.path == path.as_bytes()))
+}
+
+/// Error unless `index` has conflict stages for `path`.
+pub(crate) fn ensure_conflicted(index: &Index, path: &str) -> Result<(), AppError> {
+    let is_conflicted = index
+        .conflicts()?
+        .any(|c| c.ok().is_some_and(|c| conflict_touches(&c, path)));
+    if !is_conflicted {
+        return Err(AppError::NotConflicted(path.to_string()));
+    }
+    Ok(())
+}
+
+/// Read index stages 1/2/3 (ancestor/ours/theirs) for a conflicted path.
+pub fn get_conflict(repo: &Repository, path: &str) -> Result<ConflictInfo, AppError> {
+    crate::log_git_op_debug!("get_conflict", path = path);
+    let index = repo.index()?;
+
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        if conflict_touches(&conflict, path) {
+            return Ok(ConflictInfo {
+                path: path.to_string(),
+                ancestor: read_stage(repo, conflict.ancestor)?,
+                ours: read_stage(repo, conflict.our)?,
+                theirs: read_stage(repo, conflict.their)?,
+            });
+        }
+    }
+
+    Err(AppError::NotConflicted(path.to_string()))
+}
+
+/// Resolve a conflicted file with caller-supplied content: write it to the
+/// working tree, drop the conflict stages from the index, and stage the
+/// result. Errors if `path` isn't currently conflicted.
+pub fn resolve_conflict_with_content(
+    repo: &Repository,
+    path: &str,
+    content: &str,
+) -> Result<(), AppError> {
+    crate::log_git_op!("resolve_conflict_with_content", path = path);
+    let mut index = repo.index()?;
+    ensure_conflicted(&index, path)?;
+
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
+    std::fs::write(workdir.join(path), content)?;
+
+    // remove_path clears every stage for the path; add_path then stages the
+    // resolved file as a normal stage-0 entry.
+    index.remove_path(Path::new(path))?;
+    index.add_path(Path::new(path))?;
+    index.write()?;
+
+    Ok(())
+}
@@ -1,12 +1,17 @@
 use std::fs;
 use std::io::{BufRead, BufReader};
+use std::path::Path;
 
-use git2::{DiffOptions, Oid, Repository};
+use git2::{Delta, DiffFindOptions, DiffOptions, Repository};
 use serde::Serialize;
 
+use super::cancel::CancelToken;
+use super::commit::{diff_trees_with_renames, find_revision};
 use crate::error::AppError;
 
-/// Diff size limits. Currently hard-coded; structured for future user configuration.
+/// Diff size limits. Held in `AppState` so `set_diff_limits` can adjust them
+/// for the session; not persisted.
+#[derive(Debug, Clone, Copy)]
 pub struct DiffConfig {
     /// Max cumulative bytes of line content before remaining hunks are returned unloaded.
     pub max_diff_bytes: usize,
@@ -23,7 +28,7 @@ impl Default for DiffConfig {
     }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Clone, Default)]
 pub struct FileDiff {
     pub path: String,
     pub hunks: Vec<DiffHunk>,
@@ -31,6 +36,113 @@ pub struct FileDiff {
     pub total_lines: u32,
     #[serde(default)]
     pub is_conflicted: bool,
+    /// Computed with whitespace changes ignored. Such hunks don't match the
+    /// real file content, so staging/discarding them must be disabled.
+    #[serde(default)]
+    pub whitespace_ignored: bool,
+    /// Blob sizes in bytes, set for binary files so the UI can show something
+    /// in place of hunks. `None` on the side where the file doesn't exist.
+    #[serde(default)]
+    pub old_size: Option<u64>,
+    #[serde(default)]
+    pub new_size: Option<u64>,
+    /// One side is a Git LFS pointer file. Hunks are left empty since the
+    /// pointer text means nothing to the user; `old_lfs`/`new_lfs` say which
+    /// object changed.
+    #[serde(default)]
+    pub is_lfs_pointer: bool,
+    #[serde(default)]
+    pub old_lfs: Option<LfsPointer>,
+    #[serde(default)]
+    pub new_lfs: Option<LfsPointer>,
+    /// Best guess at the new side's encoding: `"utf-8"`, or for other
+    /// content `"iso-8859-1"`/`"windows-1252"`. `None` when there are no
+    /// new-side lines to look at.
+    #[serde(default)]
+    pub detected_encoding: Option<String>,
+    /// Line endings of the new side's lines.
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    /// The new side isn't valid UTF-8, so line contents contain replacement
+    /// characters and the UI should warn rather than trust them.
+    #[serde(default)]
+    pub is_non_utf8: bool,
+    /// The file's previous path when this diff is of a rename.
+    #[serde(default)]
+    pub old_path: Option<String>,
+}
+
+impl FileDiff {
+    /// An empty text diff of `path`; callers fill in whatever differs.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+    Mixed,
+}
+
+/// Tallies line endings and UTF-8 validity over raw lines of one side of a
+/// diff, before they go through `from_utf8_lossy`.
+#[derive(Default)]
+pub(crate) struct TextStats {
+    lines: usize,
+    lf: usize,
+    crlf: usize,
+    non_utf8: bool,
+    /// Bytes 0x80-0x9F, which are control codes in Latin-1 but printable
+    /// in Windows-1252.
+    c1_bytes: bool,
+}
+
+impl TextStats {
+    pub(crate) fn add_line(&mut self, bytes: &[u8]) {
+        self.lines += 1;
+        if bytes.ends_with(b"\r\n") {
+            self.crlf += 1;
+        } else if bytes.ends_with(b"\n") {
+            self.lf += 1;
+        }
+        if std::str::from_utf8(bytes).is_err() {
+            self.non_utf8 = true;
+            self.c1_bytes |= bytes.iter().any(|b| (0x80..0xA0).contains(b));
+        }
+    }
+
+    pub(crate) fn apply(&self, file_diff: &mut FileDiff) {
+        file_diff.line_ending = match (self.lf > 0, self.crlf > 0) {
+            (true, true) => LineEnding::Mixed,
+            (false, true) => LineEnding::Crlf,
+            _ => LineEnding::Lf,
+        };
+        file_diff.is_non_utf8 = self.non_utf8;
+        file_diff.detected_encoding = (self.lines > 0).then(|| {
+            match (self.non_utf8, self.c1_bytes) {
+                (false, _) => "utf-8",
+                (true, false) => "iso-8859-1",
+                (true, true) => "windows-1252",
+            }
+            .to_string()
+        });
+    }
+}
+
+/// The object a Git LFS pointer file stands for.
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct LfsPointer {
+    /// As written in the pointer, e.g. `sha256:4d7a...`.
+    pub oid: String,
+    /// Size of the real file in bytes.
+    pub size: u64,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -50,6 +162,10 @@ pub struct DiffLine {
     pub line_type: LineType,
     pub old_lineno: Option<u32>,
     pub new_lineno: Option<u32>,
+    /// This is the file's last line and has no trailing newline (git's
+    /// "\ No newline at end of file").
+    #[serde(default)]
+    pub no_newline: bool,
 }
 
 #[derive(Debug, Serialize, Clone, PartialEq)]
@@ -74,23 +190,19 @@ struct DiffPrintCollector {
     bytes_collected: usize,
     budget_exceeded: bool,
     max_diff_bytes: usize,
+    new_side: TextStats,
 }
 
 impl DiffPrintCollector {
     fn new(path: &str, max_diff_bytes: usize) -> Self {
         Self {
-            file_diff: FileDiff {
-                path: path.to_string(),
-                hunks: Vec::new(),
-                is_binary: false,
-                total_lines: 0,
-                is_conflicted: false,
-            },
+            file_diff: FileDiff::new(path),
             current_hunk: None,
             current_hunk_header: None,
             bytes_collected: 0,
             budget_exceeded: false,
             max_diff_bytes,
+            new_side: TextStats::default(),
         }
     }
 
@@ -102,6 +214,9 @@ impl DiffPrintCollector {
     ) -> bool {
         if delta.flags().contains(git2::DiffFlags::BINARY) {
             self.file_diff.is_binary = true;
+            let size = |file: git2::DiffFile<'_>| file.exists().then(|| file.size());
+            self.file_diff.old_size = size(delta.old_file());
+            self.file_diff.new_size = size(delta.new_file());
             return true;
         }
 
@@ -128,6 +243,9 @@ impl DiffPrintCollector {
         }
 
         if let Some(ref mut hunk) = self.current_hunk {
+            if matches!(line.origin(), '+' | ' ') {
+                self.new_side.add_line(line.content());
+            }
             let content = String::from_utf8_lossy(line.content()).to_string();
             let line_type = match line.origin() {
                 '+' => LineType::Addition,
@@ -136,6 +254,14 @@ impl DiffPrintCollector {
                 _ => LineType::Header,
             };
 
+            // libgit2 reports "\ No newline at end of file" as its own line
+            // ('=', '>' or '<') right after the line it applies to.
+            if matches!(line.origin(), '=' | '>' | '<') {
+                if let Some(last) = hunk.lines.last_mut() {
+                    last.no_newline = true;
+                }
+            }
+
             self.file_diff.total_lines += 1;
 
             if hunk.is_loaded {
@@ -145,6 +271,7 @@ impl DiffPrintCollector {
                     line_type,
                     old_lineno: line.old_lineno(),
                     new_lineno: line.new_lineno(),
+                    no_newline: false,
                 });
 
                 if self.bytes_collected > self.max_diff_bytes {
@@ -160,27 +287,36 @@ impl DiffPrintCollector {
         if let Some(h) = self.current_hunk.take() {
             self.file_diff.hunks.push(h);
         }
+        self.new_side.apply(&mut self.file_diff);
         self.file_diff
     }
 }
 
 pub fn get_file_diff(repo: &Repository, path: &str, staged: bool) -> Result<FileDiff, AppError> {
-    crate::log_git_op_debug!("get_file_diff", path = path, staged = staged);
-    get_file_diff_with_config(repo, path, staged, &DiffConfig::default())
+    // Logged by `get_file_diff_cancellable`.
+    get_file_diff_with_config(repo, path, staged, &DiffConfig::default(), false, None)
 }
 
-pub fn get_file_diff_with_config(
-    repo: &Repository,
+/// HEAD-to-index (`staged`) or index-to-workdir diff of `path`.
+fn working_diff<'r>(
+    repo: &'r Repository,
     path: &str,
     staged: bool,
-    config: &DiffConfig,
-) -> Result<FileDiff, AppError> {
-    crate::log_git_op_debug!("get_file_diff_with_config", path = path, staged = staged);
+    ignore_whitespace: bool,
+    context_lines: Option<u32>,
+) -> Result<git2::Diff<'r>, AppError> {
     let mut diff_opts = DiffOptions::new();
     diff_opts.pathspec(path);
+    if let Some(lines) = context_lines {
+        diff_opts.context_lines(lines);
+    }
     diff_opts.include_untracked(true);
     diff_opts.show_untracked_content(true);
     diff_opts.recurse_untracked_dirs(true);
+    if ignore_whitespace {
+        diff_opts.ignore_whitespace(true);
+        diff_opts.ignore_whitespace_eol(true);
+    }
 
     let diff = if staged {
         // Staged: diff between HEAD and index
@@ -190,13 +326,198 @@ pub fn get_file_diff_with_config(
         // Unstaged: diff between index and workdir (includes untracked files)
         repo.diff_index_to_workdir(None, Some(&mut diff_opts))?
     };
+    Ok(diff)
+}
 
-    let mut collector = DiffPrintCollector::new(path, config.max_diff_bytes);
-    diff.print(git2::DiffFormat::Patch, |d, h, l| {
-        collector.handle_line(d, h, l)
+/// The same diff as `get_file_diff`, as unified-diff text that `git apply`
+/// accepts. Not subject to `DiffConfig` limits; the caller asked for all of it.
+pub fn get_file_patch(repo: &Repository, path: &str, staged: bool) -> Result<String, AppError> {
+    crate::log_git_op_debug!("get_file_patch", path = path, staged = staged);
+    let diff = working_diff(repo, path, staged, false, None)?;
+
+    let mut patch = Vec::new();
+    diff.print(git2::DiffFormat::Patch, |_, _, line| {
+        // File and hunk headers carry their own text; content lines need
+        // their origin put back in front.
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin() as u8);
+        }
+        patch.extend_from_slice(line.content());
+        true
     })?;
+    Ok(String::from_utf8_lossy(&patch).into_owned())
+}
 
-    Ok(collector.finish())
+/// `context_lines` overrides git's default of 3. Hunk boundaries and indices
+/// depend on it, so `get_diff_hunk` and the hunk staging/discard functions
+/// must be given the same value.
+pub fn get_file_diff_with_config(
+    repo: &Repository,
+    path: &str,
+    staged: bool,
+    config: &DiffConfig,
+    ignore_whitespace: bool,
+    context_lines: Option<u32>,
+) -> Result<FileDiff, AppError> {
+    // Logged by `get_file_diff_cancellable`.
+    get_file_diff_cancellable(
+        repo,
+        path,
+        staged,
+        config,
+        ignore_whitespace,
+        context_lines,
+        &CancelToken::new(),
+    )
+}
+
+/// `get_file_diff_with_config`, stopping with `AppError::Cancelled` once
+/// `cancel` is set. Checked per printed line, so a huge file can be abandoned
+/// partway through.
+pub fn get_file_diff_cancellable(
+    repo: &Repository,
+    path: &str,
+    staged: bool,
+    config: &DiffConfig,
+    ignore_whitespace: bool,
+    context_lines: Option<u32>,
+    cancel: &CancelToken,
+) -> Result<FileDiff, AppError> {
+    crate::log_git_op_debug!(
+        "get_file_diff_cancellable",
+        path = path,
+        staged = staged,
+        ignore_whitespace = ignore_whitespace,
+        context_lines = context_lines
+    );
+    cancel.check()?;
+    let diff = working_diff(repo, path, staged, ignore_whitespace, context_lines)?;
+
+    let mut collector = DiffPrintCollector::new(path, config.max_diff_bytes);
+    let printed = diff.print(git2::DiffFormat::Patch, |d, h, l| {
+        !cancel.is_cancelled() && collector.handle_line(d, h, l)
+    });
+    // Returning false from the callback surfaces as a generic libgit2 error.
+    cancel.check()?;
+    printed?;
+
+    let mut file_diff = collector.finish();
+    file_diff.whitespace_ignored = ignore_whitespace;
+
+    let delta = diff.deltas().find(|d| {
+        d.new_file().path() == Some(Path::new(path)) || d.old_file().path() == Some(Path::new(path))
+    });
+    if let Some(delta) = delta {
+        let old_lfs = lfs_pointer_for(repo, &delta.old_file(), false);
+        // The unstaged new side is the working tree file, which libgit2 may
+        // not have hashed.
+        let new_lfs = lfs_pointer_for(repo, &delta.new_file(), !staged);
+        if old_lfs.is_some() || new_lfs.is_some() {
+            file_diff.hunks.clear();
+            file_diff.total_lines = 0;
+            file_diff.is_lfs_pointer = true;
+            file_diff.old_lfs = old_lfs;
+            file_diff.new_lfs = new_lfs;
+        }
+    }
+    Ok(file_diff)
+}
+
+/// The LFS spec caps pointer files below this size.
+const LFS_POINTER_MAX_SIZE: usize = 1024;
+
+fn lfs_pointer_for(
+    repo: &Repository,
+    file: &git2::DiffFile<'_>,
+    in_workdir: bool,
+) -> Option<LfsPointer> {
+    if !file.exists() {
+        return None;
+    }
+    if in_workdir {
+        let full_path = repo.workdir()?.join(file.path()?);
+        if fs::metadata(&full_path).ok()?.len() >= LFS_POINTER_MAX_SIZE as u64 {
+            return None;
+        }
+        parse_lfs_pointer(&fs::read(full_path).ok()?)
+    } else {
+        let blob = repo.find_blob(file.id()).ok()?;
+        parse_lfs_pointer(blob.content())
+    }
+}
+
+/// Parse a Git LFS pointer: a `version https://git-lfs...` line followed by
+/// `key value` lines, of which `oid` and `size` are required.
+fn parse_lfs_pointer(content: &[u8]) -> Option<LfsPointer> {
+    if content.len() >= LFS_POINTER_MAX_SIZE {
+        return None;
+    }
+    let text = std::str::from_utf8(content).ok()?;
+    let mut lines = text.lines();
+    if !lines.next()?.starts_with("version https://git-lfs") {
+        return None;
+    }
+
+    let (mut oid, mut size) = (None, None);
+    for line in lines {
+        match line.split_once(' ') {
+            Some(("oid", value)) => oid = Some(value.to_string()),
+            Some(("size", value)) => size = Some(value.parse().ok()?),
+            _ => {}
+        }
+    }
+    Some(LfsPointer {
+        oid: oid?,
+        size: size?,
+    })
+}
+
+/// Both sides of a file's changes, read under one repository lock.
+#[derive(Debug, Serialize, Clone)]
+pub struct CombinedFileDiff {
+    pub staged: FileDiff,
+    pub unstaged: FileDiff,
+}
+
+pub fn get_combined_file_diff(
+    repo: &Repository,
+    path: &str,
+    config: &DiffConfig,
+    ignore_whitespace: bool,
+    context_lines: Option<u32>,
+) -> Result<CombinedFileDiff, AppError> {
+    crate::log_git_op_debug!("get_combined_file_diff", path = path);
+    // Same routing as the `get_file_diff` command, so untracked files are
+    // read under `max_file_size`.
+    let unstaged = if is_untracked(repo, path)? {
+        get_untracked_file_diff_with_config(repo, path, config)?
+    } else {
+        get_file_diff_with_config(repo, path, false, config, ignore_whitespace, context_lines)?
+    };
+    Ok(CombinedFileDiff {
+        staged: get_file_diff_with_config(
+            repo,
+            path,
+            true,
+            config,
+            ignore_whitespace,
+            context_lines,
+        )?,
+        unstaged,
+    })
+}
+
+/// Whether `path` exists only in the working tree, so its diff should come
+/// from `get_untracked_file_diff`, which honours `max_file_size`, rather than
+/// an index-to-workdir diff that reads the whole file into libgit2.
+/// Ignored and unknown paths are not untracked.
+pub fn is_untracked(repo: &Repository, path: &str) -> Result<bool, AppError> {
+    crate::log_git_op_debug!("is_untracked", path = path);
+    match repo.status_file(Path::new(path)) {
+        Ok(status) => Ok(status.contains(git2::Status::WT_NEW)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(false),
+        Err(e) => Err(e.into()),
+    }
 }
 
 /// Get diff for an untracked file by reading its content directly
@@ -211,9 +532,7 @@ pub fn get_untracked_file_diff_with_config(
     config: &DiffConfig,
 ) -> Result<FileDiff, AppError> {
     crate::log_git_op_debug!("get_untracked_file_diff_with_config", path = path);
-    let workdir = repo
-        .workdir()
-        .ok_or(AppError::InvalidPath("No working directory".into()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     let file_path = workdir.join(path);
 
     // Check file size first
@@ -223,36 +542,41 @@ pub fn get_untracked_file_diff_with_config(
     if file_size > config.max_file_size {
         // Large file: count lines and read only up to budget
         let file = fs::File::open(&file_path)?;
-        let reader = BufReader::new(file);
+        let mut reader = BufReader::new(file);
         let mut diff_lines: Vec<DiffLine> = Vec::new();
         let mut bytes_collected: usize = 0;
         let mut total_line_count: u32 = 0;
         let mut budget_exceeded = false;
+        let mut stats = TextStats::default();
+        let mut raw_line = Vec::new();
 
-        for line_result in reader.lines() {
-            let line_text = match line_result {
-                Ok(l) => l,
-                Err(_) => {
-                    // Likely binary content or encoding issue
-                    return Ok(FileDiff {
-                        path: path.to_string(),
-                        hunks: Vec::new(),
-                        is_binary: true,
-                        total_lines: 0,
-                        is_conflicted: false,
-                    });
-                }
-            };
+        loop {
+            raw_line.clear();
+            if reader.read_until(b'\n', &mut raw_line)? == 0 {
+                break;
+            }
+            if raw_line.contains(&0) {
+                return Ok(FileDiff {
+                    is_binary: true,
+                    ..FileDiff::new(path)
+                });
+            }
+            stats.add_line(&raw_line);
             total_line_count += 1;
 
             if !budget_exceeded {
-                let content = format!("{}\n", line_text);
+                let line_text = raw_line
+                    .strip_suffix(b"\n")
+                    .map(|l| l.strip_suffix(b"\r").unwrap_or(l))
+                    .unwrap_or(&raw_line);
+                let content = format!("{}\n", String::from_utf8_lossy(line_text));
                 bytes_collected += content.len();
                 diff_lines.push(DiffLine {
                     content,
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(total_line_count),
+                    no_newline: false,
                 });
                 if bytes_collected > config.max_diff_bytes {
                     budget_exceeded = true;
@@ -270,13 +594,13 @@ pub fn get_untracked_file_diff_with_config(
             is_loaded: !budget_exceeded,
         };
 
-        return Ok(FileDiff {
-            path: path.to_string(),
+        let mut file_diff = FileDiff {
             hunks: vec![hunk],
-            is_binary: false,
             total_lines: total_line_count,
-            is_conflicted: false,
-        });
+            ..FileDiff::new(path)
+        };
+        stats.apply(&mut file_diff);
+        return Ok(file_diff);
     }
 
     // Check if file is binary by looking for null bytes in first 8KB
@@ -285,11 +609,8 @@ pub fn get_untracked_file_diff_with_config(
 
     if is_binary {
         return Ok(FileDiff {
-            path: path.to_string(),
-            hunks: Vec::new(),
             is_binary: true,
-            total_lines: 0,
-            is_conflicted: false,
+            ..FileDiff::new(path)
         });
     }
 
@@ -298,13 +619,7 @@ pub fn get_untracked_file_diff_with_config(
     let lines: Vec<&str> = text.lines().collect();
 
     if lines.is_empty() {
-        return Ok(FileDiff {
-            path: path.to_string(),
-            hunks: Vec::new(),
-            is_binary: false,
-            total_lines: 0,
-            is_conflicted: false,
-        });
+        return Ok(FileDiff::new(path));
     }
 
     let total_lines = lines.len() as u32;
@@ -318,6 +633,7 @@ pub fn get_untracked_file_diff_with_config(
             line_type: LineType::Addition,
             old_lineno: None,
             new_lineno: Some((i + 1) as u32),
+            no_newline: i + 1 == lines.len() && !text.ends_with('\n'),
         })
         .collect();
 
@@ -331,13 +647,17 @@ pub fn get_untracked_file_diff_with_config(
         is_loaded: true,
     };
 
-    Ok(FileDiff {
-        path: path.to_string(),
+    let mut file_diff = FileDiff {
         hunks: vec![hunk],
-        is_binary: false,
         total_lines,
-        is_conflicted: false,
-    })
+        ..FileDiff::new(path)
+    };
+    let mut stats = TextStats::default();
+    for raw_line in content.split_inclusive(|&b| b == b'\n') {
+        stats.add_line(raw_line);
+    }
+    stats.apply(&mut file_diff);
+    Ok(file_diff)
 }
 
 /// State machine for parsing conflict markers in a file.
@@ -355,9 +675,7 @@ const CONFLICT_CONTEXT_LINES: usize = 3;
 /// Returns one hunk per conflict region with surrounding context lines.
 pub fn get_conflicted_file_diff(repo: &Repository, path: &str) -> Result<FileDiff, AppError> {
     crate::log_git_op_debug!("get_conflicted_file_diff", path = path);
-    let workdir = repo
-        .workdir()
-        .ok_or(AppError::InvalidPath("No working directory".into()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     let file_path = workdir.join(path);
 
     // Check if file is binary by looking for null bytes in first 8KB
@@ -366,11 +684,9 @@ pub fn get_conflicted_file_diff(repo: &Repository, path: &str) -> Result<FileDif
 
     if is_binary {
         return Ok(FileDiff {
-            path: path.to_string(),
-            hunks: Vec::new(),
             is_binary: true,
-            total_lines: 0,
             is_conflicted: true,
+            ..FileDiff::new(path)
         });
     }
 
@@ -379,11 +695,8 @@ pub fn get_conflicted_file_diff(repo: &Repository, path: &str) -> Result<FileDif
 
     if lines.is_empty() {
         return Ok(FileDiff {
-            path: path.to_string(),
-            hunks: Vec::new(),
-            is_binary: false,
-            total_lines: 0,
             is_conflicted: true,
+            ..FileDiff::new(path)
         });
     }
 
@@ -411,11 +724,9 @@ pub fn get_conflicted_file_diff(repo: &Repository, path: &str) -> Result<FileDif
     if regions.is_empty() {
         // No conflict markers found — return empty
         return Ok(FileDiff {
-            path: path.to_string(),
-            hunks: Vec::new(),
-            is_binary: false,
             total_lines: total_lines as u32,
             is_conflicted: true,
+            ..FileDiff::new(path)
         });
     }
 
@@ -490,6 +801,7 @@ pub fn get_conflicted_file_diff(repo: &Repository, path: &str) -> Result<FileDif
                 line_type,
                 old_lineno: None,
                 new_lineno: Some(lineno),
+                no_newline: false,
             });
         }
 
@@ -506,11 +818,10 @@ pub fn get_conflicted_file_diff(repo: &Repository, path: &str) -> Result<FileDif
     }
 
     Ok(FileDiff {
-        path: path.to_string(),
         hunks,
-        is_binary: false,
         total_lines: total_lines as u32,
         is_conflicted: true,
+        ..FileDiff::new(path)
     })
 }
 
@@ -527,7 +838,7 @@ pub fn get_conflicted_diff_hunk(
         .hunks
         .into_iter()
         .nth(hunk_index)
-        .ok_or_else(|| AppError::InvalidPath(format!("Hunk index {} out of range", hunk_index)))
+        .ok_or(AppError::HunkOutOfRange(hunk_index))
 }
 
 pub fn get_commit_file_diff(
@@ -546,8 +857,7 @@ pub fn get_commit_file_diff_with_config(
     config: &DiffConfig,
 ) -> Result<FileDiff, AppError> {
     crate::log_git_op_debug!("get_commit_file_diff_with_config", hash = hash, path = path);
-    let oid = Oid::from_str(hash)?;
-    let commit = repo.find_commit(oid)?;
+    let commit = find_revision(repo, hash)?;
     let tree = commit.tree()?;
 
     let parent_tree = if commit.parent_count() > 0 {
@@ -559,41 +869,365 @@ pub fn get_commit_file_diff_with_config(
     let mut diff_opts = DiffOptions::new();
     diff_opts.pathspec(path);
 
-    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
+    let mut diff =
+        repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
+
+    // Scoped to `path`, the target of a rename looks like a whole-file add.
+    // Only then is it worth a full diff to look for where it came from.
+    let mut old_path = None;
+    let added = diff.deltas().any(|d| d.status() == Delta::Added);
+    if added && parent_tree.is_some() {
+        let mut full = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        full.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+        old_path = full
+            .deltas()
+            .filter(|d| d.status() == Delta::Renamed)
+            .find(|d| d.new_file().path() == Some(Path::new(path)))
+            .and_then(|d| d.old_file().path())
+            .map(|p| p.to_string_lossy().into_owned());
+    }
+    if let Some(old_path) = &old_path {
+        let mut diff_opts = DiffOptions::new();
+        diff_opts.pathspec(old_path).pathspec(path);
+        diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut diff_opts))?;
+        diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+    }
 
     let mut collector = DiffPrintCollector::new(path, config.max_diff_bytes);
     diff.print(git2::DiffFormat::Patch, |d, h, l| {
         collector.handle_line(d, h, l)
     })?;
 
-    Ok(collector.finish())
+    let mut file_diff = collector.finish();
+    file_diff.old_path = old_path;
+    Ok(file_diff)
+}
+
+pub fn get_commit_diff(repo: &Repository, hash: &str) -> Result<Vec<FileDiff>, AppError> {
+    crate::log_git_op_debug!("get_commit_diff", hash = hash);
+    get_commit_diff_with_config(repo, hash, &DiffConfig::default())
+}
+
+/// One `FileDiff` per file the commit changes relative to its first parent,
+/// with renames detected. `config.max_diff_bytes` covers the whole commit:
+/// once it is spent, later files come back with unloaded hunks, which
+/// `get_commit_diff_hunk` loads as it does for a single truncated file.
+pub fn get_commit_diff_with_config(
+    repo: &Repository,
+    hash: &str,
+    config: &DiffConfig,
+) -> Result<Vec<FileDiff>, AppError> {
+    crate::log_git_op_debug!("get_commit_diff_with_config", hash = hash);
+    let commit = find_revision(repo, hash)?;
+    let tree = commit.tree()?;
+
+    let parent_tree = if commit.parent_count() > 0 {
+        Some(commit.parent(0)?.tree()?)
+    } else {
+        None
+    };
+
+    let diff = diff_trees_with_renames(repo, parent_tree.as_ref(), Some(&tree))?;
+
+    let delta_paths = |d: &git2::DiffDelta<'_>| {
+        (
+            d.old_file().path().map(Path::to_path_buf),
+            d.new_file().path().map(Path::to_path_buf),
+        )
+    };
+    let paths: Vec<_> = diff.deltas().map(|d| delta_paths(&d)).collect();
+    let mut collectors: Vec<DiffPrintCollector> = diff
+        .deltas()
+        .map(|d| {
+            let path = d.new_file().path().unwrap_or(Path::new(""));
+            let mut collector =
+                DiffPrintCollector::new(&path.to_string_lossy(), config.max_diff_bytes);
+            if d.status() == Delta::Renamed {
+                collector.file_diff.old_path = d
+                    .old_file()
+                    .path()
+                    .map(|p| p.to_string_lossy().into_owned());
+            }
+            collector
+        })
+        .collect();
+
+    // Deltas are printed in order; each one's lines go to its own collector,
+    // which takes over the byte count from the one before.
+    let mut current = 0;
+    diff.print(git2::DiffFormat::Patch, |d, h, l| {
+        let key = delta_paths(&d);
+        while paths[current] != key && current + 1 < paths.len() {
+            let spent = collectors[current].bytes_collected;
+            current += 1;
+            collectors[current].bytes_collected = spent;
+            collectors[current].budget_exceeded = spent > config.max_diff_bytes;
+        }
+        collectors[current].handle_line(d, h, l)
+    })?;
+
+    Ok(collectors
+        .into_iter()
+        .map(DiffPrintCollector::finish)
+        .collect())
+}
+
+/// A file's full content as of some commit.
+#[derive(Debug, Serialize, Clone)]
+pub struct FileContent {
+    pub path: String,
+    /// Lossy UTF-8; empty when `is_binary`.
+    pub content: String,
+    pub is_binary: bool,
+}
+
+pub fn get_file_at_commit(
+    repo: &Repository,
+    hash: &str,
+    path: &str,
+) -> Result<FileContent, AppError> {
+    crate::log_git_op_debug!("get_file_at_commit", hash = hash, path = path);
+    let commit = find_revision(repo, hash)?;
+    let entry = commit
+        .tree()?
+        .get_path(Path::new(path))
+        .map_err(|_| AppError::InvalidPath(format!("{path} does not exist in commit {hash}")))?;
+    let blob = entry
+        .to_object(repo)?
+        .into_blob()
+        .map_err(|_| AppError::InvalidPath(format!("{path} is not a file in commit {hash}")))?;
+
+    // Same null-byte sniff as the workdir readers.
+    let bytes = blob.content();
+    let is_binary = bytes.iter().take(8192).any(|&b| b == 0);
+    let content = if is_binary {
+        String::new()
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+
+    Ok(FileContent {
+        path: path.to_string(),
+        content,
+        is_binary,
+    })
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TreeEntryKind {
+    Blob,
+    Tree,
+    /// A gitlink (mode 160000): the entry points at a commit in another repo.
+    Submodule,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TreeEntryInfo {
+    pub name: String,
+    /// Repo-relative path, for passing back into `list_tree`/`get_file_at_commit`.
+    pub path: String,
+    pub kind: TreeEntryKind,
+    pub mode: u32,
+}
+
+const GITLINK_MODE: i32 = 0o160000;
+
+/// Entries of the tree at `hash`, or of the subdirectory `dir` within it, in
+/// git's tree order.
+pub fn list_tree(
+    repo: &Repository,
+    hash: &str,
+    dir: Option<&str>,
+) -> Result<Vec<TreeEntryInfo>, AppError> {
+    crate::log_git_op_debug!("list_tree", hash = hash, dir = dir);
+    let root = find_revision(repo, hash)?.tree()?;
+    let dir = dir.map(|d| d.trim_matches('/')).unwrap_or("");
+    let tree = if dir.is_empty() {
+        root
+    } else {
+        let entry = root
+            .get_path(Path::new(dir))
+            .map_err(|_| AppError::InvalidPath(format!("{dir} does not exist in commit {hash}")))?;
+        entry.to_object(repo)?.into_tree().map_err(|_| {
+            AppError::InvalidPath(format!("{dir} is not a directory in commit {hash}"))
+        })?
+    };
+
+    Ok(tree
+        .iter()
+        .map(|entry| {
+            let name = String::from_utf8_lossy(entry.name_bytes()).into_owned();
+            let kind = if entry.filemode() == GITLINK_MODE {
+                TreeEntryKind::Submodule
+            } else if entry.kind() == Some(git2::ObjectType::Tree) {
+                TreeEntryKind::Tree
+            } else {
+                TreeEntryKind::Blob
+            };
+            TreeEntryInfo {
+                path: if dir.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{dir}/{name}")
+                },
+                name,
+                kind,
+                mode: entry.filemode() as u32,
+            }
+        })
+        .collect())
+}
+
+/// Before/after bytes of an image file, base64-encoded for `data:` URLs.
+#[derive(Debug, Serialize, Clone)]
+pub struct ImageBlobs {
+    pub path: String,
+    pub mime_type: String,
+    /// `None` where the file doesn't exist (added or deleted).
+    pub old_base64: Option<String>,
+    pub new_base64: Option<String>,
+    /// A side exceeded `DiffConfig::max_file_size`; both sides are left empty.
+    pub too_large: bool,
+}
+
+impl ImageBlobs {
+    fn too_large(path: &str, mime_type: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            mime_type: mime_type.to_string(),
+            old_base64: None,
+            new_base64: None,
+            too_large: true,
+        }
+    }
+}
+
+fn image_mime_type(path: &str) -> Option<&'static str> {
+    let ext = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+fn blob_in_tree<'r>(
+    repo: &'r Repository,
+    tree: Option<&git2::Tree>,
+    path: &str,
+) -> Result<Option<git2::Blob<'r>>, AppError> {
+    let Some(entry) = tree.and_then(|t| t.get_path(Path::new(path)).ok()) else {
+        return Ok(None);
+    };
+    Ok(entry.to_object(repo)?.into_blob().ok())
+}
+
+/// Old and new versions of an image. With `hash`, compares the commit to its
+/// first parent; without, compares HEAD to the working tree.
+pub fn get_image_blobs(
+    repo: &Repository,
+    hash: Option<&str>,
+    path: &str,
+    config: &DiffConfig,
+) -> Result<ImageBlobs, AppError> {
+    use base64::Engine;
+
+    crate::log_git_op_debug!("get_image_blobs", hash = hash, path = path);
+    // The working-tree side reads `path` from disk.
+    if Path::new(path).is_absolute() || path.split('/').any(|c| c == "..") {
+        return Err(AppError::InvalidPath(path.to_string()));
+    }
+    let mime_type = image_mime_type(path)
+        .ok_or_else(|| AppError::InvalidPath(format!("{path} is not a supported image")))?;
+    let too_big = |size: Option<u64>| size.is_some_and(|size| size > config.max_file_size);
+
+    let (old, new) = match hash {
+        Some(hash) => {
+            let commit = find_revision(repo, hash)?;
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let old = blob_in_tree(repo, parent_tree.as_ref(), path)?;
+            let new = blob_in_tree(repo, Some(&commit.tree()?), path)?;
+            if too_big(old.as_ref().map(|b| b.size() as u64))
+                || too_big(new.as_ref().map(|b| b.size() as u64))
+            {
+                return Ok(ImageBlobs::too_large(path, mime_type));
+            }
+            (
+                old.map(|b| b.content().to_vec()),
+                new.map(|b| b.content().to_vec()),
+            )
+        }
+        None => {
+            let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+            let old = blob_in_tree(repo, head_tree.as_ref(), path)?;
+            let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
+            let file_path = workdir.join(path);
+            let disk_size = fs::metadata(&file_path).ok().map(|m| m.len());
+            if too_big(old.as_ref().map(|b| b.size() as u64)) || too_big(disk_size) {
+                return Ok(ImageBlobs::too_large(path, mime_type));
+            }
+            let new = match disk_size {
+                Some(_) => Some(fs::read(&file_path)?),
+                None => None,
+            };
+            (old.map(|b| b.content().to_vec()), new)
+        }
+    };
+
+    let encode = |bytes: Vec<u8>| base64::engine::general_purpose::STANDARD.encode(bytes);
+    Ok(ImageBlobs {
+        path: path.to_string(),
+        mime_type: mime_type.to_string(),
+        old_base64: old.map(encode),
+        new_base64: new.map(encode),
+        too_large: false,
+    })
 }
 
 /// Load a single hunk's full line content (no budget limit).
+/// `ignore_whitespace` must match the diff the index came from, otherwise
+/// hunk boundaries differ.
 pub fn get_diff_hunk(
     repo: &Repository,
     path: &str,
     staged: bool,
     hunk_index: usize,
+    ignore_whitespace: bool,
+    context_lines: Option<u32>,
 ) -> Result<DiffHunk, AppError> {
     crate::log_git_op_debug!(
         "get_diff_hunk",
         path = path,
         staged = staged,
-        hunk = hunk_index
+        hunk = hunk_index,
+        ignore_whitespace = ignore_whitespace,
+        context_lines = context_lines
     );
     // Re-run the diff with no budget limit
     let no_limit = DiffConfig {
         max_diff_bytes: usize::MAX,
         max_file_size: u64::MAX,
     };
-    let file_diff = get_file_diff_with_config(repo, path, staged, &no_limit)?;
+    let file_diff = get_file_diff_with_config(
+        repo,
+        path,
+        staged,
+        &no_limit,
+        ignore_whitespace,
+        context_lines,
+    )?;
 
     file_diff
         .hunks
         .into_iter()
         .nth(hunk_index)
-        .ok_or_else(|| AppError::InvalidPath(format!("Hunk index {} out of range", hunk_index)))
+        .ok_or(AppError::HunkOutOfRange(hunk_index))
 }
 
 /// Load a single hunk for an untracked file (always hunk 0).
@@ -613,7 +1247,7 @@ pub fn get_untracked_diff_hunk(
         .hunks
         .into_iter()
         .nth(hunk_index)
-        .ok_or_else(|| AppError::InvalidPath(format!("Hunk index {} out of range", hunk_index)))
+        .ok_or(AppError::HunkOutOfRange(hunk_index))
 }
 
 /// Load a single hunk from a commit file diff (no budget limit).
@@ -639,7 +1273,7 @@ pub fn get_commit_diff_hunk(
         .hunks
         .into_iter()
         .nth(hunk_index)
-        .ok_or_else(|| AppError::InvalidPath(format!("Hunk index {} out of range", hunk_index)))
+        .ok_or(AppError::HunkOutOfRange(hunk_index))
 }
 
 #[cfg(test)]
@@ -653,6 +1287,28 @@ mod tests {
         assert_eq!(config.max_file_size, 1_048_576);
     }
 
+    #[test]
+    fn test_parse_lfs_pointer() {
+        let pointer = b"version https://git-lfs.github.com/spec/v1\n\
+            oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+            size 12345\n";
+        assert_eq!(
+            parse_lfs_pointer(pointer),
+            Some(LfsPointer {
+                oid: "sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393"
+                    .to_string(),
+                size: 12345,
+            })
+        );
+
+        assert_eq!(parse_lfs_pointer(b"just some text\nsize 3\n"), None);
+        // Missing size
+        assert_eq!(
+            parse_lfs_pointer(b"version https://git-lfs.github.com/spec/v1\noid sha256:ab\n"),
+            None
+        );
+    }
+
     #[test]
     fn test_conflicted_file_diff_serialization() {
         // Verify serde rename works correctly
@@ -661,6 +1317,7 @@ mod tests {
             line_type: LineType::ConflictMarker,
             old_lineno: None,
             new_lineno: Some(1),
+            no_newline: false,
         };
         let json = serde_json::to_string(&line).unwrap();
         assert!(json.contains("\"conflict_marker\""));
@@ -670,6 +1327,7 @@ mod tests {
             line_type: LineType::ConflictOurs,
             old_lineno: None,
             new_lineno: Some(1),
+            no_newline: false,
         };
         let json = serde_json::to_string(&line_ours).unwrap();
         assert!(json.contains("\"conflict_ours\""));
@@ -679,6 +1337,7 @@ mod tests {
             line_type: LineType::ConflictTheirs,
             old_lineno: None,
             new_lineno: Some(1),
+            no_newline: false,
         };
         let json = serde_json::to_string(&line_theirs).unwrap();
         assert!(json.contains("\"conflict_theirs\""));
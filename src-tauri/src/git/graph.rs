@@ -1,17 +1,27 @@
+use git2::{Oid, Repository};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 
+use super::commit::{commit_to_info, find_revision, history_revwalk};
 use super::CommitInfo;
+use crate::error::AppError;
 
 #[derive(Debug, Serialize, Clone)]
 pub struct GraphCommit {
     #[serde(flatten)]
     pub commit: CommitInfo,
     pub column: usize,
+    /// Logical branch this commit belongs to; see `GraphLine::branch_id`.
+    pub branch_id: usize,
     pub lines: Vec<GraphLine>,
     pub refs: Vec<RefInfo>,
     /// True if this is the tip of its branch (first commit in its column)
     pub is_tip: bool,
+    /// True if this commit sits in the collapsed overflow column because every
+    /// regular column was taken.
+    pub overflow: bool,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -20,6 +30,23 @@ pub struct GraphLine {
     pub to_column: usize,
     pub is_merge: bool,
     pub line_type: GraphLineType,
+    /// Stable id of the branch this line belongs to, for coloring. A branch
+    /// keeps its id while it moves between columns; ids of ended branches are
+    /// reused (lowest first) so they stay small.
+    pub branch_id: usize,
+}
+
+/// One parent link in the graph, spanning however many rows lie between the
+/// child and its parent. An alternative to the per-row `lines` for renderers
+/// that draw one path per edge.
+#[derive(Debug, Serialize, Clone)]
+pub struct GraphEdge {
+    pub parent_row: usize,
+    pub child_row: usize,
+    pub from_column: usize,
+    pub to_column: usize,
+    pub is_merge: bool,
+    pub branch_id: usize,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -31,6 +58,10 @@ pub enum GraphLineType {
     FromAbove,
     /// Pass-through line (column active but no commit here)
     PassThrough,
+    /// Line from this commit toward a parent that isn't in the history, e.g.
+    /// one cut off by a shallow fetch. Nothing will arrive to end it, so no
+    /// column is kept open; renderers draw a short stub.
+    ToBoundary,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -48,14 +79,115 @@ pub enum RefType {
     Tag,
 }
 
+/// Column bookkeeping carried from one row to the next. Exposed so a page of
+/// the graph can start from the state left behind by the commits above it.
+#[derive(Debug, Default, Clone)]
+pub struct GraphState {
+    /// Column already claimed by each commit that has been seen as a parent.
+    column_map: HashMap<String, usize>,
+    /// Commit each column is waiting for, `None` when the column is free.
+    active_columns: Vec<Option<String>>,
+    /// Commits waiting in the shared overflow column (index `max_columns`).
+    overflow: HashSet<String>,
+    /// Branch id each waiting commit will inherit when it arrives.
+    pending_branch_ids: HashMap<String, usize>,
+    branch_ids: BranchIds,
+    /// Parents known not to be in the history at all, as opposed to not
+    /// reached yet. Only a caller that has the whole walk can tell:
+    /// `build_commit_graph`, or the paged graph from its cached walk.
+    missing_parents: HashSet<String>,
+}
+
+/// Allocator for `GraphLine::branch_id`: hands out the lowest free id.
+#[derive(Debug, Default, Clone)]
+struct BranchIds {
+    next: usize,
+    free: BTreeSet<usize>,
+}
+
+impl BranchIds {
+    fn take(&mut self) -> usize {
+        self.free.pop_first().unwrap_or_else(|| {
+            self.next += 1;
+            self.next - 1
+        })
+    }
+
+    fn release(&mut self, id: usize) {
+        self.free.insert(id);
+    }
+}
+
+/// Lay out `commits` (in display order) into columns. At most `max_columns`
+/// regular columns are used; branches beyond that share one collapsed column
+/// at index `max_columns` and are flagged `overflow`. Pass `usize::MAX` for no
+/// cap.
+///
+/// `commits` is taken to be the whole history, so a parent that isn't among
+/// them is missing (say, cut off by a shallow fetch) and gets a `ToBoundary`
+/// stub instead of a line that never ends.
 pub fn build_commit_graph(
     commits: Vec<CommitInfo>,
     branch_refs: HashMap<String, Vec<RefInfo>>,
+    max_columns: usize,
 ) -> Vec<GraphCommit> {
-    crate::log_git_op_debug!("build_commit_graph", count = commits.len());
+    let present: HashSet<&str> = commits.iter().map(|c| c.hash.as_str()).collect();
+    let missing_parents = commits
+        .iter()
+        .flat_map(|c| &c.parent_hashes)
+        .filter(|p| !present.contains(p.as_str()))
+        .cloned()
+        .collect();
+    build_commit_graph_with_state(
+        commits,
+        branch_refs,
+        max_columns,
+        &mut GraphState {
+            missing_parents,
+            ..GraphState::default()
+        },
+    )
+}
+
+/// First free regular column, growing the list while under `max_columns`.
+/// `None` means every regular column is taken and the caller should use the
+/// overflow column.
+fn free_column(active_columns: &mut Vec<Option<String>>, max_columns: usize) -> Option<usize> {
+    if let Some(col) = active_columns.iter().position(|c| c.is_none()) {
+        return Some(col);
+    }
+    if active_columns.len() < max_columns {
+        active_columns.push(None);
+        return Some(active_columns.len() - 1);
+    }
+    None
+}
+
+/// Like `build_commit_graph`, but continues from (and updates) `state`.
+/// Retroactive line fixes only reach commits in this batch, so rows emitted
+/// by an earlier batch keep whatever lines they were given. Use the same
+/// `max_columns` for every batch that shares a `state`.
+pub fn build_commit_graph_with_state(
+    commits: Vec<CommitInfo>,
+    branch_refs: HashMap<String, Vec<RefInfo>>,
+    max_columns: usize,
+    state: &mut GraphState,
+) -> Vec<GraphCommit> {
+    crate::log_git_op_debug!(
+        "build_commit_graph",
+        count = commits.len(),
+        max_columns = max_columns
+    );
     let mut result: Vec<GraphCommit> = Vec::with_capacity(commits.len());
-    let mut column_map: HashMap<String, usize> = HashMap::new();
-    let mut active_columns: Vec<Option<String>> = Vec::new();
+    let GraphState {
+        column_map,
+        active_columns,
+        overflow,
+        pending_branch_ids,
+        branch_ids,
+        missing_parents,
+    } = state;
+    let overflow_col = max_columns;
 
     for commit in commits {
         // Determine column for this commit
@@ -64,22 +196,20 @@ pub fn build_commit_graph(
             (col, false)
         } else {
             // This is a new branch tip - find an empty column or create a new one
-            let col = active_columns
-                .iter()
-                .position(|c| c.is_none())
-                .unwrap_or_else(|| {
-                    active_columns.push(None);
-                    active_columns.len() - 1
-                });
+            let col = free_column(active_columns, max_columns).unwrap_or(overflow_col);
             (col, true)
         };
+        let in_overflow = column == overflow_col;
+        let branch_id = pending_branch_ids
+            .remove(&commit.hash)
+            .unwrap_or_else(|| branch_ids.take());
 
         // Build graph lines - first add pass-through and from-above lines
         let mut lines = Vec::new();
 
         // For each active column, draw appropriate line
         for (col_idx, col_content) in active_columns.iter().enumerate() {
-            if col_content.is_some() {
+            if let Some(waiting) = col_content {
                 if col_idx == column {
                     // This is the column where our commit is - draw from above to node
                     lines.push(GraphLine {
@@ -87,6 +217,7 @@ pub fn build_commit_graph(
                         to_column: col_idx,
                         is_merge: false,
                         line_type: GraphLineType::FromAbove,
+                        branch_id,
                     });
                 } else {
                     // This column has an active branch passing through
@@ -95,20 +226,59 @@ pub fn build_commit_graph(
                         to_column: col_idx,
                         is_merge: false,
                         line_type: GraphLineType::PassThrough,
+                        branch_id: pending_branch_ids.get(waiting).copied().unwrap_or_default(),
                     });
                 }
             }
         }
+        // The overflow column is shared, so it can both end at this commit
+        // and keep passing through for the others still waiting in it.
+        if in_overflow && overflow.remove(&commit.hash) {
+            lines.push(GraphLine {
+                from_column: overflow_col,
+                to_column: overflow_col,
+                is_merge: false,
+                line_type: GraphLineType::FromAbove,
+                branch_id,
+            });
+        }
+        // Several branches share the pass-through; color it as the oldest.
+        if let Some(oldest) = overflow
+            .iter()
+            .filter_map(|waiting| pending_branch_ids.get(waiting))
+            .min()
+        {
+            lines.push(GraphLine {
+                from_column: overflow_col,
+                to_column: overflow_col,
+                is_merge: false,
+                line_type: GraphLineType::PassThrough,
+                branch_id: *oldest,
+            });
+        }
 
         // Clear this column (commit arrived)
-        if column < active_columns.len() {
+        if !in_overflow && column < active_columns.len() {
             active_columns[column] = None;
         }
 
         // Handle first parent — continue, converge, or take over
-        if let Some(parent) = commit.parent_hashes.first() {
+        let first_parent = commit.parent_hashes.first();
+        if first_parent.is_some_and(|p| missing_parents.contains(p)) {
+            lines.push(GraphLine {
+                from_column: column,
+                to_column: column,
+                is_merge: false,
+                line_type: GraphLineType::ToBoundary,
+                branch_id,
+            });
+            // Like a root commit, nothing continues this branch
+            branch_ids.release(branch_id);
+        } else if let Some(parent) = first_parent {
             if let Some(&existing_col) = column_map.get(parent) {
-                if column < existing_col {
+                // Only regular columns can be taken over; the overflow column
+                // is shared, so its rows can't be rewritten.
+                if column < existing_col && existing_col != overflow_col {
                     // Current commit has lower column — it takes over the parent.
                     // Move parent from existing_col to current column.
                     column_map.insert(parent.clone(), column);
@@ -119,6 +289,10 @@ pub fn build_commit_graph(
                         active_columns.resize(column + 1, None);
                     }
                     active_columns[column] = Some(parent.clone());
+                    // The parent continues this branch; the old column's ends.
+                    if let Some(old_id) = pending_branch_ids.insert(parent.clone(), branch_id) {
+                        branch_ids.release(old_id);
+                    }
 
                     // Straight continuation line
                     lines.push(GraphLine {
@@ -126,6 +300,7 @@ pub fn build_commit_graph(
                         to_column: column,
                         is_merge: false,
                         line_type: GraphLineType::ToParent,
+                        branch_id,
                     });
 
                     // Remove the spurious pass-through line for the old column
@@ -168,9 +343,30 @@ pub fn build_commit_graph(
                         to_column: existing_col,
                         is_merge: false,
                         line_type: GraphLineType::ToParent,
+                        branch_id,
                     });
                     // Column stays freed — this branch has ended
+                    branch_ids.release(branch_id);
+                }
+            } else if in_overflow {
+                // Leave the overflow column as soon as a regular one frees up
+                let parent_column = free_column(active_columns, max_columns);
+                match parent_column {
+                    Some(col) => active_columns[col] = Some(parent.clone()),
+                    None => {
+                        overflow.insert(parent.clone());
+                    }
                 }
+                let parent_column = parent_column.unwrap_or(overflow_col);
+                column_map.insert(parent.clone(), parent_column);
+                pending_branch_ids.insert(parent.clone(), branch_id);
+                lines.push(GraphLine {
+                    from_column: column,
+                    to_column: parent_column,
+                    is_merge: false,
+                    line_type: GraphLineType::ToParent,
+                    branch_id,
+                });
             } else {
                 // Parent not yet assigned — continue in same column
                 column_map.insert(parent.clone(), column);
@@ -178,42 +374,60 @@ pub fn build_commit_graph(
                     active_columns.resize(column + 1, None);
                 }
                 active_columns[column] = Some(parent.clone());
+                pending_branch_ids.insert(parent.clone(), branch_id);
                 lines.push(GraphLine {
                     from_column: column,
                     to_column: column,
                     is_merge: false,
                     line_type: GraphLineType::ToParent,
+                    branch_id,
                 });
             }
+        } else {
+            // Root commit — nothing continues this branch
+            branch_ids.release(branch_id);
         }
 
         // Merge parents go to other columns
         for parent in commit.parent_hashes.iter().skip(1) {
+            if missing_parents.contains(parent) {
+                lines.push(GraphLine {
+                    from_column: column,
+                    to_column: column,
+                    is_merge: true,
+                    line_type: GraphLineType::ToBoundary,
+                    branch_id,
+                });
+                continue;
+            }
             let parent_column = if let Some(&col) = column_map.get(parent) {
                 col
             } else {
                 // Find an empty column for this parent
-                let col = active_columns
-                    .iter()
-                    .position(|c| c.is_none())
-                    .unwrap_or_else(|| {
-                        active_columns.push(None);
-                        active_columns.len() - 1
-                    });
+                let col = match free_column(active_columns, max_columns) {
+                    Some(col) => {
+                        active_columns[col] = Some(parent.clone());
+                        col
+                    }
+                    None => {
+                        overflow.insert(parent.clone());
+                        overflow_col
+                    }
+                };
                 column_map.insert(parent.clone(), col);
-                if col >= active_columns.len() {
-                    active_columns.push(Some(parent.clone()));
-                } else {
-                    active_columns[col] = Some(parent.clone());
-                }
                 col
             };
+            // A merge line takes the color of the branch being merged in
+            let parent_branch_id = *pending_branch_ids
+                .entry(parent.clone())
+                .or_insert_with(|| branch_ids.take());
 
             lines.push(GraphLine {
                 from_column: column,
                 to_column: parent_column,
                 is_merge: true,
                 line_type: GraphLineType::ToParent,
+                branch_id: parent_branch_id,
             });
         }
 
@@ -223,15 +437,261 @@ pub fn build_commit_graph(
         result.push(GraphCommit {
             commit,
             column,
+            branch_id,
             lines,
             refs,
             is_tip,
+            overflow: in_overflow,
         });
     }
 
     result
 }
 
+/// Lay out `commits` like `build_commit_graph` and return the parent links as
+/// a flat edge list instead of per-row lines.
+pub fn build_commit_graph_edges(commits: Vec<CommitInfo>, max_columns: usize) -> Vec<GraphEdge> {
+    graph_edges(&build_commit_graph(commits, HashMap::new(), max_columns))
+}
+
+/// Edges for an already laid-out graph. Links to parents that aren't among
+/// the rows (e.g. below a truncated walk) are left out.
+pub fn graph_edges(graph: &[GraphCommit]) -> Vec<GraphEdge> {
+    let rows: HashMap<&str, usize> = graph
+        .iter()
+        .enumerate()
+        .map(|(row, gc)| (gc.commit.hash.as_str(), row))
+        .collect();
+
+    let mut edges = Vec::new();
+    for (child_row, child) in graph.iter().enumerate() {
+        for (i, parent) in child.commit.parent_hashes.iter().enumerate() {
+            let Some(&parent_row) = rows.get(parent.as_str()) else {
+                continue;
+            };
+            let parent_gc = &graph[parent_row];
+            edges.push(GraphEdge {
+                parent_row,
+                child_row,
+                from_column: child.column,
+                to_column: parent_gc.column,
+                is_merge: i > 0,
+                // Merge edges take the color of the branch being merged in
+                branch_id: if i > 0 {
+                    parent_gc.branch_id
+                } else {
+                    child.branch_id
+                },
+            });
+        }
+    }
+    edges
+}
+
+/// One page of the commit graph: `limit` rows starting at row `skip`. The
+/// column state is seeded from the rows above, so a branch that started above
+/// the window keeps the same column it has in the full graph. `refs` limits
+/// the walk as in `get_commits`; `max_columns` caps the width as in
+/// `build_commit_graph`.
+pub fn get_commit_graph_page(
+    repo: &Repository,
+    skip: usize,
+    limit: usize,
+    refs: Option<&[String]>,
+    max_columns: usize,
+) -> Result<Vec<GraphCommit>, AppError> {
+    crate::log_git_op_debug!(
+        "get_commit_graph_page",
+        skip = skip,
+        limit = limit,
+        refs = refs,
+        max_columns = max_columns
+    );
+    get_commit_graph_page_cached(
+        repo,
+        skip,
+        limit,
+        refs,
+        max_columns,
+        &mut RefsCache::default(),
+        &mut GraphPageCache::default(),
+    )
+}
+
+/// The history walk and the column state at each row a page ended on, kept
+/// between `get_commit_graph_page_cached` calls until any ref moves or the
+/// `refs` filter or `max_columns` changes.
+#[derive(Debug, Default)]
+pub struct GraphPageCache {
+    key: Option<(Option<Vec<String>>, usize, u64)>,
+    /// Every commit of the walk, in row order.
+    order: Vec<Oid>,
+    /// State at the start of each row a page has ended on.
+    boundaries: BTreeMap<usize, GraphState>,
+    /// Parents of walked commits that the walk never reaches.
+    missing_parents: HashSet<String>,
+}
+
+/// `get_commit_graph_page` with the ref labels taken from `cache` and the
+/// walk and page boundaries from `pages`, so scrolling down resumes from the
+/// previous page instead of replaying every row above it.
+pub fn get_commit_graph_page_cached(
+    repo: &Repository,
+    skip: usize,
+    limit: usize,
+    refs: Option<&[String]>,
+    max_columns: usize,
+    cache: &mut RefsCache,
+    pages: &mut GraphPageCache,
+) -> Result<Vec<GraphCommit>, AppError> {
+    crate::log_git_op_debug!(
+        "get_commit_graph_page_cached",
+        skip = skip,
+        limit = limit,
+        refs = refs,
+        max_columns = max_columns
+    );
+    let key = (
+        refs.map(<[String]>::to_vec),
+        max_columns,
+        refs_signature(repo)?,
+    );
+    if pages.key.as_ref() != Some(&key) {
+        let order = history_revwalk(repo, refs)?.collect::<Result<Vec<Oid>, _>>()?;
+        *pages = GraphPageCache {
+            missing_parents: missing_parents(repo, &order)?,
+            order,
+            boundaries: BTreeMap::new(),
+            key: Some(key),
+        };
+    }
+    let skip = skip.min(pages.order.len());
+    let end = skip.saturating_add(limit).min(pages.order.len());
+
+    // Only hashes and parents are needed to replay rows above the window.
+    let (resume_row, mut state) = pages
+        .boundaries
+        .range(..=skip)
+        .next_back()
+        .map(|(row, state)| (*row, state.clone()))
+        .unwrap_or_else(|| {
+            let state = GraphState {
+                missing_parents: pages.missing_parents.clone(),
+                ..GraphState::default()
+            };
+            (0, state)
+        });
+    let skipped: Vec<CommitInfo> = pages.order[resume_row..skip]
+        .iter()
+        .filter_map(|oid| {
+            let commit = repo.find_commit(*oid).ok()?;
+            Some(CommitInfo {
+                hash: oid.to_string(),
+                short_hash: String::new(),
+                message: String::new(),
+                author_name: String::new(),
+                author_email: String::new(),
+                author_email_hash: String::new(),
+                timestamp: 0,
+                author_time: 0,
+                author_offset_minutes: 0,
+                commit_time: 0,
+                commit_offset_minutes: 0,
+                parent_hashes: commit.parent_ids().map(|p| p.to_string()).collect(),
+            })
+        })
+        .collect();
+    build_commit_graph_with_state(skipped, HashMap::new(), max_columns, &mut state);
+
+    let window: Vec<CommitInfo> = pages.order[skip..end]
+        .iter()
+        .filter_map(|oid| Some(commit_to_info(&repo.find_commit(*oid).ok()?)))
+        .collect();
+    let refs = collect_refs_cached(repo, cache)?;
+
+    let graph = build_commit_graph_with_state(window, refs, max_columns, &mut state);
+    pages.boundaries.insert(end, state);
+    Ok(graph)
+}
+
+/// Parents of the commits in `order` that aren't in it themselves. A full
+/// walk reaches every parent it can read, so only a shallow clone's boundary
+/// commits can have any.
+fn missing_parents(repo: &Repository, order: &[Oid]) -> Result<HashSet<String>, AppError> {
+    if !repo.is_shallow() {
+        return Ok(HashSet::new());
+    }
+    let walked: HashSet<&Oid> = order.iter().collect();
+    let mut missing = HashSet::new();
+    for oid in order {
+        for parent in repo.find_commit(*oid)?.parent_ids() {
+            if !walked.contains(&parent) {
+                missing.insert(parent.to_string());
+            }
+        }
+    }
+    Ok(missing)
+}
+
+/// Branches and tags pointing at one commit, for a detail view that doesn't
+/// have the graph at hand. `hash` may be any revision `find_revision` accepts.
+pub fn refs_at_commit(repo: &Repository, hash: &str) -> Result<Vec<RefInfo>, AppError> {
+    crate::log_git_op_debug!("refs_at_commit", hash = hash);
+    let commit = find_revision(repo, hash)?;
+    Ok(collect_refs(repo)?
+        .remove(&commit.id().to_string())
+        .unwrap_or_default())
+}
+
+/// `collect_refs`' result for one repository, kept until any ref moves.
+#[derive(Debug, Default)]
+pub struct RefsCache {
+    signature: Option<u64>,
+    refs: HashMap<String, Vec<RefInfo>>,
+}
+
+/// Hash of every ref's name and target plus HEAD (which decides `is_head`).
+/// Listing refs is cheap next to `collect_refs`, which resolves each branch
+/// name and peels every tag.
+fn refs_signature(repo: &Repository) -> Result<u64, git2::Error> {
+    let mut hasher = DefaultHasher::new();
+    if let Ok(head) = repo.head() {
+        head.name_bytes().hash(&mut hasher);
+        head.target().hash(&mut hasher);
+    }
+    for reference in repo.references()? {
+        let reference = reference?;
+        reference.name_bytes().hash(&mut hasher);
+        reference.target().hash(&mut hasher);
+    }
+    Ok(hasher.finish())
+}
+
+/// `collect_refs`, reusing `cache` while no ref has been created, deleted or
+/// moved since it was filled.
+pub fn collect_refs_cached(
+    repo: &Repository,
+    cache: &mut RefsCache,
+) -> Result<HashMap<String, Vec<RefInfo>>, AppError> {
+    crate::log_git_op_debug!("collect_refs_cached");
+    let signature = refs_signature(repo)?;
+    if cache.signature != Some(signature) {
+        *cache = RefsCache {
+            signature: Some(signature),
+            refs: collect_refs(repo)?,
+        };
+    }
+    Ok(cache.refs.clone())
+}
+
+/// Drop whatever `cache` holds and fill it again from the repository.
+pub fn refresh_refs(repo: &Repository, cache: &mut RefsCache) -> Result<(), AppError> {
+    crate::log_git_op_debug!("refresh_refs");
+    *cache = RefsCache::default();
+    collect_refs_cached(repo, cache)?;
+    Ok(())
+}
+
 pub fn collect_refs(repo: &git2::Repository) -> Result<HashMap<String, Vec<RefInfo>>, git2::Error> {
     crate::log_git_op_debug!("collect_refs");
     let mut refs_map: HashMap<String, Vec<RefInfo>> = HashMap::new();
@@ -262,16 +722,24 @@ pub fn collect_refs(repo: &git2::Repository) -> Result<HashMap<String, Vec<RefIn
         }
     }
 
-    // Collect tags
+    // Collect tags, filed under the commit an annotated tag points at rather
+    // than the tag object itself
     repo.tag_foreach(|oid, name| {
         let name = String::from_utf8_lossy(name)
             .trim_start_matches("refs/tags/")
             .to_string();
-        refs_map.entry(oid.to_string()).or_default().push(RefInfo {
-            name,
-            ref_type: RefType::Tag,
-            is_head: false,
-        });
+        let target = repo
+            .find_object(oid, None)
+            .and_then(|o| o.peel_to_commit())
+            .map_or(oid, |c| c.id());
+        refs_map
+            .entry(target.to_string())
+            .or_default()
+            .push(RefInfo {
+                name,
+                ref_type: RefType::Tag,
+                is_head: false,
+            });
         true
     })?;
 
@@ -289,7 +757,12 @@ mod tests {
             message: message.to_string(),
             author_name: "Test User".to_string(),
             author_email: "test@example.com".to_string(),
+            author_email_hash: String::new(),
             timestamp: 0,
+            author_time: 0,
+            author_offset_minutes: 0,
+            commit_time: 0,
+            commit_offset_minutes: 0,
             parent_hashes,
         }
     }
@@ -358,7 +831,7 @@ mod tests {
         let commits: Vec<CommitInfo> = vec![];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert!(graph.is_empty());
     }
@@ -368,7 +841,7 @@ mod tests {
         let commits = vec![create_commit_info("abc1234", "Initial commit", vec![])];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 1);
         assert_eq!(graph[0].commit.hash, "abc1234");
@@ -385,7 +858,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 3);
         // All commits should be in column 0 for linear history
@@ -415,7 +888,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 3);
         // Both commit3 and commit2 are tips (they appear first for their columns)
@@ -444,7 +917,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 4);
         // Merge commit should have lines to both parents
@@ -472,7 +945,7 @@ mod tests {
             .collect();
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 10);
     }
@@ -490,7 +963,7 @@ mod tests {
             }],
         );
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 1);
         assert_eq!(graph[0].refs.len(), 1);
@@ -506,7 +979,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // First commit should have a ToParent line
         let has_to_parent = graph[0]
@@ -528,7 +1001,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 4);
     }
@@ -548,7 +1021,7 @@ mod tests {
             .collect();
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph.len(), 200);
         // All commits should be in column 0 for linear history
@@ -588,7 +1061,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // The merge commit should have a ToParent line with is_merge: true
         let merge_lines = &graph[0].lines;
@@ -624,7 +1097,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // Find the columns for each branch
         let a2_col = graph
@@ -694,7 +1167,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         let a1_col = graph
             .iter()
@@ -744,7 +1217,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         for gc in &graph {
             let has_from_above = gc.lines.iter().any(|l| {
@@ -758,17 +1231,16 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_real_merge_pattern() {
-        // Reproduces the exact merge pattern from this project's git history:
-        //   * d36d66d          col 0
-        //   *   759c432        col 0, merge (0→1)
-        //   | * 2823615        col 1, converges (1→0)
-        //   * | c401927        col 0
-        //   |/
-        //   * def9d37          col 0
-        //   * 5007578          col 0
-        let commits = vec![
+    // The exact merge pattern from this project's git history:
+    //   * d36d66d          col 0
+    //   *   759c432        col 0, merge (0→1)
+    //   | * 2823615        col 1, converges (1→0)
+    //   * | c401927        col 0
+    //   |/
+    //   * def9d37          col 0
+    //   * 5007578          col 0
+    fn real_merge_pattern() -> Vec<CommitInfo> {
+        vec![
             create_commit_info("d36d66d", "fix: updated", vec!["759c432".to_string()]),
             create_commit_info(
                 "759c432",
@@ -787,9 +1259,13 @@ mod tests {
                 vec!["5007578".to_string()],
             ),
             create_commit_info("5007578", "chore: format fixes", vec![]),
-        ];
+        ]
+    }
+
+    #[test]
+    fn test_real_merge_pattern() {
         let refs = HashMap::new();
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(real_merge_pattern(), refs, usize::MAX);
 
         // d36d66d: col 0
         assert_eq!(graph[0].column, 0, "d36d66d should be col 0");
@@ -831,6 +1307,43 @@ mod tests {
         assert_eq!(max_col, 1, "Max column should be 1");
     }
 
+    #[test]
+    fn test_real_merge_pattern_edges() {
+        let edges = build_commit_graph_edges(real_merge_pattern(), usize::MAX);
+
+        // One edge per parent link: five first-parent edges plus the merge
+        assert_eq!(edges.len(), 6);
+        let merges: Vec<&GraphEdge> = edges.iter().filter(|e| e.is_merge).collect();
+        assert_eq!(merges.len(), 1);
+        assert_eq!(
+            (merges[0].child_row, merges[0].parent_row),
+            (1, 2),
+            "759c432 merges 2823615"
+        );
+        assert_eq!((merges[0].from_column, merges[0].to_column), (0, 1));
+
+        // 2823615 converges back into def9d37, skipping c401927's row
+        let convergence = edges.iter().find(|e| e.child_row == 2).unwrap();
+        assert_eq!(convergence.parent_row, 4);
+        assert_eq!((convergence.from_column, convergence.to_column), (1, 0));
+        assert_eq!(convergence.branch_id, merges[0].branch_id);
+
+        assert!(edges.iter().all(|e| e.parent_row > e.child_row));
+    }
+
+    #[test]
+    fn test_graph_edges_skip_parents_outside_rows() {
+        let commits = vec![
+            create_commit_info("c2", "C2", vec!["c1".to_string()]),
+            create_commit_info("c1", "C1", vec!["c0".to_string()]),
+        ];
+
+        let edges = build_commit_graph_edges(commits, usize::MAX);
+
+        assert_eq!(edges.len(), 1);
+        assert_eq!((edges[0].child_row, edges[0].parent_row), (0, 1));
+    }
+
     #[test]
     fn test_real_stash_pattern() {
         // Reproduces stash refs pattern:
@@ -867,7 +1380,7 @@ mod tests {
             create_commit_info("ee20873", "chore(release): 1.0.1", vec![]),
         ];
         let refs = HashMap::new();
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         assert_eq!(graph[0].column, 0, "2d45a98 should be col 0");
         assert_eq!(graph[1].column, 0, "c762b9b should be col 0");
@@ -897,7 +1410,7 @@ mod tests {
             create_commit_info("1fc486d", "root", vec![]),
         ];
         let refs = HashMap::new();
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // All non-merge-branch commits should be col 0
         for gc in &graph {
@@ -937,7 +1450,7 @@ mod tests {
             create_commit_info("base1", "Root", vec![]),
         ];
         let refs = HashMap::new();
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // Max column should be 1 — col 1 is reused after feat1 converges
         let max_col = graph.iter().map(|g| g.column).max().unwrap();
@@ -961,7 +1474,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         for row in 1..graph.len() {
             for line in &graph[row].lines {
@@ -999,7 +1512,7 @@ mod tests {
         ];
         let refs = HashMap::new();
 
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // Main commits all col 0
         for gc in &graph {
@@ -1056,7 +1569,7 @@ mod tests {
             create_commit_info("root", "root", vec![]),
         ];
         let refs = HashMap::new();
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // root should be in column 0 (mainline took it over)
         let root_gc = graph.iter().find(|g| g.commit.hash == "root").unwrap();
@@ -1111,7 +1624,7 @@ mod tests {
             create_commit_info("root", "root", vec![]),
         ];
         let refs = HashMap::new();
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // root should be in column 0
         let root_gc = graph.iter().find(|g| g.commit.hash == "root").unwrap();
@@ -1163,7 +1676,7 @@ mod tests {
             create_commit_info("root", "root", vec![]),
         ];
         let refs = HashMap::new();
-        let graph = build_commit_graph(commits, refs);
+        let graph = build_commit_graph(commits, refs, usize::MAX);
 
         // root should be in column 0
         let root_gc = graph.iter().find(|g| g.commit.hash == "root").unwrap();
@@ -1188,4 +1701,228 @@ mod tests {
 
         validate_graph_invariants(&graph);
     }
+
+    /// `n` two-commit branches forking from one root, newest first.
+    fn fan_out(n: usize) -> Vec<CommitInfo> {
+        let mut commits = Vec::new();
+        for i in 0..n {
+            commits.push(create_commit_info(
+                &format!("b{i}_2"),
+                &format!("B{i}.2"),
+                vec![format!("b{i}_1")],
+            ));
+        }
+        for i in 0..n {
+            commits.push(create_commit_info(
+                &format!("b{i}_1"),
+                &format!("B{i}.1"),
+                vec!["root".to_string()],
+            ));
+        }
+        commits.push(create_commit_info("root", "Root", vec![]));
+        commits
+    }
+
+    #[test]
+    fn test_max_columns_caps_fan_out() {
+        let graph = build_commit_graph(fan_out(8), HashMap::new(), 3);
+
+        validate_graph_invariants(&graph);
+        for gc in &graph {
+            assert!(
+                gc.column <= 3,
+                "{} in column {}",
+                gc.commit.message,
+                gc.column
+            );
+            assert_eq!(gc.overflow, gc.column == 3, "{}", gc.commit.message);
+            for line in &gc.lines {
+                assert!(line.from_column <= 3 && line.to_column <= 3);
+            }
+        }
+        assert_eq!(graph.iter().filter(|gc| gc.overflow).count(), 10);
+        // The first branches keep their own columns all the way down
+        for (i, hash) in ["b0_1", "b1_1", "b2_1"].iter().enumerate() {
+            let gc = graph.iter().find(|g| g.commit.hash == *hash).unwrap();
+            assert_eq!(gc.column, i);
+            assert!(!gc.overflow);
+        }
+        let root = graph.last().unwrap();
+        assert_eq!(root.column, 0);
+        assert!(!root.overflow);
+    }
+
+    #[test]
+    fn test_max_columns_leaves_in_range_layout_alone() {
+        let commits = vec![
+            create_commit_info("branch_a2", "A2", vec!["branch_a1".to_string()]),
+            create_commit_info("branch_b2", "B2", vec!["branch_b1".to_string()]),
+            create_commit_info("branch_c2", "C2", vec!["branch_c1".to_string()]),
+            create_commit_info("branch_a1", "A1", vec!["root".to_string()]),
+            create_commit_info("branch_b1", "B1", vec!["root".to_string()]),
+            create_commit_info("branch_c1", "C1", vec!["root".to_string()]),
+            create_commit_info("root", "Root", vec![]),
+        ];
+
+        let capped = build_commit_graph(commits.clone(), HashMap::new(), 3);
+        let unlimited = build_commit_graph(commits, HashMap::new(), usize::MAX);
+
+        for (c, u) in capped.iter().zip(&unlimited) {
+            assert!(!c.overflow);
+            assert_eq!(c.column, u.column);
+            let lines = |gc: &GraphCommit| {
+                gc.lines
+                    .iter()
+                    .map(|l| (l.from_column, l.to_column, l.is_merge))
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(lines(c), lines(u), "{}", c.commit.message);
+        }
+    }
+
+    #[test]
+    fn test_max_columns_octopus_merge_overflows_extra_parents() {
+        let parents: Vec<String> = (0..5).map(|i| format!("p{i}")).collect();
+        let mut commits = vec![create_commit_info("merge", "Octopus", parents.clone())];
+        for p in &parents {
+            commits.push(create_commit_info(p, p, vec!["root".to_string()]));
+        }
+        commits.push(create_commit_info("root", "Root", vec![]));
+
+        let graph = build_commit_graph(commits, HashMap::new(), 2);
+
+        validate_graph_invariants(&graph);
+        let merge_targets: Vec<usize> = graph[0]
+            .lines
+            .iter()
+            .filter(|l| l.is_merge)
+            .map(|l| l.to_column)
+            .collect();
+        assert_eq!(merge_targets, vec![1, 2, 2, 2]);
+        let overflowed: Vec<&str> = graph
+            .iter()
+            .filter(|gc| gc.overflow)
+            .map(|gc| gc.commit.hash.as_str())
+            .collect();
+        assert_eq!(overflowed, vec!["p2", "p3", "p4"]);
+    }
+
+    #[test]
+    fn test_branch_id_follows_branch_through_merge_and_takeover() {
+        //   * merge
+        //   |\
+        //   | * commit2 (feature)
+        //   * | commit_main
+        //   |/
+        //   * commit1
+        let commits = vec![
+            create_commit_info(
+                "merge",
+                "Merge commit",
+                vec!["commit_main".to_string(), "commit2".to_string()],
+            ),
+            create_commit_info("commit2", "Feature commit", vec!["commit1".to_string()]),
+            create_commit_info("commit_main", "Main commit", vec!["commit1".to_string()]),
+            create_commit_info("commit1", "Initial commit", vec![]),
+        ];
+
+        let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
+
+        let main_id = graph[0].branch_id;
+        let feature_id = graph[1].branch_id;
+        assert_ne!(main_id, feature_id);
+        // commit1 was taken over by main, so it stays main's color
+        assert_eq!(graph[2].branch_id, main_id);
+        assert_eq!(graph[3].branch_id, main_id);
+
+        let merge_line = graph[0].lines.iter().find(|l| l.is_merge).unwrap();
+        assert_eq!(merge_line.branch_id, feature_id);
+        for gc in &graph {
+            for line in &gc.lines {
+                if matches!(line.line_type, GraphLineType::FromAbove) {
+                    assert_eq!(line.branch_id, gc.branch_id, "{}", gc.commit.message);
+                }
+                if matches!(line.line_type, GraphLineType::PassThrough) {
+                    let expected = if line.from_column == graph[1].column {
+                        feature_id
+                    } else {
+                        main_id
+                    };
+                    assert_eq!(line.branch_id, expected, "{}", gc.commit.message);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_branch_id_reused_after_branch_ends() {
+        let commits = vec![
+            create_commit_info("a", "A", vec!["base".to_string()]),
+            create_commit_info("b", "B", vec!["base".to_string()]),
+            create_commit_info("c", "C", vec!["base".to_string()]),
+            create_commit_info("base", "Base", vec![]),
+        ];
+
+        let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
+
+        let ids: Vec<usize> = graph.iter().map(|gc| gc.branch_id).collect();
+        // b converges into base and ends, so c picks its id back up
+        assert_eq!(ids, vec![0, 1, 1, 0]);
+        assert_eq!(graph[2].column, graph[1].column);
+    }
+
+    #[test]
+    fn test_missing_first_parent_ends_in_boundary() {
+        // Shallow clone: c1's parent was never fetched.
+        let commits = vec![
+            create_commit_info("c2", "C2", vec!["c1".to_string()]),
+            create_commit_info("c1", "C1", vec!["gone".to_string()]),
+            create_commit_info("other", "Other", vec![]),
+        ];
+
+        let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
+
+        let boundary: Vec<_> = graph[1]
+            .lines
+            .iter()
+            .filter(|l| matches!(l.line_type, GraphLineType::ToBoundary))
+            .collect();
+        assert_eq!(boundary.len(), 1);
+        assert_eq!((boundary[0].from_column, boundary[0].to_column), (0, 0));
+        assert!(!graph[1]
+            .lines
+            .iter()
+            .any(|l| matches!(l.line_type, GraphLineType::ToParent)));
+        // Column 0 was freed, so the unrelated root starts a new tip there
+        // instead of being pushed right by a line that never ends.
+        assert_eq!(graph[2].column, 0);
+        assert!(graph[2].is_tip);
+        assert!(graph[2]
+            .lines
+            .iter()
+            .all(|l| !matches!(l.line_type, GraphLineType::PassThrough)));
+    }
+
+    #[test]
+    fn test_missing_merge_parent_ends_in_boundary() {
+        let commits = vec![
+            create_commit_info("m", "Merge", vec!["a".to_string(), "gone".to_string()]),
+            create_commit_info("a", "A", vec![]),
+        ];
+
+        let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
+
+        let merge_stub = graph[0]
+            .lines
+            .iter()
+            .find(|l| l.is_merge)
+            .expect("merge line");
+        assert!(matches!(merge_stub.line_type, GraphLineType::ToBoundary));
+        assert_eq!(merge_stub.to_column, graph[0].column);
+        assert!(graph.iter().all(|gc| gc.column == 0));
+        assert!(!graph[1]
+            .lines
+            .iter()
+            .any(|l| matches!(l.line_type, GraphLineType::PassThrough)));
+    }
 }
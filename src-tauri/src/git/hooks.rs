@@ -0,0 +1,59 @@
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use git2::Repository;
+
+use crate::error::AppError;
+
+/// Locate a hook script: `core.hooksPath` (relative to the worktree) or
+/// `.git/hooks`. Returns `None` if it's missing or not executable, the same
+/// way git silently skips such hooks.
+fn hook_path(repo: &Repository, name: &str) -> Result<Option<PathBuf>, AppError> {
+    let dir = match repo.config()?.get_path("core.hooksPath") {
+        Ok(dir) if dir.is_relative() => repo.workdir().unwrap_or(repo.path()).join(dir),
+        Ok(dir) => dir,
+        Err(_) => repo.path().join("hooks"),
+    };
+    let path = dir.join(name);
+    // Without an executable bit to check, git runs any hook file.
+    let runnable = path
+        .metadata()
+        .is_ok_and(|m| m.is_file() && (cfg!(not(unix)) || super::is_executable(&m)));
+    Ok(runnable.then_some(path))
+}
+
+/// Run hook `name` if present, from the worktree root. A non-zero exit fails
+/// with `AppError::HookFailed` carrying the hook's stdout and stderr.
+pub fn run_hook(repo: &Repository, name: &str) -> Result<(), AppError> {
+    let Some(path) = hook_path(repo, name)? else {
+        return Ok(());
+    };
+    crate::log_git_op!("run_hook", hook = name);
+
+    // Windows can't exec shell scripts directly; Git for Windows runs them
+    // through its bundled sh.
+    let mut command = if cfg!(windows) {
+        let mut command = Command::new("sh");
+        command.arg(&path);
+        command
+    } else {
+        Command::new(&path)
+    };
+    let output = command
+        .current_dir(repo.workdir().unwrap_or(repo.path()))
+        .env("GIT_INDEX_FILE", repo.path().join("index"))
+        .stdin(Stdio::null())
+        .output()?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    let text = text.trim();
+    Err(AppError::HookFailed(if text.is_empty() {
+        format!("{name} hook exited with {}", output.status)
+    } else {
+        text.to_string()
+    }))
+}
@@ -0,0 +1,46 @@
+use git2::Repository;
+
+use crate::error::AppError;
+
+/// Append `pattern` to the repo's top-level `.gitignore`, creating the file if
+/// needed. A pattern that's already listed is left alone. Returns the file's
+/// patterns (blank lines and comments skipped) after the write.
+pub fn add_to_gitignore(repo: &Repository, pattern: &str) -> Result<Vec<String>, AppError> {
+    crate::log_git_op!("add_to_gitignore", pattern = pattern);
+    let pattern = pattern.trim();
+    if pattern.is_empty() || pattern.contains(['\n', '\r']) {
+        return Err(AppError::InvalidPath(format!(
+            "Invalid ignore pattern: {pattern:?}"
+        )));
+    }
+
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
+    let gitignore = workdir.join(".gitignore");
+    let mut content = match std::fs::read_to_string(&gitignore) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+
+    if !content.lines().any(|line| line.trim() == pattern) {
+        let eol = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        // Don't glue the new pattern onto a last line that lacks its newline.
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push_str(eol);
+        }
+        content.push_str(pattern);
+        content.push_str(eol);
+        std::fs::write(&gitignore, &content)?;
+    }
+
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect())
+}
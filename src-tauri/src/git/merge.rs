@@ -0,0 +1,153 @@
+use git2::{BranchType, ErrorClass, ErrorCode, Index, Oid, Repository};
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::git::commit::find_revision;
+use crate::git::repository::checkout_tree_checked;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeKind {
+    /// HEAD already contains the branch; nothing changed.
+    UpToDate,
+    /// HEAD was moved forward to the branch tip without a merge commit.
+    FastForward,
+    /// A merge commit with two parents was created.
+    MergeCommit,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MergeResult {
+    pub kind: MergeKind,
+    /// HEAD after the merge.
+    pub commit_hash: String,
+}
+
+/// Merge `branch_name` (a local branch, or a remote-tracking branch such as
+/// `origin/main`) into HEAD. Fast-forwards when possible, otherwise creates a
+/// merge commit. On conflicts no commit is made: the repository is left in
+/// the merging state with conflict markers in the working tree, and
+/// `AppError::MergeConflict` lists the conflicted paths; resolve them and
+/// `continue_operation` makes the merge commit.
+pub fn merge_branch(repo: &Repository, branch_name: &str) -> Result<MergeResult, AppError> {
+    crate::log_git_op!("merge_branch", branch = branch_name);
+    let branch = repo
+        .find_branch(branch_name, BranchType::Local)
+        .or_else(|_| repo.find_branch(branch_name, BranchType::Remote))?;
+    let their_commit = branch.get().peel_to_commit()?;
+    let annotated = repo.reference_to_annotated_commit(branch.get())?;
+
+    let (analysis, _preference) = repo.merge_analysis(&[&annotated])?;
+
+    if analysis.is_up_to_date() {
+        let head = repo.head()?.peel_to_commit()?;
+        return Ok(MergeResult {
+            kind: MergeKind::UpToDate,
+            commit_hash: head.id().to_string(),
+        });
+    }
+
+    if analysis.is_fast_forward() {
+        // Local modifications in the way fail here, before HEAD moves.
+        checkout_tree_checked(repo, &their_commit.tree()?, false)?;
+        let mut head = repo.head()?;
+        if head.is_branch() {
+            head.set_target(
+                their_commit.id(),
+                &format!("merge {branch_name}: Fast-forward"),
+            )?;
+        } else {
+            repo.set_head_detached(their_commit.id())?;
+        }
+        return Ok(MergeResult {
+            kind: MergeKind::FastForward,
+            commit_hash: their_commit.id().to_string(),
+        });
+    }
+
+    repo.merge(&[&annotated], None, None)?;
+
+    let mut index = repo.index()?;
+    if index.has_conflicts() {
+        return Err(AppError::MergeConflict {
+            paths: conflicted_paths(&index)?,
+            step: None,
+        });
+    }
+
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let head_commit = repo.head()?.peel_to_commit()?;
+    let signature = repo.signature()?;
+    let message = format!("Merge branch '{branch_name}'");
+    let oid = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &[&head_commit, &their_commit],
+    )?;
+    repo.cleanup_state()?;
+
+    Ok(MergeResult {
+        kind: MergeKind::MergeCommit,
+        commit_hash: oid.to_string(),
+    })
+}
+
+/// Best common ancestor of revisions `a` and `b` (anything `find_revision`
+/// accepts), like `git merge-base a b`.
+pub fn merge_base(repo: &Repository, a: &str, b: &str) -> Result<String, AppError> {
+    crate::log_git_op_debug!("merge_base", a = a, b = b);
+    let a = find_revision(repo, a)?.id();
+    let b = find_revision(repo, b)?.id();
+    let base = repo.merge_base(a, b).map_err(unrelated_histories)?;
+    Ok(base.to_string())
+}
+
+/// Like `git merge-base` with more than two revisions: the best common
+/// ancestor of the first revision and a hypothetical merge of the rest.
+pub fn merge_base_many(repo: &Repository, revs: &[String]) -> Result<String, AppError> {
+    crate::log_git_op_debug!("merge_base_many", revs = revs);
+    if revs.len() < 2 {
+        return Err(AppError::InvalidArgument(
+            "merge base needs at least two revisions".to_string(),
+        ));
+    }
+    let oids = revs
+        .iter()
+        .map(|rev| Ok(find_revision(repo, rev)?.id()))
+        .collect::<Result<Vec<Oid>, AppError>>()?;
+    let base = repo.merge_base_many(&oids).map_err(unrelated_histories)?;
+    Ok(base.to_string())
+}
+
+/// libgit2 reports a missing merge base as a bare "no merge base found";
+/// say why instead.
+pub(crate) fn unrelated_histories(e: git2::Error) -> AppError {
+    if e.code() != ErrorCode::NotFound {
+        return e.into();
+    }
+    AppError::Git(git2::Error::new(
+        ErrorCode::NotFound,
+        ErrorClass::Merge,
+        "revisions have no common ancestor (unrelated histories)",
+    ))
+}
+
+/// Paths with conflict entries in `index`, in index order, each listed once.
+pub(crate) fn conflicted_paths(index: &Index) -> Result<Vec<String>, AppError> {
+    let mut paths = Vec::new();
+    for conflict in index.conflicts()? {
+        let conflict = conflict?;
+        let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+        if let Some(entry) = entry {
+            let path = String::from_utf8_lossy(&entry.path).into_owned();
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    Ok(paths)
+}
@@ -1,16 +1,53 @@
+pub mod blame;
+pub mod cancel;
 pub mod commit;
+pub mod config;
+pub mod conflict;
 pub mod diff;
 pub mod graph;
+pub mod hooks;
+pub mod ignore;
+pub mod merge;
 pub mod operations;
+pub mod rebase;
+pub mod remote;
 pub mod repository;
+pub mod reset;
+pub mod signing;
 pub mod staging;
 pub mod stash;
+pub mod submodule;
 pub mod worktree;
 
+pub use blame::*;
+pub use cancel::*;
 pub use commit::*;
+pub use config::*;
+pub use conflict::*;
 pub use diff::*;
 pub use graph::*;
+pub use hooks::*;
+pub use ignore::*;
+pub use merge::*;
+pub use rebase::*;
+pub use remote::*;
 pub use repository::*;
+pub use reset::*;
+pub use signing::*;
 pub use staging::*;
 pub use stash::*;
+pub use submodule::*;
 pub use worktree::*;
+
+/// Whether `meta` has any executable bit set. Always false off Unix, which
+/// has no such bit.
+#[cfg(unix)]
+pub(crate) fn is_executable(meta: &std::fs::Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    meta.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_executable(_meta: &std::fs::Metadata) -> bool {
+    false
+}
@@ -1,6 +1,8 @@
-use git2::{Repository, RepositoryState, ResetType, Status, StatusOptions};
+use git2::{CherrypickOptions, Oid, Repository, RepositoryState, ResetType, Status, StatusOptions};
+use serde::Serialize;
 
 use crate::error::AppError;
+use crate::git::find_revision;
 
 pub fn abort_operation(repo: &Repository) -> Result<(), AppError> {
     crate::log_git_op!("abort_operation", state = format!("{:?}", repo.state()));
@@ -12,7 +14,10 @@ pub fn abort_operation(repo: &Repository) -> Result<(), AppError> {
             rebase.abort()?;
             Ok(())
         }
-        RepositoryState::CherryPick
+        // Merges, cherry-picks and reverts haven't moved HEAD yet: dropping
+        // the state files and hard-resetting to HEAD undoes them.
+        RepositoryState::Merge
+        | RepositoryState::CherryPick
         | RepositoryState::CherryPickSequence
         | RepositoryState::Revert
         | RepositoryState::RevertSequence => {
@@ -25,6 +30,9 @@ pub fn abort_operation(repo: &Repository) -> Result<(), AppError> {
     }
 }
 
+/// Finish the merge, rebase, cherry-pick or revert that `get_repo_state`
+/// reports once its conflicts are resolved and staged. Returns the hash of
+/// the last commit made.
 pub fn continue_operation(repo: &Repository) -> Result<String, AppError> {
     crate::log_git_op!("continue_operation", state = format!("{:?}", repo.state()));
     let conflicts = list_conflicted_paths(repo)?;
@@ -36,6 +44,7 @@ pub fn continue_operation(repo: &Repository) -> Result<String, AppError> {
         RepositoryState::Rebase
         | RepositoryState::RebaseInteractive
         | RepositoryState::RebaseMerge => continue_rebase(repo),
+        RepositoryState::Merge => continue_merge(repo),
         RepositoryState::CherryPick | RepositoryState::CherryPickSequence => {
             reject_if_sequencer(repo)?;
             continue_cherry_pick(repo)
@@ -48,6 +57,100 @@ pub fn continue_operation(repo: &Repository) -> Result<String, AppError> {
     }
 }
 
+/// Outcome of applying a range of commits one by one.
+#[derive(Debug, Serialize, Clone)]
+pub struct RangeResult {
+    /// New commits, in the order they were created.
+    pub applied: Vec<String>,
+    /// The commit that conflicted, if the range stopped early.
+    pub conflict: Option<RangeConflict>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RangeConflict {
+    /// The original commit being applied.
+    pub hash: String,
+    pub paths: Vec<String>,
+}
+
+/// Cherry-pick one commit onto HEAD and commit it with the original author
+/// and message. Returns the new commit, or `None` if it conflicted; the
+/// repository is then left mid-cherry-pick for `continue_operation`.
+pub fn cherry_pick(repo: &Repository, hash: &str) -> Result<Option<String>, AppError> {
+    crate::log_git_op!("cherry_pick", hash = hash);
+    let commit = repo.find_commit(Oid::from_str(hash)?)?;
+
+    let mut opts = CherrypickOptions::new();
+    if commit.parent_count() > 1 {
+        opts.mainline(1);
+    }
+    repo.cherrypick(&commit, Some(&mut opts))?;
+
+    if repo.index()?.has_conflicts() {
+        return Ok(None);
+    }
+    continue_cherry_pick(repo).map(Some)
+}
+
+/// Cherry-pick `from..=to` (oldest first) onto HEAD, one commit each. `from`
+/// must be a first-parent ancestor of `to`, or `to` itself. Stops at the
+/// first conflict, leaving it in progress for `continue_operation`; the rest
+/// of the range is not applied.
+pub fn cherry_pick_range(repo: &Repository, from: &str, to: &str) -> Result<RangeResult, AppError> {
+    crate::log_git_op!("cherry_pick_range", from = from, to = to);
+    let mut range = commit_range(repo, from, to)?;
+    range.reverse();
+    apply_range(repo, &range, cherry_pick)
+}
+
+/// Revert `from..=to` on top of HEAD, newest first, one revert commit each.
+/// Same range and conflict rules as `cherry_pick_range`.
+pub fn revert_range(repo: &Repository, from: &str, to: &str) -> Result<RangeResult, AppError> {
+    crate::log_git_op!("revert_range", from = from, to = to);
+    let range = commit_range(repo, from, to)?;
+    apply_range(repo, &range, super::revert_commit_and_commit)
+}
+
+/// Hashes from `to` back to `from` along first parents, newest first.
+fn commit_range(repo: &Repository, from: &str, to: &str) -> Result<Vec<String>, AppError> {
+    let from_id = find_revision(repo, from)?.id();
+    let mut commit = find_revision(repo, to)?;
+    let mut range = vec![commit.id().to_string()];
+    while commit.id() != from_id {
+        commit = commit
+            .parent(0)
+            .map_err(|_| AppError::InvalidArgument(format!("{from} is not an ancestor of {to}")))?;
+        range.push(commit.id().to_string());
+    }
+    Ok(range)
+}
+
+fn apply_range(
+    repo: &Repository,
+    range: &[String],
+    apply: fn(&Repository, &str) -> Result<Option<String>, AppError>,
+) -> Result<RangeResult, AppError> {
+    let mut applied = Vec::new();
+    for hash in range {
+        match apply(repo, hash)? {
+            Some(new_hash) => applied.push(new_hash),
+            None => {
+                return Ok(RangeResult {
+                    applied,
+                    conflict: Some(RangeConflict {
+                        hash: hash.clone(),
+                        paths: list_conflicted_paths(repo)?,
+                    }),
+                });
+            }
+        }
+    }
+    Ok(RangeResult {
+        applied,
+        conflict: None,
+    })
+}
+
 fn list_conflicted_paths(repo: &Repository) -> Result<Vec<String>, AppError> {
     let mut opts = StatusOptions::new();
     opts.include_untracked(false).include_ignored(false);
@@ -98,6 +201,36 @@ fn continue_rebase(repo: &Repository) -> Result<String, AppError> {
     Ok(last_oid.to_string())
 }
 
+fn continue_merge(repo: &Repository) -> Result<String, AppError> {
+    // One line per merged commit; more than one for an octopus merge.
+    let merge_heads = std::fs::read_to_string(repo.path().join("MERGE_HEAD"))
+        .map_err(|e| AppError::Internal(format!("Failed to read MERGE_HEAD: {e}")))?;
+    let message = std::fs::read_to_string(repo.path().join("MERGE_MSG"))
+        .unwrap_or_else(|_| "Merge".to_string());
+    // After a conflict MERGE_MSG lists the conflicted paths as `#` comments;
+    // strip them and trailing blank lines the way git's message cleanup does.
+    let message = git2::message_prettify(&message, Some(b'#'))?;
+
+    let mut index = repo.index()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let mut parents = vec![repo.head()?.peel_to_commit()?];
+    for line in merge_heads.lines().filter(|l| !l.trim().is_empty()) {
+        parents.push(repo.find_commit(Oid::from_str(line.trim())?)?);
+    }
+    let parents: Vec<&git2::Commit> = parents.iter().collect();
+    let signature = repo.signature()?;
+    let oid = repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        &message,
+        &tree,
+        &parents,
+    )?;
+    repo.cleanup_state()?;
+    Ok(oid.to_string())
+}
+
 fn continue_cherry_pick(repo: &Repository) -> Result<String, AppError> {
     let oid_str = std::fs::read_to_string(repo.path().join("CHERRY_PICK_HEAD"))
         .map_err(|e| AppError::Internal(format!("Failed to read CHERRY_PICK_HEAD: {e}")))?;
@@ -123,7 +256,7 @@ fn continue_revert(repo: &Repository) -> Result<String, AppError> {
     Ok(new_oid.to_string())
 }
 
-fn commit_index_to_head(
+pub(crate) fn commit_index_to_head(
     repo: &Repository,
     author: &git2::Signature<'_>,
     message: &str,
@@ -0,0 +1,98 @@
+use git2::{AnnotatedCommit, BranchType, ErrorCode, Repository};
+use serde::Serialize;
+
+use crate::error::AppError;
+use crate::git::merge::conflicted_paths;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RebaseResult {
+    /// HEAD after the rebase.
+    pub commit_hash: String,
+    /// Commits replayed. Patches already present upstream are skipped and
+    /// not counted.
+    pub commits_rebased: usize,
+}
+
+/// Replay the commits of HEAD that aren't in `upstream` on top of `onto`
+/// (defaults to `upstream`), keeping each commit's original author. Both
+/// names are local branches or remote-tracking branches such as
+/// `origin/main`.
+///
+/// All or nothing: if any step conflicts the rebase is aborted, restoring the
+/// branch and working tree, and `AppError::MergeConflict` carries the
+/// conflicted paths and the index of the failing step.
+pub fn rebase_onto(
+    repo: &Repository,
+    upstream: &str,
+    onto: Option<&str>,
+) -> Result<RebaseResult, AppError> {
+    crate::log_git_op!("rebase_onto", upstream = upstream, onto = onto);
+    let upstream = branch_annotated_commit(repo, upstream)?;
+    let onto = onto.map(|o| branch_annotated_commit(repo, o)).transpose()?;
+
+    // libgit2 refuses to start with a dirty index or working tree.
+    let mut rebase = repo.rebase(None, Some(&upstream), onto.as_ref(), None)?;
+    let committer = repo.signature()?;
+
+    // Every failure, conflict or otherwise, must leave the repo as it was
+    // rather than mid-rebase.
+    let commits_rebased = match replay(repo, &mut rebase, &committer) {
+        Ok(count) => count,
+        Err(e) => {
+            if let Err(abort_err) = rebase.abort() {
+                log::error!(target: "yagg::error", "rebase abort failed err={abort_err}");
+            }
+            return Err(e);
+        }
+    };
+
+    Ok(RebaseResult {
+        commit_hash: repo.head()?.peel_to_commit()?.id().to_string(),
+        commits_rebased,
+    })
+}
+
+/// Apply every remaining operation of `rebase` and finish it, returning how
+/// many commits were made. The caller aborts on error.
+fn replay(
+    repo: &Repository,
+    rebase: &mut git2::Rebase,
+    committer: &git2::Signature,
+) -> Result<usize, AppError> {
+    let mut commits_rebased = 0;
+
+    while let Some(op) = rebase.next() {
+        let op = op?;
+        let step = rebase.operation_current().unwrap_or(commits_rebased);
+
+        let index = repo.index()?;
+        if index.has_conflicts() {
+            return Err(AppError::MergeConflict {
+                paths: conflicted_paths(&index)?,
+                step: Some(step),
+            });
+        }
+
+        let original = repo.find_commit(op.id())?;
+        let author = original.author();
+        match rebase.commit(Some(&author), committer, None) {
+            Ok(_) => commits_rebased += 1,
+            // The patch is already upstream; nothing to commit for this step.
+            Err(e) if e.code() == ErrorCode::Applied => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    rebase.finish(Some(committer))?;
+    Ok(commits_rebased)
+}
+
+fn branch_annotated_commit<'r>(
+    repo: &'r Repository,
+    name: &str,
+) -> Result<AnnotatedCommit<'r>, AppError> {
+    let branch = repo
+        .find_branch(name, BranchType::Local)
+        .or_else(|_| repo.find_branch(name, BranchType::Remote))?;
+    Ok(repo.reference_to_annotated_commit(branch.get())?)
+}
@@ -0,0 +1,401 @@
+use std::cell::RefCell;
+
+use git2::{
+    BranchType, Cred, CredentialType, ErrorClass, ErrorCode, FetchOptions, PushOptions,
+    RemoteCallbacks, Repository,
+};
+use serde::Serialize;
+
+use crate::error::AppError;
+
+/// A configured remote and where it fetches from and pushes to.
+#[derive(Debug, Serialize, Clone)]
+pub struct RemoteInfo {
+    pub name: String,
+    pub fetch_url: String,
+    /// Explicit `pushurl`, or `None` when pushes go to `fetch_url`.
+    pub push_url: Option<String>,
+}
+
+/// A single remote-tracking ref that moved during a fetch.
+#[derive(Debug, Serialize, Clone)]
+pub struct RefUpdate {
+    /// Full local ref name, e.g. `refs/remotes/origin/main`.
+    pub ref_name: String,
+    /// Previous tip, or `None` when the ref was newly created.
+    pub old_hash: Option<String>,
+    pub new_hash: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FetchResult {
+    pub remote: String,
+    pub updated_refs: usize,
+    pub updates: Vec<RefUpdate>,
+}
+
+/// The remote's verdict on one ref we asked it to update.
+#[derive(Debug, Serialize, Clone)]
+pub struct PushRefStatus {
+    /// Remote ref name, e.g. `refs/heads/main`.
+    pub ref_name: String,
+    /// `None` when the remote accepted the update, otherwise its reason.
+    pub rejection: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PushResult {
+    pub remote: String,
+    pub branch: String,
+    pub updates: Vec<PushRefStatus>,
+}
+
+/// Progress reported while a push runs.
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PushProgress {
+    /// Objects sent so far out of `total`, and the bytes written.
+    Transfer {
+        current: usize,
+        total: usize,
+        bytes: usize,
+    },
+    /// Output from the remote side, shown by git as `remote: ...`.
+    Remote { message: String },
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TagPushResult {
+    pub remote: String,
+    pub tag: String,
+    pub updates: Vec<PushRefStatus>,
+}
+
+/// How many times libgit2 may ask for credentials before we give up. It
+/// re-invokes the callback after every rejected attempt, so without a cap a
+/// bad agent key loops forever.
+const MAX_CREDENTIAL_ATTEMPTS: usize = 3;
+
+/// Build callbacks that authenticate through the SSH agent for SSH remotes
+/// and the configured credential helper for HTTPS remotes.
+fn credential_callbacks<'a>(repo: &Repository) -> Result<RemoteCallbacks<'a>, AppError> {
+    let config = repo.config()?;
+    let mut attempts = 0;
+    let mut callbacks = RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        attempts += 1;
+        if attempts > MAX_CREDENTIAL_ATTEMPTS {
+            return Err(git2::Error::new(
+                ErrorCode::Auth,
+                ErrorClass::Net,
+                "authentication failed after repeated attempts",
+            ));
+        }
+        if allowed.contains(CredentialType::SSH_KEY) {
+            return Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"));
+        }
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            return Cred::credential_helper(&config, url, username_from_url);
+        }
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(username_from_url.unwrap_or("git"));
+        }
+        Cred::default()
+    });
+    Ok(callbacks)
+}
+
+/// Map a git2 error from a network operation, pulling authentication
+/// failures out into `AppError::AuthFailed` so the UI can prompt for
+/// credentials instead of showing a generic git error.
+fn map_remote_error(e: git2::Error) -> AppError {
+    let is_auth = e.code() == ErrorCode::Auth
+        || (matches!(e.class(), ErrorClass::Http | ErrorClass::Ssh)
+            && e.message().to_lowercase().contains("authentication"));
+    if is_auth {
+        AppError::AuthFailed(e.message().to_string())
+    } else {
+        AppError::Git(e)
+    }
+}
+
+pub fn list_remotes(repo: &Repository) -> Result<Vec<RemoteInfo>, AppError> {
+    crate::log_git_op_debug!("list_remotes");
+    let mut remotes = Vec::new();
+    for name in repo.remotes()?.iter().flatten().flatten() {
+        remotes.push(remote_info(repo, name)?);
+    }
+    Ok(remotes)
+}
+
+fn remote_info(repo: &Repository, name: &str) -> Result<RemoteInfo, AppError> {
+    let remote = repo.find_remote(name)?;
+    Ok(RemoteInfo {
+        name: name.to_string(),
+        fetch_url: remote.url()?.to_string(),
+        push_url: remote.pushurl()?.map(String::from),
+    })
+}
+
+pub fn add_remote(repo: &Repository, name: &str, url: &str) -> Result<RemoteInfo, AppError> {
+    crate::log_git_op!("add_remote", name = name);
+    if name.trim().is_empty() || !git2::Remote::is_valid_name(name) {
+        return Err(AppError::InvalidRemoteName(name.to_string()));
+    }
+    // A duplicate fails here with `ErrorCode::Exists`.
+    repo.remote(name, url)?;
+    remote_info(repo, name)
+}
+
+/// Delete a remote along with its remote-tracking branches and config.
+pub fn remove_remote(repo: &Repository, name: &str) -> Result<(), AppError> {
+    crate::log_git_op!("remove_remote", name = name);
+    // Fails with `ErrorCode::NotFound` for an unknown remote.
+    repo.find_remote(name)?;
+    repo.remote_delete(name)?;
+    Ok(())
+}
+
+/// Change a remote's fetch URL. An explicit push URL, if any, is untouched.
+pub fn set_remote_url(repo: &Repository, name: &str, url: &str) -> Result<RemoteInfo, AppError> {
+    crate::log_git_op!("set_remote_url", name = name);
+    repo.find_remote(name)?;
+    repo.remote_set_url(name, url)?;
+    remote_info(repo, name)
+}
+
+/// Fetch from a named remote using its configured refspecs and report every
+/// remote-tracking ref that moved.
+pub fn fetch_remote(repo: &Repository, remote_name: &str) -> Result<FetchResult, AppError> {
+    crate::log_git_op!("fetch_remote", remote = remote_name);
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let mut updates: Vec<RefUpdate> = Vec::new();
+    {
+        let mut callbacks = credential_callbacks(repo)?;
+        callbacks.update_tips(|ref_name, old, new| {
+            updates.push(RefUpdate {
+                ref_name: ref_name.to_string(),
+                old_hash: (!old.is_zero()).then(|| old.to_string()),
+                new_hash: new.to_string(),
+            });
+            true
+        });
+        let mut fetch_opts = FetchOptions::new();
+        fetch_opts.remote_callbacks(callbacks);
+
+        remote
+            .fetch(&[] as &[&str], Some(&mut fetch_opts), None)
+            .map_err(map_remote_error)?;
+    }
+
+    Ok(FetchResult {
+        remote: remote_name.to_string(),
+        updated_refs: updates.len(),
+        updates,
+    })
+}
+
+/// Push a local branch to the same-named branch on `remote_name`. With
+/// `force` the refspec gets a leading `+`, allowing non-fast-forward updates.
+/// A non-fast-forward rejection surfaces as `AppError::PushRejected`; any
+/// other per-ref rejection (e.g. a server-side hook) is returned in
+/// `updates` so the caller can show the remote's reason.
+pub fn push_branch(
+    repo: &Repository,
+    remote_name: &str,
+    branch_name: &str,
+    force: bool,
+) -> Result<PushResult, AppError> {
+    // Logged by `push_branch_with_progress`.
+    push_branch_with_progress(repo, remote_name, branch_name, force, |_| {})
+}
+
+/// `push_branch`, reporting transfer progress and the remote's output to
+/// `progress` as the push runs.
+pub fn push_branch_with_progress(
+    repo: &Repository,
+    remote_name: &str,
+    branch_name: &str,
+    force: bool,
+    mut progress: impl FnMut(PushProgress),
+) -> Result<PushResult, AppError> {
+    crate::log_git_op!(
+        "push_branch_with_progress",
+        remote = remote_name,
+        branch = branch_name,
+        force = force
+    );
+    // Fail early with a clear error rather than a refspec parse failure.
+    repo.find_branch(branch_name, BranchType::Local)?;
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let ref_name = format!("refs/heads/{branch_name}");
+    let refspec = if force {
+        format!("+{ref_name}:{ref_name}")
+    } else {
+        format!("{ref_name}:{ref_name}")
+    };
+    let updates = push_refspec(repo, &mut remote, &refspec, &ref_name, &mut progress)?;
+
+    Ok(PushResult {
+        remote: remote_name.to_string(),
+        branch: branch_name.to_string(),
+        updates,
+    })
+}
+
+/// `refs/tags/<tag_name>`, checked before it goes into a refspec, where a
+/// `:` or `*` would change what gets pushed.
+fn tag_ref_name(tag_name: &str) -> Result<String, AppError> {
+    let ref_name = format!("refs/tags/{tag_name}");
+    if tag_name.contains([':', '*']) || !git2::Reference::is_valid_name(&ref_name) {
+        return Err(AppError::InvalidTagName(tag_name.to_string()));
+    }
+    Ok(ref_name)
+}
+
+/// Publish local tag `tag_name` to `remote_name`. The remote refuses to move
+/// an existing tag; that shows up as a rejection in `updates`.
+pub fn push_tag(
+    repo: &Repository,
+    remote_name: &str,
+    tag_name: &str,
+) -> Result<TagPushResult, AppError> {
+    crate::log_git_op!("push_tag", remote = remote_name, tag = tag_name);
+    let ref_name = tag_ref_name(tag_name)?;
+    repo.find_reference(&ref_name)?;
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let refspec = format!("{ref_name}:{ref_name}");
+    let updates = push_refspec(repo, &mut remote, &refspec, &ref_name, &mut |_| {})?;
+
+    Ok(TagPushResult {
+        remote: remote_name.to_string(),
+        tag: tag_name.to_string(),
+        updates,
+    })
+}
+
+/// Delete tag `tag_name` on `remote_name` (`git push <remote> :refs/tags/<tag>`).
+/// The local tag, if any, is left alone.
+pub fn delete_remote_tag(
+    repo: &Repository,
+    remote_name: &str,
+    tag_name: &str,
+) -> Result<TagPushResult, AppError> {
+    crate::log_git_op!("delete_remote_tag", remote = remote_name, tag = tag_name);
+    let ref_name = tag_ref_name(tag_name)?;
+    let mut remote = repo.find_remote(remote_name)?;
+
+    let refspec = format!(":{ref_name}");
+    let updates = push_refspec(repo, &mut remote, &refspec, &ref_name, &mut |_| {})?;
+
+    Ok(TagPushResult {
+        remote: remote_name.to_string(),
+        tag: tag_name.to_string(),
+        updates,
+    })
+}
+
+/// Push one refspec with the usual credentials and collect the remote's
+/// per-ref verdicts. Non-fast-forward rejections become `PushRejected`;
+/// other rejections are only reported in the returned statuses.
+fn push_refspec(
+    repo: &Repository,
+    remote: &mut git2::Remote,
+    refspec: &str,
+    ref_name: &str,
+    progress: &mut dyn FnMut(PushProgress),
+) -> Result<Vec<PushRefStatus>, AppError> {
+    let mut updates: Vec<PushRefStatus> = Vec::new();
+    // Both progress callbacks report through the one sink.
+    let progress = RefCell::new(progress);
+    {
+        let mut callbacks = credential_callbacks(repo)?;
+        callbacks.push_transfer_progress(|current, total, bytes| {
+            (progress.borrow_mut())(PushProgress::Transfer {
+                current,
+                total,
+                bytes,
+            });
+        });
+        callbacks.sideband_progress(|data| {
+            let message = String::from_utf8_lossy(data).trim_end().to_string();
+            if !message.is_empty() {
+                (progress.borrow_mut())(PushProgress::Remote { message });
+            }
+            true
+        });
+        callbacks.push_update_reference(|ref_name, status| {
+            updates.push(PushRefStatus {
+                ref_name: ref_name.to_string(),
+                rejection: status.map(str::to_string),
+            });
+            Ok(())
+        });
+        let mut push_opts = PushOptions::new();
+        push_opts.remote_callbacks(callbacks);
+
+        remote.push(&[refspec], Some(&mut push_opts)).map_err(|e| {
+            if e.code() == ErrorCode::NotFastForward {
+                AppError::PushRejected(format!("{ref_name}: {}", e.message()))
+            } else {
+                map_remote_error(e)
+            }
+        })?;
+    }
+
+    if let Some(rejected) = updates.iter().find(|u| {
+        u.rejection
+            .as_deref()
+            .is_some_and(is_non_fast_forward_rejection)
+    }) {
+        return Err(AppError::PushRejected(format!(
+            "{}: {}",
+            rejected.ref_name,
+            rejected.rejection.as_deref().unwrap_or_default()
+        )));
+    }
+
+    Ok(updates)
+}
+
+/// Servers phrase non-fast-forward rejections differently ("non-fast-forward",
+/// "fetch first", "stale info"); match the common forms.
+fn is_non_fast_forward_rejection(status: &str) -> bool {
+    let status = status.to_lowercase();
+    ["non-fast-forward", "fetch first", "stale info"]
+        .iter()
+        .any(|needle| status.contains(needle))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Pure-function unit tests for the private helpers. Behavior tests for
+    // `fetch_remote` and `push_branch` live in tests/remote.rs.
+    #[test]
+    fn test_map_remote_error_auth() {
+        let err = git2::Error::new(ErrorCode::Auth, ErrorClass::Http, "bad credentials");
+        assert!(matches!(map_remote_error(err), AppError::AuthFailed(_)));
+
+        let err = git2::Error::new(
+            ErrorCode::GenericError,
+            ErrorClass::Ssh,
+            "Failed to authenticate SSH session: authentication required",
+        );
+        assert!(matches!(map_remote_error(err), AppError::AuthFailed(_)));
+
+        let err = git2::Error::new(ErrorCode::NotFound, ErrorClass::Net, "unreachable");
+        assert!(matches!(map_remote_error(err), AppError::Git(_)));
+    }
+
+    #[test]
+    fn test_is_non_fast_forward_rejection() {
+        assert!(is_non_fast_forward_rejection("non-fast-forward"));
+        assert!(is_non_fast_forward_rejection("rejected (fetch first)"));
+        assert!(!is_non_fast_forward_rejection("pre-receive hook declined"));
+    }
+}
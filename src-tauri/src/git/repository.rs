@@ -1,5 +1,7 @@
-use git2::{Repository, RepositoryState};
+use git2::build::CheckoutBuilder;
+use git2::{CheckoutNotificationType, Oid, Repository, RepositoryState};
 use serde::Serialize;
+use std::collections::HashSet;
 use std::path::Path;
 
 use crate::error::AppError;
@@ -11,23 +13,54 @@ pub struct RepositoryInfo {
     pub is_detached: bool,
     pub remotes: Vec<String>,
     pub head_hash: Option<String>,
-    pub repo_state: String,
+    pub repo_state: RepoState,
+    /// Upstream of the current branch, e.g. `origin/main`. `None` when
+    /// detached or no upstream is configured, as are `ahead`/`behind`.
+    pub upstream: Option<String>,
+    /// Commits on the current branch not on its upstream.
+    pub ahead: Option<usize>,
+    /// Commits on the upstream not on the current branch.
+    pub behind: Option<usize>,
 }
 
-fn map_repo_state(state: RepositoryState) -> &'static str {
+/// In-progress operation, if any. Serialized as `"clean"`, `"merge"`,
+/// `"cherry-pick"`, etc.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RepoState {
+    Clean,
+    Merge,
+    Revert,
+    CherryPick,
+    Bisect,
+    Rebase,
+    /// `git am` is applying patches.
+    ApplyMailbox,
+}
+
+fn map_repo_state(state: RepositoryState) -> RepoState {
     match state {
-        RepositoryState::Clean => "clean",
-        RepositoryState::Merge => "merge",
-        RepositoryState::Revert | RepositoryState::RevertSequence => "revert",
-        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => "cherry-pick",
-        RepositoryState::Bisect => "bisect",
+        RepositoryState::Clean => RepoState::Clean,
+        RepositoryState::Merge => RepoState::Merge,
+        RepositoryState::Revert | RepositoryState::RevertSequence => RepoState::Revert,
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => RepoState::CherryPick,
+        RepositoryState::Bisect => RepoState::Bisect,
         RepositoryState::Rebase
         | RepositoryState::RebaseInteractive
-        | RepositoryState::RebaseMerge => "rebase",
-        _ => "clean",
+        | RepositoryState::RebaseMerge => RepoState::Rebase,
+        RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => {
+            RepoState::ApplyMailbox
+        }
     }
 }
 
+/// The operation the repository is in the middle of, e.g. `Merge` after a
+/// conflicted merge until it is committed or aborted.
+pub fn get_repo_state(repo: &Repository) -> RepoState {
+    crate::log_git_op_debug!("get_repo_state");
+    map_repo_state(repo.state())
+}
+
 // open_repo intentionally uses direct `log::info!` / `log::error!` calls
 // instead of the `log_git_op!` macro. The macro is hard-coded to the
 // `yagg::git` target with the `op=name k=v` shape — this site needs the
@@ -42,6 +75,141 @@ pub fn open_repo(path: &Path) -> Result<Repository, AppError> {
     Ok(repo)
 }
 
+/// Like `open_repo`, but `path` may be anywhere inside the working tree:
+/// the repository is found by walking up to the nearest `.git`, as git
+/// itself does. Same logging exception as `open_repo`.
+pub fn discover_repo(path: &Path) -> Result<Repository, AppError> {
+    log::info!(target: "yagg::lifecycle", "discover_repo path={:?}", path);
+    let repo = Repository::discover(path).map_err(|e| {
+        log::error!(target: "yagg::error", "discover_repo failed path={:?} err={e}", path);
+        AppError::from(e)
+    })?;
+    Ok(repo)
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct RepoStats {
+    /// Commits reachable from HEAD, branches (local and remote) and tags.
+    pub commit_count: usize,
+    /// Distinct author emails over those commits, compared case-insensitively.
+    pub contributor_count: usize,
+    /// Local branches.
+    pub branch_count: usize,
+    pub tag_count: usize,
+    /// Files tracked in the index.
+    pub file_count: usize,
+}
+
+/// History walk results kept between `get_repo_stats` calls, reused for as
+/// long as the refs point at the same commits. Hold one per open repository.
+#[derive(Debug, Default)]
+pub struct HistoryCache {
+    tips: Vec<Oid>,
+    commit_count: usize,
+    contributor_count: usize,
+}
+
+/// Commits the history walk starts from, sorted so they compare equal
+/// whenever the refs point at the same place.
+fn history_tips(repo: &Repository) -> Result<Vec<Oid>, AppError> {
+    let mut tips = Vec::new();
+    if let Ok(head) = repo.head() {
+        tips.extend(head.peel_to_commit().ok().map(|c| c.id()));
+    }
+    for reference in repo.references()? {
+        let reference = reference?;
+        let name = reference.name_bytes();
+        let wanted = [&b"refs/heads/"[..], b"refs/remotes/", b"refs/tags/"]
+            .iter()
+            .any(|prefix| name.starts_with(prefix));
+        if wanted {
+            // Tags may point at trees or blobs; those add no history.
+            tips.extend(reference.peel_to_commit().ok().map(|c| c.id()));
+        }
+    }
+    tips.sort();
+    tips.dedup();
+    Ok(tips)
+}
+
+/// Counts for an "about this repository" view.
+///
+/// The commit and contributor counts need a walk over every reachable
+/// commit, which takes seconds on repositories with hundreds of thousands
+/// of commits. That part is kept in `cache` and only redone when a branch,
+/// tag or HEAD has moved; the other counts are cheap and always fresh.
+pub fn get_repo_stats(repo: &Repository, cache: &mut HistoryCache) -> Result<RepoStats, AppError> {
+    crate::log_git_op_debug!("get_repo_stats");
+    let tips = history_tips(repo)?;
+    if tips != cache.tips {
+        let mut revwalk = repo.revwalk()?;
+        for tip in &tips {
+            revwalk.push(*tip)?;
+        }
+        let mut commit_count = 0;
+        let mut authors = HashSet::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            authors.insert(commit.author().email_bytes().to_ascii_lowercase());
+            commit_count += 1;
+        }
+        *cache = HistoryCache {
+            tips,
+            commit_count,
+            contributor_count: authors.len(),
+        };
+    }
+
+    Ok(RepoStats {
+        commit_count: cache.commit_count,
+        contributor_count: cache.contributor_count,
+        branch_count: repo.branches(Some(git2::BranchType::Local))?.count(),
+        tag_count: repo.tag_names(None)?.len(),
+        file_count: repo.index()?.len(),
+    })
+}
+
+/// Create a repository in the existing directory `path`, with a working
+/// tree unless `bare`. Refuses a directory that already is a repository.
+/// Same logging exception as `open_repo`.
+pub fn init_repo(path: &Path, bare: bool) -> Result<Repository, AppError> {
+    log::info!(target: "yagg::lifecycle", "init_repo path={:?} bare={bare}", path);
+    if !path.is_dir() {
+        return Err(AppError::InvalidPath(format!(
+            "{} is not a directory",
+            path.display()
+        )));
+    }
+    if Repository::open(path).is_ok() {
+        return Err(AppError::InvalidPath(format!(
+            "{} is already a git repository",
+            path.display()
+        )));
+    }
+    let result = if bare {
+        Repository::init_bare(path)
+    } else {
+        Repository::init(path)
+    };
+    result.map_err(|e| {
+        log::error!(target: "yagg::error", "init_repo failed path={:?} err={e}", path);
+        AppError::from(e)
+    })
+}
+
+/// Upstream name plus ahead/behind counts for local branch `name`, or `None`
+/// if it has no (resolvable) upstream.
+fn upstream_tracking(repo: &Repository, name: &str) -> Option<(String, usize, usize)> {
+    let branch = repo.find_branch(name, git2::BranchType::Local).ok()?;
+    let local = branch.get().target()?;
+    let upstream = branch.upstream().ok()?;
+    let upstream_name = upstream.name().ok()??.to_string();
+    let (ahead, behind) = repo
+        .graph_ahead_behind(local, upstream.get().target()?)
+        .ok()?;
+    Some((upstream_name, ahead, behind))
+}
+
 pub fn get_repo_info(repo: &Repository) -> Result<RepositoryInfo, AppError> {
     crate::log_git_op_debug!("get_repo_info");
     let path = repo
@@ -69,7 +237,15 @@ pub fn get_repo_info(repo: &Repository) -> Result<RepositoryInfo, AppError> {
         .filter_map(|r| r.ok().flatten().map(String::from))
         .collect();
 
-    let repo_state = map_repo_state(repo.state()).to_string();
+    let repo_state = map_repo_state(repo.state());
+
+    let tracking = current_branch
+        .as_deref()
+        .and_then(|name| upstream_tracking(repo, name));
+    let (upstream, ahead, behind) = match tracking {
+        Some((upstream, ahead, behind)) => (Some(upstream), Some(ahead), Some(behind)),
+        None => (None, None, None),
+    };
 
     Ok(RepositoryInfo {
         path,
@@ -78,9 +254,46 @@ pub fn get_repo_info(repo: &Repository) -> Result<RepositoryInfo, AppError> {
         remotes,
         head_hash,
         repo_state,
+        upstream,
+        ahead,
+        behind,
     })
 }
 
+/// Update the index and working tree to `tree`, leaving HEAD alone. Unless
+/// `force`, files with uncommitted changes (or untracked files) that the
+/// checkout would overwrite are left untouched and reported as
+/// `AppError::CheckoutConflict`.
+pub(crate) fn checkout_tree_checked(
+    repo: &Repository,
+    tree: &git2::Tree,
+    force: bool,
+) -> Result<(), AppError> {
+    if force {
+        repo.checkout_tree(tree.as_object(), Some(CheckoutBuilder::new().force()))?;
+        return Ok(());
+    }
+
+    let mut conflicts = Vec::new();
+    let result = {
+        let mut checkout = CheckoutBuilder::new();
+        checkout
+            .safe()
+            .notify_on(CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path {
+                    conflicts.push(path.to_string_lossy().into_owned());
+                }
+                true
+            });
+        repo.checkout_tree(tree.as_object(), Some(&mut checkout))
+    };
+    if !conflicts.is_empty() {
+        return Err(AppError::CheckoutConflict(conflicts));
+    }
+    Ok(result?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,18 +302,42 @@ mod tests {
     // Behavior tests for `open_repo` / `get_repo_info` live in tests/repository.rs.
     #[test]
     fn test_map_repo_state_values() {
-        assert_eq!(map_repo_state(RepositoryState::Clean), "clean");
-        assert_eq!(map_repo_state(RepositoryState::Merge), "merge");
-        assert_eq!(map_repo_state(RepositoryState::Revert), "revert");
-        assert_eq!(map_repo_state(RepositoryState::RevertSequence), "revert");
-        assert_eq!(map_repo_state(RepositoryState::CherryPick), "cherry-pick");
+        assert_eq!(map_repo_state(RepositoryState::Clean), RepoState::Clean);
+        assert_eq!(map_repo_state(RepositoryState::Merge), RepoState::Merge);
+        assert_eq!(map_repo_state(RepositoryState::Revert), RepoState::Revert);
+        assert_eq!(
+            map_repo_state(RepositoryState::RevertSequence),
+            RepoState::Revert
+        );
+        assert_eq!(
+            map_repo_state(RepositoryState::CherryPick),
+            RepoState::CherryPick
+        );
         assert_eq!(
             map_repo_state(RepositoryState::CherryPickSequence),
-            "cherry-pick"
+            RepoState::CherryPick
+        );
+        assert_eq!(map_repo_state(RepositoryState::Bisect), RepoState::Bisect);
+        assert_eq!(map_repo_state(RepositoryState::Rebase), RepoState::Rebase);
+        assert_eq!(
+            map_repo_state(RepositoryState::RebaseInteractive),
+            RepoState::Rebase
         );
-        assert_eq!(map_repo_state(RepositoryState::Bisect), "bisect");
-        assert_eq!(map_repo_state(RepositoryState::Rebase), "rebase");
-        assert_eq!(map_repo_state(RepositoryState::RebaseInteractive), "rebase");
-        assert_eq!(map_repo_state(RepositoryState::RebaseMerge), "rebase");
+        assert_eq!(
+            map_repo_state(RepositoryState::RebaseMerge),
+            RepoState::Rebase
+        );
+        assert_eq!(
+            map_repo_state(RepositoryState::ApplyMailboxOrRebase),
+            RepoState::ApplyMailbox
+        );
+    }
+
+    #[test]
+    fn test_repo_state_serializes_kebab_case() {
+        let json = serde_json::to_string(&RepoState::CherryPick).unwrap();
+        assert_eq!(json, r#""cherry-pick""#);
+        let json = serde_json::to_string(&RepoState::Clean).unwrap();
+        assert_eq!(json, r#""clean""#);
     }
 }
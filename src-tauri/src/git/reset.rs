@@ -0,0 +1,69 @@
+use git2::build::CheckoutBuilder;
+use git2::{Oid, Repository, ResetType};
+
+use crate::error::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResetMode {
+    /// Move HEAD only; index and working tree keep their contents.
+    Soft,
+    /// Move HEAD and reset the index; the working tree is untouched.
+    Mixed,
+    /// Move HEAD, reset the index, and overwrite the working tree.
+    Hard,
+}
+
+impl ResetMode {
+    pub fn parse(mode: &str) -> Result<Self, AppError> {
+        match mode {
+            "soft" => Ok(ResetMode::Soft),
+            "mixed" => Ok(ResetMode::Mixed),
+            "hard" => Ok(ResetMode::Hard),
+            other => Err(AppError::InvalidArgument(format!(
+                "Unknown reset mode: {other} (expected soft, mixed, or hard)"
+            ))),
+        }
+    }
+}
+
+impl From<ResetMode> for ResetType {
+    fn from(mode: ResetMode) -> Self {
+        match mode {
+            ResetMode::Soft => ResetType::Soft,
+            ResetMode::Mixed => ResetType::Mixed,
+            ResetMode::Hard => ResetType::Hard,
+        }
+    }
+}
+
+pub fn reset_to_commit(repo: &Repository, hash: &str, mode: ResetMode) -> Result<(), AppError> {
+    crate::log_git_op!("reset_to_commit", hash = hash, mode = mode);
+    let oid = Oid::from_str(hash)
+        .map_err(|_| AppError::InvalidArgument(format!("Invalid commit hash: {hash}")))?;
+    let commit = repo.find_commit(oid)?;
+
+    let mut checkout = CheckoutBuilder::new();
+    let checkout = match mode {
+        ResetMode::Hard => Some(checkout.force()),
+        ResetMode::Soft | ResetMode::Mixed => None,
+    };
+    repo.reset(commit.as_object(), mode.into(), checkout)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reset_mode_parse() {
+        assert_eq!(ResetMode::parse("soft").unwrap(), ResetMode::Soft);
+        assert_eq!(ResetMode::parse("mixed").unwrap(), ResetMode::Mixed);
+        assert_eq!(ResetMode::parse("hard").unwrap(), ResetMode::Hard);
+        assert!(ResetMode::parse("HARD").is_err());
+        assert!(matches!(
+            ResetMode::parse("keep"),
+            Err(AppError::InvalidArgument(_))
+        ));
+    }
+}
@@ -0,0 +1,156 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use git2::{Commit, Oid, Repository, Signature, Tree};
+
+use crate::error::AppError;
+
+/// Signature formats understood by `gpg.format`. `x509` (gpgsm) is not
+/// supported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningFormat {
+    OpenPgp,
+    Ssh,
+}
+
+/// How to sign commits, as read from git config.
+#[derive(Debug, Clone)]
+pub struct SigningConfig {
+    pub format: SigningFormat,
+    /// `gpg.program` / `gpg.ssh.program`, defaulting to `gpg` / `ssh-keygen`.
+    pub program: String,
+    /// `user.signingkey`. Optional for OpenPGP (gpg picks its default key);
+    /// required for SSH, either a key file path or a `key::` literal.
+    pub key: Option<String>,
+}
+
+/// Signing settings for a commit. `sign` overrides `commit.gpgsign`; returns
+/// `None` when the commit shouldn't be signed.
+pub fn signing_config(
+    repo: &Repository,
+    sign: Option<bool>,
+) -> Result<Option<SigningConfig>, AppError> {
+    let config = repo.config()?;
+    let enabled = sign.unwrap_or_else(|| config.get_bool("commit.gpgsign").unwrap_or(false));
+    if !enabled {
+        return Ok(None);
+    }
+
+    let format = match config.get_string("gpg.format").ok().as_deref() {
+        None | Some("openpgp") => SigningFormat::OpenPgp,
+        Some("ssh") => SigningFormat::Ssh,
+        Some(other) => {
+            return Err(AppError::SigningFailed(format!(
+                "Unsupported gpg.format: {other}"
+            )))
+        }
+    };
+    let program = match format {
+        SigningFormat::OpenPgp => config
+            .get_string("gpg.openpgp.program")
+            .or_else(|_| config.get_string("gpg.program"))
+            .unwrap_or_else(|_| "gpg".to_string()),
+        SigningFormat::Ssh => config
+            .get_string("gpg.ssh.program")
+            .unwrap_or_else(|_| "ssh-keygen".to_string()),
+    };
+    let key = config.get_string("user.signingkey").ok();
+    if format == SigningFormat::Ssh && key.is_none() {
+        return Err(AppError::SigningFailed(
+            "user.signingkey must be set for SSH signing".into(),
+        ));
+    }
+
+    Ok(Some(SigningConfig {
+        format,
+        program,
+        key,
+    }))
+}
+
+/// Build the commit object, sign it, and write it without moving any ref.
+pub fn create_signed_commit(
+    repo: &Repository,
+    config: &SigningConfig,
+    author: &Signature,
+    committer: &Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Oid, AppError> {
+    crate::log_git_op!("create_signed_commit", format = config.format);
+    let buffer = repo.commit_create_buffer(author, committer, message, tree, parents)?;
+    let buffer = std::str::from_utf8(&buffer)
+        .map_err(|e| AppError::SigningFailed(format!("Commit buffer is not UTF-8: {e}")))?;
+    let signature = sign_buffer(config, buffer)?;
+    Ok(repo.commit_signed(buffer, &signature, None)?)
+}
+
+fn sign_buffer(config: &SigningConfig, buffer: &str) -> Result<String, AppError> {
+    match config.format {
+        SigningFormat::OpenPgp => sign_openpgp(config, buffer),
+        SigningFormat::Ssh => sign_ssh(config, buffer),
+    }
+}
+
+/// Same invocation git uses: a detached, armored signature on stdout.
+fn sign_openpgp(config: &SigningConfig, buffer: &str) -> Result<String, AppError> {
+    let mut command = Command::new(&config.program);
+    command.arg("--status-fd=2").arg("-bsa");
+    if let Some(key) = &config.key {
+        command.arg("-u").arg(key);
+    }
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::SigningFailed(format!("Failed to run {}: {e}", config.program)))?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| {
+            AppError::SigningFailed(format!("Failed to open stdin of {}", config.program))
+        })?
+        .write_all(buffer.as_bytes())?;
+    let output = child.wait_with_output()?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || !stderr.contains("[GNUPG:] SIG_CREATED ") {
+        return Err(AppError::SigningFailed(stderr.trim().to_string()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `ssh-keygen -Y sign` only works on files, so the buffer (and a literal
+/// `key::` public key, if configured) go through a private temp dir, which
+/// is removed on drop along with the `.sig` ssh-keygen writes beside them.
+fn sign_ssh(config: &SigningConfig, buffer: &str) -> Result<String, AppError> {
+    let key = config.key.as_deref().unwrap_or_default();
+    let temp = tempfile::Builder::new().prefix("yagg-sign-").tempdir()?;
+    let key_path = match key.strip_prefix("key::") {
+        Some(literal) => {
+            let path = temp.path().join("key.pub");
+            std::fs::write(&path, literal)?;
+            path
+        }
+        None => PathBuf::from(key),
+    };
+    let buffer_path = temp.path().join("commit");
+    std::fs::write(&buffer_path, buffer)?;
+
+    let output = Command::new(&config.program)
+        .args(["-Y", "sign", "-n", "git", "-f"])
+        .arg(&key_path)
+        .arg(&buffer_path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| AppError::SigningFailed(format!("Failed to run {}: {e}", config.program)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::SigningFailed(stderr.trim().to_string()));
+    }
+
+    Ok(std::fs::read_to_string(temp.path().join("commit.sig"))?)
+}
@@ -1,10 +1,21 @@
-use git2::{Index, Oid, Repository, RevertOptions, Status, StatusOptions};
+use git2::{
+    ApplyLocation, DiffOptions, Index, IndexAddOption, Oid, Repository, RevertOptions, Status,
+    StatusOptions,
+};
 use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 
 use crate::error::AppError;
+use crate::git::commit::diff_file_changes;
+use crate::git::diff::DiffConfig;
 
-fn create_index_entry(path: &str, mode: u32, file_size: u32, id: git2::Oid) -> git2::IndexEntry {
+pub(crate) fn create_index_entry(
+    path: &str,
+    mode: u32,
+    file_size: u32,
+    id: git2::Oid,
+) -> git2::IndexEntry {
     git2::IndexEntry {
         ctime: git2::IndexTime::new(0, 0),
         mtime: git2::IndexTime::new(0, 0),
@@ -45,11 +56,47 @@ fn get_file_content_from_index_or_head(
     }
 }
 
+const SYMLINK_MODE: u32 = 0o120000;
+
+/// Mode for an index entry that hunk/line staging writes for `path`: its
+/// index mode, else its HEAD mode, else taken from the working tree, so an
+/// executable keeps its executable bit. Symlinks are refused since their
+/// blob is the link target, which can't be changed a hunk at a time.
+fn partial_stage_mode(repo: &Repository, index: &Index, path: &str) -> Result<u32, AppError> {
+    let workdir_meta = repo
+        .workdir()
+        .and_then(|workdir| std::fs::symlink_metadata(workdir.join(path)).ok());
+    let head_mode = || {
+        let tree = repo.head().ok()?.peel_to_tree().ok()?;
+        let entry = tree.get_path(Path::new(path)).ok()?;
+        Some(entry.filemode() as u32)
+    };
+
+    let mode = match index.get_path(Path::new(path), 0) {
+        Some(entry) => entry.mode,
+        None => head_mode().unwrap_or_else(|| match &workdir_meta {
+            Some(meta) if super::is_executable(meta) => 0o100755,
+            _ => 0o100644,
+        }),
+    };
+    let workdir_symlink = workdir_meta.is_some_and(|meta| meta.file_type().is_symlink());
+    if mode == SYMLINK_MODE || workdir_symlink {
+        return Err(AppError::InvalidArgument(format!(
+            "{path} is a symlink; stage or discard it as a whole"
+        )));
+    }
+    Ok(mode)
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct FileStatus {
     pub path: String,
     pub status: FileStatusType,
     pub is_staged: bool,
+    /// Line counts for this side of the change, filled in by
+    /// `add_line_counts`. Binary files count as zero.
+    pub additions: Option<usize>,
+    pub deletions: Option<usize>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -63,6 +110,7 @@ pub enum FileStatusType {
     Copied,
     Untracked,
     Conflicted,
+    Ignored,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -70,20 +118,27 @@ pub struct FileStatuses {
     pub staged: Vec<FileStatus>,
     pub unstaged: Vec<FileStatus>,
     pub untracked: Vec<FileStatus>,
+    /// Only filled when `include_ignored` is set. Ignored directories are
+    /// reported once (`dir/`) rather than walked.
+    pub ignored: Vec<FileStatus>,
 }
 
-pub fn get_file_statuses(repo: &Repository) -> Result<FileStatuses, AppError> {
-    crate::log_git_op_debug!("get_file_statuses");
+pub fn get_file_statuses(
+    repo: &Repository,
+    include_ignored: bool,
+) -> Result<FileStatuses, AppError> {
+    crate::log_git_op_debug!("get_file_statuses", include_ignored = include_ignored);
     let mut opts = StatusOptions::new();
     opts.include_untracked(true)
         .recurse_untracked_dirs(true)
-        .include_ignored(false);
+        .include_ignored(include_ignored);
 
     let statuses = repo.statuses(Some(&mut opts))?;
 
     let mut staged = Vec::new();
     let mut unstaged = Vec::new();
     let mut untracked = Vec::new();
+    let mut ignored = Vec::new();
 
     for entry in statuses.iter() {
         let path = entry.path().unwrap_or("").to_string();
@@ -101,6 +156,8 @@ pub fn get_file_statuses(repo: &Repository) -> Result<FileStatuses, AppError> {
                 path: path.clone(),
                 status: index_status_to_type(status),
                 is_staged: true,
+                additions: None,
+                deletions: None,
             });
         }
 
@@ -112,6 +169,8 @@ pub fn get_file_statuses(repo: &Repository) -> Result<FileStatuses, AppError> {
                 path: path.clone(),
                 status: workdir_status_to_type(status),
                 is_staged: false,
+                additions: None,
+                deletions: None,
             });
         }
 
@@ -121,6 +180,19 @@ pub fn get_file_statuses(repo: &Repository) -> Result<FileStatuses, AppError> {
                 path: path.clone(),
                 status: FileStatusType::Untracked,
                 is_staged: false,
+                additions: None,
+                deletions: None,
+            });
+        }
+
+        // Ignored files (only reported when include_ignored is set)
+        if status.contains(Status::IGNORED) {
+            ignored.push(FileStatus {
+                path: path.clone(),
+                status: FileStatusType::Ignored,
+                is_staged: false,
+                additions: None,
+                deletions: None,
             });
         }
 
@@ -130,6 +202,8 @@ pub fn get_file_statuses(repo: &Repository) -> Result<FileStatuses, AppError> {
                 path,
                 status: FileStatusType::Conflicted,
                 is_staged: false,
+                additions: None,
+                deletions: None,
             });
         }
     }
@@ -138,59 +212,122 @@ pub fn get_file_statuses(repo: &Repository) -> Result<FileStatuses, AppError> {
         staged,
         unstaged,
         untracked,
+        ignored,
     })
 }
 
-pub fn stage_file(repo: &Repository, path: &str) -> Result<(), AppError> {
-    crate::log_git_op!("stage_file", path = path);
-    let mut index = repo.index()?;
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| AppError::InvalidPath("No workdir".into()))?;
-    let full_path = workdir.join(path);
+/// Fill in `additions` and `deletions` for the staged, unstaged and untracked
+/// entries of `statuses`. Costs a full diff of the index and the working
+/// tree, so `get_file_statuses` leaves it to callers that show the counts.
+/// Conflicted entries are left without counts.
+pub fn add_line_counts(repo: &Repository, statuses: &mut FileStatuses) -> Result<(), AppError> {
+    crate::log_git_op_debug!("add_line_counts");
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let staged = repo.diff_tree_to_index(head_tree.as_ref(), None, None)?;
+    let mut opts = DiffOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let unstaged = repo.diff_index_to_workdir(None, Some(&mut opts))?;
+
+    let counts = |diff: &git2::Diff| -> Result<HashMap<String, (usize, usize)>, AppError> {
+        Ok(diff_file_changes(diff)?
+            .into_iter()
+            .map(|c| (c.path, (c.additions, c.deletions)))
+            .collect())
+    };
+    let staged_counts = counts(&staged)?;
+    let unstaged_counts = counts(&unstaged)?;
+
+    let sides = [
+        (&mut statuses.staged, &staged_counts),
+        (&mut statuses.unstaged, &unstaged_counts),
+        (&mut statuses.untracked, &unstaged_counts),
+    ];
+    for (files, counts) in sides {
+        for file in files.iter_mut() {
+            if matches!(file.status, FileStatusType::Conflicted) {
+                continue;
+            }
+            let (additions, deletions) = counts.get(&file.path).copied().unwrap_or_default();
+            file.additions = Some(additions);
+            file.deletions = Some(deletions);
+        }
+    }
+    Ok(())
+}
 
-    if full_path.exists() {
-        index.add_path(Path::new(path))?;
+/// Whether `path` (relative to the workdir) matches an ignore rule. The path
+/// doesn't have to exist.
+pub fn is_path_ignored(repo: &Repository, path: &str) -> Result<bool, AppError> {
+    crate::log_git_op_debug!("is_path_ignored", path = path);
+    Ok(repo.is_path_ignored(Path::new(path))?)
+}
+
+/// Status of a single path, split the same way `get_file_statuses` splits the
+/// whole repo. A path can be both staged and unstaged at once.
+#[derive(Debug, Serialize, Clone)]
+pub struct PathStatus {
+    pub path: String,
+    pub staged: Option<FileStatusType>,
+    pub unstaged: Option<FileStatusType>,
+}
+
+/// Status of one path without scanning the whole repo. Returns `None` when
+/// the path is clean, ignored, or unknown to both the index and workdir.
+pub fn get_path_status(repo: &Repository, path: &str) -> Result<Option<PathStatus>, AppError> {
+    crate::log_git_op_debug!("get_path_status", path = path);
+    let status = match repo.status_file(Path::new(path)) {
+        Ok(status) => status,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+
+    let staged = status
+        .intersects(
+            Status::INDEX_NEW
+                | Status::INDEX_MODIFIED
+                | Status::INDEX_DELETED
+                | Status::INDEX_RENAMED
+                | Status::INDEX_TYPECHANGE,
+        )
+        .then(|| index_status_to_type(status));
+    let unstaged = if status.contains(Status::CONFLICTED) {
+        Some(FileStatusType::Conflicted)
+    } else if status.contains(Status::WT_NEW) {
+        Some(FileStatusType::Untracked)
+    } else if status.intersects(
+        Status::WT_MODIFIED | Status::WT_DELETED | Status::WT_TYPECHANGE | Status::WT_RENAMED,
+    ) {
+        Some(workdir_status_to_type(status))
     } else {
-        // File was deleted
-        index.remove_path(Path::new(path))?;
+        None
+    };
+
+    if staged.is_none() && unstaged.is_none() {
+        return Ok(None);
     }
+    Ok(Some(PathStatus {
+        path: path.to_string(),
+        staged,
+        unstaged,
+    }))
+}
 
+pub fn stage_file(repo: &Repository, path: &str) -> Result<(), AppError> {
+    crate::log_git_op!("stage_file", path = path);
+    let mut index = repo.index()?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
+    stage_path(&mut index, workdir, path)?;
     index.write()?;
     Ok(())
 }
 
 pub fn unstage_file(repo: &Repository, path: &str) -> Result<(), AppError> {
     crate::log_git_op!("unstage_file", path = path);
-    let head_tree = repo
-        .head()
-        .ok()
-        .and_then(|h| h.peel_to_commit().ok())
-        .and_then(|c| c.tree().ok());
-
+    let head_tree = head_tree(repo);
     let mut index = repo.index()?;
-
-    match head_tree {
-        Some(tree) => {
-            // Try to get the file from HEAD
-            if let Ok(entry) = tree.get_path(Path::new(path)) {
-                // File exists in HEAD, restore it to index
-                let entry_oid = entry.id();
-                index.add_frombuffer(
-                    &create_index_entry(path, entry.filemode() as u32, 0, entry_oid),
-                    repo.find_blob(entry_oid)?.content(),
-                )?;
-            } else {
-                // File doesn't exist in HEAD, remove from index
-                index.remove_path(Path::new(path))?;
-            }
-        }
-        None => {
-            // Empty repo (no commits): unstaging means removing from index
-            index.remove_path(Path::new(path))?;
-        }
-    }
-
+    unstage_path(repo, &mut index, head_tree.as_ref(), path)?;
     index.write()?;
     Ok(())
 }
@@ -203,18 +340,10 @@ pub fn unstage_file(repo: &Repository, path: &str) -> Result<(), AppError> {
 pub fn stage_files(repo: &Repository, paths: &[String]) -> Result<(), AppError> {
     crate::log_git_op!("stage_files", count = paths.len());
     let mut index = repo.index()?;
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| AppError::InvalidPath("No workdir".into()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
 
     for path in paths {
-        let full_path = workdir.join(path);
-        if full_path.exists() {
-            index.add_path(Path::new(path))?;
-        } else {
-            // File was deleted
-            index.remove_path(Path::new(path))?;
-        }
+        stage_path(&mut index, workdir, path)?;
     }
 
     index.write()?;
@@ -227,6 +356,115 @@ pub fn stage_files(repo: &Repository, paths: &[String]) -> Result<(), AppError>
 /// only once at the end.
 pub fn unstage_files(repo: &Repository, paths: &[String]) -> Result<(), AppError> {
     crate::log_git_op!("unstage_files", count = paths.len());
+    let head_tree = head_tree(repo);
+    let mut index = repo.index()?;
+
+    for path in paths {
+        unstage_path(repo, &mut index, head_tree.as_ref(), path)?;
+    }
+
+    index.write()?;
+    Ok(())
+}
+
+/// HEAD's tree, or `None` in an empty repository.
+fn head_tree(repo: &Repository) -> Option<git2::Tree<'_>> {
+    repo.head()
+        .ok()
+        .and_then(|h| h.peel_to_commit().ok())
+        .and_then(|c| c.tree().ok())
+}
+
+/// Update `path`'s index entry from the working tree, without writing the
+/// index.
+fn stage_path(index: &mut Index, workdir: &Path, path: &str) -> Result<(), AppError> {
+    // symlink_metadata so a dangling symlink isn't mistaken for a deletion.
+    if std::fs::symlink_metadata(workdir.join(path)).is_ok() {
+        index.add_path(Path::new(path))?;
+    } else {
+        // File was deleted
+        index.remove_path(Path::new(path))?;
+    }
+    Ok(())
+}
+
+/// Reset `path`'s index entry to HEAD, without writing the index.
+fn unstage_path(
+    repo: &Repository,
+    index: &mut Index,
+    head_tree: Option<&git2::Tree>,
+    path: &str,
+) -> Result<(), AppError> {
+    // Try to get the file from HEAD
+    match head_tree.and_then(|tree| tree.get_path(Path::new(path)).ok()) {
+        Some(entry) => {
+            // File exists in HEAD, restore it to index
+            let entry_oid = entry.id();
+            index.add_frombuffer(
+                &create_index_entry(path, entry.filemode() as u32, 0, entry_oid),
+                repo.find_blob(entry_oid)?.content(),
+            )?;
+        }
+        // New file, or an empty repo (no commits): unstaging means removing
+        // it from the index
+        None => index.remove_path(Path::new(path))?,
+    }
+    Ok(())
+}
+
+/// Stage every change in the working tree: modifications, new (non-ignored)
+/// files, and deletions.
+pub fn stage_all(repo: &Repository) -> Result<(), AppError> {
+    crate::log_git_op!("stage_all");
+    let mut index = repo.index()?;
+    // add_all picks up new and modified files; update_all drops entries whose
+    // files were deleted from the working tree.
+    index.add_all(["*"], IndexAddOption::DEFAULT, None)?;
+    index.update_all(["*"], None)?;
+    index.write()?;
+    Ok(())
+}
+
+/// Stage only new (untracked, non-ignored) files, leaving modifications and
+/// deletions of tracked files unstaged: "add new files only". Returns how
+/// many paths were staged.
+pub fn stage_untracked(repo: &Repository) -> Result<usize, AppError> {
+    crate::log_git_op!("stage_untracked");
+    let untracked = get_file_statuses(repo, false)?.untracked;
+    let mut index = repo.index()?;
+    for file in &untracked {
+        index.add_path(Path::new(&file.path))?;
+    }
+    index.write()?;
+    Ok(untracked.len())
+}
+
+/// Stage every change under `dir` (new, modified, and deleted files),
+/// honouring `.gitignore`. Returns how many paths were staged.
+pub fn stage_directory(repo: &Repository, dir: &str) -> Result<usize, AppError> {
+    crate::log_git_op!("stage_directory", dir = dir);
+    let dir = dir.trim_end_matches('/');
+    if dir.is_empty() || Path::new(dir).is_absolute() || dir.split('/').any(|c| c == "..") {
+        return Err(AppError::InvalidPath(format!("Invalid directory: {dir}")));
+    }
+
+    let mut index = repo.index()?;
+    let pathspec = [format!("{dir}/*")];
+    let mut staged = std::collections::HashSet::new();
+    let mut record = |path: &Path, _: &[u8]| {
+        staged.insert(path.to_path_buf());
+        0
+    };
+    index.add_all(pathspec.iter(), IndexAddOption::DEFAULT, Some(&mut record))?;
+    index.update_all(pathspec.iter(), Some(&mut record))?;
+    index.write()?;
+    Ok(staged.len())
+}
+
+/// Reset the whole index to HEAD, leaving the working tree untouched. In a
+/// repository without commits this empties the index.
+pub fn unstage_all(repo: &Repository) -> Result<(), AppError> {
+    crate::log_git_op!("unstage_all");
     let head_tree = repo
         .head()
         .ok()
@@ -234,44 +472,183 @@ pub fn unstage_files(repo: &Repository, paths: &[String]) -> Result<(), AppError
         .and_then(|c| c.tree().ok());
 
     let mut index = repo.index()?;
+    match head_tree {
+        Some(tree) => index.read_tree(&tree)?,
+        None => index.clear()?,
+    }
+    index.write()?;
+    Ok(())
+}
 
-    for path in paths {
-        match &head_tree {
-            Some(tree) => {
-                if let Ok(entry) = tree.get_path(Path::new(path)) {
-                    let entry_oid = entry.id();
-                    index.add_frombuffer(
-                        &create_index_entry(path, entry.filemode() as u32, 0, entry_oid),
-                        repo.find_blob(entry_oid)?.content(),
-                    )?;
-                } else {
-                    index.remove_path(Path::new(path))?;
-                }
-            }
-            None => {
+/// Throw away every uncommitted change, staged or not: the index is reset to
+/// HEAD and the working tree force-checked-out from it. Files that were only
+/// staged as new become untracked; with `include_untracked` those and all
+/// other untracked (non-ignored) files are deleted too.
+pub fn discard_all_changes(repo: &Repository, include_untracked: bool) -> Result<(), AppError> {
+    crate::log_git_op!("discard_all_changes", include_untracked = include_untracked);
+    let head_tree = match repo.head() {
+        Ok(head) => head.peel_to_tree()?,
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => return Err(nothing_to_discard_to()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut index = repo.index()?;
+    index.read_tree(&head_tree)?;
+    index.write()?;
+    repo.checkout_tree(
+        head_tree.as_object(),
+        Some(git2::build::CheckoutBuilder::new().force()),
+    )?;
+
+    if include_untracked {
+        let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
+        for file in get_file_statuses(repo, false)?.untracked {
+            std::fs::remove_file(workdir.join(&file.path))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Throw away all changes to one path, whatever its state: an untracked file
+/// is deleted, a file staged as new is unstaged and deleted, and a tracked
+/// file is restored from HEAD in both the index and the working tree; a
+/// clean one is left as it is. Ignored and unknown paths are an error.
+/// Returns the path's status afterwards (`None` once it is clean).
+pub fn discard_file(repo: &Repository, path: &str) -> Result<Option<PathStatus>, AppError> {
+    crate::log_git_op!("discard_file", path = path);
+    if Path::new(path).is_absolute() || path.split('/').any(|c| c == "..") {
+        return Err(AppError::InvalidPath(path.to_string()));
+    }
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
+    let status = match repo.status_file(Path::new(path)) {
+        Ok(status) => status,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {
+            return Err(AppError::InvalidArgument(format!(
+                "{path} has no changes to discard"
+            )));
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let in_head = head_tree
+        .as_ref()
+        .is_some_and(|tree| tree.get_path(Path::new(path)).is_ok());
+
+    match head_tree {
+        // Restores index and working tree, including a file that was
+        // `git rm --cached` (INDEX_DELETED | WT_NEW).
+        Some(head_tree) if in_head => {
+            repo.checkout_tree(
+                head_tree.as_object(),
+                Some(git2::build::CheckoutBuilder::new().force().path(path)),
+            )?;
+        }
+        // Not in HEAD, so discarding means the file goes away.
+        _ if status.intersects(Status::WT_NEW | Status::INDEX_NEW) => {
+            if status.contains(Status::INDEX_NEW) {
+                let mut index = repo.index()?;
                 index.remove_path(Path::new(path))?;
+                index.write()?;
+            }
+            let full_path = workdir.join(path);
+            if std::fs::symlink_metadata(&full_path).is_ok() {
+                std::fs::remove_file(full_path)?;
             }
         }
+        _ if status.contains(Status::IGNORED) => {
+            return Err(AppError::InvalidArgument(format!(
+                "{path} is ignored; there are no changes to discard"
+            )));
+        }
+        None => return Err(nothing_to_discard_to()),
+        Some(_) => {
+            return Err(AppError::InvalidArgument(format!(
+                "{path} has no changes to discard"
+            )))
+        }
     }
 
-    index.write()?;
-    Ok(())
+    get_path_status(repo, path)
+}
+
+/// The discard functions restore from HEAD, which an unborn branch lacks.
+fn nothing_to_discard_to() -> AppError {
+    AppError::Git(git2::Error::new(
+        git2::ErrorCode::UnbornBranch,
+        git2::ErrorClass::Reference,
+        "Cannot discard: no commits yet",
+    ))
+}
+
+/// The diff `hunk_index` arguments refer to. Hunk boundaries and indices
+/// depend on `context_lines`, so it must be the value the caller's diff was
+/// computed with, as must `config`: its limits decide whether there are
+/// hunks at all.
+fn hunk_diff(
+    repo: &Repository,
+    path: &str,
+    staged: bool,
+    context_lines: Option<u32>,
+    config: &DiffConfig,
+) -> Result<super::diff::FileDiff, AppError> {
+    super::diff::get_file_diff_with_config(repo, path, staged, config, false, context_lines)
+}
+
+/// Refuse a `hunk_index` past the end of `diff`, or one whose lines the
+/// diff budget left unloaded: applying it would drop the lines it changes.
+fn check_hunk(diff: &super::diff::FileDiff, hunk_index: usize) -> Result<(), AppError> {
+    match diff.hunks.get(hunk_index) {
+        None => Err(AppError::HunkOutOfRange(hunk_index)),
+        Some(hunk) if !hunk.is_loaded => Err(AppError::InvalidArgument(format!(
+            "Hunk {hunk_index} is not loaded"
+        ))),
+        Some(_) => Ok(()),
+    }
 }
 
-pub fn stage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<(), AppError> {
-    crate::log_git_op!("stage_hunk", path = path, hunk = hunk_index);
+/// Index into a file's lines of the first line a hunk side covers. A side
+/// with no lines, as in a pure insertion or deletion diffed without
+/// context, names the line it comes after instead.
+fn hunk_start_index(start: u32, count: u32) -> usize {
+    if count == 0 {
+        start as usize
+    } else {
+        (start as usize).saturating_sub(1)
+    }
+}
+
+/// With `expected_header`, refuse with `AppError::StaleHunk` unless the hunk
+/// at `hunk_index` still has that header: the working tree may have changed
+/// since the caller's diff, shifting which hunk the index names.
+/// `context_lines` is the value the caller's diff used, as for the other hunk
+/// and line functions.
+pub fn stage_hunk(
+    repo: &Repository,
+    path: &str,
+    hunk_index: usize,
+    expected_header: Option<&str>,
+    context_lines: Option<u32>,
+    config: &DiffConfig,
+) -> Result<(), AppError> {
+    crate::log_git_op!(
+        "stage_hunk",
+        path = path,
+        hunk = hunk_index,
+        context_lines = context_lines
+    );
     // Get the current diff hunks
-    let diff = super::diff::get_file_diff(repo, path, false)?;
+    let diff = hunk_diff(repo, path, false, context_lines, config)?;
 
-    if hunk_index >= diff.hunks.len() {
-        return Err(AppError::InvalidPath(format!(
-            "Hunk index {} out of range",
-            hunk_index
-        )));
+    check_hunk(&diff, hunk_index)?;
+    if expected_header.is_some_and(|header| diff.hunks[hunk_index].header != header) {
+        return Err(AppError::StaleHunk(hunk_index));
     }
 
     // Read current index content
     let mut index = repo.index()?;
+    let mode = partial_stage_mode(repo, &index, path)?;
 
     let index_content = get_file_content_from_index_or_head(repo, &index, path)?;
 
@@ -283,7 +660,7 @@ pub fn stage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<()
     let oid = repo.blob(new_content.as_bytes())?;
 
     index.add_frombuffer(
-        &create_index_entry(path, 0o100644, new_content.len() as u32, oid),
+        &create_index_entry(path, mode, new_content.len() as u32, oid),
         new_content.as_bytes(),
     )?;
 
@@ -291,17 +668,23 @@ pub fn stage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<()
     Ok(())
 }
 
-pub fn unstage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<(), AppError> {
-    crate::log_git_op!("unstage_hunk", path = path, hunk = hunk_index);
+pub fn unstage_hunk(
+    repo: &Repository,
+    path: &str,
+    hunk_index: usize,
+    context_lines: Option<u32>,
+    config: &DiffConfig,
+) -> Result<(), AppError> {
+    crate::log_git_op!(
+        "unstage_hunk",
+        path = path,
+        hunk = hunk_index,
+        context_lines = context_lines
+    );
     // Get the staged diff hunks
-    let diff = super::diff::get_file_diff(repo, path, true)?;
+    let diff = hunk_diff(repo, path, true, context_lines, config)?;
 
-    if hunk_index >= diff.hunks.len() {
-        return Err(AppError::InvalidPath(format!(
-            "Hunk index {} out of range",
-            hunk_index
-        )));
-    }
+    check_hunk(&diff, hunk_index)?;
 
     let mut index = repo.index()?;
 
@@ -309,6 +692,7 @@ pub fn unstage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<
     let index_entry = index
         .get_path(Path::new(path), 0)
         .ok_or_else(|| AppError::InvalidPath("File not in index".into()))?;
+    let mode = partial_stage_mode(repo, &index, path)?;
     let blob = repo.find_blob(index_entry.id)?;
     let index_content = String::from_utf8_lossy(blob.content()).to_string();
 
@@ -316,11 +700,24 @@ pub fn unstage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<
     let hunk = &diff.hunks[hunk_index];
     let new_content = reverse_apply_hunk(&index_content, hunk, None)?;
 
+    // A file new in the index is diffed against empty content. Unstaging
+    // everything it adds makes it untracked again rather than an empty file.
+    let in_head = repo
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_tree().ok())
+        .is_some_and(|tree| tree.get_path(Path::new(path)).is_ok());
+    if !in_head && new_content.is_empty() {
+        index.remove_path(Path::new(path))?;
+        index.write()?;
+        return Ok(());
+    }
+
     // Write back to index
     let oid = repo.blob(new_content.as_bytes())?;
 
     index.add_frombuffer(
-        &create_index_entry(path, index_entry.mode, new_content.len() as u32, oid),
+        &create_index_entry(path, mode, new_content.len() as u32, oid),
         new_content.as_bytes(),
     )?;
 
@@ -328,11 +725,85 @@ pub fn unstage_hunk(repo: &Repository, path: &str, hunk_index: usize) -> Result<
     Ok(())
 }
 
+/// CRLF if most of `content`'s lines end that way, otherwise LF. The patch
+/// helpers split with `lines()`, which drops `\r`, so they rejoin with this
+/// to avoid rewriting a Windows file as LF.
+fn dominant_line_ending(content: &str) -> &'static str {
+    let crlf = content.matches("\r\n").count();
+    let lf = content.matches('\n').count() - crlf;
+    if crlf > lf {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+/// The line ending to write patched content with: the original's, or, when
+/// the original has no line terminator (an empty or new file), the one the
+/// hunks' added lines use.
+fn patch_line_ending<'h>(
+    original: &str,
+    hunks: impl IntoIterator<Item = &'h super::diff::DiffHunk>,
+) -> &'static str {
+    if original.contains('\n') {
+        return dominant_line_ending(original);
+    }
+    let added: String = hunks
+        .into_iter()
+        .flat_map(|hunk| &hunk.lines)
+        .filter(|line| matches!(line.line_type, super::diff::LineType::Addition))
+        .map(|line| line.content.as_str())
+        .collect();
+    dominant_line_ending(&added)
+}
+
+/// Rejoin split lines with `eol`.
+fn join_lines<S: AsRef<str>>(lines: &[S], eol: &str, trailing_newline: bool) -> String {
+    // No lines is an empty file, not a lone line ending.
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut output = String::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            output.push_str(eol);
+        }
+        output.push_str(line.as_ref());
+    }
+    if trailing_newline {
+        output.push_str(eol);
+    }
+    output
+}
+
+/// Whether patched content should end with a newline. If the hunk carries a
+/// "no newline at end of file" marker (or the original is empty) and ran to
+/// the end of the file, the last line it emitted decides; otherwise the
+/// original's trailing newline carries over.
+fn trailing_newline(
+    original: &str,
+    hunk: &super::diff::DiffHunk,
+    reached_eof: bool,
+    last_emitted: Option<&super::diff::DiffLine>,
+) -> bool {
+    let marks_eof = hunk.lines.iter().any(|line| line.no_newline);
+    match last_emitted {
+        Some(line) if reached_eof && (marks_eof || original.is_empty()) => !line.no_newline,
+        _ => original.ends_with('\n'),
+    }
+}
+
+/// A diff line's text without its `\n` or `\r\n` terminator.
+fn hunk_line_text(content: &str) -> &str {
+    content.trim_end_matches('\n').trim_end_matches('\r')
+}
+
 fn apply_hunk_to_content(content: &str, hunk: &super::diff::DiffHunk) -> Result<String, AppError> {
     let lines: Vec<&str> = content.lines().collect();
     let mut result = Vec::new();
+    let mut last_emitted = None;
 
-    let start = (hunk.old_start as usize).saturating_sub(1);
+    let start = hunk_start_index(hunk.old_start, hunk.old_lines);
 
     // Add lines before the hunk
     result.extend(lines.iter().take(start).map(|s| s.to_string()));
@@ -348,12 +819,14 @@ fn apply_hunk_to_content(content: &str, hunk: &super::diff::DiffHunk) -> Result<
                 // Context line: use original content to avoid whitespace/encoding issues
                 if content_pos < lines.len() {
                     result.push(lines[content_pos].to_string());
+                    last_emitted = Some(line);
                     content_pos += 1;
                 }
             }
             super::diff::LineType::Addition => {
                 // Addition: add the new line (doesn't consume original content)
-                result.push(line.content.trim_end_matches('\n').to_string());
+                result.push(hunk_line_text(&line.content).to_string());
+                last_emitted = Some(line);
             }
             super::diff::LineType::Deletion => {
                 // Deletion: skip this line from original content
@@ -371,7 +844,12 @@ fn apply_hunk_to_content(content: &str, hunk: &super::diff::DiffHunk) -> Result<
         result.extend(lines.iter().skip(content_pos).map(|s| s.to_string()));
     }
 
-    Ok(result.join("\n") + if content.ends_with('\n') { "\n" } else { "" })
+    let trailing = trailing_newline(content, hunk, content_pos >= lines.len(), last_emitted);
+    Ok(join_lines(
+        &result,
+        patch_line_ending(content, [hunk]),
+        trailing,
+    ))
 }
 
 fn reverse_apply_hunk(
@@ -381,8 +859,9 @@ fn reverse_apply_hunk(
 ) -> Result<String, AppError> {
     let lines: Vec<&str> = content.lines().collect();
     let mut result = Vec::new();
+    let mut last_emitted = None;
 
-    let start = (hunk.new_start as usize).saturating_sub(1);
+    let start = hunk_start_index(hunk.new_start, hunk.new_lines);
 
     // Add lines before the hunk
     result.extend(lines.iter().take(start).map(|s| s.to_string()));
@@ -403,6 +882,7 @@ fn reverse_apply_hunk(
                 // Context: exists in both, use content and advance
                 if content_pos < lines.len() {
                     result.push(lines[content_pos].to_string());
+                    last_emitted = Some(line);
                     content_pos += 1;
                 }
             }
@@ -410,7 +890,8 @@ fn reverse_apply_hunk(
                 // Deletion was removed in forward apply
                 if is_selected {
                     // Restore it from hunk data
-                    result.push(line.content.trim_end_matches('\n').to_string());
+                    result.push(hunk_line_text(&line.content).to_string());
+                    last_emitted = Some(line);
                 }
                 // This line doesn't exist in content, so don't advance content_pos
             }
@@ -423,6 +904,7 @@ fn reverse_apply_hunk(
                     // Keep it in output
                     if content_pos < lines.len() {
                         result.push(lines[content_pos].to_string());
+                        last_emitted = Some(line);
                         content_pos += 1;
                     }
                 }
@@ -439,7 +921,12 @@ fn reverse_apply_hunk(
         result.extend(lines.iter().skip(content_pos).map(|s| s.to_string()));
     }
 
-    Ok(result.join("\n") + if content.ends_with('\n') { "\n" } else { "" })
+    let trailing = trailing_newline(content, hunk, content_pos >= lines.len(), last_emitted);
+    Ok(join_lines(
+        &result,
+        patch_line_ending(content, [hunk]),
+        trailing,
+    ))
 }
 
 pub fn discard_hunk(
@@ -447,36 +934,35 @@ pub fn discard_hunk(
     path: &str,
     hunk_index: usize,
     line_indices: Option<Vec<usize>>,
+    context_lines: Option<u32>,
+    config: &DiffConfig,
 ) -> Result<(), AppError> {
     crate::log_git_op!(
         "discard_hunk",
         path = path,
         hunk = hunk_index,
-        lines = line_indices.as_ref().map(|v| v.len())
+        lines = line_indices.as_ref().map(|v| v.len()),
+        context_lines = context_lines
     );
     // Get the unstaged diff
-    let diff = super::diff::get_file_diff(repo, path, false)?;
+    let diff = hunk_diff(repo, path, false, context_lines, config)?;
 
-    if hunk_index >= diff.hunks.len() {
-        return Err(AppError::InvalidPath(format!(
-            "Hunk index {} out of range",
-            hunk_index
-        )));
-    }
+    check_hunk(&diff, hunk_index)?;
 
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| AppError::InvalidPath("No working directory".into()))?;
+    // Reading and writing through a symlink would edit its target instead.
+    partial_stage_mode(repo, &repo.index()?, path)?;
+
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     let file_path = workdir.join(path);
 
     let content = std::fs::read_to_string(&file_path)
-        .map_err(|e| AppError::InvalidPath(format!("Failed to read file: {}", e)))?;
+        .map_err(|e| AppError::FileIo(format!("failed to read {path}: {e}")))?;
 
     let hunk = &diff.hunks[hunk_index];
     let new_content = reverse_apply_hunk(&content, hunk, line_indices.as_deref())?;
 
     std::fs::write(&file_path, &new_content)
-        .map_err(|e| AppError::InvalidPath(format!("Failed to write file: {}", e)))?;
+        .map_err(|e| AppError::FileIo(format!("failed to write {path}: {e}")))?;
 
     Ok(())
 }
@@ -486,25 +972,24 @@ pub fn stage_lines(
     path: &str,
     hunk_index: usize,
     line_indices: Vec<usize>,
+    context_lines: Option<u32>,
+    config: &DiffConfig,
 ) -> Result<(), AppError> {
     crate::log_git_op!(
         "stage_lines",
         path = path,
         hunk = hunk_index,
-        lines = line_indices.len()
+        lines = line_indices.len(),
+        context_lines = context_lines
     );
     // Get the current diff hunks
-    let diff = super::diff::get_file_diff(repo, path, false)?;
+    let diff = hunk_diff(repo, path, false, context_lines, config)?;
 
-    if hunk_index >= diff.hunks.len() {
-        return Err(AppError::InvalidPath(format!(
-            "Hunk index {} out of range",
-            hunk_index
-        )));
-    }
+    check_hunk(&diff, hunk_index)?;
 
     // Read current index content
     let mut index = repo.index()?;
+    let mode = partial_stage_mode(repo, &index, path)?;
 
     let index_content = get_file_content_from_index_or_head(repo, &index, path)?;
 
@@ -516,7 +1001,54 @@ pub fn stage_lines(
     let oid = repo.blob(new_content.as_bytes())?;
 
     index.add_frombuffer(
-        &create_index_entry(path, 0o100644, new_content.len() as u32, oid),
+        &create_index_entry(path, mode, new_content.len() as u32, oid),
+        new_content.as_bytes(),
+    )?;
+
+    index.write()?;
+    Ok(())
+}
+
+/// Stage selected lines from several hunks of `path`'s unstaged diff at
+/// once. `selections` pairs a hunk index with line indices within that hunk,
+/// as for `stage_lines`. All hunks are applied against the same index
+/// content, so unlike repeated `stage_lines` calls an earlier hunk can't
+/// shift the lines a later one refers to.
+pub fn stage_selection(
+    repo: &Repository,
+    path: &str,
+    selections: &[(usize, Vec<usize>)],
+    context_lines: Option<u32>,
+    config: &DiffConfig,
+) -> Result<(), AppError> {
+    crate::log_git_op!(
+        "stage_selection",
+        path = path,
+        hunks = selections.len(),
+        lines = selections.iter().map(|(_, l)| l.len()).sum::<usize>(),
+        context_lines = context_lines
+    );
+    let diff = hunk_diff(repo, path, false, context_lines, config)?;
+
+    // Hunk order is file order; a hunk listed twice gets both line sets.
+    let mut by_hunk: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (hunk_index, line_indices) in selections {
+        by_hunk.entry(*hunk_index).or_default().extend(line_indices);
+    }
+    let mut selected = Vec::with_capacity(by_hunk.len());
+    for (hunk_index, line_indices) in &by_hunk {
+        check_hunk(&diff, *hunk_index)?;
+        selected.push((&diff.hunks[*hunk_index], line_indices.as_slice()));
+    }
+
+    let mut index = repo.index()?;
+    let mode = partial_stage_mode(repo, &index, path)?;
+    let index_content = get_file_content_from_index_or_head(repo, &index, path)?;
+    let new_content = apply_selections_to_content(&index_content, &selected)?;
+
+    let oid = repo.blob(new_content.as_bytes())?;
+    index.add_frombuffer(
+        &create_index_entry(path, mode, new_content.len() as u32, oid),
         new_content.as_bytes(),
     )?;
 
@@ -528,63 +1060,121 @@ fn apply_selected_lines_to_content(
     content: &str,
     hunk: &super::diff::DiffHunk,
     selected_indices: &[usize],
+) -> Result<String, AppError> {
+    apply_selections_to_content(content, &[(hunk, selected_indices)])
+}
+
+/// `apply_selected_lines_to_content` for several hunks of the same diff in
+/// one pass, so every hunk's `old_start` still refers to `content`. Hunks
+/// must be in file order.
+fn apply_selections_to_content(
+    content: &str,
+    selections: &[(&super::diff::DiffHunk, &[usize])],
 ) -> Result<String, AppError> {
     let lines: Vec<&str> = content.lines().collect();
     let mut result = Vec::new();
-
-    let start = (hunk.old_start as usize).saturating_sub(1);
-
-    // Add lines before the hunk
-    result.extend(lines.iter().take(start).map(|s| s.to_string()));
+    let mut last_emitted = None;
 
     // Track current position in original content (index into `lines`)
-    let mut content_pos = start;
-
-    // Apply only selected lines from the hunk
-    // Key insight: context and deletion lines correspond to original content lines
-    // We must track position in the original content, not just iterate the hunk
-    for (idx, line) in hunk.lines.iter().enumerate() {
-        match line.line_type {
-            super::diff::LineType::Context => {
-                // Context line: use the line from original content at current position
-                if content_pos < lines.len() {
-                    result.push(lines[content_pos].to_string());
-                    content_pos += 1;
-                }
-            }
-            super::diff::LineType::Addition => {
-                // Only add if this line is selected
-                if selected_indices.contains(&idx) {
-                    result.push(line.content.trim_end_matches('\n').to_string());
-                }
-                // Additions don't consume original content lines
-            }
-            super::diff::LineType::Deletion => {
-                // Deletion corresponds to a line in original content
-                if selected_indices.contains(&idx) {
-                    // Selected: stage the deletion (skip this line)
-                    content_pos += 1;
-                } else {
-                    // Not selected: keep the original line
+    let mut content_pos = 0;
+
+    for (hunk, selected_indices) in selections {
+        let start = hunk_start_index(hunk.old_start, hunk.old_lines);
+
+        // Add lines between the previous hunk and this one
+        result.extend(
+            lines
+                .iter()
+                .take(start)
+                .skip(content_pos)
+                .map(|s| s.to_string()),
+        );
+        content_pos = content_pos.max(start);
+        last_emitted = None;
+
+        // Apply only selected lines from the hunk
+        // Key insight: context and deletion lines correspond to original content lines
+        // We must track position in the original content, not just iterate the hunk
+        for (idx, line) in hunk.lines.iter().enumerate() {
+            match line.line_type {
+                super::diff::LineType::Context => {
+                    // Context line: use the line from original content at current position
                     if content_pos < lines.len() {
                         result.push(lines[content_pos].to_string());
+                        last_emitted = Some(line);
                         content_pos += 1;
                     }
                 }
+                super::diff::LineType::Addition => {
+                    // Only add if this line is selected
+                    if selected_indices.contains(&idx) {
+                        result.push(hunk_line_text(&line.content).to_string());
+                        last_emitted = Some(line);
+                    }
+                    // Additions don't consume original content lines
+                }
+                super::diff::LineType::Deletion => {
+                    // Deletion corresponds to a line in original content
+                    if selected_indices.contains(&idx) {
+                        // Selected: stage the deletion (skip this line)
+                        content_pos += 1;
+                    } else {
+                        // Not selected: keep the original line
+                        if content_pos < lines.len() {
+                            result.push(lines[content_pos].to_string());
+                            last_emitted = Some(line);
+                            content_pos += 1;
+                        }
+                    }
+                }
+                super::diff::LineType::Header
+                | super::diff::LineType::ConflictMarker
+                | super::diff::LineType::ConflictOurs
+                | super::diff::LineType::ConflictTheirs => {}
             }
-            super::diff::LineType::Header
-            | super::diff::LineType::ConflictMarker
-            | super::diff::LineType::ConflictOurs
-            | super::diff::LineType::ConflictTheirs => {}
         }
     }
 
-    // Add remaining lines after the hunk (from where we left off)
+    // Add remaining lines after the last hunk (from where we left off)
     if content_pos < lines.len() {
         result.extend(lines.iter().skip(content_pos).map(|s| s.to_string()));
     }
 
-    Ok(result.join("\n") + if content.ends_with('\n') { "\n" } else { "" })
+    // Only the last hunk can reach the end of the file.
+    let trailing = match selections.last() {
+        Some((hunk, _)) => {
+            trailing_newline(content, hunk, content_pos >= lines.len(), last_emitted)
+        }
+        None => content.ends_with('\n'),
+    };
+    let eol = patch_line_ending(content, selections.iter().map(|(hunk, _)| *hunk));
+    Ok(join_lines(&result, eol, trailing))
+}
+
+/// Apply a unified diff, such as one from `get_file_patch`, to the working
+/// tree or else to the index. libgit2 checks every hunk before writing
+/// anything, so a patch that doesn't apply cleanly changes nothing.
+pub fn apply_patch(
+    repo: &Repository,
+    patch_text: &str,
+    to_working_tree: bool,
+) -> Result<(), AppError> {
+    crate::log_git_op!("apply_patch", to_working_tree = to_working_tree);
+    let diff = git2::Diff::from_buffer(patch_text.as_bytes())
+        .map_err(|e| AppError::PatchFailed(format!("not a valid patch: {}", e.message())))?;
+    if diff.deltas().len() == 0 {
+        return Err(AppError::PatchFailed(
+            "patch contains no changes".to_string(),
+        ));
+    }
+
+    let location = if to_working_tree {
+        ApplyLocation::WorkDir
+    } else {
+        ApplyLocation::Index
+    };
+    repo.apply(&diff, location, None)
+        .map_err(|e| AppError::PatchFailed(e.message().to_string()))
 }
 
 pub fn revert_commit(repo: &Repository, hash: &str) -> Result<(), AppError> {
@@ -601,6 +1191,37 @@ pub fn revert_commit(repo: &Repository, hash: &str) -> Result<(), AppError> {
     Ok(())
 }
 
+/// `revert_commit`, then commit the result like `git revert` does, with the
+/// message `Revert "<subject>"` and a `This reverts commit <hash>.` footer.
+/// Returns the new commit, or `None` if the revert conflicted; the changes
+/// are then left in place for manual resolution, as with `revert_commit`.
+pub fn revert_commit_and_commit(repo: &Repository, hash: &str) -> Result<Option<String>, AppError> {
+    crate::log_git_op!("revert_commit_and_commit", hash = hash);
+    revert_commit(repo, hash)?;
+    if repo.index()?.has_conflicts() {
+        return Ok(None);
+    }
+
+    let commit = repo.find_commit(Oid::from_str(hash)?)?;
+    let subject = commit.summary().ok().flatten().unwrap_or_default();
+    let mut message = format!(
+        "Revert \"{subject}\"\n\nThis reverts commit {}",
+        commit.id()
+    );
+    if commit.parent_count() > 1 {
+        message.push_str(&format!(
+            ", reversing\nchanges made to {}",
+            commit.parent_id(0)?
+        ));
+    }
+    message.push_str(".\n");
+
+    let signature = repo.signature()?;
+    let oid = super::operations::commit_index_to_head(repo, &signature, &message)?;
+    repo.cleanup_state()?;
+    Ok(Some(oid.to_string()))
+}
+
 pub fn revert_commit_file(repo: &Repository, hash: &str, path: &str) -> Result<(), AppError> {
     crate::log_git_op!("revert_commit_file", hash = hash, path = path);
     let oid = Oid::from_str(hash)?;
@@ -610,9 +1231,7 @@ pub fn revert_commit_file(repo: &Repository, hash: &str, path: &str) -> Result<(
     let mainline = if commit.parent_count() > 1 { 1 } else { 0 };
     let revert_index = repo.revert_commit(&commit, &our_commit, mainline, None)?;
 
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| AppError::InvalidPath("No working directory".into()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
 
     let mut index = repo.index()?;
     let file_path = workdir.join(path);
@@ -623,7 +1242,7 @@ pub fn revert_commit_file(repo: &Repository, hash: &str, path: &str) -> Result<(
         let blob = repo.find_blob(entry.id)?;
         let content = blob.content();
         std::fs::write(&file_path, content)
-            .map_err(|e| AppError::InvalidPath(format!("Failed to write file: {}", e)))?;
+            .map_err(|e| AppError::FileIo(format!("failed to write {path}: {e}")))?;
         index.add_path(Path::new(path))?;
     } else {
         // No stage-0 entry — could be a conflict (stages 1-3) or a genuinely added file.
@@ -642,7 +1261,7 @@ pub fn revert_commit_file(repo: &Repository, hash: &str, path: &str) -> Result<(
         // File was genuinely added by this commit — safe to delete
         if file_path.exists() {
             std::fs::remove_file(&file_path)
-                .map_err(|e| AppError::InvalidPath(format!("Failed to delete file: {}", e)))?;
+                .map_err(|e| AppError::FileIo(format!("failed to delete {path}: {e}")))?;
         }
         index.remove_path(Path::new(path))?;
     }
@@ -669,19 +1288,14 @@ pub fn revert_commit_file_lines(
     let diff = super::diff::get_commit_file_diff(repo, hash, path)?;
 
     if hunk_index >= diff.hunks.len() {
-        return Err(AppError::InvalidPath(format!(
-            "Hunk index {} out of range",
-            hunk_index
-        )));
+        return Err(AppError::HunkOutOfRange(hunk_index));
     }
 
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| AppError::InvalidPath("No working directory".into()))?;
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     let file_path = workdir.join(path);
 
     let content = std::fs::read_to_string(&file_path)
-        .map_err(|e| AppError::InvalidPath(format!("Failed to read file: {}", e)))?;
+        .map_err(|e| AppError::FileIo(format!("failed to read {path}: {e}")))?;
 
     // The commit diff shows old (parent) -> new (commit).
     // To revert selected lines, we reverse-apply them.
@@ -695,7 +1309,7 @@ pub fn revert_commit_file_lines(
         match line.line_type {
             super::diff::LineType::Context | super::diff::LineType::Addition => {
                 if pos < content_lines.len() {
-                    let expected = line.content.trim_end_matches('\n');
+                    let expected = hunk_line_text(&line.content);
                     let actual = content_lines[pos];
                     if expected != actual {
                         return Err(AppError::RevertConflict(
@@ -718,7 +1332,7 @@ pub fn revert_commit_file_lines(
     let new_content = reverse_apply_hunk(&content, hunk, Some(&line_indices))?;
 
     std::fs::write(&file_path, &new_content)
-        .map_err(|e| AppError::InvalidPath(format!("Failed to write file: {}", e)))?;
+        .map_err(|e| AppError::FileIo(format!("failed to write {path}: {e}")))?;
 
     // Stage the changes
     let mut index = repo.index()?;
@@ -730,12 +1344,14 @@ pub fn revert_commit_file_lines(
 
 /// Resolve a conflicted file by applying a strategy: "ours", "theirs", or "both".
 /// Reads the workdir file, strips conflict markers keeping the chosen content,
-/// writes the resolved file back, and stages it.
+/// writes the resolved file back, and stages it. Errors if `path` isn't
+/// currently conflicted.
 pub fn resolve_conflict(repo: &Repository, path: &str, strategy: &str) -> Result<(), AppError> {
     crate::log_git_op!("resolve_conflict", path = path, strategy = strategy);
-    let workdir = repo
-        .workdir()
-        .ok_or(AppError::InvalidPath("No working directory".into()))?;
+    let mut index = repo.index()?;
+    super::conflict::ensure_conflicted(&index, path)?;
+
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?;
     let file_path = workdir.join(path);
     let content = std::fs::read_to_string(&file_path)?;
 
@@ -743,7 +1359,6 @@ pub fn resolve_conflict(repo: &Repository, path: &str, strategy: &str) -> Result
 
     std::fs::write(&file_path, &resolved)?;
 
-    let mut index = repo.index()?;
     index.add_path(Path::new(path))?;
     index.write()?;
 
@@ -792,7 +1407,7 @@ fn resolve_conflict_content(content: &str, strategy: &str) -> Result<String, App
                             result.extend_from_slice(&theirs_lines);
                         }
                         _ => {
-                            return Err(AppError::InvalidPath(format!(
+                            return Err(AppError::InvalidArgument(format!(
                                 "Unknown conflict resolution strategy: {}",
                                 strategy
                             )));
@@ -806,12 +1421,11 @@ fn resolve_conflict_content(content: &str, strategy: &str) -> Result<String, App
         }
     }
 
-    let mut output = result.join("\n");
-    // Preserve trailing newline if original had one
-    if content.ends_with('\n') {
-        output.push('\n');
-    }
-    Ok(output)
+    Ok(join_lines(
+        &result,
+        dominant_line_ending(content),
+        content.ends_with('\n'),
+    ))
 }
 
 fn index_status_to_type(status: Status) -> FileStatusType {
@@ -862,24 +1476,28 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2\n".to_string(),
                     line_type: LineType::Deletion,
                     old_lineno: Some(2),
                     new_lineno: None,
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "modified2\n".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3\n".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
             ],
         };
@@ -907,24 +1525,28 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2\n".to_string(),
                     line_type: LineType::Deletion,
                     old_lineno: Some(2),
                     new_lineno: None,
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "modified2\n".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3\n".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
             ],
         };
@@ -934,6 +1556,101 @@ mod tests {
         assert!(!result.contains("modified2"));
     }
 
+    fn crlf_hunk() -> super::super::diff::DiffHunk {
+        use super::super::diff::{DiffHunk, DiffLine, LineType};
+
+        let line = |content: &str, line_type: LineType| DiffLine {
+            content: content.to_string(),
+            line_type,
+            old_lineno: None,
+            new_lineno: None,
+            no_newline: false,
+        };
+        DiffHunk {
+            header: "@@ -1,3 +1,3 @@".to_string(),
+            old_start: 1,
+            old_lines: 3,
+            new_start: 1,
+            new_lines: 3,
+            is_loaded: true,
+            lines: vec![
+                line("line1\r\n", LineType::Context),
+                line("line2\r\n", LineType::Deletion),
+                line("modified2\r\n", LineType::Addition),
+                line("line3\r\n", LineType::Context),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_dominant_line_ending() {
+        assert_eq!(dominant_line_ending("a\nb\n"), "\n");
+        assert_eq!(dominant_line_ending("a\r\nb\r\n"), "\r\n");
+        assert_eq!(dominant_line_ending("a\r\nb\r\nc\n"), "\r\n");
+        assert_eq!(dominant_line_ending("a\r\nb\nc\n"), "\n");
+        assert_eq!(dominant_line_ending("no newline"), "\n");
+    }
+
+    #[test]
+    fn test_patch_line_ending_falls_back_to_added_lines() {
+        assert_eq!(patch_line_ending("a\nb\n", [&crlf_hunk()]), "\n");
+        assert_eq!(patch_line_ending("", [&crlf_hunk()]), "\r\n");
+        assert_eq!(patch_line_ending("no newline", [&crlf_hunk()]), "\r\n");
+    }
+
+    #[test]
+    fn test_apply_hunk_to_content_preserves_crlf() {
+        let result = apply_hunk_to_content("line1\r\nline2\r\nline3\r\n", &crlf_hunk()).unwrap();
+        assert_eq!(result, "line1\r\nmodified2\r\nline3\r\n");
+    }
+
+    #[test]
+    fn test_reverse_apply_hunk_preserves_crlf() {
+        let result =
+            reverse_apply_hunk("line1\r\nmodified2\r\nline3\r\n", &crlf_hunk(), None).unwrap();
+        assert_eq!(result, "line1\r\nline2\r\nline3\r\n");
+    }
+
+    #[test]
+    fn test_apply_selected_lines_preserves_crlf() {
+        // Stage only the addition (index 2), keeping line2.
+        let result =
+            apply_selected_lines_to_content("line1\r\nline2\r\nline3\r\n", &crlf_hunk(), &[2])
+                .unwrap();
+        assert_eq!(result, "line1\r\nline2\r\nmodified2\r\nline3\r\n");
+    }
+
+    #[test]
+    fn test_apply_hunk_to_content_honors_no_newline() {
+        use super::super::diff::{DiffHunk, DiffLine, LineType};
+
+        let line = |content: &str, line_type: LineType, no_newline: bool| DiffLine {
+            content: content.to_string(),
+            line_type,
+            old_lineno: None,
+            new_lineno: None,
+            no_newline,
+        };
+        // "a\nb\n" -> "a\nb": git diffs this as -b / +b plus the marker.
+        let hunk = DiffHunk {
+            header: "@@ -1,2 +1,2 @@".to_string(),
+            old_start: 1,
+            old_lines: 2,
+            new_start: 1,
+            new_lines: 2,
+            is_loaded: true,
+            lines: vec![
+                line("a\n", LineType::Context, false),
+                line("b\n", LineType::Deletion, false),
+                line("b", LineType::Addition, true),
+                line("\n\\ No newline at end of file\n", LineType::Header, false),
+            ],
+        };
+
+        assert_eq!(apply_hunk_to_content("a\nb\n", &hunk).unwrap(), "a\nb");
+        assert_eq!(reverse_apply_hunk("a\nb", &hunk, None).unwrap(), "a\nb\n");
+    }
+
     #[test]
     fn test_index_status_to_type() {
         use git2::Status;
@@ -995,24 +1712,28 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "newline".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(2),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(4),
+                    no_newline: false,
                 },
             ],
         };
@@ -1042,30 +1763,35 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "new1".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "new2".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(2),
                     new_lineno: Some(4),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(5),
+                    no_newline: false,
                 },
             ],
         };
@@ -1097,18 +1823,21 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2".to_string(),
                     line_type: LineType::Deletion,
                     old_lineno: Some(2),
                     new_lineno: None,
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
             ],
         };
@@ -1139,18 +1868,21 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2".to_string(),
                     line_type: LineType::Deletion,
                     old_lineno: Some(2),
                     new_lineno: None,
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
             ],
         };
@@ -1182,24 +1914,28 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "old2".to_string(),
                     line_type: LineType::Deletion,
                     old_lineno: Some(2),
                     new_lineno: None,
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "new2".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
             ],
         };
@@ -1230,24 +1966,28 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "inserted".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(4),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(4),
                     new_lineno: Some(5),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(5),
                     new_lineno: Some(6),
+                    no_newline: false,
                 },
             ],
         };
@@ -1279,18 +2019,21 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "inserted".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(2),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
             ],
         };
@@ -1320,18 +2063,21 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "newline\n".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2\n".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(2),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
             ],
         };
@@ -1360,18 +2106,21 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2\n".to_string(),
                     line_type: LineType::Deletion,
                     old_lineno: Some(2),
                     new_lineno: None,
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3\n".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
             ],
         };
@@ -1400,24 +2149,28 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "new1\n".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "new2\n".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line2\n".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(2),
                     new_lineno: Some(4),
+                    no_newline: false,
                 },
             ],
         };
@@ -1446,24 +2199,28 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "old2\n".to_string(),
                     line_type: LineType::Deletion,
                     old_lineno: Some(2),
                     new_lineno: None,
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "new2\n".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "line3\n".to_string(),
                     line_type: LineType::Context,
                     old_lineno: Some(3),
                     new_lineno: Some(3),
+                    no_newline: false,
                 },
             ],
         };
@@ -1491,12 +2248,14 @@ mod tests {
                     line_type: LineType::Context,
                     old_lineno: Some(1),
                     new_lineno: Some(1),
+                    no_newline: false,
                 },
                 DiffLine {
                     content: "added".to_string(),
                     line_type: LineType::Addition,
                     old_lineno: None,
                     new_lineno: Some(2),
+                    no_newline: false,
                 },
             ],
         };
@@ -1577,7 +2336,14 @@ D
     fn test_resolve_conflict_content_invalid_strategy() {
         let content = "<<<<<<< HEAD\nA\n=======\nB\n>>>>>>> branch\n";
         let result = super::resolve_conflict_content(content, "invalid");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(AppError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_resolve_conflict_content_preserves_crlf() {
+        let content = "before\r\n<<<<<<< HEAD\r\nA\r\n=======\r\nB\r\n>>>>>>> branch\r\n";
+        let result = super::resolve_conflict_content(content, "theirs").unwrap();
+        assert_eq!(result, "before\r\nB\r\n");
     }
 
     #[test]
@@ -1,7 +1,13 @@
-use git2::{DiffOptions, Repository, StashApplyOptions};
+use std::path::Path;
+
+use git2::{DiffOptions, Index, Repository, StashApplyOptions, StashApplyProgress, StashFlags};
 use serde::Serialize;
 
 use crate::error::AppError;
+use crate::git::commit::{diff_file_changes, diff_trees_with_renames};
+use crate::git::diff::TextStats;
+use crate::git::merge::conflicted_paths;
+use crate::git::staging::create_index_entry;
 use crate::git::{CommitFileChange, DiffHunk, DiffLine, FileDiff, LineType};
 
 #[derive(Debug, Serialize, Clone)]
@@ -115,41 +121,9 @@ pub fn get_stash_details(repo: &mut Repository, index: usize) -> Result<StashDet
     };
 
     // Diff between parent and stash to get changed files
-    let diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&stash_tree), None)?;
-
-    let files_changed: Vec<CommitFileChange> = diff
-        .deltas()
-        .filter_map(|delta| {
-            let path = delta
-                .new_file()
-                .path()
-                .map(|p| p.to_string_lossy().to_string())?;
-            let status = match delta.status() {
-                git2::Delta::Added => "added",
-                git2::Delta::Deleted => "deleted",
-                git2::Delta::Modified => "modified",
-                git2::Delta::Renamed => "renamed",
-                git2::Delta::Copied => "copied",
-                _ => "modified",
-            }
-            .to_string();
-            let old_path = if delta.status() == git2::Delta::Renamed
-                || delta.status() == git2::Delta::Copied
-            {
-                delta
-                    .old_file()
-                    .path()
-                    .map(|p| p.to_string_lossy().to_string())
-            } else {
-                None
-            };
-            Some(CommitFileChange {
-                path,
-                status,
-                old_path,
-            })
-        })
-        .collect();
+    let diff = diff_trees_with_renames(repo, parent_tree.as_ref(), Some(&stash_tree))?;
+
+    let files_changed = diff_file_changes(&diff)?;
 
     Ok(StashDetails {
         index,
@@ -161,10 +135,288 @@ pub fn get_stash_details(repo: &mut Repository, index: usize) -> Result<StashDet
     })
 }
 
+/// Stash the working tree and index. `include_untracked` also captures (and
+/// removes) untracked files; `keep_index` leaves staged changes in place.
+/// Returns the new stash, which is always at index 0.
+pub fn create_stash(
+    repo: &mut Repository,
+    message: Option<&str>,
+    include_untracked: bool,
+    keep_index: bool,
+) -> Result<StashInfo, AppError> {
+    crate::log_git_op!(
+        "create_stash",
+        include_untracked = include_untracked,
+        keep_index = keep_index
+    );
+    let mut flags = StashFlags::DEFAULT;
+    if include_untracked {
+        flags |= StashFlags::INCLUDE_UNTRACKED;
+    }
+    if keep_index {
+        flags |= StashFlags::KEEP_INDEX;
+    }
+
+    let signature = repo.signature()?;
+    repo.stash_save2(&signature, message, Some(flags))
+        .map_err(|e| match e.code() {
+            git2::ErrorCode::NotFound => AppError::NothingToStash,
+            _ => e.into(),
+        })?;
+
+    list_stashes(repo)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::Internal("Stash was created but could not be listed".into()))
+}
+
+/// Stash only `paths`, leaving every other change in place. Returns the new
+/// stash, which is always at index 0.
+///
+/// libgit2 has no pathspec stash, so the stash is assembled by hand: an index
+/// commit holding the selected paths' staged state and a working-tree commit
+/// on top of it, both based on HEAD, recorded in the `refs/stash` reflog. The
+/// selected paths are then reset to HEAD in the index and working tree.
+///
+/// Limitation: the layout mirrors `git stash` closely enough for list, apply
+/// and drop (here or on the command line), but it is not produced by git's own
+/// stash machinery. Untracked files are never captured, and unselected paths
+/// are recorded as unchanged, so `git stash apply --index` only restores the
+/// staging of the selected paths.
+pub fn stash_paths(
+    repo: &mut Repository,
+    paths: &[String],
+    message: Option<&str>,
+) -> Result<StashInfo, AppError> {
+    crate::log_git_op!("stash_paths", count = paths.len());
+    if paths.is_empty() {
+        return Err(AppError::InvalidPath("No paths to stash".to_string()));
+    }
+    if let Some(bad) = paths
+        .iter()
+        .find(|p| Path::new(p).is_absolute() || p.split('/').any(|c| c == ".."))
+    {
+        return Err(AppError::InvalidPath(bad.clone()));
+    }
+
+    save_path_stash(repo, paths, message)?;
+
+    list_stashes(repo)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::Internal("Stash was created but could not be listed".into()))
+}
+
+/// Index mode of a submodule entry.
+const GITLINK_MODE: u32 = 0o160000;
+
+fn save_path_stash(
+    repo: &Repository,
+    paths: &[String],
+    message: Option<&str>,
+) -> Result<(), AppError> {
+    let workdir = repo.workdir().ok_or(AppError::NoWorkdir)?.to_path_buf();
+    let head = repo.head()?.peel_to_commit()?;
+    let head_tree = head.tree()?;
+    let mut repo_index = repo.index()?;
+
+    // The reset below writes file entries; a directory or submodule would
+    // turn into a bogus index entry partway through.
+    for path in paths {
+        let in_head_ok = head_tree
+            .get_path(Path::new(path))
+            .ok()
+            .is_none_or(|e| e.kind() == Some(git2::ObjectType::Blob));
+        let in_index_ok = repo_index
+            .get_path(Path::new(path), 0)
+            .is_none_or(|e| e.mode != GITLINK_MODE);
+        let on_disk_ok = std::fs::symlink_metadata(workdir.join(path))
+            .ok()
+            .is_none_or(|meta| !meta.is_dir());
+        if !(in_head_ok && in_index_ok && on_disk_ok) {
+            return Err(AppError::InvalidPath(format!(
+                "{path} is not a file; only files can be stashed by path"
+            )));
+        }
+    }
+
+    // Index commit: HEAD plus the selected paths as currently staged.
+    let mut staged = Index::new()?;
+    staged.read_tree(&head_tree)?;
+    for path in paths {
+        match repo_index.get_path(Path::new(path), 0) {
+            Some(entry) => staged.add(&entry)?,
+            None => staged.remove_path(Path::new(path))?,
+        }
+    }
+    let index_tree = repo.find_tree(staged.write_tree_to(repo)?)?;
+
+    // Working-tree commit: the index commit plus the selected files on disk.
+    // Paths git doesn't track (neither staged nor in HEAD) are left alone.
+    let mut worktree = Index::new()?;
+    worktree.read_tree(&index_tree)?;
+    for path in paths {
+        let tracked_mode = staged
+            .get_path(Path::new(path), 0)
+            .map(|e| e.mode)
+            .or_else(|| {
+                head_tree
+                    .get_path(Path::new(path))
+                    .ok()
+                    .map(|e| e.filemode() as u32)
+            });
+        let Some(mode) = tracked_mode else {
+            continue;
+        };
+        let full_path = workdir.join(path);
+        if full_path.is_file() {
+            let content = std::fs::read(&full_path)
+                .map_err(|e| AppError::FileIo(format!("failed to read {path}: {e}")))?;
+            let id = repo.blob(&content)?;
+            worktree.add(&create_index_entry(path, mode, content.len() as u32, id))?;
+        } else {
+            worktree.remove_path(Path::new(path))?;
+        }
+    }
+    let worktree_tree = repo.find_tree(worktree.write_tree_to(repo)?)?;
+
+    if index_tree.id() == head_tree.id() && worktree_tree.id() == head_tree.id() {
+        return Err(AppError::NothingToStash);
+    }
+
+    // Same messages `git stash` writes, so parse_branch_from_stash_message
+    // and other tools read them the same way.
+    let branch = if repo.head_detached()? {
+        "(no branch)".to_string()
+    } else {
+        repo.head()?.shorthand().unwrap_or("HEAD").to_string()
+    };
+    let short_id = head.as_object().short_id()?;
+    let head_line = format!(
+        "{} {}",
+        short_id.as_str().unwrap_or_default(),
+        head.summary().ok().flatten().unwrap_or_default()
+    );
+    let stash_message = match message {
+        Some(m) => format!("On {branch}: {m}"),
+        None => format!("WIP on {branch}: {head_line}"),
+    };
+
+    let signature = repo.signature()?;
+    let index_commit = repo.commit(
+        None,
+        &signature,
+        &signature,
+        &format!("index on {branch}: {head_line}"),
+        &index_tree,
+        &[&head],
+    )?;
+    let index_commit = repo.find_commit(index_commit)?;
+    let stash_commit = repo.commit(
+        None,
+        &signature,
+        &signature,
+        &stash_message,
+        &worktree_tree,
+        &[&head, &index_commit],
+    )?;
+    // Recorded before the paths are reset, as `git stash` does: if the reset
+    // fails, the changes are still reachable from the stash.
+    repo.reference_ensure_log("refs/stash")?;
+    repo.reference("refs/stash", stash_commit, true, &stash_message)?;
+
+    // Reset the selected paths to HEAD. Paths HEAD doesn't have were staged
+    // as new files; they're now in the stash, so remove them from disk too.
+    let mut in_head = Vec::new();
+    for path in paths {
+        match head_tree.get_path(Path::new(path)) {
+            Ok(entry) => {
+                repo_index.add(&create_index_entry(
+                    path,
+                    entry.filemode() as u32,
+                    0,
+                    entry.id(),
+                ))?;
+                in_head.push(path.as_str());
+            }
+            Err(_) => {
+                if repo_index.get_path(Path::new(path), 0).is_some() {
+                    repo_index.remove_path(Path::new(path))?;
+                    let full_path = workdir.join(path);
+                    if full_path.is_file() {
+                        std::fs::remove_file(&full_path).map_err(|e| {
+                            AppError::FileIo(format!("failed to delete {path}: {e}"))
+                        })?;
+                    }
+                }
+            }
+        }
+    }
+    repo_index.write()?;
+    if !in_head.is_empty() {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force();
+        for path in &in_head {
+            checkout.path(path);
+        }
+        repo.checkout_tree(head_tree.as_object(), Some(&mut checkout))?;
+    }
+
+    Ok(())
+}
+
 pub fn apply_stash(repo: &mut Repository, index: usize) -> Result<(), AppError> {
-    crate::log_git_op!("apply_stash", index = index);
-    let mut opts = StashApplyOptions::new();
-    repo.stash_apply(index, Some(&mut opts))?;
+    // Logged by `apply_stash_with_progress`.
+    apply_stash_with_progress(repo, index, |_| {})
+}
+
+/// Apply stash `index`, reporting each stage of the apply to `progress`.
+///
+/// If the stash doesn't merge cleanly with HEAD, the conflicts are left in
+/// the index and working tree (as `git stash apply` does) and
+/// `AppError::MergeConflict` names them. If it would overwrite files changed
+/// since the stash was made, nothing is applied and
+/// `AppError::CheckoutConflict` names those files.
+pub fn apply_stash_with_progress(
+    repo: &mut Repository,
+    index: usize,
+    mut progress: impl FnMut(StashApplyProgress),
+) -> Result<(), AppError> {
+    crate::log_git_op!("apply_stash_with_progress", index = index);
+    let mut blocked = Vec::new();
+    let result = {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout
+            .safe()
+            .notify_on(git2::CheckoutNotificationType::CONFLICT)
+            .notify(|_, path, _, _, _| {
+                if let Some(path) = path {
+                    blocked.push(path.to_string_lossy().into_owned());
+                }
+                true
+            });
+        let mut opts = StashApplyOptions::new();
+        opts.checkout_options(checkout);
+        opts.progress_cb(|stage| {
+            progress(stage);
+            true
+        });
+        repo.stash_apply(index, Some(&mut opts))
+    };
+    match result {
+        Err(e) if e.code() == git2::ErrorCode::Conflict => {
+            return Err(AppError::CheckoutConflict(blocked));
+        }
+        result => result?,
+    }
+
+    let repo_index = repo.index()?;
+    if repo_index.has_conflicts() {
+        return Err(AppError::MergeConflict {
+            paths: conflicted_paths(&repo_index)?,
+            step: None,
+        });
+    }
     Ok(())
 }
 
@@ -174,6 +426,22 @@ pub fn drop_stash(repo: &mut Repository, index: usize) -> Result<(), AppError> {
     Ok(())
 }
 
+/// Drop every stash and return how many were removed. Always drops index 0:
+/// indices shift down after each drop, so cached indices would skip entries.
+pub fn clear_stashes(repo: &mut Repository) -> Result<usize, AppError> {
+    crate::log_git_op!("clear_stashes");
+    let mut count = 0;
+    repo.stash_foreach(|_, _, _| {
+        count += 1;
+        true
+    })?;
+
+    for _ in 0..count {
+        repo.stash_drop(0)?;
+    }
+    Ok(count)
+}
+
 pub fn get_stash_file_diff(
     repo: &mut Repository,
     index: usize,
@@ -217,15 +485,10 @@ pub fn get_stash_file_diff(
         Some(&mut diff_opts),
     )?;
 
-    let mut file_diff = FileDiff {
-        path: path.to_string(),
-        hunks: Vec::new(),
-        is_binary: false,
-        total_lines: 0,
-        is_conflicted: false,
-    };
+    let mut file_diff = FileDiff::new(path);
 
     let mut current_hunk: Option<DiffHunk> = None;
+    let mut new_side = TextStats::default();
 
     diff.print(git2::DiffFormat::Patch, |delta, hunk, line| {
         if delta.flags().contains(git2::DiffFlags::BINARY) {
@@ -250,6 +513,9 @@ pub fn get_stash_file_diff(
         }
 
         if let Some(ref mut hunk) = current_hunk {
+            if matches!(line.origin(), '+' | ' ') {
+                new_side.add_line(line.content());
+            }
             let content = String::from_utf8_lossy(line.content()).to_string();
             let line_type = match line.origin() {
                 '+' => LineType::Addition,
@@ -265,6 +531,7 @@ pub fn get_stash_file_diff(
                 line_type,
                 old_lineno: line.old_lineno(),
                 new_lineno: line.new_lineno(),
+                no_newline: false,
             });
         }
 
@@ -274,6 +541,7 @@ pub fn get_stash_file_diff(
     if let Some(h) = current_hunk {
         file_diff.hunks.push(h);
     }
+    new_side.apply(&mut file_diff);
 
     Ok(file_diff)
 }
@@ -0,0 +1,59 @@
+use git2::{Repository, SubmoduleIgnore, SubmoduleStatus};
+use serde::Serialize;
+
+use crate::error::AppError;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmoduleState {
+    /// Not cloned into the worktree (`git submodule update --init` not run).
+    Uninitialized,
+    /// Checked out at a different commit than the superproject records.
+    OutOfDate,
+    Clean,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SubmoduleInfo {
+    pub name: String,
+    pub path: String,
+    pub url: Option<String>,
+    /// Commit recorded in the superproject's HEAD tree.
+    pub head_oid: Option<String>,
+    /// Commit currently checked out in the submodule's worktree.
+    pub workdir_oid: Option<String>,
+    pub state: SubmoduleState,
+}
+
+/// Submodules from `.gitmodules` and the index. Uncommitted changes inside a
+/// submodule don't count as drift; only its checked-out commit does.
+pub fn list_submodules(repo: &Repository) -> Result<Vec<SubmoduleInfo>, AppError> {
+    crate::log_git_op_debug!("list_submodules");
+    let mut submodules = Vec::new();
+    for submodule in repo.submodules()? {
+        let name = String::from_utf8_lossy(submodule.name_bytes()).into_owned();
+        let flags = repo.submodule_status(&name, SubmoduleIgnore::Dirty)?;
+        let state = if flags
+            .intersects(SubmoduleStatus::WD_UNINITIALIZED | SubmoduleStatus::WD_DELETED)
+            || !flags.contains(SubmoduleStatus::IN_WD)
+        {
+            SubmoduleState::Uninitialized
+        } else if flags.contains(SubmoduleStatus::WD_MODIFIED) {
+            SubmoduleState::OutOfDate
+        } else {
+            SubmoduleState::Clean
+        };
+
+        submodules.push(SubmoduleInfo {
+            name,
+            path: submodule.path().to_string_lossy().into_owned(),
+            url: submodule
+                .opt_url_bytes()
+                .map(|url| String::from_utf8_lossy(url).into_owned()),
+            head_oid: submodule.head_id().map(|oid| oid.to_string()),
+            workdir_oid: submodule.workdir_id().map(|oid| oid.to_string()),
+            state,
+        });
+    }
+    Ok(submodules)
+}
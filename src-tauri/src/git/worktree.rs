@@ -270,6 +270,20 @@ pub fn add_worktree(
         new_branch = new_branch
     );
 
+    // An empty existing directory is fine (e.g. one just made by a folder
+    // picker), but libgit2 insists on creating it itself, so drop it first.
+    // Anything else would be checked out over.
+    if path.exists() {
+        let is_empty_dir = path.is_dir() && std::fs::read_dir(path)?.next().is_none();
+        if !is_empty_dir {
+            return Err(AppError::InvalidPath(format!(
+                "Worktree path is not empty: {}",
+                path.display()
+            )));
+        }
+        std::fs::remove_dir(path)?;
+    }
+
     let mut opts = git2::WorktreeAddOptions::new();
 
     // Resolve the reference to set as the worktree HEAD. The reference must
@@ -339,6 +353,22 @@ pub fn remove_worktree(repo: &Repository, name: &str, force: bool) -> Result<(),
     Ok(())
 }
 
+/// Prune every linked worktree whose working directory is gone (the same set
+/// `git worktree prune` removes: invalid and unlocked). Returns the pruned
+/// names.
+pub fn prune_worktrees(repo: &Repository) -> Result<Vec<String>, AppError> {
+    crate::log_git_op!("prune_worktrees");
+    let mut pruned = Vec::new();
+    for name in repo.worktrees()?.iter().flatten().flatten() {
+        let wt = repo.find_worktree(name)?;
+        if wt.is_prunable(None)? {
+            wt.prune(None)?;
+            pruned.push(name.to_string());
+        }
+    }
+    Ok(pruned)
+}
+
 /// Move a linked worktree's working directory to a new path.
 ///
 /// libgit2 does not expose `git worktree move` directly, so this performs the
@@ -6,6 +6,7 @@ pub mod logger;
 mod state;
 pub mod terminal;
 pub mod update_logger;
+pub mod watcher;
 
 /// Log a Tauri command invocation. Captures the command name and (optionally)
 /// key parameters by identifier and value. Never log file contents, diff
@@ -90,48 +91,103 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::get_current_dir,
             commands::open_repository,
+            commands::init_repository,
             commands::get_repository_info,
+            commands::get_repo_state,
+            commands::get_repo_stats,
+            commands::get_recent_repos,
+            commands::watch_repository,
             commands::get_all_commit_graph,
+            commands::get_commit_graph_page,
+            commands::refresh_refs,
+            commands::get_commit_graph_edges,
+            commands::refs_at_commit,
+            commands::get_commits,
+            commands::search_commits,
+            commands::find_commit_in_graph,
+            commands::resolve_revision,
             commands::get_commit_details,
+            commands::get_branch_diff_files,
             commands::get_commit_file_diff,
+            commands::get_commit_diff,
             commands::get_commit_diff_hunk,
+            commands::get_file_at_commit,
+            commands::list_tree,
+            commands::reset_to_commit,
+            commands::get_blame,
+            commands::get_file_history,
             commands::list_branches,
             commands::list_tags,
             commands::checkout_commit,
+            commands::checkout_reflog_entry,
+            commands::branches_containing,
+            commands::count_commits_between,
             commands::checkout_branch,
             commands::create_branch_and_checkout,
+            commands::create_branch,
+            commands::set_upstream,
             commands::validate_branch_name,
             commands::delete_branch,
             commands::delete_tag,
+            commands::create_tag,
+            commands::move_tag,
             commands::get_file_statuses,
+            commands::get_path_status,
+            commands::is_path_ignored,
+            commands::add_to_gitignore,
             commands::stage_file,
             commands::unstage_file,
             commands::stage_files,
             commands::unstage_files,
+            commands::stage_all,
+            commands::stage_untracked,
+            commands::stage_directory,
+            commands::unstage_all,
             commands::stage_hunk,
             commands::unstage_hunk,
             commands::stage_lines,
+            commands::stage_selection,
             commands::discard_hunk,
+            commands::apply_patch,
             commands::get_file_diff,
+            commands::get_untracked_file_diff,
+            commands::get_file_patch,
+            commands::get_combined_file_diff,
+            commands::get_image_blobs,
             commands::get_diff_hunk,
+            commands::set_diff_limits,
             commands::create_commit,
+            commands::amend_commit,
+            commands::squash_commits,
+            commands::validate_commit_message,
+            commands::get_config,
+            commands::set_config,
             commands::revert_file,
+            commands::discard_file,
+            commands::discard_all_changes,
             commands::revert_commit,
             commands::revert_commit_file,
             commands::revert_commit_file_lines,
             commands::delete_file,
             commands::delete_files,
+            commands::get_conflict,
             commands::resolve_conflict,
             commands::abort_operation,
             commands::continue_operation,
+            commands::cancel_operation,
+            commands::cherry_pick_range,
+            commands::revert_range,
             commands::install_cli,
             commands::uninstall_cli,
             commands::check_cli_installed,
             commands::get_app_info,
             commands::list_stashes,
             commands::get_stash_details,
+            commands::create_stash,
+            commands::stash_paths,
             commands::apply_stash,
             commands::drop_stash,
+            commands::clear_stashes,
             commands::get_stash_file_diff,
             commands::list_gone_branches,
             commands::list_merged_branches,
@@ -157,9 +213,23 @@ pub fn run() {
             commands::list_worktrees,
             commands::add_worktree,
             commands::remove_worktree,
+            commands::prune_worktrees,
             commands::move_worktree,
             commands::lock_worktree,
             commands::unlock_worktree,
+            commands::list_remotes,
+            commands::add_remote,
+            commands::remove_remote,
+            commands::set_remote_url,
+            commands::fetch_remote,
+            commands::push_branch,
+            commands::push_tag,
+            commands::delete_remote_tag,
+            commands::merge_branch,
+            commands::merge_base,
+            commands::merge_base_many,
+            commands::rebase_branch,
+            commands::list_submodules,
         ])
         .on_window_event(|window, event| {
             if let tauri::WindowEvent::Destroyed = event {
@@ -57,10 +57,14 @@ pub fn init(debug_enabled: bool) -> Result<(), String> {
     Ok(())
 }
 
+/// Per-user application data directory (`<data_dir>/yagg`).
+pub fn app_data_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_DIR_NAME))
+}
+
 /// Directory containing per-instance log files.
 pub fn log_dir() -> Option<PathBuf> {
-    let data_dir = dirs::data_dir()?;
-    Some(data_dir.join(APP_DIR_NAME).join(LOG_DIR_NAME))
+    Some(app_data_dir()?.join(LOG_DIR_NAME))
 }
 
 /// Path to the current instance's log file. Available after [`init`].
@@ -1,10 +1,15 @@
+pub mod recent_repos;
+
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use git2::Repository;
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 
 use crate::error::AppError;
+use crate::git::{CancelToken, DiffConfig, GraphPageCache, HistoryCache, RefsCache};
 use crate::terminal::TerminalManager;
+use crate::watcher::RepoWatcher;
 
 pub struct AppState {
     /// `Arc<Mutex<...>>` so async commands can `clone()` the handle and move
@@ -12,6 +17,50 @@ pub struct AppState {
     /// the async runtime without holding the mutex across `.await`.
     pub repository: Arc<Mutex<Option<Repository>>>,
     pub terminal_manager: TerminalManager,
+    /// Session-wide diff limits read by every diff command.
+    pub diff_config: Mutex<DiffConfig>,
+    /// Recently opened repository paths, most recent first. Mirrored to disk
+    /// by [`AppState::remember_repo`].
+    pub recent_repos: Mutex<Vec<String>>,
+    /// Filesystem watch on the open repository, if `watch_repository` was
+    /// called. Cleared when another repository is opened.
+    pub watcher: Mutex<Option<RepoWatcher>>,
+    /// `get_repo_stats`'s history walk for the open repository. Reset when
+    /// another repository is opened.
+    pub history_cache: Arc<Mutex<HistoryCache>>,
+    /// Branch and tag labels for the commit graph of the open repository.
+    /// Reset when another repository is opened.
+    pub refs_cache: Arc<Mutex<RefsCache>>,
+    /// Commit graph walk and page boundaries of the open repository. Reset
+    /// when another repository is opened.
+    pub graph_page_cache: Arc<Mutex<GraphPageCache>>,
+    /// Cancellation tokens of running operations, keyed by the id the
+    /// frontend passed in. See [`AppState::begin_operation`].
+    pub operations: Mutex<HashMap<String, CancelToken>>,
+}
+
+/// Keeps an operation registered for `cancel_operation` until dropped.
+pub struct OperationGuard<'a> {
+    state: &'a AppState,
+    id: Option<String>,
+    token: CancelToken,
+}
+
+impl OperationGuard<'_> {
+    pub fn token(&self) -> CancelToken {
+        self.token.clone()
+    }
+}
+
+impl Drop for OperationGuard<'_> {
+    fn drop(&mut self) {
+        let Some(id) = &self.id else { return };
+        let mut operations = self.state.operations.lock();
+        // A later operation may have reused the id; leave its token alone.
+        if operations.get(id).is_some_and(|t| t.same_as(&self.token)) {
+            operations.remove(id);
+        }
+    }
 }
 
 impl AppState {
@@ -19,6 +68,55 @@ impl AppState {
         Self {
             repository: Arc::new(Mutex::new(None)),
             terminal_manager: TerminalManager::new(),
+            diff_config: Mutex::new(DiffConfig::default()),
+            recent_repos: Mutex::new(
+                recent_repos::recent_repos_path()
+                    .map(|p| recent_repos::load(&p))
+                    .unwrap_or_default(),
+            ),
+            watcher: Mutex::new(None),
+            history_cache: Arc::new(Mutex::new(HistoryCache::default())),
+            refs_cache: Arc::new(Mutex::new(RefsCache::default())),
+            graph_page_cache: Arc::new(Mutex::new(GraphPageCache::default())),
+            operations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Fresh cancellation token for an operation, registered under `id` (if
+    /// the caller gave one) for as long as the guard lives.
+    pub fn begin_operation(&self, id: Option<String>) -> OperationGuard<'_> {
+        let token = CancelToken::new();
+        if let Some(id) = &id {
+            self.operations.lock().insert(id.clone(), token.clone());
+        }
+        OperationGuard {
+            state: self,
+            id,
+            token,
+        }
+    }
+
+    /// Signal the operation registered under `id`. Returns false if there is
+    /// none, e.g. because it already finished.
+    pub fn cancel_operation(&self, id: &str) -> bool {
+        match self.operations.lock().get(id) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record `path` as the most recently opened repository and persist the
+    /// list. Persistence is best-effort; failures are only logged.
+    pub fn remember_repo(&self, path: String) {
+        let mut repos = self.recent_repos.lock();
+        recent_repos::push(&mut repos, path);
+        if let Some(file) = recent_repos::recent_repos_path() {
+            if let Err(e) = recent_repos::save(&file, &repos) {
+                log::error!(target: "yagg::error", "could not save recent repos: {e}");
+            }
         }
     }
 
@@ -52,6 +150,14 @@ mod tests {
         assert!(repo.is_none());
     }
 
+    #[test]
+    fn test_app_state_diff_config_defaults() {
+        let state = AppState::new();
+        let config = state.diff_config.lock();
+        assert_eq!(config.max_diff_bytes, DiffConfig::default().max_diff_bytes);
+        assert_eq!(config.max_file_size, DiffConfig::default().max_file_size);
+    }
+
     #[test]
     fn test_app_state_mutex_is_unlocked() {
         let state = AppState::new();
@@ -65,4 +171,17 @@ mod tests {
             assert!(lock2.is_none());
         }
     }
+
+    #[test]
+    fn test_cancel_operation_reaches_running_token() {
+        let state = AppState::new();
+        let guard = state.begin_operation(Some("walk-1".to_string()));
+        let token = guard.token();
+
+        assert!(state.cancel_operation("walk-1"));
+        assert!(token.is_cancelled());
+
+        drop(guard);
+        assert!(!state.cancel_operation("walk-1"));
+    }
 }
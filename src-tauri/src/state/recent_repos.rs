@@ -0,0 +1,82 @@
+//! Most-recently-opened repository list, persisted as a JSON array of paths
+//! in `<data_dir>/yagg/recent_repos.json`.
+
+use std::path::{Path, PathBuf};
+
+const FILE_NAME: &str = "recent_repos.json";
+pub const MAX_RECENT_REPOS: usize = 10;
+
+/// Location of the persisted list. `None` if the data directory can't be
+/// resolved.
+pub fn recent_repos_path() -> Option<PathBuf> {
+    Some(crate::logger::app_data_dir()?.join(FILE_NAME))
+}
+
+/// Read the list from `path`. A missing or malformed file yields an empty
+/// list — losing the history is preferable to failing startup.
+pub fn load(path: &Path) -> Vec<String> {
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut repos: Vec<String> = serde_json::from_str(&contents).unwrap_or_default();
+    repos.truncate(MAX_RECENT_REPOS);
+    repos
+}
+
+pub fn save(path: &Path, repos: &[String]) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(repos)?)
+}
+
+/// Move (or insert) `repo_path` to the front, keeping at most
+/// [`MAX_RECENT_REPOS`] entries.
+pub fn push(repos: &mut Vec<String>, repo_path: String) {
+    repos.retain(|p| *p != repo_path);
+    repos.insert(0, repo_path);
+    repos.truncate(MAX_RECENT_REPOS);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_dedups_and_moves_to_front() {
+        let mut repos = vec!["/a".to_string(), "/b".to_string(), "/c".to_string()];
+        push(&mut repos, "/b".to_string());
+        assert_eq!(repos, vec!["/b", "/a", "/c"]);
+    }
+
+    #[test]
+    fn push_caps_length() {
+        let mut repos = Vec::new();
+        for i in 0..(MAX_RECENT_REPOS + 3) {
+            push(&mut repos, format!("/repo{i}"));
+        }
+        assert_eq!(repos.len(), MAX_RECENT_REPOS);
+        assert_eq!(repos[0], format!("/repo{}", MAX_RECENT_REPOS + 2));
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("nested").join(FILE_NAME);
+        let repos = vec!["/x".to_string(), "/y".to_string()];
+
+        save(&path, &repos).unwrap();
+
+        assert_eq!(load(&path), repos);
+    }
+
+    #[test]
+    fn load_missing_or_malformed_is_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(FILE_NAME);
+        assert!(load(&path).is_empty());
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load(&path).is_empty());
+    }
+}
@@ -271,6 +271,9 @@ mod tests {
     fn test_serialize_terminal_error() {
         let error = AppError::Terminal("pty failed".to_string());
         let json = serde_json::to_string(&error).unwrap();
-        assert_eq!(json, "\"Terminal error: pty failed\"");
+        assert_eq!(
+            json,
+            r#"{"code":"terminal","message":"Terminal error: pty failed"}"#
+        );
     }
 }
@@ -0,0 +1,156 @@
+//! Filesystem watcher that tells the frontend when the open repository
+//! changes on disk, so it can refresh instead of polling.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use git2::Repository;
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+use crate::error::AppError;
+
+pub const REPO_CHANGED_EVENT: &str = "repo-changed";
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+#[derive(Serialize, Clone)]
+struct RepoChangedPayload {
+    paths: Vec<String>,
+}
+
+/// Live watch on one repository. Watching stops when this is dropped.
+pub struct RepoWatcher {
+    _debouncer: Debouncer<RecommendedWatcher>,
+}
+
+impl RepoWatcher {
+    /// Watch `workdir` and `git_dir` recursively and emit `repo-changed`
+    /// with the affected paths, at most once per debounce window.
+    pub fn start(
+        workdir: Option<&Path>,
+        git_dir: &Path,
+        app_handle: AppHandle,
+    ) -> Result<Self, AppError> {
+        let filter_git_dir = git_dir.to_path_buf();
+        let filter_workdir = workdir.map(Path::to_path_buf);
+        // A handle of our own, since the watcher thread outlives the lock on
+        // the app's repository.
+        let ignore_repo = Repository::open(git_dir)?;
+        let mut debouncer = new_debouncer(DEBOUNCE, move |result: DebounceEventResult| {
+            let events = match result {
+                Ok(events) => events,
+                Err(e) => {
+                    log::error!(target: "yagg::error", "watcher error: {e}");
+                    return;
+                }
+            };
+            let paths: Vec<String> = events
+                .into_iter()
+                .filter(|event| is_relevant(&event.path, &filter_git_dir))
+                .filter(|event| match &filter_workdir {
+                    Some(workdir) => !is_ignored(&ignore_repo, workdir, &event.path),
+                    None => true,
+                })
+                .map(|event| event.path.to_string_lossy().into_owned())
+                .collect();
+            if !paths.is_empty() {
+                let _ = app_handle.emit(REPO_CHANGED_EVENT, RepoChangedPayload { paths });
+            }
+        })
+        .map_err(watch_error)?;
+
+        // The git dir normally sits inside the workdir, but worktrees and
+        // bare-ish layouts keep it elsewhere.
+        let mut roots: Vec<PathBuf> = workdir.map(Path::to_path_buf).into_iter().collect();
+        if !roots.iter().any(|root| git_dir.starts_with(root)) {
+            roots.push(git_dir.to_path_buf());
+        }
+        for root in &roots {
+            debouncer
+                .watcher()
+                .watch(root, RecursiveMode::Recursive)
+                .map_err(watch_error)?;
+        }
+
+        Ok(Self {
+            _debouncer: debouncer,
+        })
+    }
+}
+
+fn watch_error(e: notify_debouncer_mini::notify::Error) -> AppError {
+    AppError::Internal(format!("Failed to watch repository: {e}"))
+}
+
+/// Object-database writes and lock files are noise: every commit, fetch, or
+/// index update that matters also touches `index`, `HEAD`, or a ref, which
+/// do get through.
+fn is_relevant(path: &Path, git_dir: &Path) -> bool {
+    if path.extension().is_some_and(|ext| ext == "lock") {
+        return false;
+    }
+    match path.strip_prefix(git_dir) {
+        Ok(inside) => !inside.starts_with("objects") && !inside.starts_with("logs"),
+        Err(_) => true,
+    }
+}
+
+/// Gitignored paths such as `target/` or `node_modules/` change constantly
+/// during builds without changing anything git shows.
+fn is_ignored(repo: &Repository, workdir: &Path, path: &Path) -> bool {
+    match path.strip_prefix(workdir) {
+        Ok(relative) if !relative.starts_with(".git") => {
+            repo.is_path_ignored(relative).unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_relevant_keeps_worktree_files() {
+        let git_dir = Path::new("/repo/.git");
+        assert!(is_relevant(Path::new("/repo/src/main.rs"), git_dir));
+    }
+
+    #[test]
+    fn is_relevant_keeps_index_head_and_refs() {
+        let git_dir = Path::new("/repo/.git");
+        assert!(is_relevant(Path::new("/repo/.git/index"), git_dir));
+        assert!(is_relevant(Path::new("/repo/.git/HEAD"), git_dir));
+        assert!(is_relevant(
+            Path::new("/repo/.git/refs/heads/main"),
+            git_dir
+        ));
+    }
+
+    #[test]
+    fn is_relevant_skips_objects_logs_and_locks() {
+        let git_dir = Path::new("/repo/.git");
+        assert!(!is_relevant(
+            Path::new("/repo/.git/objects/ab/cdef"),
+            git_dir
+        ));
+        assert!(!is_relevant(Path::new("/repo/.git/logs/HEAD"), git_dir));
+        assert!(!is_relevant(Path::new("/repo/.git/index.lock"), git_dir));
+    }
+
+    #[test]
+    fn is_ignored_skips_gitignored_paths() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/\n*.log\n").unwrap();
+        let ignored = |path: &str| is_ignored(&repo, dir.path(), &dir.path().join(path));
+
+        assert!(ignored("target/debug/app"));
+        assert!(ignored("build.log"));
+        assert!(!ignored("src/main.rs"));
+        assert!(!ignored(".gitignore"));
+        assert!(!ignored(".git/index"));
+    }
+}
@@ -0,0 +1,57 @@
+//! Integration tests for `get_blame`.
+
+mod common;
+
+use common::{create_commit_with_file, create_initial_commit, create_test_repo};
+use std::fs;
+use yagg_lib::error::AppError;
+use yagg_lib::git::get_blame;
+
+#[test]
+fn get_blame_single_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_commit_with_file(&repo, &temp_dir, "f.txt", "a\nb\nc\n", "Add f");
+
+    let lines = get_blame(&repo, "f.txt").unwrap();
+
+    assert_eq!(lines.len(), 3);
+    for (i, line) in lines.iter().enumerate() {
+        assert_eq!(line.line_number, i + 1);
+        assert_eq!(line.commit_hash, oid.to_string());
+        assert_eq!(line.short_hash, oid.to_string()[..7]);
+        assert_eq!(line.author_name, "Test User");
+        assert!(line.timestamp > 0);
+    }
+}
+
+#[test]
+fn get_blame_maps_lines_to_multiple_commits() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_commit_with_file(&repo, &temp_dir, "f.txt", "one\ntwo\nthree\n", "v1");
+    let second =
+        create_commit_with_file(&repo, &temp_dir, "f.txt", "one\nTWO\nthree\nfour\n", "v2");
+
+    let lines = get_blame(&repo, "f.txt").unwrap();
+
+    let hashes: Vec<&str> = lines.iter().map(|l| l.commit_hash.as_str()).collect();
+    let (first, second) = (first.to_string(), second.to_string());
+    assert_eq!(hashes, vec![&first, &second, &first, &second]);
+    let numbers: Vec<usize> = lines.iter().map(|l| l.line_number).collect();
+    assert_eq!(numbers, vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn get_blame_untracked_file_errors() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("new.txt"), "fresh\n").unwrap();
+
+    let result = get_blame(&repo, "new.txt");
+    assert!(matches!(result, Err(AppError::InvalidPath(_))));
+}
+
+#[test]
+fn get_blame_empty_repo_errors() {
+    let (_temp_dir, repo) = create_test_repo();
+    assert!(get_blame(&repo, "anything.txt").is_err());
+}
@@ -7,6 +7,11 @@ mod common;
 
 use common::{create_commit_with_file, create_initial_commit, create_test_repo};
 use git2::{BranchType, Oid, Repository};
+use yagg_lib::commands::branches::{
+    ahead_behind, checkout_detached, checkout_head_reflog_entry, checkout_local_branch,
+    collect_tags, create_local_branch, create_tag_at, find_branches_containing, move_tag_to,
+    set_branch_upstream,
+};
 use yagg_lib::error::AppError;
 
 // Mirror of `delete_branch` (in commands/branches.rs) — exercises the
@@ -433,3 +438,496 @@ fn create_branch_and_checkout_flips_head_to_new_branch() {
     let original_target = original.get().peel_to_commit().unwrap().id();
     assert_eq!(original_target, main_oid);
 }
+
+#[test]
+fn create_branch_defaults_to_head_without_checkout() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let head_oid = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "second");
+    let head_before = repo.head().unwrap().shorthand().ok().map(String::from);
+
+    let info = create_local_branch(&repo, "feature", None, false).unwrap();
+
+    assert_eq!(info.name, "feature");
+    assert_eq!(info.target_hash, head_oid.to_string());
+    assert!(!info.is_head);
+    assert!(!info.is_remote);
+    assert_eq!(
+        repo.head().unwrap().shorthand().ok().map(String::from),
+        head_before
+    );
+}
+
+#[test]
+fn create_branch_at_start_point_hash() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "second");
+
+    let info = create_local_branch(&repo, "old", Some(&first.to_string()), false).unwrap();
+    assert_eq!(info.target_hash, first.to_string());
+}
+
+#[test]
+fn create_branch_at_start_point_ref_name() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_initial_commit(&repo, &temp_dir);
+    let commit = repo.find_commit(first).unwrap();
+    repo.branch("base", &commit, false).unwrap();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "second");
+
+    let info = create_local_branch(&repo, "from-base", Some("base"), false).unwrap();
+    assert_eq!(info.target_hash, first.to_string());
+}
+
+#[test]
+fn create_branch_with_checkout_moves_head() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "second");
+
+    let info = create_local_branch(&repo, "topic", Some(&first.to_string()), true).unwrap();
+
+    assert!(info.is_head);
+    assert_eq!(repo.head().unwrap().shorthand().ok(), Some("topic"));
+    // Working tree follows the checkout: a.txt only exists on the later commit.
+    assert!(!temp_dir.path().join("a.txt").exists());
+}
+
+#[test]
+fn create_branch_rejects_invalid_name() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    for name in ["bad name", "-evil", "a..b"] {
+        let result = create_local_branch(&repo, name, None, false);
+        assert!(
+            matches!(result, Err(AppError::InvalidBranchName(_))),
+            "expected InvalidBranchName for {name}, got {result:?}"
+        );
+    }
+}
+
+#[test]
+fn create_branch_rejects_existing() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_initial_commit(&repo, &temp_dir);
+    let commit = repo.find_commit(oid).unwrap();
+    repo.branch("existing", &commit, false).unwrap();
+
+    let err = create_local_branch(&repo, "existing", None, true).unwrap_err();
+    assert_eq!(err.code(), "git_exists");
+    assert!(err.to_string().contains("already exists"), "got: {err}");
+}
+
+#[test]
+fn create_branch_failed_checkout_removes_branch() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "second");
+    std::fs::write(temp_dir.path().join("a.txt"), "local edit").unwrap();
+
+    let result = create_local_branch(&repo, "topic", Some(&first.to_string()), true);
+
+    assert!(matches!(result, Err(AppError::CheckoutConflict(_))));
+    assert!(repo.find_branch("topic", BranchType::Local).is_err());
+    assert_ne!(repo.head().unwrap().shorthand().ok(), Some("topic"));
+}
+
+#[test]
+fn create_branch_unknown_start_point() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let result = create_local_branch(&repo, "x", Some("no-such-ref"), false);
+    assert!(matches!(result, Err(AppError::Git(_))));
+    assert!(repo.find_branch("x", BranchType::Local).is_err());
+}
+
+#[test]
+fn create_tag_lightweight_at_hash() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_initial_commit(&repo, &temp_dir);
+
+    let info = create_tag_at(&repo, "v0.1.0", &oid.to_string(), None).unwrap();
+
+    assert_eq!(info.name, "v0.1.0");
+    assert_eq!(info.target_hash, oid.to_string());
+    assert!(!info.is_annotated);
+    assert!(info.message.is_none());
+    assert!(info.tagger_name.is_none());
+    assert!(info.tagger_email.is_none());
+    assert!(info.tagged_time.is_none());
+    assert_eq!(info.last_commit_summary.as_deref(), Some("Initial commit"));
+    assert!(repo.find_reference("refs/tags/v0.1.0").is_ok());
+}
+
+#[test]
+fn create_tag_annotated_at_ref_name() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let head_oid = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "second");
+    let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+    let info = create_tag_at(&repo, "v1.0.0", &branch, Some("Release 1.0")).unwrap();
+
+    assert!(info.is_annotated);
+    assert_eq!(info.target_hash, head_oid.to_string());
+    assert_eq!(info.message.as_deref(), Some("Release 1.0"));
+    assert_eq!(info.tagger_name.as_deref(), Some("Test User"));
+    assert_eq!(info.tagger_email.as_deref(), Some("test@example.com"));
+    assert!(info.tagged_time.is_some());
+}
+
+/// Annotated tag on HEAD whose tagger is `name` at `seconds`.
+fn annotated_tag_at(repo: &Repository, tag: &str, name: &str, seconds: i64) {
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    let sig = git2::Signature::new(name, "t@example.com", &git2::Time::new(seconds, 0)).unwrap();
+    repo.tag(tag, head.as_object(), &sig, "release", false)
+        .unwrap();
+}
+
+#[test]
+fn collect_tags_sorts_by_name_by_default() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    annotated_tag_at(&repo, "b", "Bob", 200);
+    annotated_tag_at(&repo, "a", "Alice", 100);
+    create_tag_at(&repo, "c", "HEAD", None).unwrap();
+
+    let names: Vec<String> = collect_tags(&repo, None)
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn collect_tags_sorts_by_tagged_time_descending() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_tag_at(&repo, "light", "HEAD", None).unwrap();
+    annotated_tag_at(&repo, "v1", "Alice", 100);
+    annotated_tag_at(&repo, "v3", "Carol", 300);
+    annotated_tag_at(&repo, "v2", "Bob", 200);
+
+    let tags = collect_tags(&repo, Some("tagged_time")).unwrap();
+
+    let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, vec!["v3", "v2", "v1", "light"]);
+    assert_eq!(tags[0].tagger_name.as_deref(), Some("Carol"));
+    assert_eq!(tags[0].tagger_email.as_deref(), Some("t@example.com"));
+    assert_eq!(tags[0].tagged_time, Some(300));
+}
+
+#[test]
+fn collect_tags_rejects_unknown_sort() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(matches!(
+        collect_tags(&repo, Some("size")),
+        Err(AppError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn create_tag_rejects_duplicate() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_initial_commit(&repo, &temp_dir);
+    create_tag_at(&repo, "v1", &oid.to_string(), None).unwrap();
+
+    assert!(create_tag_at(&repo, "v1", &oid.to_string(), Some("again")).is_err());
+}
+
+#[test]
+fn create_tag_unknown_target() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let result = create_tag_at(&repo, "v1", "no-such-ref", None);
+    assert!(matches!(result, Err(AppError::Git(_))));
+}
+
+#[test]
+fn move_tag_lightweight_forward() {
+    let (temp_dir, repo) = create_test_repo();
+    let old = create_initial_commit(&repo, &temp_dir);
+    create_tag_at(&repo, "v1.0-rc", &old.to_string(), None).unwrap();
+    let new = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "fix");
+
+    let info = move_tag_to(&repo, "v1.0-rc", "HEAD", None, true).unwrap();
+
+    assert_eq!(info.target_hash, new.to_string());
+    assert!(!info.is_annotated);
+    let target = repo.find_reference("refs/tags/v1.0-rc").unwrap().target();
+    assert_eq!(target, Some(new));
+}
+
+#[test]
+fn move_tag_annotated_keeps_or_replaces_message() {
+    let (temp_dir, repo) = create_test_repo();
+    let old = create_initial_commit(&repo, &temp_dir);
+    create_tag_at(&repo, "v2.0-rc", &old.to_string(), Some("RC for 2.0")).unwrap();
+    let new = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "fix");
+
+    let info = move_tag_to(&repo, "v2.0-rc", &new.to_string(), None, true).unwrap();
+    assert!(info.is_annotated);
+    assert_eq!(info.target_hash, new.to_string());
+    assert_eq!(info.message.as_deref(), Some("RC for 2.0"));
+
+    let info = move_tag_to(&repo, "v2.0-rc", &old.to_string(), Some("Back out"), true).unwrap();
+    assert_eq!(info.target_hash, old.to_string());
+    assert_eq!(info.message.as_deref(), Some("Back out"));
+}
+
+#[test]
+fn move_tag_requires_force() {
+    let (temp_dir, repo) = create_test_repo();
+    let old = create_initial_commit(&repo, &temp_dir);
+    create_tag_at(&repo, "v1", &old.to_string(), None).unwrap();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "fix");
+
+    let result = move_tag_to(&repo, "v1", "HEAD", None, false);
+
+    assert!(matches!(&result, Err(e) if e.code() == "git_exists"));
+    let target = repo.find_reference("refs/tags/v1").unwrap().target();
+    assert_eq!(target, Some(old));
+}
+
+#[test]
+fn move_tag_unknown_tag() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let result = move_tag_to(&repo, "nope", "HEAD", None, true);
+    assert!(matches!(&result, Err(e) if e.code() == "git_not_found"));
+}
+
+/// Add an `origin` remote with a remote-tracking branch `origin/<name>` at HEAD.
+fn add_remote_tracking(repo: &Repository, name: &str) {
+    repo.remote("origin", "https://example.com/repo.git")
+        .unwrap();
+    let head = repo.head().unwrap().target().unwrap();
+    repo.reference(&format!("refs/remotes/origin/{name}"), head, true, "test")
+        .unwrap();
+}
+
+#[test]
+fn set_branch_upstream_sets_and_clears() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_local_branch(&repo, "topic", None, false).unwrap();
+    add_remote_tracking(&repo, "topic");
+
+    set_branch_upstream(&repo, "topic", Some("origin/topic")).unwrap();
+    let branch = repo.find_branch("topic", BranchType::Local).unwrap();
+    let upstream = branch.upstream().unwrap();
+    assert_eq!(upstream.name().unwrap(), Some("origin/topic"));
+
+    set_branch_upstream(&repo, "topic", None).unwrap();
+    let branch = repo.find_branch("topic", BranchType::Local).unwrap();
+    assert!(branch.upstream().is_err());
+}
+
+#[test]
+fn set_branch_upstream_rejects_missing_remote_branch() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_local_branch(&repo, "topic", None, false).unwrap();
+    add_remote_tracking(&repo, "other");
+
+    let result = set_branch_upstream(&repo, "topic", Some("origin/topic"));
+    assert!(matches!(result, Err(AppError::InvalidUpstream(u)) if u == "origin/topic"));
+    let branch = repo.find_branch("topic", BranchType::Local).unwrap();
+    assert!(branch.upstream().is_err());
+}
+
+#[test]
+fn set_branch_upstream_unknown_local_branch() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    add_remote_tracking(&repo, "topic");
+
+    let result = set_branch_upstream(&repo, "topic", Some("origin/topic"));
+    assert!(matches!(result, Err(AppError::Git(_))));
+}
+
+#[test]
+fn checkout_reflog_entry_recovers_reset_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_initial_commit(&repo, &temp_dir);
+    let lost = create_commit_with_file(&repo, &temp_dir, "lost.txt", "precious", "lost work");
+    let first_obj = repo.find_object(first, None).unwrap();
+    repo.reset(&first_obj, git2::ResetType::Hard, None).unwrap();
+    assert!(!temp_dir.path().join("lost.txt").exists());
+
+    // Entry 0 is the reset itself; entry 1 is the commit that was undone.
+    let hash = checkout_head_reflog_entry(&repo, 1).unwrap();
+
+    assert_eq!(hash, lost.to_string());
+    assert!(repo.head_detached().unwrap());
+    assert_eq!(repo.head().unwrap().target(), Some(lost));
+    assert!(temp_dir.path().join("lost.txt").exists());
+}
+
+#[test]
+fn checkout_reflog_entry_out_of_range() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let result = checkout_head_reflog_entry(&repo, 99);
+    assert!(matches!(result, Err(AppError::InvalidArgument(m)) if m.contains("out of range")));
+    assert!(!repo.head_detached().unwrap());
+}
+
+#[test]
+fn branches_containing_only_descendant_branches() {
+    let (temp_dir, repo) = create_test_repo();
+    let base = create_initial_commit(&repo, &temp_dir);
+    let base_commit = repo.find_commit(base).unwrap();
+    repo.branch("old", &base_commit, false).unwrap();
+    let feature_commit = create_commit_with_file(&repo, &temp_dir, "f.txt", "f", "feature");
+    let head = repo.head().unwrap().shorthand().unwrap().to_string();
+    let tip = repo.find_commit(feature_commit).unwrap();
+    repo.branch("at-tip", &tip, false).unwrap();
+    add_remote_tracking(&repo, "topic");
+
+    let mut containing = find_branches_containing(&repo, &feature_commit.to_string()).unwrap();
+    containing.sort();
+    let mut expected = vec![
+        head.clone(),
+        "at-tip".to_string(),
+        "origin/topic".to_string(),
+    ];
+    expected.sort();
+    assert_eq!(containing, expected);
+
+    let mut containing_base = find_branches_containing(&repo, &base.to_string()).unwrap();
+    containing_base.sort();
+    assert!(containing_base.contains(&"old".to_string()));
+    assert!(containing_base.contains(&head));
+}
+
+#[test]
+fn branches_containing_detached_commit_in_no_branch() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.set_head_detached(head.id()).unwrap();
+    let orphan = create_commit_with_file(&repo, &temp_dir, "d.txt", "d", "detached work");
+
+    assert!(find_branches_containing(&repo, &orphan.to_string())
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn ahead_behind_diverged_branches() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let fork = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &fork, false).unwrap();
+
+    create_commit_with_file(&repo, &temp_dir, "m.txt", "m", "main work");
+
+    repo.set_head("refs/heads/feature").unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+    for i in 0..3 {
+        let name = format!("f{i}.txt");
+        create_commit_with_file(&repo, &temp_dir, &name, "f", "feature work");
+    }
+
+    assert_eq!(ahead_behind(&repo, &main, "feature").unwrap(), (3, 1));
+    assert_eq!(ahead_behind(&repo, "feature", &main).unwrap(), (1, 3));
+    assert_eq!(ahead_behind(&repo, "feature", "HEAD").unwrap(), (0, 0));
+}
+
+#[test]
+fn ahead_behind_unknown_revision() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    assert!(ahead_behind(&repo, "HEAD", "no-such-branch").is_err());
+}
+
+#[test]
+fn checkout_refuses_to_overwrite_local_changes() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "base\n", "base");
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let base = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("other", &base, false).unwrap();
+    let changed = create_commit_with_file(&repo, &temp_dir, "a.txt", "main\n", "main");
+
+    checkout_local_branch(&repo, "other", false).unwrap();
+    let path = temp_dir.path().join("a.txt");
+    std::fs::write(&path, "local edit\n").unwrap();
+
+    match checkout_local_branch(&repo, &main, false) {
+        Err(AppError::CheckoutConflict(paths)) => assert_eq!(paths, vec!["a.txt".to_string()]),
+        other => panic!("expected CheckoutConflict, got {other:?}"),
+    }
+    assert!(matches!(
+        checkout_detached(&repo, changed, false),
+        Err(AppError::CheckoutConflict(_))
+    ));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "local edit\n");
+    assert_eq!(repo.head().unwrap().shorthand().unwrap(), "other");
+
+    checkout_local_branch(&repo, &main, true).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "main\n");
+    assert_eq!(repo.head().unwrap().shorthand().unwrap(), main);
+}
+
+#[test]
+fn checkout_keeps_unrelated_local_changes() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a\n", "base");
+    let base = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("other", &base, false).unwrap();
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "b\n", "add b");
+
+    std::fs::write(temp_dir.path().join("a.txt"), "edited\n").unwrap();
+    checkout_local_branch(&repo, "other", false).unwrap();
+
+    let a = std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap();
+    assert_eq!(a, "edited\n");
+    assert!(!temp_dir.path().join("b.txt").exists());
+}
+
+fn top_reflog_message(repo: &Repository) -> String {
+    let reflog = repo.reflog("HEAD").unwrap();
+    let entry = reflog.get(0).unwrap();
+    entry.message().unwrap().unwrap_or_default().to_string()
+}
+
+#[test]
+fn checkout_writes_cli_style_reflog_message() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let tip = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &tip, false).unwrap();
+
+    checkout_local_branch(&repo, "feature", false).unwrap();
+    assert_eq!(
+        top_reflog_message(&repo),
+        format!("checkout: moving from {main} to feature")
+    );
+
+    checkout_detached(&repo, tip.id(), false).unwrap();
+    assert_eq!(
+        top_reflog_message(&repo),
+        format!("checkout: moving from feature to {}", tip.id())
+    );
+    assert!(repo.head_detached().unwrap());
+
+    checkout_local_branch(&repo, &main, false).unwrap();
+    assert_eq!(
+        top_reflog_message(&repo),
+        format!("checkout: moving from {} to {main}", tip.id())
+    );
+}
@@ -7,18 +7,19 @@ mod common;
 use common::{create_commit_with_file, create_initial_commit, create_test_repo};
 use std::fs;
 use std::path::Path;
+use yagg_lib::error::AppError;
 use yagg_lib::git::{
     self, get_commit_details, get_commit_diff_hunk, get_commit_file_diff, get_commits,
 };
 
 // =============================================================================
-// get_commits (5 tests)
+// get_commits (14 tests)
 // =============================================================================
 
 #[test]
 fn get_commits_empty_repo() {
     let (_temp_dir, repo) = create_test_repo();
-    let commits = get_commits(&repo, 0, 10).unwrap();
+    let commits = get_commits(&repo, 0, 10, None, None).unwrap();
     assert!(commits.is_empty());
 }
 
@@ -27,7 +28,7 @@ fn get_commits_single_commit() {
     let (temp_dir, repo) = create_test_repo();
     let oid = create_commit_with_file(&repo, &temp_dir, "file.txt", "content", "First commit");
 
-    let commits = get_commits(&repo, 0, 10).unwrap();
+    let commits = get_commits(&repo, 0, 10, None, None).unwrap();
     assert_eq!(commits.len(), 1);
     assert_eq!(commits[0].hash, oid.to_string());
     assert_eq!(commits[0].message, "First commit");
@@ -42,7 +43,7 @@ fn get_commits_multiple_commits() {
     create_commit_with_file(&repo, &temp_dir, "file2.txt", "content2", "Second commit");
     create_commit_with_file(&repo, &temp_dir, "file3.txt", "content3", "Third commit");
 
-    let commits = get_commits(&repo, 0, 10).unwrap();
+    let commits = get_commits(&repo, 0, 10, None, None).unwrap();
     assert_eq!(commits.len(), 3);
     assert_eq!(commits[0].message, "Third commit");
     assert_eq!(commits[1].message, "Second commit");
@@ -56,7 +57,7 @@ fn get_commits_with_skip() {
     create_commit_with_file(&repo, &temp_dir, "file2.txt", "content2", "Second commit");
     create_commit_with_file(&repo, &temp_dir, "file3.txt", "content3", "Third commit");
 
-    let commits = get_commits(&repo, 1, 10).unwrap();
+    let commits = get_commits(&repo, 1, 10, None, None).unwrap();
     assert_eq!(commits.len(), 2);
     assert_eq!(commits[0].message, "Second commit");
     assert_eq!(commits[1].message, "First commit");
@@ -69,7 +70,7 @@ fn get_commits_with_limit() {
     create_commit_with_file(&repo, &temp_dir, "file2.txt", "content2", "Second commit");
     create_commit_with_file(&repo, &temp_dir, "file3.txt", "content3", "Third commit");
 
-    let commits = get_commits(&repo, 0, 2).unwrap();
+    let commits = get_commits(&repo, 0, 2, None, None).unwrap();
     assert_eq!(commits.len(), 2);
     assert_eq!(commits[0].message, "Third commit");
     assert_eq!(commits[1].message, "Second commit");
@@ -80,7 +81,7 @@ fn get_commits_short_hash() {
     let (temp_dir, repo) = create_test_repo();
     let oid = create_commit_with_file(&repo, &temp_dir, "file.txt", "content", "Commit");
 
-    let commits = get_commits(&repo, 0, 10).unwrap();
+    let commits = get_commits(&repo, 0, 10, None, None).unwrap();
     let full_hash = oid.to_string();
     assert_eq!(commits[0].short_hash, &full_hash[..7]);
 }
@@ -92,13 +93,371 @@ fn get_commits_parent_hashes() {
         create_commit_with_file(&repo, &temp_dir, "file1.txt", "content1", "First commit");
     create_commit_with_file(&repo, &temp_dir, "file2.txt", "content2", "Second commit");
 
-    let commits = get_commits(&repo, 0, 10).unwrap();
+    let commits = get_commits(&repo, 0, 10, None, None).unwrap();
     assert_eq!(commits[0].parent_hashes, vec![first_oid.to_string()]);
     assert!(commits[1].parent_hashes.is_empty());
 }
 
+/// `main` gets "Main commit" and `side` gets "Side commit", both on top of
+/// the initial commit. HEAD is left on `main`.
+fn diverged_branches() -> (tempfile::TempDir, git2::Repository) {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    {
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("side", &head, false).unwrap();
+    }
+
+    repo.set_head("refs/heads/side").unwrap();
+    create_commit_with_file(&repo, &temp_dir, "side.txt", "side", "Side commit");
+    repo.set_head(&format!("refs/heads/{main}")).unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+    create_commit_with_file(&repo, &temp_dir, "main.txt", "main", "Main commit");
+    (temp_dir, repo)
+}
+
+fn messages(commits: &[git::CommitInfo]) -> Vec<&str> {
+    commits.iter().map(|c| c.message.trim()).collect()
+}
+
+#[test]
+fn get_commits_without_refs_walks_all_branches() {
+    let (_temp_dir, repo) = diverged_branches();
+
+    let commits = get_commits(&repo, 0, 10, None, None).unwrap();
+    let messages = messages(&commits);
+    assert_eq!(commits.len(), 3);
+    assert!(messages.contains(&"Main commit"));
+    assert!(messages.contains(&"Side commit"));
+}
+
+#[test]
+fn get_commits_with_single_ref_excludes_other_branches() {
+    let (_temp_dir, repo) = diverged_branches();
+
+    let commits = get_commits(&repo, 0, 10, Some(&["side".to_string()]), None).unwrap();
+    assert_eq!(messages(&commits), vec!["Side commit", "Initial commit"]);
+
+    let graph =
+        git::get_commit_graph_page(&repo, 0, 10, Some(&["side".to_string()]), usize::MAX).unwrap();
+    assert_eq!(graph.len(), 2);
+    assert!(graph
+        .iter()
+        .all(|gc| gc.commit.message.trim() != "Main commit"));
+}
+
+#[test]
+fn get_commits_with_unknown_ref_errors() {
+    let (_temp_dir, repo) = diverged_branches();
+
+    let result = get_commits(&repo, 0, 10, Some(&["no-such-branch".to_string()]), None);
+    assert!(result.is_err());
+}
+
+/// Commit a file on HEAD with `name <email>` as both author and committer.
+fn commit_as(
+    repo: &git2::Repository,
+    temp_dir: &tempfile::TempDir,
+    name: &str,
+    email: &str,
+    message: &str,
+) {
+    fs::write(temp_dir.path().join("log.txt"), message).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("log.txt")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now(name, email).unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .unwrap();
+}
+
+fn alice_and_bob_history() -> (tempfile::TempDir, git2::Repository) {
+    let (temp_dir, repo) = create_test_repo();
+    commit_as(&repo, &temp_dir, "Alice", "alice@example.com", "a1");
+    commit_as(&repo, &temp_dir, "Bob", "bob@example.com", "b1");
+    commit_as(&repo, &temp_dir, "Alice", "alice@example.com", "a2");
+    commit_as(&repo, &temp_dir, "Bob", "bob@example.com", "b2");
+    commit_as(&repo, &temp_dir, "Alice", "alice@example.com", "a3");
+    (temp_dir, repo)
+}
+
+#[test]
+fn get_commits_author_filter_matches_name_case_insensitively() {
+    let (_temp_dir, repo) = alice_and_bob_history();
+
+    let commits = get_commits(&repo, 0, 10, None, Some("aLiCe")).unwrap();
+    assert_eq!(messages(&commits), vec!["a3", "a2", "a1"]);
+}
+
+#[test]
+fn get_commits_author_filter_matches_email() {
+    let (_temp_dir, repo) = alice_and_bob_history();
+
+    let commits = get_commits(&repo, 0, 10, None, Some("bob@")).unwrap();
+    assert_eq!(messages(&commits), vec!["b2", "b1"]);
+}
+
+#[test]
+fn get_commits_author_filter_paginates_over_matches() {
+    let (_temp_dir, repo) = alice_and_bob_history();
+
+    let first = get_commits(&repo, 0, 2, None, Some("alice")).unwrap();
+    let second = get_commits(&repo, 2, 2, None, Some("alice")).unwrap();
+    assert_eq!(messages(&first), vec!["a3", "a2"]);
+    assert_eq!(messages(&second), vec!["a1"]);
+    assert!(get_commits(&repo, 0, 10, None, Some("carol"))
+        .unwrap()
+        .is_empty());
+}
+
+/// A root commit authored at 1_000_000 (UTC+2) and committed at 2_000_000
+/// (UTC-5), as after a rebase.
+fn commit_with_divergent_times(repo: &git2::Repository) -> git2::Oid {
+    let author = git2::Signature::new(
+        "Author",
+        "author@example.com",
+        &git2::Time::new(1_000_000, 120),
+    )
+    .unwrap();
+    let committer = git2::Signature::new(
+        "Committer",
+        "committer@example.com",
+        &git2::Time::new(2_000_000, -300),
+    )
+    .unwrap();
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    repo.commit(Some("HEAD"), &author, &committer, "rebased", &tree, &[])
+        .unwrap()
+}
+
+#[test]
+fn get_commits_separate_author_and_commit_times() {
+    let (_temp_dir, repo) = create_test_repo();
+    commit_with_divergent_times(&repo);
+
+    let commits = get_commits(&repo, 0, 10, None, None).unwrap();
+
+    let info = &commits[0];
+    assert_eq!(info.author_time, 1_000_000);
+    assert_eq!(info.author_offset_minutes, 120);
+    assert_eq!(info.commit_time, 2_000_000);
+    assert_eq!(info.commit_offset_minutes, -300);
+    assert_eq!(info.timestamp, info.commit_time);
+}
+
+// =============================================================================
+// search_commits (3 tests)
+// =============================================================================
+
+#[test]
+fn search_commits_matches_message_body() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Add parser\n\nFixes #12");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "Tweak docs");
+    create_commit_with_file(
+        &repo,
+        &temp_dir,
+        "c.txt",
+        "c",
+        "Refactor lexer\n\nfixes #40",
+    );
+
+    let commits = git::search_commits(&repo, "FIXES #", 0, 10).unwrap();
+    // Results carry the summary line even though the match was in the body.
+    assert_eq!(messages(&commits), vec!["Refactor lexer", "Add parser"]);
+}
+
+#[test]
+fn search_commits_paginates_over_matches() {
+    let (temp_dir, repo) = create_test_repo();
+    for i in 0..6 {
+        let message = if i % 2 == 0 {
+            format!("wip {i}")
+        } else {
+            format!("release {i}")
+        };
+        create_commit_with_file(&repo, &temp_dir, "f.txt", &i.to_string(), &message);
+    }
+
+    let first = git::search_commits(&repo, "wip", 0, 2).unwrap();
+    let second = git::search_commits(&repo, "wip", 2, 2).unwrap();
+    assert_eq!(messages(&first), vec!["wip 4", "wip 2"]);
+    assert_eq!(messages(&second), vec!["wip 0"]);
+}
+
+#[test]
+fn search_commits_no_match_is_empty() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(git::search_commits(&repo, "nothing here", 0, 10)
+        .unwrap()
+        .is_empty());
+}
+
+// =============================================================================
+// find_commit_in_graph (3 tests)
+// =============================================================================
+
+#[test]
+fn find_commit_in_graph_unique_prefix() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "first");
+    let middle = create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "second");
+    create_commit_with_file(&repo, &temp_dir, "c.txt", "c", "third");
+
+    let hash = middle.to_string();
+    let location = git::find_commit_in_graph(&repo, &hash[..7]).unwrap();
+
+    assert_eq!(location.commit.hash, hash);
+    assert_eq!(location.commit.message, "second");
+    assert_eq!(location.index, Some(1));
+}
+
+#[test]
+fn find_commit_in_graph_ambiguous_prefix_lists_candidates() {
+    let (_temp_dir, repo) = create_test_repo();
+    let sig = git2::Signature::new("Test", "test@example.com", &git2::Time::new(0, 0)).unwrap();
+    let tree = repo
+        .find_tree(repo.treebuilder(None).unwrap().write().unwrap())
+        .unwrap();
+
+    // Keep committing until two commits share a 4-char prefix (the shortest
+    // libgit2 accepts); the birthday bound makes that a few hundred commits.
+    let mut seen: std::collections::HashMap<String, git2::Oid> = Default::default();
+    let mut parent: Option<git2::Commit> = None;
+    let (first, second) = loop {
+        assert!(seen.len() < 10_000, "no prefix collision found");
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        let message = format!("commit {}", seen.len());
+        let oid = repo
+            .commit(Some("HEAD"), &sig, &sig, &message, &tree, &parents)
+            .unwrap();
+        let short = oid.to_string()[..4].to_string();
+        if let Some(&other) = seen.get(&short) {
+            break (other, oid);
+        }
+        seen.insert(short, oid);
+        parent = Some(repo.find_commit(oid).unwrap());
+    };
+
+    let result = git::find_commit_in_graph(&repo, &first.to_string()[..4]);
+
+    match result {
+        Err(AppError::AmbiguousCommit(candidates)) => {
+            assert!(candidates.contains(&first.to_string()));
+            assert!(candidates.contains(&second.to_string()));
+        }
+        other => panic!("expected AmbiguousCommit, got {other:?}"),
+    }
+    // A longer prefix still resolves
+    let location = git::find_commit_in_graph(&repo, &second.to_string()[..12]).unwrap();
+    assert_eq!(location.commit.hash, second.to_string());
+    assert_eq!(location.index, Some(0));
+}
+
+#[test]
+fn find_commit_in_graph_unknown_prefix_errors() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_initial_commit(&repo, &temp_dir);
+
+    // Flip the first hex digit so the prefix can't match this commit
+    let hash = oid.to_string();
+    let flipped = if hash.starts_with('0') { "1" } else { "0" };
+    let prefix = format!("{flipped}{}", &hash[1..8]);
+
+    assert!(matches!(
+        git::find_commit_in_graph(&repo, &prefix),
+        Err(AppError::Git(_))
+    ));
+}
+
+// =============================================================================
+// resolve_revision (5 tests)
+// =============================================================================
+
+#[test]
+fn resolve_revision_head_and_ancestor() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "first");
+    let second = create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "second");
+
+    assert_eq!(
+        git::resolve_revision(&repo, "HEAD").unwrap(),
+        second.to_string()
+    );
+    assert_eq!(
+        git::resolve_revision(&repo, "HEAD~1").unwrap(),
+        first.to_string()
+    );
+    assert_eq!(
+        git::resolve_revision(&repo, &second.to_string()[..7]).unwrap(),
+        second.to_string()
+    );
+}
+
+#[test]
+fn resolve_revision_annotated_tag_peels_to_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_initial_commit(&repo, &temp_dir);
+    let sig = repo.signature().unwrap();
+    let target = repo.find_object(oid, None).unwrap();
+    repo.tag("v1.0", &target, &sig, "release", false).unwrap();
+
+    assert_eq!(
+        git::resolve_revision(&repo, "v1.0").unwrap(),
+        oid.to_string()
+    );
+}
+
+#[test]
+fn resolve_revision_invalid_spec_errors() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(matches!(
+        git::resolve_revision(&repo, "no-such-branch"),
+        Err(AppError::Git(_))
+    ));
+    assert!(matches!(
+        git::resolve_revision(&repo, "HEAD~5"),
+        Err(AppError::Git(_))
+    ));
+}
+
+#[test]
+fn get_commit_details_accepts_revspec() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "first");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "second");
+
+    let details = get_commit_details(&repo, "HEAD^").unwrap();
+    assert_eq!(details.hash, first.to_string());
+}
+
+#[test]
+fn commit_diff_and_tree_accept_revspec() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "one\n", "first");
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "two\n", "second");
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+
+    let diff = get_commit_file_diff(&repo, &format!("{main}~0"), "a.txt").unwrap();
+    assert_eq!(diff.hunks.len(), 1);
+    let file = git::get_file_at_commit(&repo, "HEAD~1", "a.txt").unwrap();
+    assert_eq!(file.content, "one\n");
+    let tree = git::list_tree(&repo, &main, None).unwrap();
+    assert_eq!(tree.len(), 1);
+}
+
 // =============================================================================
-// get_commit_details (4 tests)
+// get_commit_details (10 tests)
 // =============================================================================
 
 #[test]
@@ -172,6 +531,133 @@ fn get_commit_details_files_changed() {
     assert!(details.files_changed.iter().any(|f| f.path == "file2.txt"));
 }
 
+#[test]
+fn get_commit_details_detects_rename() {
+    let (temp_dir, repo) = create_test_repo();
+    let content = "alpha\nbeta\ngamma\ndelta\n";
+    create_commit_with_file(&repo, &temp_dir, "old.txt", content, "Create");
+
+    fs::rename(
+        temp_dir.path().join("old.txt"),
+        temp_dir.path().join("new.txt"),
+    )
+    .unwrap();
+    let mut index = repo.index().unwrap();
+    index.remove_path(Path::new("old.txt")).unwrap();
+    index.add_path(Path::new("new.txt")).unwrap();
+    index.write().unwrap();
+    let sig = repo.signature().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "Rename", &tree, &[&parent])
+        .unwrap();
+
+    let details = get_commit_details(&repo, &oid.to_string()).unwrap();
+
+    assert_eq!(details.files_changed.len(), 1);
+    let change = &details.files_changed[0];
+    assert_eq!(change.status, "renamed");
+    assert_eq!(change.path, "new.txt");
+    assert_eq!(change.old_path.as_deref(), Some("old.txt"));
+}
+
+#[test]
+fn get_commit_details_line_stats() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "one\ntwo\nthree\n", "Create a");
+    fs::write(temp_dir.path().join("b.txt"), "x\ny\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("b.txt")).unwrap();
+    index.write().unwrap();
+    let oid = create_commit_with_file(&repo, &temp_dir, "a.txt", "one\nTWO\n", "Edit a, add b");
+
+    let details = get_commit_details(&repo, &oid.to_string()).unwrap();
+
+    let a = details
+        .files_changed
+        .iter()
+        .find(|f| f.path == "a.txt")
+        .unwrap();
+    assert_eq!((a.additions, a.deletions), (1, 2));
+    let b = details
+        .files_changed
+        .iter()
+        .find(|f| f.path == "b.txt")
+        .unwrap();
+    assert_eq!((b.additions, b.deletions), (2, 0));
+    assert_eq!(details.stats.files_changed, 2);
+    assert_eq!(details.stats.insertions, 3);
+    assert_eq!(details.stats.deletions, 2);
+}
+
+#[test]
+fn get_commit_details_root_commit_counts_all_lines_as_additions() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_commit_with_file(&repo, &temp_dir, "f.txt", "1\n2\n3\n4\n", "Root");
+
+    let details = get_commit_details(&repo, &oid.to_string()).unwrap();
+
+    assert_eq!(details.files_changed[0].additions, 4);
+    assert_eq!(details.files_changed[0].deletions, 0);
+    assert_eq!(details.stats.insertions, 4);
+    assert_eq!(details.stats.deletions, 0);
+}
+
+#[test]
+fn get_commit_details_binary_file_has_zero_line_counts() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "img.bin", "\0\x01\n\x02\n", "Add binary");
+    let oid = create_commit_with_file(
+        &repo,
+        &temp_dir,
+        "img.bin",
+        "\0\x03\n\x04\n\x05\n",
+        "Edit binary",
+    );
+
+    let details = get_commit_details(&repo, &oid.to_string()).unwrap();
+
+    let change = &details.files_changed[0];
+    assert_eq!((change.additions, change.deletions), (0, 0));
+    assert_eq!(details.stats.files_changed, 1);
+    assert_eq!(details.stats.insertions, 0);
+    assert_eq!(details.stats.deletions, 0);
+}
+
+#[test]
+fn get_commit_details_merge_parent_subjects() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Main work\n\nBody");
+    let side = create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "Side work");
+
+    let sig = repo.signature().unwrap();
+    let tree = repo.find_commit(side).unwrap().tree().unwrap();
+    let first = repo.find_commit(first).unwrap();
+    let side = repo.find_commit(side).unwrap();
+    let merge = repo
+        .commit(None, &sig, &sig, "Merge side", &tree, &[&first, &side])
+        .unwrap();
+
+    let details = get_commit_details(&repo, &merge.to_string()).unwrap();
+    assert_eq!(details.parent_subjects, vec!["Main work", "Side work"]);
+    assert_eq!(details.parent_hashes.len(), 2);
+}
+
+#[test]
+fn get_commit_details_separate_author_and_commit_times() {
+    let (_temp_dir, repo) = create_test_repo();
+    let oid = commit_with_divergent_times(&repo);
+
+    let details = get_commit_details(&repo, &oid.to_string()).unwrap();
+
+    assert_eq!(details.author_time, 1_000_000);
+    assert_eq!(details.author_offset_minutes, 120);
+    assert_eq!(details.commit_time, 2_000_000);
+    assert_eq!(details.commit_offset_minutes, -300);
+    assert_eq!(details.timestamp, details.commit_time);
+}
+
 // (commit_to_info is a private helper — its inline test stays in
 // src/git/commit.rs::tests because integration tests can't see private items.)
 
@@ -184,9 +670,9 @@ fn commands_commits_graph_logic() {
     let (temp_dir, repo) = create_test_repo();
     create_initial_commit(&repo, &temp_dir);
 
-    let commits = git::get_commits(&repo, 0, 10).unwrap();
+    let commits = git::get_commits(&repo, 0, 10, None, None).unwrap();
     let refs = git::collect_refs(&repo).unwrap();
-    let graph = git::build_commit_graph(commits, refs);
+    let graph = git::build_commit_graph(commits, refs, usize::MAX);
 
     assert!(!graph.is_empty());
 }
@@ -249,9 +735,9 @@ fn commands_commits_get_all_commit_graph_returns_all() {
             .unwrap();
     }
 
-    let commits = git::get_all_commits(&repo).unwrap();
+    let commits = git::get_all_commits(&repo, None).unwrap();
     let refs = git::collect_refs(&repo).unwrap();
-    let graph = git::build_commit_graph(commits, refs);
+    let graph = git::build_commit_graph(commits, refs, usize::MAX);
 
     assert_eq!(
         graph.len(),
@@ -273,9 +759,9 @@ fn commands_commits_graph_paginates() {
         );
     }
 
-    let commits = git::get_commits(&repo, 0, 3).unwrap();
+    let commits = git::get_commits(&repo, 0, 3, None, None).unwrap();
     let refs = git::collect_refs(&repo).unwrap();
-    let graph = git::build_commit_graph(commits, refs);
+    let graph = git::build_commit_graph(commits, refs, usize::MAX);
 
     assert_eq!(graph.len(), 3, "limit=3 should return 3 entries");
 }
@@ -285,7 +771,7 @@ fn commands_commits_graph_skip_past_end_returns_empty() {
     let (temp_dir, repo) = create_test_repo();
     create_initial_commit(&repo, &temp_dir);
 
-    let commits = git::get_commits(&repo, 100, 10).unwrap();
+    let commits = git::get_commits(&repo, 100, 10, None, None).unwrap();
     assert!(
         commits.is_empty(),
         "skip past end should yield empty result"
@@ -393,3 +879,599 @@ fn commands_commit_create_commit_with_parent() {
     let commit = repo.find_commit(commit_oid).unwrap();
     assert_eq!(commit.parent_count(), 1);
 }
+
+// =============================================================================
+// create_commit (6 tests)
+// =============================================================================
+
+fn stage_new_file(repo: &git2::Repository, temp_dir: &tempfile::TempDir, name: &str) {
+    fs::write(temp_dir.path().join(name), name).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(name)).unwrap();
+    index.write().unwrap();
+}
+
+#[test]
+fn create_commit_defaults_author_to_config() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    stage_new_file(&repo, &temp_dir, "a.txt");
+
+    let hash = git::create_commit(
+        &repo,
+        "Add a",
+        &git::AuthorOverride::default(),
+        None,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&hash).unwrap())
+        .unwrap();
+    assert_eq!(commit.author().name().unwrap(), "Test User");
+    assert_eq!(commit.author().email().unwrap(), "test@example.com");
+    assert_eq!(commit.parent_count(), 1);
+}
+
+#[test]
+fn create_commit_with_custom_author() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    stage_new_file(&repo, &temp_dir, "a.txt");
+
+    let author = git::AuthorOverride {
+        name: Some("Pair Partner".into()),
+        email: Some("pair@example.com".into()),
+        time: Some(1_600_000_000),
+    };
+    let hash = git::create_commit(&repo, "Pairing", &author, None, false, false).unwrap();
+
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&hash).unwrap())
+        .unwrap();
+    assert_eq!(commit.author().name().unwrap(), "Pair Partner");
+    assert_eq!(commit.author().email().unwrap(), "pair@example.com");
+    assert_eq!(commit.author().when().seconds(), 1_600_000_000);
+    assert_eq!(commit.committer().name().unwrap(), "Test User");
+    assert_eq!(commit.committer().email().unwrap(), "test@example.com");
+    assert_ne!(commit.committer().when().seconds(), 1_600_000_000);
+}
+
+#[test]
+fn create_commit_partial_override_keeps_config_email() {
+    let (temp_dir, repo) = create_test_repo();
+    stage_new_file(&repo, &temp_dir, "a.txt");
+
+    let author = git::AuthorOverride {
+        name: Some("Alias".into()),
+        ..Default::default()
+    };
+    let hash = git::create_commit(&repo, "Root", &author, None, false, false).unwrap();
+
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&hash).unwrap())
+        .unwrap();
+    assert_eq!(commit.author().name().unwrap(), "Alias");
+    assert_eq!(commit.author().email().unwrap(), "test@example.com");
+    assert_eq!(commit.parent_count(), 0);
+}
+
+#[test]
+fn create_commit_rejects_invalid_email() {
+    let (temp_dir, repo) = create_test_repo();
+    let head = create_initial_commit(&repo, &temp_dir);
+    stage_new_file(&repo, &temp_dir, "a.txt");
+
+    let author = git::AuthorOverride {
+        email: Some("not-an-email".into()),
+        ..Default::default()
+    };
+    let result = git::create_commit(&repo, "Nope", &author, None, false, false);
+
+    assert!(matches!(result, Err(AppError::InvalidEmail(e)) if e == "not-an-email"));
+    assert_eq!(repo.head().unwrap().target(), Some(head));
+}
+
+#[test]
+fn create_commit_nothing_staged_is_error() {
+    let (temp_dir, repo) = create_test_repo();
+    let head = create_initial_commit(&repo, &temp_dir);
+    // Unstaged changes don't count.
+    fs::write(temp_dir.path().join("initial.txt"), "edited").unwrap();
+
+    let result = git::create_commit(
+        &repo,
+        "Empty",
+        &git::AuthorOverride::default(),
+        None,
+        false,
+        false,
+    );
+    assert!(matches!(result, Err(AppError::NothingToCommit)));
+    assert_eq!(repo.head().unwrap().target(), Some(head));
+
+    // Same for the first commit of an empty repository.
+    let (_empty_dir, empty) = create_test_repo();
+    let result = git::create_commit(
+        &empty,
+        "Root",
+        &git::AuthorOverride::default(),
+        None,
+        false,
+        false,
+    );
+    assert!(matches!(result, Err(AppError::NothingToCommit)));
+}
+
+#[test]
+fn create_commit_allow_empty() {
+    let (temp_dir, repo) = create_test_repo();
+    let head = create_initial_commit(&repo, &temp_dir);
+
+    let hash = git::create_commit(
+        &repo,
+        "Trigger CI",
+        &git::AuthorOverride::default(),
+        None,
+        false,
+        true,
+    )
+    .unwrap();
+
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&hash).unwrap())
+        .unwrap();
+    assert_eq!(commit.parent_id(0).unwrap(), head);
+    assert_eq!(commit.tree_id(), repo.find_commit(head).unwrap().tree_id());
+}
+
+// =============================================================================
+// pre-commit hook (3 tests)
+// =============================================================================
+
+#[cfg(unix)]
+fn install_pre_commit_hook(repo: &git2::Repository, script: &str) {
+    use std::os::unix::fs::PermissionsExt;
+    let path = repo.path().join("hooks").join("pre-commit");
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(&path, script).unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn create_commit_aborts_when_pre_commit_hook_fails() {
+    let (temp_dir, repo) = create_test_repo();
+    let head = create_initial_commit(&repo, &temp_dir);
+    stage_new_file(&repo, &temp_dir, "a.txt");
+    install_pre_commit_hook(&repo, "#!/bin/sh\necho lint failed >&2\nexit 1\n");
+
+    let result = git::create_commit(
+        &repo,
+        "Add a",
+        &git::AuthorOverride::default(),
+        None,
+        false,
+        false,
+    );
+
+    assert!(matches!(result, Err(AppError::HookFailed(out)) if out == "lint failed"));
+    assert_eq!(repo.head().unwrap().target(), Some(head));
+}
+
+#[cfg(unix)]
+#[test]
+fn create_commit_skip_hooks_bypasses_pre_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    let head = create_initial_commit(&repo, &temp_dir);
+    stage_new_file(&repo, &temp_dir, "a.txt");
+    install_pre_commit_hook(&repo, "#!/bin/sh\nexit 1\n");
+
+    let hash = git::create_commit(
+        &repo,
+        "Add a",
+        &git::AuthorOverride::default(),
+        None,
+        true,
+        false,
+    )
+    .unwrap();
+
+    assert_ne!(hash, head.to_string());
+    assert_eq!(repo.head().unwrap().target().unwrap().to_string(), hash);
+}
+
+#[cfg(unix)]
+#[test]
+fn create_commit_includes_files_staged_by_hook() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    stage_new_file(&repo, &temp_dir, "a.txt");
+    install_pre_commit_hook(
+        &repo,
+        "#!/bin/sh\necho generated > generated.txt\ngit add generated.txt\n",
+    );
+
+    let hash = git::create_commit(
+        &repo,
+        "Add a",
+        &git::AuthorOverride::default(),
+        None,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let commit = repo
+        .find_commit(git2::Oid::from_str(&hash).unwrap())
+        .unwrap();
+    assert!(commit
+        .tree()
+        .unwrap()
+        .get_path(Path::new("generated.txt"))
+        .is_ok());
+}
+
+// =============================================================================
+// amend_commit (4 tests)
+// =============================================================================
+
+#[test]
+fn amend_commit_replaces_head_with_staged_tree() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_initial_commit(&repo, &temp_dir);
+    let old_head = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Add a");
+
+    fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("b.txt")).unwrap();
+    index.write().unwrap();
+
+    let new_hash = git::amend_commit(&repo, None).unwrap();
+
+    assert_ne!(new_hash, old_head.to_string());
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id().to_string(), new_hash);
+    assert_eq!(head.parent_ids().collect::<Vec<_>>(), vec![first]);
+    assert_eq!(head.message().ok(), Some("Add a"));
+    let tree = head.tree().unwrap();
+    assert!(tree.get_name("a.txt").is_some());
+    assert!(tree.get_name("b.txt").is_some());
+}
+
+#[test]
+fn amend_commit_with_new_message() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    git::amend_commit(&repo, Some("Reworded")).unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.message().ok(), Some("Reworded"));
+}
+
+#[test]
+fn amend_commit_preserves_author_updates_committer() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Amender").unwrap();
+    config.set_str("user.email", "amender@example.com").unwrap();
+
+    git::amend_commit(&repo, None).unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.author().name().ok(), Some("Test User"));
+    assert_eq!(head.committer().name().ok(), Some("Amender"));
+    assert_eq!(head.committer().email().ok(), Some("amender@example.com"));
+}
+
+#[test]
+fn amend_commit_refuses_without_commits() {
+    let (_temp_dir, repo) = create_test_repo();
+
+    let err = git::amend_commit(&repo, Some("msg")).unwrap_err();
+    assert!(err.to_string().contains("No commit to amend"));
+    assert_eq!(err.code(), "git_unborn_branch");
+}
+
+// =============================================================================
+// squash_commits (6 tests)
+// =============================================================================
+
+#[test]
+fn squash_commits_three_into_one() {
+    let (temp_dir, repo) = create_test_repo();
+    let base = create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Add a");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "Add b");
+    let old_head = create_commit_with_file(&repo, &temp_dir, "a.txt", "a2", "Change a");
+    let old_tree = repo.find_commit(old_head).unwrap().tree_id();
+
+    let new_hash = git::squash_commits(&repo, 3, "Add a and b").unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id().to_string(), new_hash);
+    assert_eq!(head.tree_id(), old_tree);
+    assert_eq!(head.parent_ids().collect::<Vec<_>>(), vec![base]);
+    assert_eq!(head.message().ok(), Some("Add a and b"));
+    assert_eq!(get_commits(&repo, 0, 10, None, None).unwrap().len(), 2);
+    assert!(repo.statuses(None).unwrap().is_empty());
+}
+
+#[test]
+fn squash_commits_moves_head_in_one_reflog_entry() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Add a");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "Add b");
+    let entries_before = repo.reflog("HEAD").unwrap().len();
+
+    let new_hash = git::squash_commits(&repo, 2, "Add a and b").unwrap();
+
+    let reflog = repo.reflog("HEAD").unwrap();
+    assert_eq!(reflog.len(), entries_before + 1);
+    let entry = reflog.get(0).unwrap();
+    assert_eq!(entry.id_new().to_string(), new_hash);
+    assert_eq!(
+        entry.message().ok().flatten(),
+        Some("squash 2 commits: Add a and b")
+    );
+}
+
+#[test]
+fn squash_commits_keeps_oldest_author() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Add a");
+    let mut config = repo.config().unwrap();
+    config.set_str("user.name", "Second Author").unwrap();
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "Add b");
+
+    git::squash_commits(&repo, 2, "Squashed").unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.author().name().ok(), Some("Test User"));
+    assert_eq!(head.committer().name().ok(), Some("Second Author"));
+}
+
+#[test]
+fn squash_commits_refuses_to_pass_root() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let head = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Add a");
+
+    for count in [2, 5, 0] {
+        let result = git::squash_commits(&repo, count, "msg");
+        assert!(
+            matches!(result, Err(AppError::InvalidArgument(_))),
+            "count {count}: {result:?}"
+        );
+    }
+    assert_eq!(repo.head().unwrap().target(), Some(head));
+}
+
+#[test]
+fn squash_commits_unborn_head_is_error() {
+    let (_temp_dir, repo) = create_test_repo();
+
+    let err = git::squash_commits(&repo, 1, "msg").unwrap_err();
+    assert_eq!(err.code(), "git_unborn_branch");
+}
+
+#[test]
+fn squash_commits_rejects_empty_message() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Add a");
+
+    assert!(matches!(
+        git::squash_commits(&repo, 1, "  "),
+        Err(AppError::InvalidCommitMessage(_))
+    ));
+}
+
+// =============================================================================
+// get_file_history (5 tests)
+// =============================================================================
+
+#[test]
+fn get_file_history_only_touching_commits() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let a1 = create_commit_with_file(&repo, &temp_dir, "a.txt", "1", "a v1");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "1", "b v1");
+    let a2 = create_commit_with_file(&repo, &temp_dir, "a.txt", "2", "a v2");
+
+    let history = git::get_file_history(&repo, "a.txt", 0, 10).unwrap();
+
+    let hashes: Vec<String> = history.iter().map(|c| c.hash.clone()).collect();
+    assert_eq!(hashes, vec![a2.to_string(), a1.to_string()]);
+}
+
+#[test]
+fn get_file_history_pagination() {
+    let (temp_dir, repo) = create_test_repo();
+    let mut oids = Vec::new();
+    for i in 0..5 {
+        oids.push(create_commit_with_file(
+            &repo,
+            &temp_dir,
+            "f.txt",
+            &i.to_string(),
+            &format!("v{i}"),
+        ));
+    }
+
+    let page = git::get_file_history(&repo, "f.txt", 1, 2).unwrap();
+
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].hash, oids[3].to_string());
+    assert_eq!(page[1].hash, oids[2].to_string());
+}
+
+#[test]
+fn get_file_history_follows_rename() {
+    let (temp_dir, repo) = create_test_repo();
+    let content = "line one\nline two\nline three\nline four\n";
+    let created = create_commit_with_file(&repo, &temp_dir, "old.txt", content, "create");
+
+    // Rename old.txt -> new.txt with identical content.
+    fs::rename(
+        temp_dir.path().join("old.txt"),
+        temp_dir.path().join("new.txt"),
+    )
+    .unwrap();
+    let mut index = repo.index().unwrap();
+    index.remove_path(Path::new("old.txt")).unwrap();
+    index.add_path(Path::new("new.txt")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let renamed = repo
+        .commit(Some("HEAD"), &sig, &sig, "rename", &tree, &[&parent])
+        .unwrap();
+
+    let history = git::get_file_history(&repo, "new.txt", 0, 10).unwrap();
+
+    let hashes: Vec<String> = history.iter().map(|c| c.hash.clone()).collect();
+    assert_eq!(hashes, vec![renamed.to_string(), created.to_string()]);
+}
+
+#[test]
+fn get_file_history_unknown_path_is_empty() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(git::get_file_history(&repo, "nope.txt", 0, 10)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn get_file_history_empty_repo() {
+    let (_temp_dir, repo) = create_test_repo();
+    assert!(git::get_file_history(&repo, "f.txt", 0, 10)
+        .unwrap()
+        .is_empty());
+}
+
+// =============================================================================
+// get_branch_diff_files (2 tests)
+// =============================================================================
+
+#[test]
+fn get_branch_diff_files_only_head_side_changes() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "shared.txt", "base\n", "base");
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let fork = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &fork, false).unwrap();
+
+    // Only on main: must not show up.
+    create_commit_with_file(&repo, &temp_dir, "main-only.txt", "m\n", "main work");
+
+    repo.set_head("refs/heads/feature").unwrap();
+    repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+        .unwrap();
+    create_commit_with_file(&repo, &temp_dir, "feature.txt", "f\n", "add feature");
+    create_commit_with_file(&repo, &temp_dir, "shared.txt", "changed\n", "edit shared");
+
+    let mut files = git::get_branch_diff_files(&repo, &main, "feature").unwrap();
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    let summary: Vec<(&str, &str)> = files
+        .iter()
+        .map(|f| (f.path.as_str(), f.status.as_str()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![("feature.txt", "added"), ("shared.txt", "modified")]
+    );
+}
+
+#[test]
+fn get_branch_diff_files_detects_renames() {
+    let (temp_dir, repo) = create_test_repo();
+    let content = "line one\nline two\nline three\nline four\n";
+    create_commit_with_file(&repo, &temp_dir, "old.txt", content, "base");
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let fork = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &fork, false).unwrap();
+    repo.set_head("refs/heads/feature").unwrap();
+
+    fs::rename(
+        temp_dir.path().join("old.txt"),
+        temp_dir.path().join("new.txt"),
+    )
+    .unwrap();
+    let mut index = repo.index().unwrap();
+    index.remove_path(Path::new("old.txt")).unwrap();
+    index.add_path(Path::new("new.txt")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "rename", &tree, &[&fork])
+        .unwrap();
+
+    let files = git::get_branch_diff_files(&repo, &main, "feature").unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].status, "renamed");
+    assert_eq!(files[0].path, "new.txt");
+    assert_eq!(files[0].old_path.as_deref(), Some("old.txt"));
+}
+
+// =============================================================================
+// Cancellation (3 tests)
+// =============================================================================
+
+#[test]
+fn get_commits_cancellable_runs_to_completion_when_not_cancelled() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a\n", "second");
+
+    let token = git::CancelToken::new();
+    let commits = git::get_commits_cancellable(&repo, 0, 10, None, None, &token).unwrap();
+    assert_eq!(commits.len(), 2);
+}
+
+#[test]
+fn get_commits_cancellable_stops_when_cancelled() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    for i in 0..20 {
+        let name = format!("f{i}.txt");
+        create_commit_with_file(&repo, &temp_dir, &name, "x\n", &name);
+    }
+
+    // A clone shares the flag, as with the token held by `cancel_operation`.
+    let token = git::CancelToken::new();
+    token.clone().cancel();
+    let result = git::get_commits_cancellable(&repo, 0, 100, None, Some("nobody"), &token);
+    assert!(matches!(result, Err(AppError::Cancelled)));
+}
+
+#[test]
+fn get_file_diff_cancellable_stops_when_cancelled() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "big.txt", "old\n", "base");
+    let content: String = (0..5000).map(|i| format!("line {i}\n")).collect();
+    fs::write(temp_dir.path().join("big.txt"), content).unwrap();
+
+    let token = git::CancelToken::new();
+    token.cancel();
+    let result = git::get_file_diff_cancellable(
+        &repo,
+        "big.txt",
+        false,
+        &git::DiffConfig::default(),
+        false,
+        None,
+        &token,
+    );
+    assert!(matches!(result, Err(AppError::Cancelled)));
+}
@@ -0,0 +1,51 @@
+//! Integration tests for reading and writing git config. Only local scope is
+//! written: global scope would touch the real `~/.gitconfig`.
+
+mod common;
+
+use common::{create_initial_commit, create_test_repo};
+use yagg_lib::git::{get_config, set_config, ConfigScope};
+
+#[test]
+fn set_config_local_reads_back() {
+    let (_temp_dir, repo) = create_test_repo();
+
+    set_config(&repo, "core.autocrlf", "input", ConfigScope::Local).unwrap();
+
+    assert_eq!(
+        get_config(&repo, "core.autocrlf").unwrap().as_deref(),
+        Some("input")
+    );
+    let local = repo
+        .config()
+        .unwrap()
+        .open_level(git2::ConfigLevel::Local)
+        .unwrap();
+    assert_eq!(local.get_string("core.autocrlf").unwrap(), "input");
+}
+
+#[test]
+fn get_config_unset_key_is_none() {
+    let (_temp_dir, repo) = create_test_repo();
+
+    assert_eq!(get_config(&repo, "yagg.doesNotExist").unwrap(), None);
+}
+
+#[test]
+fn set_config_user_name_used_for_commits() {
+    let (temp_dir, repo) = create_test_repo();
+    set_config(&repo, "user.name", "Configured Name", ConfigScope::Local).unwrap();
+    set_config(
+        &repo,
+        "user.email",
+        "configured@example.com",
+        ConfigScope::Local,
+    )
+    .unwrap();
+
+    create_initial_commit(&repo, &temp_dir);
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.author().name().ok(), Some("Configured Name"));
+    assert_eq!(head.author().email().ok(), Some("configured@example.com"));
+}
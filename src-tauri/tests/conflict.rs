@@ -0,0 +1,210 @@
+//! Integration tests for reading and resolving conflict stages. Conflicts are produced by
+//! a real `merge_branch` so the index holds genuine stage 1/2/3 entries.
+
+mod common;
+
+use common::{create_commit_with_file, create_initial_commit, create_test_repo};
+use git2::{build::CheckoutBuilder, Repository};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use yagg_lib::error::AppError;
+use yagg_lib::git::{get_conflict, merge_branch, resolve_conflict, resolve_conflict_with_content};
+
+fn switch_to(repo: &Repository, branch: &str) {
+    repo.set_head(&format!("refs/heads/{branch}")).unwrap();
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))
+        .unwrap();
+}
+
+/// Commit `ours` on the current branch and `theirs` on `feature` (both
+/// forked from HEAD), then merge `feature` so `file` ends up conflicted.
+fn conflicted_merge(repo: &Repository, temp_dir: &TempDir, file: &str, ours: &str, theirs: &str) {
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &head, false).unwrap();
+
+    switch_to(repo, "feature");
+    create_commit_with_file(repo, temp_dir, file, theirs, "theirs");
+    switch_to(repo, &main);
+    create_commit_with_file(repo, temp_dir, file, ours, "ours");
+
+    assert!(matches!(
+        merge_branch(repo, "feature"),
+        Err(AppError::MergeConflict { .. })
+    ));
+}
+
+#[test]
+fn get_conflict_returns_all_three_stages() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "f.txt", "base\n", "base");
+    conflicted_merge(&repo, &temp_dir, "f.txt", "ours\n", "theirs\n");
+
+    let info = get_conflict(&repo, "f.txt").unwrap();
+
+    assert_eq!(info.path, "f.txt");
+    let ancestor = info.ancestor.expect("ancestor stage");
+    let ours = info.ours.expect("ours stage");
+    let theirs = info.theirs.expect("theirs stage");
+    assert_eq!(ancestor.content.as_deref(), Some("base\n"));
+    assert_eq!(ours.content.as_deref(), Some("ours\n"));
+    assert_eq!(theirs.content.as_deref(), Some("theirs\n"));
+    assert_eq!(ours.filemode, 0o100644);
+    assert!(!ours.is_binary);
+}
+
+#[test]
+fn get_conflict_add_add_has_no_ancestor() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    conflicted_merge(&repo, &temp_dir, "new.txt", "mine\n", "yours\n");
+
+    let info = get_conflict(&repo, "new.txt").unwrap();
+
+    assert!(info.ancestor.is_none());
+    assert_eq!(info.ours.unwrap().content.as_deref(), Some("mine\n"));
+    assert_eq!(info.theirs.unwrap().content.as_deref(), Some("yours\n"));
+}
+
+#[test]
+fn get_conflict_flags_binary_stages() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    conflicted_merge(&repo, &temp_dir, "bin.dat", "a\0b", "c\0d");
+
+    let info = get_conflict(&repo, "bin.dat").unwrap();
+
+    let ours = info.ours.unwrap();
+    assert!(ours.is_binary);
+    assert!(ours.content.is_none());
+    assert!(info.theirs.unwrap().is_binary);
+}
+
+#[test]
+fn get_conflict_rejects_clean_path() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let result = get_conflict(&repo, "initial.txt");
+    assert!(matches!(result, Err(AppError::NotConflicted(_))));
+}
+
+// ============================================================================
+// resolve_conflict_with_content
+// ============================================================================
+
+#[test]
+fn resolve_conflict_with_content_clears_stages() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "f.txt", "base\n", "base");
+    conflicted_merge(&repo, &temp_dir, "f.txt", "ours\n", "theirs\n");
+
+    resolve_conflict_with_content(&repo, "f.txt", "merged\n").unwrap();
+
+    let index = repo.index().unwrap();
+    assert!(!index.has_conflicts());
+    let entry = index.get_path(Path::new("f.txt"), 0).unwrap();
+    let blob = repo.find_blob(entry.id).unwrap();
+    assert_eq!(blob.content(), b"merged\n");
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("f.txt")).unwrap(),
+        "merged\n"
+    );
+    assert!(matches!(
+        get_conflict(&repo, "f.txt"),
+        Err(AppError::NotConflicted(_))
+    ));
+}
+
+#[test]
+fn resolve_conflict_with_content_leaves_other_conflicts() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &head, false).unwrap();
+    switch_to(&repo, "feature");
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "theirs a\n", "a");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "theirs b\n", "b");
+    switch_to(&repo, &main);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "ours a\n", "a");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "ours b\n", "b");
+    assert!(merge_branch(&repo, "feature").is_err());
+
+    resolve_conflict_with_content(&repo, "a.txt", "fixed a\n").unwrap();
+
+    assert!(get_conflict(&repo, "a.txt").is_err());
+    assert!(get_conflict(&repo, "b.txt").is_ok());
+}
+
+#[test]
+fn resolve_conflict_with_content_rejects_clean_path() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let result = resolve_conflict_with_content(&repo, "initial.txt", "new");
+    assert!(matches!(result, Err(AppError::NotConflicted(_))));
+    // Working file untouched on rejection.
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+}
+
+// ============================================================================
+// resolve_conflict
+// ============================================================================
+
+#[test]
+fn resolve_conflict_strategy_stages_file() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "f.txt", "base\n", "base");
+    conflicted_merge(&repo, &temp_dir, "f.txt", "ours\n", "theirs\n");
+
+    resolve_conflict(&repo, "f.txt", "ours").unwrap();
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("f.txt")).unwrap(),
+        "ours\n"
+    );
+    let index = repo.index().unwrap();
+    assert!(!index.has_conflicts());
+    let entry = index.get_path(Path::new("f.txt"), 0).unwrap();
+    assert_eq!(repo.find_blob(entry.id).unwrap().content(), b"ours\n");
+}
+
+#[test]
+fn resolve_conflict_strategy_rejects_clean_path() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let marked = "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\n";
+    fs::write(temp_dir.path().join("initial.txt"), marked).unwrap();
+
+    let result = resolve_conflict(&repo, "initial.txt", "ours");
+    assert!(matches!(result, Err(AppError::NotConflicted(_))));
+    // Neither rewritten nor staged.
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        marked
+    );
+    assert!(repo
+        .statuses(None)
+        .unwrap()
+        .iter()
+        .all(|s| !s.status().is_index_modified()));
+}
+
+#[test]
+fn resolve_conflict_unknown_strategy_is_invalid_argument() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    conflicted_merge(&repo, &temp_dir, "f.txt", "ours\n", "theirs\n");
+
+    let result = resolve_conflict(&repo, "f.txt", "mine");
+    assert!(matches!(result, Err(AppError::InvalidArgument(_))));
+    assert!(get_conflict(&repo, "f.txt").is_ok());
+}
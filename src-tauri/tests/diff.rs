@@ -8,11 +8,12 @@ mod common;
 use common::{create_commit_with_file, create_initial_commit, create_test_repo};
 use std::fs;
 use std::path::Path;
+use yagg_lib::error::AppError;
 use yagg_lib::git::{
     self, get_commit_diff_hunk, get_commit_file_diff, get_commit_file_diff_with_config,
     get_conflicted_diff_hunk, get_conflicted_file_diff, get_diff_hunk, get_file_diff,
     get_file_diff_with_config, get_untracked_diff_hunk, get_untracked_file_diff,
-    get_untracked_file_diff_with_config, DiffConfig, DiffLine, LineType,
+    get_untracked_file_diff_with_config, DiffConfig, DiffLine, LineEnding, LineType,
 };
 
 // =============================================================================
@@ -79,6 +80,214 @@ fn get_file_diff_no_changes() {
     assert!(diff.hunks.is_empty());
 }
 
+// =============================================================================
+// get_combined_file_diff (3 tests)
+// =============================================================================
+
+#[test]
+fn get_combined_file_diff_returns_both_sides() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "one\n", "Initial commit");
+
+    let file_path = temp_dir.path().join("file.txt");
+    fs::write(&file_path, "two\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("file.txt")).unwrap();
+    index.write().unwrap();
+    fs::write(&file_path, "three\n").unwrap();
+
+    let combined =
+        git::get_combined_file_diff(&repo, "file.txt", &DiffConfig::default(), false, None)
+            .unwrap();
+
+    let added = |diff: &git::FileDiff| -> Vec<String> {
+        diff.hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .filter(|l| l.line_type == LineType::Addition)
+            .map(|l| l.content.clone())
+            .collect()
+    };
+    assert_eq!(added(&combined.staged), vec!["two\n"]);
+    assert_eq!(added(&combined.unstaged), vec!["three\n"]);
+}
+
+#[test]
+fn get_combined_file_diff_staged_only() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "one\n", "Initial commit");
+    fs::write(temp_dir.path().join("file.txt"), "two\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("file.txt")).unwrap();
+    index.write().unwrap();
+
+    let combined =
+        git::get_combined_file_diff(&repo, "file.txt", &DiffConfig::default(), false, None)
+            .unwrap();
+    assert!(!combined.staged.hunks.is_empty());
+    assert!(combined.unstaged.hunks.is_empty());
+}
+
+#[test]
+fn get_combined_file_diff_untracked_respects_max_file_size() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "one\n", "Initial commit");
+    let content: String = (0..100).map(|i| format!("line {i}\n")).collect();
+    fs::write(temp_dir.path().join("big.txt"), content).unwrap();
+    let config = DiffConfig {
+        max_file_size: 64,
+        max_diff_bytes: 64,
+    };
+
+    let combined = git::get_combined_file_diff(&repo, "big.txt", &config, false, None).unwrap();
+
+    assert!(combined.staged.hunks.is_empty());
+    let hunk = &combined.unstaged.hunks[0];
+    assert_eq!(hunk.header, "@@ -0,0 +1,100 @@\n");
+    assert!(!hunk.is_loaded);
+    assert!(hunk.lines.len() < 100);
+}
+
+// =============================================================================
+// get_file_patch (2 tests)
+// =============================================================================
+
+fn short_blob_id(content: &str) -> String {
+    git2::Oid::hash_object(git2::ObjectType::Blob, content.as_bytes())
+        .unwrap()
+        .to_string()[..7]
+        .to_string()
+}
+
+#[test]
+fn get_file_patch_small_modification() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "a\nb\nc\n", "Initial commit");
+    fs::write(temp_dir.path().join("file.txt"), "a\nB\nc\n").unwrap();
+
+    let expected = format!(
+        "diff --git a/file.txt b/file.txt\n\
+         index {}..{} 100644\n\
+         --- a/file.txt\n\
+         +++ b/file.txt\n\
+         @@ -1,3 +1,3 @@\n\
+         \x20a\n\
+         -b\n\
+         +B\n\
+         \x20c\n",
+        short_blob_id("a\nb\nc\n"),
+        short_blob_id("a\nB\nc\n"),
+    );
+    assert_eq!(
+        git::get_file_patch(&repo, "file.txt", false).unwrap(),
+        expected
+    );
+    assert_eq!(git::get_file_patch(&repo, "file.txt", true).unwrap(), "");
+}
+
+#[test]
+fn get_file_patch_applies_elsewhere() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "one\ntwo\n", "Initial commit");
+    fs::write(temp_dir.path().join("file.txt"), "one\ntwo").unwrap();
+    fs::write(temp_dir.path().join("new.txt"), "fresh\n").unwrap();
+    let modified = git::get_file_patch(&repo, "file.txt", false).unwrap();
+    let added = git::get_file_patch(&repo, "new.txt", false).unwrap();
+    assert!(modified.contains("\\ No newline at end of file"));
+
+    // Undo both changes, then apply the patches back.
+    fs::write(temp_dir.path().join("file.txt"), "one\ntwo\n").unwrap();
+    fs::remove_file(temp_dir.path().join("new.txt")).unwrap();
+    for patch in [modified, added] {
+        let diff = git2::Diff::from_buffer(patch.as_bytes()).unwrap();
+        repo.apply(&diff, git2::ApplyLocation::WorkDir, None)
+            .unwrap();
+    }
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+        "one\ntwo"
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("new.txt")).unwrap(),
+        "fresh\n"
+    );
+}
+
+// =============================================================================
+// context_lines (3 tests)
+// =============================================================================
+
+/// Twenty numbered lines with lines 5 and 15 changed.
+fn two_distant_edits(repo: &git2::Repository, temp_dir: &tempfile::TempDir) {
+    let original: String = (1..=20).map(|i| format!("line{i}\n")).collect();
+    create_commit_with_file(repo, temp_dir, "f.txt", &original, "Initial commit");
+    let modified = original
+        .replace("line5\n", "FIVE\n")
+        .replace("line15\n", "FIFTEEN\n");
+    fs::write(temp_dir.path().join("f.txt"), modified).unwrap();
+}
+
+fn context_count(hunk: &git::DiffHunk) -> usize {
+    hunk.lines
+        .iter()
+        .filter(|l| l.line_type == LineType::Context)
+        .count()
+}
+
+#[test]
+fn context_lines_zero_gives_tight_hunks() {
+    let (temp_dir, repo) = create_test_repo();
+    two_distant_edits(&repo, &temp_dir);
+
+    let diff = get_file_diff_with_config(
+        &repo,
+        "f.txt",
+        false,
+        &DiffConfig::default(),
+        false,
+        Some(0),
+    )
+    .unwrap();
+    assert_eq!(diff.hunks.len(), 2);
+    assert!(diff.hunks.iter().all(|h| context_count(h) == 0));
+    assert_eq!(diff.hunks[0].old_start, 5);
+    assert_eq!(diff.hunks[0].old_lines, 1);
+}
+
+#[test]
+fn context_lines_five_merges_nearby_hunks() {
+    let (temp_dir, repo) = create_test_repo();
+    two_distant_edits(&repo, &temp_dir);
+
+    let default = get_file_diff(&repo, "f.txt", false).unwrap();
+    assert_eq!(default.hunks.len(), 2);
+    assert_eq!(context_count(&default.hunks[0]), 6);
+
+    // 5 lines either side of edits 10 lines apart overlap into one hunk.
+    let diff = get_file_diff_with_config(
+        &repo,
+        "f.txt",
+        false,
+        &DiffConfig::default(),
+        false,
+        Some(5),
+    )
+    .unwrap();
+    assert_eq!(diff.hunks.len(), 1);
+    assert_eq!(diff.hunks[0].old_start, 1);
+    assert_eq!(context_count(&diff.hunks[0]), 18);
+}
+
+#[test]
+fn context_lines_get_diff_hunk_uses_same_setting() {
+    let (temp_dir, repo) = create_test_repo();
+    two_distant_edits(&repo, &temp_dir);
+
+    let hunk = get_diff_hunk(&repo, "f.txt", false, 1, false, Some(0)).unwrap();
+    assert_eq!(hunk.old_start, 15);
+    assert!(get_diff_hunk(&repo, "f.txt", false, 1, false, Some(5)).is_err());
+}
+
 // =============================================================================
 // get_untracked_file_diff (3 tests)
 // =============================================================================
@@ -127,7 +336,74 @@ fn get_untracked_file_diff_binary_file() {
 }
 
 // =============================================================================
-// get_commit_file_diff (5 tests)
+// is_untracked routing (3 tests)
+// =============================================================================
+
+#[test]
+fn is_untracked_distinguishes_new_from_modified() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "tracked.txt", "a\n", "Initial commit");
+    fs::write(temp_dir.path().join("tracked.txt"), "a\nb\n").unwrap();
+    fs::write(temp_dir.path().join("new.txt"), "x\ny\n").unwrap();
+
+    assert!(!git::is_untracked(&repo, "tracked.txt").unwrap());
+    assert!(git::is_untracked(&repo, "new.txt").unwrap());
+
+    // A tracked modification diffs against the index as usual.
+    let tracked = get_file_diff(&repo, "tracked.txt", false).unwrap();
+    assert_eq!(tracked.hunks.len(), 1);
+    assert!(tracked.hunks[0]
+        .lines
+        .iter()
+        .any(|l| l.line_type == LineType::Context));
+
+    // An untracked file reads the same either way, but only the untracked
+    // reader applies `max_file_size`.
+    let via_index = get_file_diff(&repo, "new.txt", false).unwrap();
+    let untracked = get_untracked_file_diff(&repo, "new.txt").unwrap();
+    let contents = |d: &git::FileDiff| -> Vec<String> {
+        d.hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.line_type == LineType::Addition)
+            .map(|l| l.content.clone())
+            .collect()
+    };
+    assert_eq!(contents(&via_index), contents(&untracked));
+    assert_eq!(untracked.hunks[0].header, "@@ -0,0 +1,2 @@\n");
+
+    let tiny = DiffConfig {
+        max_file_size: 1,
+        max_diff_bytes: 1,
+    };
+    let truncated = get_untracked_file_diff_with_config(&repo, "new.txt", &tiny).unwrap();
+    assert!(!truncated.hunks[0].is_loaded);
+}
+
+#[test]
+fn is_untracked_false_once_staged() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("new.txt"), "x\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("new.txt")).unwrap();
+    index.write().unwrap();
+
+    assert!(!git::is_untracked(&repo, "new.txt").unwrap());
+}
+
+#[test]
+fn is_untracked_false_for_ignored_and_missing() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, ".gitignore", "*.log\n", "Initial commit");
+    fs::write(temp_dir.path().join("debug.log"), "noise\n").unwrap();
+
+    assert!(!git::is_untracked(&repo, "debug.log").unwrap());
+    assert!(!git::is_untracked(&repo, "nope.txt").unwrap());
+}
+
+// =============================================================================
+// get_commit_file_diff (6 tests)
 // =============================================================================
 
 #[test]
@@ -200,6 +476,45 @@ fn get_commit_file_diff_deleted_file() {
     assert!(has_only_deletions);
 }
 
+#[test]
+fn get_commit_file_diff_rename_with_edits() {
+    let (temp_dir, repo) = create_test_repo();
+    let original: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+    create_commit_with_file(&repo, &temp_dir, "old.txt", &original, "Add file");
+
+    fs::remove_file(temp_dir.path().join("old.txt")).unwrap();
+    let edited = original.replace("line 5\n", "line five\n");
+    fs::write(temp_dir.path().join("new.txt"), &edited).unwrap();
+    let mut index = repo.index().unwrap();
+    index.remove_path(Path::new("old.txt")).unwrap();
+    index.add_path(Path::new("new.txt")).unwrap();
+    index.write().unwrap();
+
+    let sig = repo.signature().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "Rename file", &tree, &[&parent])
+        .unwrap();
+
+    let diff = get_commit_file_diff(&repo, &oid.to_string(), "new.txt").unwrap();
+    assert_eq!(diff.path, "new.txt");
+    assert_eq!(diff.old_path.as_deref(), Some("old.txt"));
+    let changed: Vec<_> = diff
+        .hunks
+        .iter()
+        .flat_map(|h| &h.lines)
+        .filter(|l| matches!(l.line_type, LineType::Addition | LineType::Deletion))
+        .map(|l| l.content.as_str())
+        .collect();
+    assert_eq!(changed, ["line 5\n", "line five\n"]);
+
+    // A file added outright has no old path.
+    let first = parent.id().to_string();
+    let diff = get_commit_file_diff(&repo, &first, "old.txt").unwrap();
+    assert_eq!(diff.old_path, None);
+}
+
 #[test]
 fn get_commit_file_diff_invalid_hash() {
     let (_temp_dir, repo) = create_test_repo();
@@ -220,6 +535,363 @@ fn get_commit_file_diff_nonexistent_commit() {
     assert!(result.is_err());
 }
 
+// =============================================================================
+// get_commit_diff (2 tests)
+// =============================================================================
+
+/// Commit on top of HEAD that modifies a.txt, adds b.txt and deletes c.txt.
+fn commit_touching_three_files(repo: &git2::Repository, temp_dir: &tempfile::TempDir) -> String {
+    create_commit_with_file(repo, temp_dir, "a.txt", "a1\na2\n", "Add a");
+    create_commit_with_file(repo, temp_dir, "c.txt", "c1\n", "Add c");
+
+    fs::write(temp_dir.path().join("a.txt"), "a1\nA2\n").unwrap();
+    fs::write(temp_dir.path().join("b.txt"), "b1\nb2\nb3\n").unwrap();
+    fs::remove_file(temp_dir.path().join("c.txt")).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("a.txt")).unwrap();
+    index.add_path(Path::new("b.txt")).unwrap();
+    index.remove_path(Path::new("c.txt")).unwrap();
+    index.write().unwrap();
+
+    let sig = repo.signature().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Touch three", &tree, &[&parent])
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn get_commit_diff_returns_every_file() {
+    let (temp_dir, repo) = create_test_repo();
+    let hash = commit_touching_three_files(&repo, &temp_dir);
+
+    let diffs = git::get_commit_diff(&repo, &hash).unwrap();
+    let paths: Vec<_> = diffs.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(paths, ["a.txt", "b.txt", "c.txt"]);
+    for diff in &diffs {
+        let single = get_commit_file_diff(&repo, &hash, &diff.path).unwrap();
+        assert_eq!(diff.total_lines, single.total_lines, "{}", diff.path);
+        assert_eq!(diff.hunks.len(), single.hunks.len(), "{}", diff.path);
+        assert!(diff.hunks.iter().all(|h| h.is_loaded));
+    }
+    let kinds = |d: &git::FileDiff| -> Vec<LineType> {
+        d.hunks[0]
+            .lines
+            .iter()
+            .map(|l| l.line_type.clone())
+            .filter(|t| matches!(t, LineType::Addition | LineType::Deletion))
+            .collect()
+    };
+    assert_eq!(kinds(&diffs[0]), [LineType::Deletion, LineType::Addition]);
+    assert_eq!(
+        kinds(&diffs[1]),
+        [LineType::Addition, LineType::Addition, LineType::Addition]
+    );
+    assert_eq!(kinds(&diffs[2]), [LineType::Deletion]);
+}
+
+#[test]
+fn get_commit_diff_shares_byte_budget_across_files() {
+    let (temp_dir, repo) = create_test_repo();
+    let hash = commit_touching_three_files(&repo, &temp_dir);
+    let config = DiffConfig {
+        max_diff_bytes: 4,
+        ..DiffConfig::default()
+    };
+
+    let diffs = git::get_commit_diff_with_config(&repo, &hash, &config).unwrap();
+    assert_eq!(diffs.len(), 3);
+    assert!(diffs[0].hunks[0].is_loaded);
+    for diff in &diffs[1..] {
+        assert!(!diff.hunks[0].is_loaded, "{}", diff.path);
+        assert!(diff.hunks[0].lines.is_empty());
+    }
+
+    let hunk = get_commit_diff_hunk(&repo, &hash, "b.txt", 0).unwrap();
+    let added = hunk
+        .lines
+        .iter()
+        .filter(|l| l.line_type == LineType::Addition);
+    assert_eq!(added.count(), 3);
+}
+
+// =============================================================================
+// Binary sizes (3 tests)
+// =============================================================================
+
+fn commit_binary(repo: &git2::Repository, temp_dir: &tempfile::TempDir, bytes: &[u8]) -> git2::Oid {
+    fs::write(temp_dir.path().join("blob.bin"), bytes).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("blob.bin")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, "Binary", &tree, &parents)
+        .unwrap()
+}
+
+#[test]
+fn binary_file_diff_reports_sizes() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_binary(&repo, &temp_dir, &[0u8; 12]);
+    fs::write(temp_dir.path().join("blob.bin"), [0u8; 15]).unwrap();
+
+    let diff = get_file_diff(&repo, "blob.bin", false).unwrap();
+    assert!(diff.is_binary);
+    assert!(diff.hunks.is_empty());
+    assert_eq!(diff.old_size, Some(12));
+    assert_eq!(diff.new_size, Some(15));
+}
+
+#[test]
+fn binary_commit_file_diff_reports_sizes() {
+    let (temp_dir, repo) = create_test_repo();
+    let added = commit_binary(&repo, &temp_dir, &[0u8; 12]);
+    let modified = commit_binary(&repo, &temp_dir, &[1u8, 0, 2]);
+
+    let diff = get_commit_file_diff(&repo, &added.to_string(), "blob.bin").unwrap();
+    assert!(diff.is_binary);
+    assert_eq!(diff.old_size, None);
+    assert_eq!(diff.new_size, Some(12));
+
+    let diff = get_commit_file_diff(&repo, &modified.to_string(), "blob.bin").unwrap();
+    assert_eq!(diff.old_size, Some(12));
+    assert_eq!(diff.new_size, Some(3));
+}
+
+#[test]
+fn text_file_diff_has_no_sizes() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "a\n", "Initial commit");
+    fs::write(temp_dir.path().join("file.txt"), "b\n").unwrap();
+
+    let diff = get_file_diff(&repo, "file.txt", false).unwrap();
+    assert_eq!(diff.old_size, None);
+    assert_eq!(diff.new_size, None);
+}
+
+// =============================================================================
+// LFS pointers (2 tests)
+// =============================================================================
+
+fn lfs_pointer(oid: &str, size: u64) -> String {
+    format!("version https://git-lfs.github.com/spec/v1\noid sha256:{oid}\nsize {size}\n")
+}
+
+#[test]
+fn lfs_pointer_unstaged_change() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(
+        &repo,
+        &temp_dir,
+        "big.psd",
+        &lfs_pointer("aaaa", 100),
+        "Add",
+    );
+    fs::write(temp_dir.path().join("big.psd"), lfs_pointer("bbbb", 200)).unwrap();
+
+    let diff = get_file_diff(&repo, "big.psd", false).unwrap();
+    assert!(diff.is_lfs_pointer);
+    assert!(!diff.is_binary);
+    assert!(diff.hunks.is_empty());
+    assert_eq!(diff.old_lfs.unwrap().oid, "sha256:aaaa");
+    let new = diff.new_lfs.unwrap();
+    assert_eq!(new.oid, "sha256:bbbb");
+    assert_eq!(new.size, 200);
+}
+
+#[test]
+fn lfs_pointer_staged_new_file() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "a\n", "Initial commit");
+    fs::write(temp_dir.path().join("big.psd"), lfs_pointer("cccc", 42)).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("big.psd")).unwrap();
+    index.write().unwrap();
+
+    let diff = get_file_diff(&repo, "big.psd", true).unwrap();
+    assert!(diff.is_lfs_pointer);
+    assert!(diff.old_lfs.is_none());
+    assert_eq!(diff.new_lfs.unwrap().size, 42);
+
+    // Ordinary text files are untouched.
+    fs::write(temp_dir.path().join("file.txt"), "b\n").unwrap();
+    let diff = get_file_diff(&repo, "file.txt", false).unwrap();
+    assert!(!diff.is_lfs_pointer);
+    assert!(!diff.hunks.is_empty());
+}
+
+// =============================================================================
+// Line endings and encoding (3 tests)
+// =============================================================================
+
+#[test]
+fn latin1_modification_reported_non_utf8() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "menu.txt", "cafe\n", "Initial commit");
+    fs::write(temp_dir.path().join("menu.txt"), b"caf\xe9\n").unwrap();
+
+    let diff = get_file_diff(&repo, "menu.txt", false).unwrap();
+    assert!(diff.is_non_utf8);
+    assert_eq!(diff.detected_encoding.as_deref(), Some("iso-8859-1"));
+    assert_eq!(diff.line_ending, LineEnding::Lf);
+
+    // The old side being plain ASCII doesn't count; only new-side lines do.
+    let diff = get_file_diff(&repo, "menu.txt", true).unwrap();
+    assert!(!diff.is_non_utf8);
+    assert_eq!(diff.detected_encoding, None);
+}
+
+#[test]
+fn crlf_files_report_line_endings() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "dos.txt", "one\r\n", "Initial commit");
+    fs::write(temp_dir.path().join("dos.txt"), "one\r\ntwo\r\n").unwrap();
+
+    let diff = get_file_diff(&repo, "dos.txt", false).unwrap();
+    assert_eq!(diff.line_ending, LineEnding::Crlf);
+    assert_eq!(diff.detected_encoding.as_deref(), Some("utf-8"));
+    assert!(!diff.is_non_utf8);
+
+    fs::write(temp_dir.path().join("mixed.txt"), "one\r\ntwo\n").unwrap();
+    let diff = get_untracked_file_diff(&repo, "mixed.txt").unwrap();
+    assert_eq!(diff.line_ending, LineEnding::Mixed);
+}
+
+#[test]
+fn large_untracked_non_utf8_file_is_text() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let mut content = Vec::new();
+    for _ in 0..100 {
+        content.extend_from_slice(b"\x93quoted\x94\r\n");
+    }
+    fs::write(temp_dir.path().join("notes.txt"), &content).unwrap();
+
+    let config = DiffConfig {
+        max_file_size: 64,
+        ..DiffConfig::default()
+    };
+    let diff = get_untracked_file_diff_with_config(&repo, "notes.txt", &config).unwrap();
+    assert!(!diff.is_binary);
+    assert_eq!(diff.total_lines, 100);
+    assert!(diff.is_non_utf8);
+    assert_eq!(diff.detected_encoding.as_deref(), Some("windows-1252"));
+    assert_eq!(diff.line_ending, LineEnding::Crlf);
+    assert_eq!(diff.hunks[0].lines[0].content, "\u{fffd}quoted\u{fffd}\n");
+}
+
+// =============================================================================
+// get_image_blobs (5 tests)
+// =============================================================================
+
+fn b64(bytes: &[u8]) -> String {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD.encode(bytes)
+}
+
+const PNG_V1: &[u8] = b"\x89PNG\r\n\x1a\nfirst";
+const PNG_V2: &[u8] = b"\x89PNG\r\n\x1a\nsecond version";
+
+#[test]
+fn get_image_blobs_working_tree_against_head() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("logo.PNG"), PNG_V1).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("logo.PNG")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Logo", &tree, &[&parent])
+        .unwrap();
+    fs::write(temp_dir.path().join("logo.PNG"), PNG_V2).unwrap();
+
+    let blobs = git::get_image_blobs(&repo, None, "logo.PNG", &DiffConfig::default()).unwrap();
+    assert_eq!(blobs.mime_type, "image/png");
+    assert_eq!(blobs.old_base64, Some(b64(PNG_V1)));
+    assert_eq!(blobs.new_base64, Some(b64(PNG_V2)));
+    assert!(!blobs.too_large);
+}
+
+#[test]
+fn get_image_blobs_for_commit_that_adds_image() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("photo.jpg"), PNG_V1).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("photo.jpg")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "Photo", &tree, &[&parent])
+        .unwrap();
+
+    let blobs = git::get_image_blobs(
+        &repo,
+        Some(&oid.to_string()),
+        "photo.jpg",
+        &DiffConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(blobs.mime_type, "image/jpeg");
+    assert_eq!(blobs.old_base64, None);
+    assert_eq!(blobs.new_base64, Some(b64(PNG_V1)));
+}
+
+#[test]
+fn get_image_blobs_respects_max_file_size() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("big.gif"), PNG_V2).unwrap();
+
+    let config = DiffConfig {
+        max_file_size: 8,
+        ..DiffConfig::default()
+    };
+    let blobs = git::get_image_blobs(&repo, None, "big.gif", &config).unwrap();
+    assert!(blobs.too_large);
+    assert_eq!(blobs.old_base64, None);
+    assert_eq!(blobs.new_base64, None);
+}
+
+#[test]
+fn get_image_blobs_rejects_non_images() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let result = git::get_image_blobs(&repo, None, "initial.txt", &DiffConfig::default());
+    assert!(matches!(result, Err(AppError::InvalidPath(_))));
+}
+
+#[test]
+fn get_image_blobs_rejects_paths_outside_repo() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let outside = tempfile::TempDir::new().unwrap();
+    let image = outside.path().join("secret.png");
+    fs::write(&image, PNG_V1).unwrap();
+    let dir_name = outside.path().file_name().unwrap().to_str().unwrap();
+
+    for path in [
+        format!("../{dir_name}/secret.png"),
+        image.to_str().unwrap().to_string(),
+    ] {
+        let err = git::get_image_blobs(&repo, None, &path, &DiffConfig::default()).unwrap_err();
+        assert!(
+            matches!(&err, AppError::InvalidPath(p) if *p == path),
+            "{err:?}"
+        );
+        assert_eq!(err.to_string(), format!("Invalid path: {path}"));
+    }
+}
+
 // =============================================================================
 // Line numbers + truncation (5 tests)
 // =============================================================================
@@ -304,7 +976,7 @@ fn large_file_diff_truncation() {
         max_file_size: 1_048_576,
     };
 
-    let diff = get_file_diff_with_config(&repo, "big.txt", false, &config).unwrap();
+    let diff = get_file_diff_with_config(&repo, "big.txt", false, &config, false, None).unwrap();
     assert!(!diff.hunks.is_empty());
 
     let loaded_count = diff.hunks.iter().filter(|h| h.is_loaded).count();
@@ -397,7 +1069,158 @@ fn commit_diff_truncation() {
 }
 
 // =============================================================================
-// get_diff_hunk single-hunk loaders (4 tests)
+// get_file_at_commit (4 tests)
+// =============================================================================
+
+#[test]
+fn get_file_at_commit_returns_historical_content() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_commit_with_file(&repo, &temp_dir, "file.txt", "v1\n", "First");
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "v2\n", "Second");
+
+    let file = git::get_file_at_commit(&repo, &first.to_string(), "file.txt").unwrap();
+    assert_eq!(file.path, "file.txt");
+    assert_eq!(file.content, "v1\n");
+    assert!(!file.is_binary);
+}
+
+#[test]
+fn get_file_at_commit_detects_binary() {
+    let (temp_dir, repo) = create_test_repo();
+    fs::write(temp_dir.path().join("image.bin"), [0x89, b'P', 0, 0, 1]).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("image.bin")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "Add binary", &tree, &[])
+        .unwrap();
+
+    let file = git::get_file_at_commit(&repo, &oid.to_string(), "image.bin").unwrap();
+    assert!(file.is_binary);
+    assert!(file.content.is_empty());
+}
+
+#[test]
+fn get_file_at_commit_missing_path_errors() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_initial_commit(&repo, &temp_dir);
+
+    let result = git::get_file_at_commit(&repo, &oid.to_string(), "nope.txt");
+    assert!(
+        matches!(result, Err(AppError::InvalidPath(msg)) if msg.contains("nope.txt does not exist"))
+    );
+}
+
+#[test]
+fn get_file_at_commit_rejects_directory() {
+    let (temp_dir, repo) = create_test_repo();
+    fs::create_dir(temp_dir.path().join("src")).unwrap();
+    let oid = create_commit_with_file(&repo, &temp_dir, "src/main.rs", "fn main() {}\n", "Add");
+
+    let result = git::get_file_at_commit(&repo, &oid.to_string(), "src");
+    assert!(matches!(result, Err(AppError::InvalidPath(_))));
+}
+
+// =============================================================================
+// list_tree (4 tests)
+// =============================================================================
+
+fn commit_nested_tree(repo: &git2::Repository, temp_dir: &tempfile::TempDir) -> git2::Oid {
+    fs::create_dir_all(temp_dir.path().join("src/bin")).unwrap();
+    fs::write(temp_dir.path().join("README.md"), "readme\n").unwrap();
+    fs::write(temp_dir.path().join("src/lib.rs"), "\n").unwrap();
+    fs::write(temp_dir.path().join("src/bin/tool.rs"), "\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Layout", &tree, &[])
+        .unwrap()
+}
+
+#[test]
+fn list_tree_root() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = commit_nested_tree(&repo, &temp_dir);
+
+    let entries = git::list_tree(&repo, &oid.to_string(), None).unwrap();
+    let summary: Vec<(&str, git::TreeEntryKind, u32)> = entries
+        .iter()
+        .map(|e| (e.path.as_str(), e.kind, e.mode))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("README.md", git::TreeEntryKind::Blob, 0o100644),
+            ("src", git::TreeEntryKind::Tree, 0o040000),
+        ]
+    );
+}
+
+#[test]
+fn list_tree_nested_directory() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = commit_nested_tree(&repo, &temp_dir);
+
+    let entries = git::list_tree(&repo, &oid.to_string(), Some("src/")).unwrap();
+    let summary: Vec<(&str, &str, git::TreeEntryKind)> = entries
+        .iter()
+        .map(|e| (e.name.as_str(), e.path.as_str(), e.kind))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("bin", "src/bin", git::TreeEntryKind::Tree),
+            ("lib.rs", "src/lib.rs", git::TreeEntryKind::Blob),
+        ]
+    );
+}
+
+#[test]
+fn list_tree_marks_submodule_gitlinks() {
+    let (temp_dir, repo) = create_test_repo();
+    let base = create_initial_commit(&repo, &temp_dir);
+
+    // A gitlink only needs a commit id; it doesn't have to exist locally.
+    let mut builder = repo
+        .treebuilder(Some(&repo.find_commit(base).unwrap().tree().unwrap()))
+        .unwrap();
+    builder.insert("vendor", base, 0o160000).unwrap();
+    let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let parent = repo.find_commit(base).unwrap();
+    let oid = repo
+        .commit(Some("HEAD"), &sig, &sig, "Add submodule", &tree, &[&parent])
+        .unwrap();
+
+    let entries = git::list_tree(&repo, &oid.to_string(), None).unwrap();
+    let vendor = entries.iter().find(|e| e.name == "vendor").unwrap();
+    assert_eq!(vendor.kind, git::TreeEntryKind::Submodule);
+    assert_eq!(vendor.mode, 0o160000);
+}
+
+#[test]
+fn list_tree_rejects_missing_or_file_dir() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = commit_nested_tree(&repo, &temp_dir).to_string();
+
+    assert!(matches!(
+        git::list_tree(&repo, &oid, Some("nope")),
+        Err(AppError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        git::list_tree(&repo, &oid, Some("README.md")),
+        Err(AppError::InvalidPath(_))
+    ));
+}
+
+// =============================================================================
+// get_diff_hunk single-hunk loaders (6 tests)
 // =============================================================================
 
 #[test]
@@ -408,7 +1231,7 @@ fn get_diff_hunk_loads_single() {
     let file_path = temp_dir.path().join("file.txt");
     fs::write(&file_path, "line1\nmodified\n").unwrap();
 
-    let hunk = get_diff_hunk(&repo, "file.txt", false, 0).unwrap();
+    let hunk = get_diff_hunk(&repo, "file.txt", false, 0, false, None).unwrap();
     assert!(hunk.is_loaded);
     assert!(!hunk.lines.is_empty());
 }
@@ -421,7 +1244,7 @@ fn get_diff_hunk_out_of_range() {
     let file_path = temp_dir.path().join("file.txt");
     fs::write(&file_path, "modified\n").unwrap();
 
-    let result = get_diff_hunk(&repo, "file.txt", false, 99);
+    let result = get_diff_hunk(&repo, "file.txt", false, 99, false, None);
     assert!(result.is_err());
 }
 
@@ -463,6 +1286,133 @@ fn get_commit_diff_hunk_loads_single() {
     assert!(!hunk.lines.is_empty());
 }
 
+/// 200 lines where every tenth is replaced by a 50-line block, giving many
+/// hunks so a small byte budget leaves the later ones unloaded.
+fn many_hunks_contents() -> (String, String) {
+    let mut original = String::new();
+    let mut modified = String::new();
+    for i in 0..200 {
+        original.push_str(&format!("original line {}\n", i));
+        if i % 10 == 0 {
+            for j in 0..50 {
+                modified.push_str(&format!("added content {} for block {}\n", j, i));
+            }
+        } else {
+            modified.push_str(&format!("original line {}\n", i));
+        }
+    }
+    (original, modified)
+}
+
+#[test]
+fn get_diff_hunk_loads_truncated_hunk() {
+    let (temp_dir, repo) = create_test_repo();
+    let (original, modified) = many_hunks_contents();
+    create_commit_with_file(&repo, &temp_dir, "big.txt", &original, "Initial");
+    fs::write(temp_dir.path().join("big.txt"), &modified).unwrap();
+
+    let config = DiffConfig {
+        max_diff_bytes: 512,
+        max_file_size: 1_048_576,
+    };
+    let diff = get_file_diff_with_config(&repo, "big.txt", false, &config, false, None).unwrap();
+    let index = diff.hunks.iter().position(|h| !h.is_loaded).unwrap();
+
+    let hunk = get_diff_hunk(&repo, "big.txt", false, index, false, None).unwrap();
+    assert!(hunk.is_loaded);
+    assert_eq!(hunk.header, diff.hunks[index].header);
+    let added = hunk
+        .lines
+        .iter()
+        .filter(|l| matches!(l.line_type, LineType::Addition))
+        .count();
+    assert_eq!(added, 50);
+}
+
+#[test]
+fn get_commit_diff_hunk_loads_truncated_hunk() {
+    let (temp_dir, repo) = create_test_repo();
+    let (original, modified) = many_hunks_contents();
+    create_commit_with_file(&repo, &temp_dir, "big.txt", &original, "Initial");
+    let oid = create_commit_with_file(&repo, &temp_dir, "big.txt", &modified, "Big change");
+
+    let config = DiffConfig {
+        max_diff_bytes: 512,
+        max_file_size: 1_048_576,
+    };
+    let diff =
+        get_commit_file_diff_with_config(&repo, &oid.to_string(), "big.txt", &config).unwrap();
+    let index = diff.hunks.iter().position(|h| !h.is_loaded).unwrap();
+
+    let hunk = get_commit_diff_hunk(&repo, &oid.to_string(), "big.txt", index).unwrap();
+    assert!(hunk.is_loaded);
+    assert_eq!(hunk.header, diff.hunks[index].header);
+    assert!(!hunk.lines.is_empty());
+}
+
+// =============================================================================
+// ignore_whitespace (3 tests)
+// =============================================================================
+
+#[test]
+fn get_file_diff_ignore_whitespace_hides_indentation_churn() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "f.rs", "fn a() {\nx();\n}\n", "Initial");
+    fs::write(temp_dir.path().join("f.rs"), "fn a() {\n    x();\n}\n").unwrap();
+
+    let normal =
+        get_file_diff_with_config(&repo, "f.rs", false, &DiffConfig::default(), false, None)
+            .unwrap();
+    assert!(!normal.hunks.is_empty());
+    assert!(!normal.whitespace_ignored);
+
+    let ignored =
+        get_file_diff_with_config(&repo, "f.rs", false, &DiffConfig::default(), true, None)
+            .unwrap();
+    assert!(ignored.hunks.is_empty());
+    assert!(ignored.whitespace_ignored);
+}
+
+#[test]
+fn get_file_diff_ignore_whitespace_keeps_real_changes() {
+    let (temp_dir, repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "f.txt", "a\nb  \nc\n", "Initial");
+    fs::write(temp_dir.path().join("f.txt"), "a\nb\nC\n").unwrap();
+
+    let diff = get_file_diff_with_config(&repo, "f.txt", false, &DiffConfig::default(), true, None)
+        .unwrap();
+
+    assert_eq!(diff.hunks.len(), 1);
+    let changed: Vec<&str> = diff.hunks[0]
+        .lines
+        .iter()
+        .filter(|l| matches!(l.line_type, LineType::Addition | LineType::Deletion))
+        .map(|l| l.content.as_str())
+        .collect();
+    assert_eq!(changed, vec!["c\n", "C\n"]);
+}
+
+#[test]
+fn get_diff_hunk_ignore_whitespace_matches_file_diff() {
+    let (temp_dir, repo) = create_test_repo();
+    let body: String = (0..30).map(|i| format!("line{i}\n")).collect();
+    create_commit_with_file(&repo, &temp_dir, "f.txt", &body, "Initial");
+    // Whitespace-only edit near the top, real edit near the bottom: with
+    // whitespace ignored, hunk 0 is the real edit.
+    let edited = body
+        .replace("line1\n", "  line1\n")
+        .replace("line28\n", "changed\n");
+    fs::write(temp_dir.path().join("f.txt"), edited).unwrap();
+
+    let hunk = get_diff_hunk(&repo, "f.txt", false, 0, true, None).unwrap();
+
+    assert!(hunk
+        .lines
+        .iter()
+        .any(|l| l.line_type == LineType::Addition && l.content == "changed\n"));
+    assert!(get_diff_hunk(&repo, "f.txt", false, 1, true, None).is_err());
+}
+
 // =============================================================================
 // Conflict diffs (8 tests)
 // =============================================================================
@@ -771,7 +1721,7 @@ fn commands_diff_get_diff_hunk_routes_to_unstaged_when_flags_unset() {
     let file_path = temp_dir.path().join("initial.txt");
     fs::write(&file_path, "changed\n").unwrap();
 
-    let result = get_diff_hunk(&repo, "initial.txt", false, 0);
+    let result = get_diff_hunk(&repo, "initial.txt", false, 0, false, None);
     assert!(
         result.is_ok(),
         "default routing should succeed, got {result:?}"
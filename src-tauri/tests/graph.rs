@@ -14,7 +14,9 @@ use std::path::Path;
 use std::sync::atomic::{AtomicI64, Ordering};
 use tempfile::TempDir;
 use yagg_lib::git::{
-    build_commit_graph, collect_refs, CommitInfo, GraphCommit, GraphLineType, RefType,
+    build_commit_graph, collect_refs, collect_refs_cached, get_all_commits, get_commit_graph_page,
+    get_commit_graph_page_cached, refresh_refs, refs_at_commit, CommitInfo, GraphCommit,
+    GraphLineType, GraphPageCache, RefType, RefsCache,
 };
 
 /// Monotonically increasing timestamp so commits created in rapid succession
@@ -72,7 +74,12 @@ fn commits_from_oids(repo: &Repository, tip_oids: &[git2::Oid]) -> Vec<CommitInf
             message: commit.message().unwrap_or("").to_string(),
             author_name: "Test".to_string(),
             author_email: "test@test.com".to_string(),
+            author_email_hash: String::new(),
             timestamp: commit.time().seconds(),
+            author_time: commit.author().when().seconds(),
+            author_offset_minutes: commit.author().when().offset_minutes(),
+            commit_time: commit.time().seconds(),
+            commit_offset_minutes: commit.time().offset_minutes(),
             parent_hashes,
         });
     }
@@ -200,6 +207,133 @@ fn test_collect_refs_tags() {
     assert!(has_tag);
 }
 
+#[test]
+fn test_collect_refs_annotated_tag_on_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_commit_with_file(&repo, &temp_dir, "file.txt", "content", "Initial commit");
+    let sig = repo.signature().unwrap();
+    repo.tag(
+        "v1.2",
+        repo.find_commit(oid).unwrap().as_object(),
+        &sig,
+        "Release",
+        false,
+    )
+    .unwrap();
+
+    let refs = collect_refs(&repo).unwrap();
+
+    assert!(refs[&oid.to_string()].iter().any(|r| r.name == "v1.2"));
+}
+
+#[test]
+fn test_refs_at_commit_branch_and_tag() {
+    let (temp_dir, repo) = create_test_repo();
+    let base = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Base");
+    let base_commit = repo.find_commit(base).unwrap();
+    repo.branch("release", &base_commit, false).unwrap();
+    let sig = repo.signature().unwrap();
+    repo.tag("v1.2", base_commit.as_object(), &sig, "Release", false)
+        .unwrap();
+    let tip = create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "Tip");
+
+    let mut refs = refs_at_commit(&repo, &base.to_string()[..7]).unwrap();
+    refs.sort_by(|a, b| a.name.cmp(&b.name));
+    let names: Vec<_> = refs.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(names, ["release", "v1.2"]);
+    assert!(matches!(refs[0].ref_type, RefType::Branch));
+    assert!(matches!(refs[1].ref_type, RefType::Tag));
+    assert!(refs.iter().all(|r| !r.is_head));
+
+    let tip_refs = refs_at_commit(&repo, &tip.to_string()).unwrap();
+    assert!(tip_refs.iter().any(|r| r.is_head));
+    assert!(tip_refs.iter().all(|r| !matches!(r.ref_type, RefType::Tag)));
+
+    assert!(refs_at_commit(&repo, "does-not-exist").is_err());
+}
+
+#[test]
+fn test_collect_refs_cached_sees_new_branch() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Base");
+    let mut cache = RefsCache::default();
+
+    let before = collect_refs_cached(&repo, &mut cache).unwrap();
+    assert!(!before[&oid.to_string()].iter().any(|r| r.name == "topic"));
+
+    repo.branch("topic", &repo.find_commit(oid).unwrap(), false)
+        .unwrap();
+
+    let after = collect_refs_cached(&repo, &mut cache).unwrap();
+    assert!(after[&oid.to_string()].iter().any(|r| r.name == "topic"));
+}
+
+#[test]
+fn test_graph_page_cached_follows_moved_branch() {
+    let (temp_dir, repo) = create_test_repo();
+    let base = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Base");
+    repo.branch("topic", &repo.find_commit(base).unwrap(), false)
+        .unwrap();
+    let tip = create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "Tip");
+    let mut cache = RefsCache::default();
+    let topic_row = |graph: &[GraphCommit]| {
+        graph
+            .iter()
+            .position(|c| c.refs.iter().any(|r| r.name == "topic"))
+    };
+
+    let graph = get_commit_graph_page_cached(
+        &repo,
+        0,
+        10,
+        None,
+        usize::MAX,
+        &mut cache,
+        &mut GraphPageCache::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        graph[topic_row(&graph).unwrap()].commit.hash,
+        base.to_string()
+    );
+
+    repo.reference("refs/heads/topic", tip, true, "move topic")
+        .unwrap();
+
+    let graph = get_commit_graph_page_cached(
+        &repo,
+        0,
+        10,
+        None,
+        usize::MAX,
+        &mut cache,
+        &mut GraphPageCache::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        graph[topic_row(&graph).unwrap()].commit.hash,
+        tip.to_string()
+    );
+}
+
+#[test]
+fn test_refresh_refs_refills_cache() {
+    let (temp_dir, repo) = create_test_repo();
+    let oid = create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "Base");
+    let mut cache = RefsCache::default();
+
+    refresh_refs(&repo, &mut cache).unwrap();
+
+    let refs = collect_refs_cached(&repo, &mut cache).unwrap();
+    let mut cached: Vec<_> = refs.keys().collect();
+    let fresh = collect_refs(&repo).unwrap();
+    let mut expected: Vec<_> = fresh.keys().collect();
+    cached.sort();
+    expected.sort();
+    assert_eq!(cached, expected);
+    assert!(refs[&oid.to_string()].iter().any(|r| r.is_head));
+}
+
 #[test]
 fn test_collect_refs_head() {
     let (temp_dir, repo) = create_test_repo();
@@ -354,12 +488,17 @@ fn test_matches_git_log_graph_merge() {
             message: commit.message().unwrap_or("").to_string(),
             author_name: "Test".to_string(),
             author_email: "test@test.com".to_string(),
+            author_email_hash: String::new(),
             timestamp: commit.time().seconds(),
+            author_time: commit.author().when().seconds(),
+            author_offset_minutes: commit.author().when().offset_minutes(),
+            commit_time: commit.time().seconds(),
+            commit_offset_minutes: commit.time().offset_minutes(),
             parent_hashes,
         });
     }
 
-    let graph = build_commit_graph(commits, HashMap::new());
+    let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
 
     // Verify: main-line commits should all be in column 0
     // (matching git log --graph where main branch is leftmost)
@@ -440,7 +579,7 @@ fn test_integration_sequential_pr_merges() {
     );
 
     let commits = commits_from_oids(&repo, &[merge3]);
-    let graph = build_commit_graph(commits, HashMap::new());
+    let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
 
     // All merges + base at col 0
     for gc in &graph {
@@ -489,7 +628,7 @@ fn test_integration_overlapping_branches() {
     );
 
     let commits = commits_from_oids(&repo, &[merge_b]);
-    let graph = build_commit_graph(commits, HashMap::new());
+    let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
 
     // feat_a and feat_b should be in different columns
     let feat_a_col = graph
@@ -542,7 +681,7 @@ fn test_integration_deep_diamond() {
     );
 
     let commits = commits_from_oids(&repo, &[top_merge]);
-    let graph = build_commit_graph(commits, HashMap::new());
+    let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
 
     // top_merge at col 0
     let top = graph
@@ -601,7 +740,7 @@ fn test_integration_long_running_feature() {
     );
 
     let commits = commits_from_oids(&repo, &[final_merge]);
-    let graph = build_commit_graph(commits, HashMap::new());
+    let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
 
     // final_merge at col 0
     let fm = graph
@@ -665,7 +804,7 @@ fn test_integration_tags_and_refs() {
 
     let branch_refs = collect_refs(&repo).unwrap();
     let commits = commits_from_oids(&repo, &[feat3, feat2a]);
-    let graph = build_commit_graph(commits, branch_refs);
+    let graph = build_commit_graph(commits, branch_refs, usize::MAX);
 
     // Check tag refs
     let base_gc = graph
@@ -741,7 +880,7 @@ fn test_integration_octopus_merge() {
     );
 
     let commits = commits_from_oids(&repo, &[octopus]);
-    let graph = build_commit_graph(commits, HashMap::new());
+    let graph = build_commit_graph(commits, HashMap::new(), usize::MAX);
 
     // octopus has 2 merge lines (parents 2 and 3)
     let oct_gc = graph
@@ -789,7 +928,7 @@ fn test_integration_fast_forward_refs() {
 
     let branch_refs = collect_refs(&repo).unwrap();
     let commits = commits_from_oids(&repo, &[c3]);
-    let graph = build_commit_graph(commits, branch_refs);
+    let graph = build_commit_graph(commits, branch_refs, usize::MAX);
 
     // All col 0
     for gc in &graph {
@@ -821,3 +960,298 @@ fn test_integration_fast_forward_refs() {
 
     validate_graph_invariants(&graph);
 }
+
+// ============================================================================
+// get_commit_graph_page (10 tests)
+// ============================================================================
+
+/// main: base - m1..m6, feature: base - f1..f3 with commit times interleaved,
+/// so the feature column is opened near the top and stays active for most
+/// of the history.
+fn interleaved_branches_repo() -> (TempDir, Repository) {
+    let (temp_dir, repo) = create_test_repo();
+    let base = commit_with_parents(&repo, &temp_dir, &[], "base.txt", "base", "base");
+    let mut main_tip = base;
+    let mut feature_tip = base;
+    for i in 0..6 {
+        main_tip = commit_with_parents(
+            &repo,
+            &temp_dir,
+            &[main_tip],
+            "main.txt",
+            &i.to_string(),
+            &format!("m{i}"),
+        );
+        if i < 3 {
+            feature_tip = commit_with_parents(
+                &repo,
+                &temp_dir,
+                &[feature_tip],
+                "feature.txt",
+                &i.to_string(),
+                &format!("f{i}"),
+            );
+        }
+    }
+    repo.branch("main", &repo.find_commit(main_tip).unwrap(), true)
+        .unwrap();
+    repo.branch("feature", &repo.find_commit(feature_tip).unwrap(), true)
+        .unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    (temp_dir, repo)
+}
+
+fn full_graph(repo: &Repository, max_columns: usize) -> Vec<GraphCommit> {
+    build_commit_graph(
+        get_all_commits(repo, None).unwrap(),
+        collect_refs(repo).unwrap(),
+        max_columns,
+    )
+}
+
+#[test]
+fn test_graph_page_columns_match_full_graph() {
+    let (_temp_dir, repo) = interleaved_branches_repo();
+    let full = full_graph(&repo, usize::MAX);
+    assert_eq!(full.len(), 10);
+    assert!(full.iter().any(|gc| gc.column > 0));
+
+    for skip in 0..full.len() {
+        let page = get_commit_graph_page(&repo, skip, 3, None, usize::MAX).unwrap();
+        let expected = &full[skip..(skip + 3).min(full.len())];
+        assert_eq!(page.len(), expected.len(), "skip {skip}");
+        for (got, want) in page.iter().zip(expected) {
+            assert_eq!(got.commit.hash, want.commit.hash, "skip {skip}");
+            assert_eq!(
+                got.column, want.column,
+                "skip {skip}: {} changed column",
+                want.commit.message
+            );
+            assert_eq!(got.is_tip, want.is_tip, "skip {skip}");
+            assert_eq!(got.branch_id, want.branch_id, "skip {skip}");
+        }
+    }
+}
+
+#[test]
+fn test_graph_pages_with_shared_cache_match_full_graph() {
+    let (_temp_dir, repo) = interleaved_branches_repo();
+    let full = full_graph(&repo, usize::MAX);
+    let mut refs_cache = RefsCache::default();
+    let mut pages = GraphPageCache::default();
+    let mut page = |skip: usize| {
+        get_commit_graph_page_cached(
+            &repo,
+            skip,
+            3,
+            None,
+            usize::MAX,
+            &mut refs_cache,
+            &mut pages,
+        )
+        .unwrap()
+    };
+
+    // Scrolling down resumes from each page's end; the jumps back and into
+    // the middle of a page resume from an earlier boundary.
+    for skip in [0, 3, 6, 9, 0, 4, 8] {
+        let got = page(skip);
+        let expected = &full[skip..(skip + 3).min(full.len())];
+        assert_eq!(got.len(), expected.len(), "skip {skip}");
+        for (got, want) in got.iter().zip(expected) {
+            assert_eq!(got.commit.hash, want.commit.hash, "skip {skip}");
+            assert_eq!(got.column, want.column, "skip {skip}");
+            assert_eq!(got.branch_id, want.branch_id, "skip {skip}");
+            assert_eq!(got.is_tip, want.is_tip, "skip {skip}");
+        }
+    }
+}
+
+#[test]
+fn test_graph_page_cache_resets_when_refs_move() {
+    let (temp_dir, repo) = interleaved_branches_repo();
+    let mut refs_cache = RefsCache::default();
+    let mut pages = GraphPageCache::default();
+    get_commit_graph_page_cached(&repo, 0, 3, None, usize::MAX, &mut refs_cache, &mut pages)
+        .unwrap();
+
+    let main_tip = repo.head().unwrap().target().unwrap();
+    let new_tip = commit_with_parents(&repo, &temp_dir, &[main_tip], "n.txt", "n", "new");
+    repo.reference("refs/heads/main", new_tip, true, "test")
+        .unwrap();
+
+    let page =
+        get_commit_graph_page_cached(&repo, 0, 3, None, usize::MAX, &mut refs_cache, &mut pages)
+            .unwrap();
+    let full = full_graph(&repo, usize::MAX);
+    assert_eq!(page[0].commit.hash, new_tip.to_string());
+    for (got, want) in page.iter().zip(&full) {
+        assert_eq!(got.commit.hash, want.commit.hash);
+        assert_eq!(got.column, want.column);
+    }
+}
+
+#[test]
+fn test_graph_page_overflow_matches_full_graph() {
+    let (_temp_dir, repo) = interleaved_branches_repo();
+    let full = full_graph(&repo, 1);
+    assert!(full.iter().all(|gc| gc.column <= 1));
+    assert!(full.iter().any(|gc| gc.overflow));
+
+    for skip in 0..full.len() {
+        let page = get_commit_graph_page(&repo, skip, 3, None, 1).unwrap();
+        for (got, want) in page.iter().zip(&full[skip..]) {
+            assert_eq!(got.commit.hash, want.commit.hash, "skip {skip}");
+            assert_eq!(got.column, want.column, "skip {skip}");
+            assert_eq!(got.overflow, want.overflow, "skip {skip}");
+        }
+    }
+}
+
+#[test]
+fn test_graph_page_branch_from_above_is_not_tip() {
+    let (_temp_dir, repo) = interleaved_branches_repo();
+    let full = full_graph(&repo, usize::MAX);
+    let f0_row = full
+        .iter()
+        .position(|gc| gc.commit.message == "f0")
+        .unwrap();
+
+    // Start the page on f0, whose branch tip sits on an earlier page.
+    let page = get_commit_graph_page(&repo, f0_row, 2, None, usize::MAX).unwrap();
+
+    assert_eq!(page[0].commit.message, "f0");
+    assert!(!page[0].is_tip);
+    assert_eq!(page[0].column, full[f0_row].column);
+    assert!(page[0].lines.iter().any(|l| {
+        matches!(l.line_type, GraphLineType::FromAbove) && l.from_column == page[0].column
+    }));
+}
+
+#[test]
+fn test_graph_page_past_end_is_empty() {
+    let (_temp_dir, repo) = interleaved_branches_repo();
+
+    assert!(get_commit_graph_page(&repo, 50, 10, None, usize::MAX)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        get_commit_graph_page(&repo, 8, 10, None, usize::MAX)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+/// Write a commit object directly, so it can name a parent that doesn't
+/// exist or carry a time older than `commit_with_parents` hands out.
+fn raw_commit(repo: &Repository, parent: Option<&str>, time: i64, message: &str) -> git2::Oid {
+    let tree = repo.treebuilder(None).unwrap().write().unwrap();
+    let parent = parent.map(|p| format!("parent {p}\n")).unwrap_or_default();
+    let raw = format!(
+        "tree {tree}\n\
+         {parent}\
+         author T <t@example.com> {time} +0000\n\
+         committer T <t@example.com> {time} +0000\n\
+         \n\
+         {message}\n"
+    );
+    repo.odb()
+        .unwrap()
+        .write(git2::ObjectType::Commit, raw.as_bytes())
+        .unwrap()
+}
+
+#[test]
+fn test_graph_shallow_clone_has_no_dangling_lines() {
+    let (temp_dir, repo) = create_test_repo();
+    // A commit whose parent object was never fetched, listed in
+    // .git/shallow as a shallow clone's boundary commits are.
+    let edge = raw_commit(
+        &repo,
+        Some("1111111111111111111111111111111111111111"),
+        1_700_000_000,
+        "Shallow edge",
+    );
+    repo.reference("refs/heads/main", edge, true, "test")
+        .unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    let tip = commit_with_parents(&repo, &temp_dir, &[edge], "a.txt", "a", "tip");
+    repo.reference("refs/heads/main", tip, true, "test")
+        .unwrap();
+    fs::write(repo.path().join("shallow"), format!("{edge}\n")).unwrap();
+    let repo = Repository::open(temp_dir.path()).unwrap();
+
+    for graph in [
+        full_graph(&repo, usize::MAX),
+        get_commit_graph_page(&repo, 0, 10, None, usize::MAX).unwrap(),
+    ] {
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph[1].commit.hash, edge.to_string());
+        // The boundary commit ends the history like a root would.
+        assert!(!graph[1]
+            .lines
+            .iter()
+            .any(|l| matches!(l.line_type, GraphLineType::ToParent)));
+        validate_graph_invariants(&graph);
+    }
+}
+
+#[test]
+fn test_graph_pages_in_shallow_clone_end_at_boundary() {
+    let (temp_dir, repo) = create_test_repo();
+    let edge = raw_commit(
+        &repo,
+        Some("1111111111111111111111111111111111111111"),
+        1_690_000_000,
+        "Shallow edge",
+    );
+    // An unrelated, older history below the edge, so a lane left open for
+    // the missing parent would run on through its rows.
+    let old = raw_commit(&repo, None, 1_600_000_000, "old root");
+    repo.reference("refs/heads/old", old, true, "test").unwrap();
+    repo.reference("refs/heads/main", edge, true, "test")
+        .unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    let tip = commit_with_parents(&repo, &temp_dir, &[edge], "a.txt", "a", "tip");
+    repo.reference("refs/heads/main", tip, true, "test")
+        .unwrap();
+    fs::write(repo.path().join("shallow"), format!("{edge}\n")).unwrap();
+    let repo = Repository::open(temp_dir.path()).unwrap();
+
+    let full = full_graph(&repo, usize::MAX);
+    assert_eq!(full.len(), 3);
+    assert_eq!(full[1].commit.hash, edge.to_string());
+    assert!(!full[1]
+        .lines
+        .iter()
+        .any(|l| matches!(l.line_type, GraphLineType::ToParent)));
+    assert!(full[2].lines.is_empty());
+    for (skip, want) in full.iter().enumerate() {
+        let page = get_commit_graph_page(&repo, skip, 1, None, usize::MAX).unwrap();
+        assert_eq!(page[0].commit.hash, want.commit.hash);
+        assert_eq!(page[0].column, want.column, "skip {skip}");
+        assert_eq!(
+            format!("{:?}", page[0].lines),
+            format!("{:?}", want.lines),
+            "skip {skip}"
+        );
+    }
+}
+
+#[test]
+fn test_graph_page_reports_broken_walk() {
+    let (_temp_dir, repo) = create_test_repo();
+    // A parent that was never fetched and no .git/shallow to excuse it.
+    let orphan = raw_commit(
+        &repo,
+        Some("1111111111111111111111111111111111111111"),
+        1_690_000_000,
+        "orphan",
+    );
+    repo.reference("refs/heads/main", orphan, true, "test")
+        .unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+
+    assert!(get_commit_graph_page(&repo, 0, 10, None, usize::MAX).is_err());
+}
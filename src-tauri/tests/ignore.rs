@@ -0,0 +1,111 @@
+//! Integration tests for `.gitignore` editing.
+
+mod common;
+
+use common::{create_initial_commit, create_test_repo};
+use std::fs;
+use yagg_lib::error::AppError;
+use yagg_lib::git::{add_to_gitignore, get_file_statuses, is_path_ignored, FileStatusType};
+
+#[test]
+fn add_to_gitignore_creates_file() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("debug.log"), "noise").unwrap();
+
+    let patterns = add_to_gitignore(&repo, "debug.log").unwrap();
+
+    assert_eq!(patterns, vec!["debug.log"]);
+    let content = fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
+    assert_eq!(content, "debug.log\n");
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(!statuses.untracked.iter().any(|s| s.path == "debug.log"));
+}
+
+#[test]
+fn add_to_gitignore_appends_to_file_without_trailing_newline() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let gitignore = temp_dir.path().join(".gitignore");
+    fs::write(&gitignore, "# build output\ntarget/").unwrap();
+
+    let patterns = add_to_gitignore(&repo, "*.tmp").unwrap();
+
+    assert_eq!(patterns, vec!["target/", "*.tmp"]);
+    assert_eq!(
+        fs::read_to_string(&gitignore).unwrap(),
+        "# build output\ntarget/\n*.tmp\n"
+    );
+}
+
+#[test]
+fn add_to_gitignore_skips_duplicates() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let gitignore = temp_dir.path().join(".gitignore");
+    fs::write(&gitignore, "target/\r\n*.tmp\r\n").unwrap();
+
+    let patterns = add_to_gitignore(&repo, "*.tmp").unwrap();
+
+    assert_eq!(patterns, vec!["target/", "*.tmp"]);
+    assert_eq!(
+        fs::read_to_string(&gitignore).unwrap(),
+        "target/\r\n*.tmp\r\n"
+    );
+
+    add_to_gitignore(&repo, "dist/").unwrap();
+    assert_eq!(
+        fs::read_to_string(&gitignore).unwrap(),
+        "target/\r\n*.tmp\r\ndist/\r\n"
+    );
+}
+
+#[test]
+fn add_to_gitignore_rejects_blank_or_multiline_pattern() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(matches!(
+        add_to_gitignore(&repo, "   "),
+        Err(AppError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        add_to_gitignore(&repo, "a\nb"),
+        Err(AppError::InvalidPath(_))
+    ));
+    assert!(!temp_dir.path().join(".gitignore").exists());
+}
+
+#[test]
+fn is_path_ignored_follows_gitignore() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join(".gitignore"), "*.log\nbuild/\n").unwrap();
+
+    assert!(is_path_ignored(&repo, "debug.log").unwrap());
+    assert!(is_path_ignored(&repo, "build/out.bin").unwrap());
+    assert!(!is_path_ignored(&repo, "initial.txt").unwrap());
+    assert!(!is_path_ignored(&repo, "src/main.rs").unwrap());
+}
+
+#[test]
+fn get_file_statuses_reports_ignored_only_when_requested() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join(".gitignore"), "*.log\n").unwrap();
+    fs::write(temp_dir.path().join("debug.log"), "noise").unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.ignored.is_empty());
+    assert!(!statuses.untracked.iter().any(|s| s.path == "debug.log"));
+
+    let statuses = get_file_statuses(&repo, true).unwrap();
+    assert_eq!(statuses.ignored.len(), 1);
+    assert_eq!(statuses.ignored[0].path, "debug.log");
+    assert!(matches!(
+        statuses.ignored[0].status,
+        FileStatusType::Ignored
+    ));
+    assert!(!statuses.untracked.iter().any(|s| s.path == "debug.log"));
+    assert!(statuses.untracked.iter().any(|s| s.path == ".gitignore"));
+}
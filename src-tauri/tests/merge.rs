@@ -0,0 +1,225 @@
+//! Integration tests for `merge_branch`: fast-forward, true merge, and the
+//! conflicting case that must stop before committing and finish through
+//! `continue_operation`.
+
+mod common;
+
+use common::{create_commit_with_file, create_initial_commit, create_test_repo};
+use git2::{build::CheckoutBuilder, Repository, RepositoryState};
+use std::fs;
+use tempfile::TempDir;
+use yagg_lib::error::AppError;
+use yagg_lib::git::operations::continue_operation;
+use yagg_lib::git::{merge_base, merge_base_many, merge_branch, MergeKind};
+
+fn head_branch(repo: &Repository) -> String {
+    repo.head().unwrap().shorthand().unwrap().to_string()
+}
+
+/// Point HEAD at `branch` and force the working tree to match it.
+fn switch_to(repo: &Repository, branch: &str) {
+    repo.set_head(&format!("refs/heads/{branch}")).unwrap();
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))
+        .unwrap();
+}
+
+/// Create `feature` at the current HEAD, commit `file` on it, then switch
+/// back to the original branch.
+fn branch_with_commit(
+    repo: &Repository,
+    temp_dir: &TempDir,
+    file: &str,
+    content: &str,
+) -> (String, git2::Oid) {
+    let main = head_branch(repo);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &head, false).unwrap();
+    switch_to(repo, "feature");
+    let tip = create_commit_with_file(repo, temp_dir, file, content, "feature work");
+    switch_to(repo, &main);
+    (main, tip)
+}
+
+#[test]
+fn merge_branch_fast_forward() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let (main, feature_tip) = branch_with_commit(&repo, &temp_dir, "f.txt", "feature");
+
+    let result = merge_branch(&repo, "feature").unwrap();
+
+    assert_eq!(result.kind, MergeKind::FastForward);
+    assert_eq!(result.commit_hash, feature_tip.to_string());
+    assert_eq!(head_branch(&repo), main);
+    assert_eq!(repo.head().unwrap().target(), Some(feature_tip));
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("f.txt")).unwrap(),
+        "feature"
+    );
+}
+
+#[test]
+fn merge_branch_up_to_date() {
+    let (temp_dir, repo) = create_test_repo();
+    let base = create_initial_commit(&repo, &temp_dir);
+    let commit = repo.find_commit(base).unwrap();
+    repo.branch("feature", &commit, false).unwrap();
+
+    let result = merge_branch(&repo, "feature").unwrap();
+
+    assert_eq!(result.kind, MergeKind::UpToDate);
+    assert_eq!(result.commit_hash, base.to_string());
+}
+
+#[test]
+fn merge_branch_creates_merge_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let (_main, feature_tip) = branch_with_commit(&repo, &temp_dir, "f.txt", "feature");
+    let main_tip = create_commit_with_file(&repo, &temp_dir, "m.txt", "main", "main work");
+
+    let result = merge_branch(&repo, "feature").unwrap();
+
+    assert_eq!(result.kind, MergeKind::MergeCommit);
+    let merge = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(merge.id().to_string(), result.commit_hash);
+    let parents: Vec<_> = merge.parent_ids().collect();
+    assert_eq!(parents, vec![main_tip, feature_tip]);
+    assert_eq!(
+        merge.summary().ok().flatten(),
+        Some("Merge branch 'feature'")
+    );
+    assert!(temp_dir.path().join("f.txt").exists());
+    assert!(temp_dir.path().join("m.txt").exists());
+    assert_eq!(repo.state(), RepositoryState::Clean);
+}
+
+#[test]
+fn merge_branch_conflict_does_not_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    branch_with_commit(&repo, &temp_dir, "shared.txt", "from feature\n");
+    let main_tip = create_commit_with_file(&repo, &temp_dir, "shared.txt", "from main\n", "main");
+
+    let result = merge_branch(&repo, "feature");
+
+    match result {
+        Err(AppError::MergeConflict { paths, step }) => {
+            assert_eq!(paths, vec!["shared.txt"]);
+            assert_eq!(step, None);
+        }
+        other => panic!("expected MergeConflict, got {other:?}"),
+    }
+    assert_eq!(repo.head().unwrap().target(), Some(main_tip));
+    assert_eq!(repo.state(), RepositoryState::Merge);
+    let content = fs::read_to_string(temp_dir.path().join("shared.txt")).unwrap();
+    assert!(content.contains("<<<<<<<"));
+}
+
+#[test]
+fn merge_branch_conflict_then_continue_commits_both_parents() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let (_main, feature_tip) = branch_with_commit(&repo, &temp_dir, "shared.txt", "from feature\n");
+    let main_tip = create_commit_with_file(&repo, &temp_dir, "shared.txt", "from main\n", "main");
+    assert!(matches!(
+        merge_branch(&repo, "feature"),
+        Err(AppError::MergeConflict { .. })
+    ));
+
+    fs::write(temp_dir.path().join("shared.txt"), "resolved\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("shared.txt")).unwrap();
+    index.write().unwrap();
+
+    let hash = continue_operation(&repo).unwrap();
+
+    let merge = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(merge.id().to_string(), hash);
+    let parents: Vec<_> = merge.parent_ids().collect();
+    assert_eq!(parents, vec![main_tip, feature_tip]);
+    assert_eq!(merge.message().ok(), Some("Merge branch 'feature'\n"));
+    assert_eq!(repo.state(), RepositoryState::Clean);
+    assert!(!repo.path().join("MERGE_HEAD").exists());
+}
+
+#[test]
+fn merge_branch_fast_forward_refuses_to_overwrite_local_changes() {
+    let (temp_dir, repo) = create_test_repo();
+    let base = create_initial_commit(&repo, &temp_dir);
+    branch_with_commit(&repo, &temp_dir, "f.txt", "feature");
+    fs::write(temp_dir.path().join("f.txt"), "local").unwrap();
+
+    match merge_branch(&repo, "feature") {
+        Err(AppError::CheckoutConflict(paths)) => assert_eq!(paths, vec!["f.txt"]),
+        other => panic!("expected CheckoutConflict, got {other:?}"),
+    }
+    assert_eq!(repo.head().unwrap().target(), Some(base));
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("f.txt")).unwrap(),
+        "local"
+    );
+}
+
+#[test]
+fn merge_branch_unknown_branch() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(matches!(merge_branch(&repo, "nope"), Err(AppError::Git(_))));
+}
+
+#[test]
+fn merge_base_of_branched_history() {
+    let (temp_dir, repo) = create_test_repo();
+    let fork = create_initial_commit(&repo, &temp_dir);
+    let (main, feature_tip) = branch_with_commit(&repo, &temp_dir, "f.txt", "feature");
+    create_commit_with_file(&repo, &temp_dir, "m.txt", "main", "main work");
+
+    assert_eq!(
+        merge_base(&repo, &main, "feature").unwrap(),
+        fork.to_string()
+    );
+    // An ancestor is its own merge base with a descendant.
+    assert_eq!(
+        merge_base(&repo, &fork.to_string(), &feature_tip.to_string()).unwrap(),
+        fork.to_string()
+    );
+
+    repo.branch("other", &repo.find_commit(feature_tip).unwrap(), false)
+        .unwrap();
+    let revs = vec![main.clone(), "feature".to_string(), "other".to_string()];
+    assert_eq!(merge_base_many(&repo, &revs).unwrap(), fork.to_string());
+    assert!(matches!(
+        merge_base_many(&repo, &[main]),
+        Err(AppError::InvalidArgument(_))
+    ));
+}
+
+#[test]
+fn merge_base_unrelated_histories() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let main = head_branch(&repo);
+
+    // A second root commit on an orphan branch.
+    let sig = repo.signature().unwrap();
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let orphan = repo
+        .commit(None, &sig, &sig, "orphan root", &tree, &[])
+        .unwrap();
+    repo.branch("orphan", &repo.find_commit(orphan).unwrap(), false)
+        .unwrap();
+
+    match merge_base(&repo, &main, "orphan") {
+        Err(AppError::Git(e)) => {
+            assert_eq!(e.code(), git2::ErrorCode::NotFound);
+            assert!(e.message().contains("no common ancestor"));
+        }
+        other => panic!("expected unrelated-histories error, got {other:?}"),
+    }
+    let revs = vec![main, "orphan".to_string()];
+    assert!(merge_base_many(&repo, &revs).is_err());
+}
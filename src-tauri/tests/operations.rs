@@ -1,9 +1,10 @@
 //! Integration tests for the abort / continue operations on real
-//! in-progress rebase, cherry-pick, and revert states. Each setup helper
-//! builds a small commit history in a tempdir and uses libgit2's high-level
-//! cherrypick/revert/rebase to produce the actual on-disk state files
-//! (CHERRY_PICK_HEAD, REVERT_HEAD, .git/rebase-merge/, etc.) — so the tests
-//! exercise the same code paths the app sees in production.
+//! in-progress merge, rebase, cherry-pick, and revert states. Each setup
+//! helper builds a small commit history in a tempdir and uses libgit2's
+//! high-level merge/cherrypick/revert/rebase to produce the actual on-disk
+//! state files (MERGE_HEAD, CHERRY_PICK_HEAD, REVERT_HEAD,
+//! .git/rebase-merge/, etc.) — so the tests exercise the same code paths
+//! the app sees in production.
 
 mod common;
 
@@ -13,7 +14,10 @@ use std::fs;
 use std::path::Path;
 use tempfile::TempDir;
 use yagg_lib::error::AppError;
-use yagg_lib::git::operations::{abort_operation, continue_operation};
+use yagg_lib::git::operations::{
+    abort_operation, cherry_pick_range, continue_operation, revert_range,
+};
+use yagg_lib::git::{get_repo_state, merge_branch, RepoState};
 
 fn write_and_commit(
     repo: &Repository,
@@ -137,6 +141,27 @@ fn setup_rebase_conflict(temp_dir: &TempDir, repo: &Repository) -> Oid {
     feature_oid
 }
 
+/// History: base → main (ver-main)  [HEAD]
+///              └→ feature (ver-feature)
+/// Merge feature into main → conflict on file.txt. Returns main's tip.
+fn setup_merge_conflict(temp_dir: &TempDir, repo: &Repository) -> Oid {
+    let base_oid = write_and_commit(repo, temp_dir, "file.txt", "base\n", "base");
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let base = repo.find_commit(base_oid).unwrap();
+    repo.branch("feature", &base, false).unwrap();
+    repo.set_head("refs/heads/feature").unwrap();
+    write_and_commit(repo, temp_dir, "file.txt", "ver-feature\n", "feature");
+    repo.set_head(&format!("refs/heads/{main}")).unwrap();
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))
+        .unwrap();
+    let main_tip = write_and_commit(repo, temp_dir, "file.txt", "ver-main\n", "main");
+    assert!(matches!(
+        merge_branch(repo, "feature"),
+        Err(AppError::MergeConflict { .. })
+    ));
+    main_tip
+}
+
 fn resolve(temp_dir: &TempDir, repo: &Repository, filename: &str, content: &str) {
     let file_path = temp_dir.path().join(filename);
     fs::write(&file_path, content).unwrap();
@@ -188,6 +213,43 @@ fn abort_rebase_clears_state() {
     assert!(!repo.index().unwrap().has_conflicts());
 }
 
+#[test]
+fn abort_merge_clears_state_and_restores_worktree() {
+    let (temp_dir, repo) = create_test_repo();
+    let main_tip = setup_merge_conflict(&temp_dir, &repo);
+    assert_eq!(get_repo_state(&repo), RepoState::Merge);
+
+    abort_operation(&repo).unwrap();
+
+    assert_eq!(get_repo_state(&repo), RepoState::Clean);
+    assert!(!repo.path().join("MERGE_HEAD").exists());
+    assert_eq!(repo.head().unwrap().target(), Some(main_tip));
+    assert!(!repo.index().unwrap().has_conflicts());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+        "ver-main\n"
+    );
+}
+
+#[test]
+fn get_repo_state_reports_in_progress_operation() {
+    let (temp_dir, repo) = create_test_repo();
+    write_and_commit(&repo, &temp_dir, "file.txt", "base\n", "base");
+    assert_eq!(get_repo_state(&repo), RepoState::Clean);
+
+    let (temp_dir, repo) = create_test_repo();
+    setup_cherry_pick_conflict(&temp_dir, &repo);
+    assert_eq!(get_repo_state(&repo), RepoState::CherryPick);
+
+    let (temp_dir, repo) = create_test_repo();
+    setup_revert_conflict(&temp_dir, &repo);
+    assert_eq!(get_repo_state(&repo), RepoState::Revert);
+
+    let (temp_dir, repo) = create_test_repo();
+    setup_merge_conflict(&temp_dir, &repo);
+    assert_eq!(get_repo_state(&repo), RepoState::Merge);
+}
+
 #[test]
 fn abort_when_clean_returns_no_operation_in_progress() {
     let (temp_dir, repo) = create_test_repo();
@@ -284,6 +346,24 @@ fn continue_rebase_after_resolution_creates_commit_and_clears_state() {
     assert_eq!(workdir_content, "rebased\n");
 }
 
+#[test]
+fn continue_merge_after_resolution_returns_to_clean() {
+    let (temp_dir, repo) = create_test_repo();
+    let main_tip = setup_merge_conflict(&temp_dir, &repo);
+    assert_eq!(get_repo_state(&repo), RepoState::Merge);
+
+    resolve(&temp_dir, &repo, "file.txt", "merged\n");
+
+    let new_oid_str = continue_operation(&repo).unwrap();
+
+    assert_eq!(get_repo_state(&repo), RepoState::Clean);
+    let new_commit = repo
+        .find_commit(Oid::from_str(&new_oid_str).unwrap())
+        .unwrap();
+    assert_eq!(new_commit.parent_count(), 2);
+    assert_eq!(new_commit.parent_id(0).unwrap(), main_tip);
+}
+
 #[test]
 fn continue_when_clean_returns_no_operation_in_progress() {
     let (temp_dir, repo) = create_test_repo();
@@ -315,3 +395,120 @@ fn continue_rejects_multi_commit_cherry_pick_sequencer() {
         other => panic!("expected Internal error, got {:?}", other),
     }
 }
+
+// ============================================================================
+// cherry_pick_range / revert_range
+// ============================================================================
+
+/// History: base → A → B → C on `feature`; HEAD back on base with `main.txt`
+/// committed. Returns (A, C).
+fn setup_feature_range(temp_dir: &TempDir, repo: &Repository) -> (Oid, Oid) {
+    let base_oid = write_and_commit(repo, temp_dir, "file.txt", "base\n", "base");
+    let a = write_and_commit(repo, temp_dir, "a.txt", "a\n", "A");
+    write_and_commit(repo, temp_dir, "b.txt", "b\n", "B");
+    let c = write_and_commit(repo, temp_dir, "c.txt", "c\n", "C");
+    let base = repo.find_commit(base_oid).unwrap();
+    repo.reset(base.as_object(), ResetType::Hard, None).unwrap();
+    write_and_commit(repo, temp_dir, "main.txt", "main\n", "main");
+    (a, c)
+}
+
+fn summaries(repo: &Repository, hashes: &[String]) -> Vec<String> {
+    hashes
+        .iter()
+        .map(|h| {
+            let commit = repo.find_commit(Oid::from_str(h).unwrap()).unwrap();
+            commit.summary().unwrap().unwrap().to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn cherry_pick_range_applies_oldest_first() {
+    let (temp_dir, repo) = create_test_repo();
+    let (a, c) = setup_feature_range(&temp_dir, &repo);
+
+    let result = cherry_pick_range(&repo, &a.to_string(), &c.to_string()).unwrap();
+
+    assert!(result.conflict.is_none());
+    assert_eq!(summaries(&repo, &result.applied), vec!["A", "B", "C"]);
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id().to_string(), result.applied[2]);
+    for file in ["a.txt", "b.txt", "c.txt", "main.txt"] {
+        assert!(temp_dir.path().join(file).exists(), "{file}");
+    }
+    assert_eq!(repo.state(), RepositoryState::Clean);
+}
+
+#[test]
+fn cherry_pick_range_stops_at_conflict() {
+    let (temp_dir, repo) = create_test_repo();
+    let base_oid = write_and_commit(&repo, &temp_dir, "file.txt", "base\n", "base");
+    let a = write_and_commit(&repo, &temp_dir, "a.txt", "a\n", "A");
+    let b = write_and_commit(&repo, &temp_dir, "file.txt", "ver-b\n", "B");
+    let c = write_and_commit(&repo, &temp_dir, "c.txt", "c\n", "C");
+    let base = repo.find_commit(base_oid).unwrap();
+    repo.reset(base.as_object(), ResetType::Hard, None).unwrap();
+    write_and_commit(&repo, &temp_dir, "file.txt", "ver-main\n", "main");
+
+    let result = cherry_pick_range(&repo, &a.to_string(), &c.to_string()).unwrap();
+
+    assert_eq!(summaries(&repo, &result.applied), vec!["A"]);
+    let conflict = result.conflict.unwrap();
+    assert_eq!(conflict.hash, b.to_string());
+    assert_eq!(conflict.paths, vec!["file.txt"]);
+    assert_eq!(repo.state(), RepositoryState::CherryPick);
+    assert!(!temp_dir.path().join("c.txt").exists());
+}
+
+#[test]
+fn revert_range_reverts_newest_first() {
+    let (temp_dir, repo) = create_test_repo();
+    write_and_commit(&repo, &temp_dir, "file.txt", "base\n", "base");
+    let a = write_and_commit(&repo, &temp_dir, "a.txt", "a\n", "A");
+    write_and_commit(&repo, &temp_dir, "b.txt", "b\n", "B");
+    let c = write_and_commit(&repo, &temp_dir, "c.txt", "c\n", "C");
+
+    let result = revert_range(&repo, &a.to_string(), &c.to_string()).unwrap();
+
+    assert!(result.conflict.is_none());
+    assert_eq!(
+        summaries(&repo, &result.applied),
+        vec!["Revert \"C\"", "Revert \"B\"", "Revert \"A\""]
+    );
+    for file in ["a.txt", "b.txt", "c.txt"] {
+        assert!(!temp_dir.path().join(file).exists(), "{file}");
+    }
+    assert_eq!(repo.state(), RepositoryState::Clean);
+}
+
+#[test]
+fn revert_range_stops_at_conflict() {
+    let (temp_dir, repo) = create_test_repo();
+    write_and_commit(&repo, &temp_dir, "file.txt", "base\n", "base");
+    let a = write_and_commit(&repo, &temp_dir, "file.txt", "ver-a\n", "A");
+    let b = write_and_commit(&repo, &temp_dir, "b.txt", "b\n", "B");
+    write_and_commit(&repo, &temp_dir, "file.txt", "ver-c\n", "C");
+
+    let result = revert_range(&repo, &a.to_string(), &b.to_string()).unwrap();
+
+    assert_eq!(summaries(&repo, &result.applied), vec!["Revert \"B\""]);
+    let conflict = result.conflict.unwrap();
+    assert_eq!(conflict.hash, a.to_string());
+    assert_eq!(conflict.paths, vec!["file.txt"]);
+    assert_eq!(repo.state(), RepositoryState::Revert);
+}
+
+#[test]
+fn cherry_pick_range_rejects_unrelated_from() {
+    let (temp_dir, repo) = create_test_repo();
+    let (a, c) = setup_feature_range(&temp_dir, &repo);
+
+    let err = cherry_pick_range(&repo, &c.to_string(), &a.to_string()).unwrap_err();
+    assert!(matches!(err, AppError::InvalidArgument(_)), "{err:?}");
+    assert_eq!(
+        err.to_string(),
+        format!("Invalid argument: {c} is not an ancestor of {a}")
+    );
+    assert_eq!(repo.state(), RepositoryState::Clean);
+}
@@ -0,0 +1,89 @@
+//! Integration tests for `rebase_onto`: a clean linear rebase, and a
+//! conflicting one that must be aborted rather than left half-done.
+
+mod common;
+
+use common::{create_commit_with_file, create_initial_commit, create_test_repo};
+use git2::{build::CheckoutBuilder, Repository, RepositoryState};
+use std::fs;
+use yagg_lib::error::AppError;
+use yagg_lib::git::rebase_onto;
+
+/// Point HEAD at `branch` and force the working tree to match it.
+fn switch_to(repo: &Repository, branch: &str) {
+    repo.set_head(&format!("refs/heads/{branch}")).unwrap();
+    repo.checkout_head(Some(CheckoutBuilder::new().force()))
+        .unwrap();
+}
+
+/// Fork `feature` from the current HEAD and check it out.
+fn start_feature(repo: &Repository) -> String {
+    let main = repo.head().unwrap().shorthand().unwrap().to_string();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.branch("feature", &head, false).unwrap();
+    switch_to(repo, "feature");
+    main
+}
+
+#[test]
+fn rebase_onto_linear() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let main = start_feature(&repo);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "feature a");
+    create_commit_with_file(&repo, &temp_dir, "b.txt", "b", "feature b");
+    switch_to(&repo, &main);
+    let main_tip = create_commit_with_file(&repo, &temp_dir, "m.txt", "m", "main work");
+    switch_to(&repo, "feature");
+
+    let result = rebase_onto(&repo, &main, None).unwrap();
+
+    assert_eq!(result.commits_rebased, 2);
+    let head = repo.head().unwrap();
+    assert_eq!(head.shorthand().unwrap(), "feature");
+    let tip = head.peel_to_commit().unwrap();
+    assert_eq!(tip.id().to_string(), result.commit_hash);
+    assert_eq!(tip.summary().ok().flatten(), Some("feature b"));
+    assert_eq!(tip.author().email().unwrap(), "test@example.com");
+    let first = tip.parent(0).unwrap();
+    assert_eq!(first.summary().ok().flatten(), Some("feature a"));
+    assert_eq!(first.parent_id(0).unwrap(), main_tip);
+    assert!(temp_dir.path().join("m.txt").exists());
+    assert_eq!(repo.state(), RepositoryState::Clean);
+}
+
+#[test]
+fn rebase_onto_conflict_aborts() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let main = start_feature(&repo);
+    create_commit_with_file(&repo, &temp_dir, "a.txt", "a", "feature a");
+    let feature_tip = create_commit_with_file(
+        &repo,
+        &temp_dir,
+        "shared.txt",
+        "from feature\n",
+        "feature b",
+    );
+    switch_to(&repo, &main);
+    create_commit_with_file(&repo, &temp_dir, "shared.txt", "from main\n", "main work");
+    switch_to(&repo, "feature");
+
+    let result = rebase_onto(&repo, &main, None);
+
+    match result {
+        Err(AppError::MergeConflict { paths, step }) => {
+            assert_eq!(paths, vec!["shared.txt"]);
+            assert_eq!(step, Some(1));
+        }
+        other => panic!("expected MergeConflict, got {other:?}"),
+    }
+    let head = repo.head().unwrap();
+    assert_eq!(head.shorthand().unwrap(), "feature");
+    assert_eq!(head.target(), Some(feature_tip));
+    assert_eq!(repo.state(), RepositoryState::Clean);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("shared.txt")).unwrap(),
+        "from feature\n"
+    );
+}
@@ -0,0 +1,380 @@
+//! Integration tests for remote operations. Each test builds an "upstream"
+//! repository in one tempdir and a local clone-like repository in another,
+//! wired together with a `file://` remote so no network access is needed.
+//! Push tests use a bare upstream, since pushing to a checked-out branch of
+//! a non-bare repository is refused.
+
+mod common;
+
+use common::{create_commit_with_file, create_initial_commit, create_test_repo};
+use git2::Repository;
+use tempfile::TempDir;
+use yagg_lib::error::AppError;
+use yagg_lib::git::{
+    add_remote, delete_remote_tag, fetch_remote, list_remotes, push_branch,
+    push_branch_with_progress, push_tag, remove_remote, set_remote_url, PushProgress,
+};
+
+/// Create an upstream repo with one commit, plus an empty local repo whose
+/// `origin` points at it. Returns (upstream_dir, upstream, local_dir, local).
+fn setup_upstream_and_local() -> (TempDir, Repository, TempDir, Repository) {
+    let (upstream_dir, upstream) = create_test_repo();
+    create_initial_commit(&upstream, &upstream_dir);
+
+    let (local_dir, local) = create_test_repo();
+    let url = format!("file://{}", upstream_dir.path().display());
+    local.remote("origin", &url).unwrap();
+
+    (upstream_dir, upstream, local_dir, local)
+}
+
+fn upstream_branch(upstream: &Repository) -> String {
+    upstream.head().unwrap().shorthand().unwrap().to_string()
+}
+
+#[test]
+fn fetch_remote_creates_tracking_refs() {
+    let (_upstream_dir, upstream, _local_dir, local) = setup_upstream_and_local();
+    let branch = upstream_branch(&upstream);
+    let upstream_tip = upstream.head().unwrap().target().unwrap();
+
+    let result = fetch_remote(&local, "origin").unwrap();
+
+    assert_eq!(result.remote, "origin");
+    assert_eq!(result.updated_refs, result.updates.len());
+    let tracking = format!("refs/remotes/origin/{branch}");
+    let update = result
+        .updates
+        .iter()
+        .find(|u| u.ref_name == tracking)
+        .expect("tracking ref should be reported");
+    assert_eq!(update.old_hash, None);
+    assert_eq!(update.new_hash, upstream_tip.to_string());
+
+    let local_ref = local.find_reference(&tracking).unwrap();
+    assert_eq!(local_ref.target(), Some(upstream_tip));
+}
+
+#[test]
+fn fetch_remote_reports_moved_tip() {
+    let (upstream_dir, upstream, _local_dir, local) = setup_upstream_and_local();
+    let branch = upstream_branch(&upstream);
+    let old_tip = upstream.head().unwrap().target().unwrap();
+    fetch_remote(&local, "origin").unwrap();
+
+    let new_tip = create_commit_with_file(&upstream, &upstream_dir, "new.txt", "new", "Second");
+    let result = fetch_remote(&local, "origin").unwrap();
+
+    assert_eq!(result.updated_refs, 1);
+    let update = &result.updates[0];
+    assert_eq!(update.ref_name, format!("refs/remotes/origin/{branch}"));
+    assert_eq!(update.old_hash, Some(old_tip.to_string()));
+    assert_eq!(update.new_hash, new_tip.to_string());
+}
+
+#[test]
+fn fetch_remote_up_to_date_reports_nothing() {
+    let (_upstream_dir, _upstream, _local_dir, local) = setup_upstream_and_local();
+    fetch_remote(&local, "origin").unwrap();
+
+    let result = fetch_remote(&local, "origin").unwrap();
+    assert_eq!(result.updated_refs, 0);
+    assert!(result.updates.is_empty());
+}
+
+#[test]
+fn fetch_remote_unknown_remote() {
+    let (_upstream_dir, _upstream, _local_dir, local) = setup_upstream_and_local();
+    let result = fetch_remote(&local, "does-not-exist");
+    assert!(result.is_err());
+}
+
+// ============================================================================
+// push_branch
+// ============================================================================
+
+/// Create a local repo with one commit and a bare `origin` to push into.
+/// Returns (local_dir, local, bare_dir, bare, branch).
+fn setup_local_and_bare() -> (TempDir, Repository, TempDir, Repository, String) {
+    let (local_dir, local) = create_test_repo();
+    create_initial_commit(&local, &local_dir);
+    let branch = upstream_branch(&local);
+
+    let bare_dir = TempDir::new().unwrap();
+    let bare = Repository::init_bare(bare_dir.path()).unwrap();
+    let url = format!("file://{}", bare_dir.path().display());
+    local.remote("origin", &url).unwrap();
+
+    (local_dir, local, bare_dir, bare, branch)
+}
+
+#[test]
+fn push_branch_creates_remote_branch() {
+    let (_local_dir, local, _bare_dir, bare, branch) = setup_local_and_bare();
+    let tip = local.head().unwrap().target().unwrap();
+
+    let result = push_branch(&local, "origin", &branch, false).unwrap();
+
+    assert_eq!(result.remote, "origin");
+    assert_eq!(result.branch, branch);
+    assert_eq!(result.updates.len(), 1);
+    assert_eq!(result.updates[0].ref_name, format!("refs/heads/{branch}"));
+    assert!(result.updates[0].rejection.is_none());
+
+    let remote_ref = bare
+        .find_reference(&format!("refs/heads/{branch}"))
+        .unwrap();
+    assert_eq!(remote_ref.target(), Some(tip));
+}
+
+#[test]
+fn push_branch_reports_transfer_progress() {
+    let (_local_dir, local, _bare_dir, _bare, branch) = setup_local_and_bare();
+
+    let mut reported = Vec::new();
+    push_branch_with_progress(&local, "origin", &branch, false, |p| reported.push(p)).unwrap();
+
+    let last_transfer = reported
+        .iter()
+        .rev()
+        .find_map(|p| match p {
+            PushProgress::Transfer { current, total, .. } => Some((*current, *total)),
+            PushProgress::Remote { .. } => None,
+        })
+        .expect("no transfer progress reported");
+    assert!(last_transfer.1 > 0);
+    assert_eq!(last_transfer.0, last_transfer.1);
+}
+
+#[test]
+fn push_branch_non_fast_forward_rejected() {
+    let (local_dir, local, _bare_dir, _bare, branch) = setup_local_and_bare();
+    create_commit_with_file(&local, &local_dir, "a.txt", "a", "Second");
+    push_branch(&local, "origin", &branch, false).unwrap();
+
+    // Rewind the local branch so the next push would drop "Second".
+    let head = local.head().unwrap().peel_to_commit().unwrap();
+    let parent = head.parent(0).unwrap();
+    local
+        .reset(parent.as_object(), git2::ResetType::Hard, None)
+        .unwrap();
+    create_commit_with_file(&local, &local_dir, "b.txt", "b", "Diverged");
+
+    let result = push_branch(&local, "origin", &branch, false);
+    assert!(matches!(result, Err(AppError::PushRejected(_))));
+}
+
+#[test]
+fn push_branch_force_overwrites_remote() {
+    let (local_dir, local, _bare_dir, bare, branch) = setup_local_and_bare();
+    create_commit_with_file(&local, &local_dir, "a.txt", "a", "Second");
+    push_branch(&local, "origin", &branch, false).unwrap();
+
+    let head = local.head().unwrap().peel_to_commit().unwrap();
+    let parent = head.parent(0).unwrap();
+    local
+        .reset(parent.as_object(), git2::ResetType::Hard, None)
+        .unwrap();
+    let diverged = create_commit_with_file(&local, &local_dir, "b.txt", "b", "Diverged");
+
+    push_branch(&local, "origin", &branch, true).unwrap();
+
+    let remote_ref = bare
+        .find_reference(&format!("refs/heads/{branch}"))
+        .unwrap();
+    assert_eq!(remote_ref.target(), Some(diverged));
+}
+
+#[test]
+fn push_branch_unknown_branch() {
+    let (_local_dir, local, _bare_dir, _bare, _branch) = setup_local_and_bare();
+    let result = push_branch(&local, "origin", "no-such-branch", false);
+    assert!(matches!(result, Err(AppError::Git(_))));
+}
+
+// ============================================================================
+// push_tag / delete_remote_tag
+// ============================================================================
+
+#[test]
+fn push_tag_publishes_annotated_tag() {
+    let (_local_dir, local, _bare_dir, bare, _branch) = setup_local_and_bare();
+    let head = local.head().unwrap().peel_to_commit().unwrap();
+    let sig = local.signature().unwrap();
+    let tag_oid = local
+        .tag("v1.0", head.as_object(), &sig, "Release 1.0", false)
+        .unwrap();
+
+    let result = push_tag(&local, "origin", "v1.0").unwrap();
+
+    assert_eq!(result.remote, "origin");
+    assert_eq!(result.tag, "v1.0");
+    assert_eq!(result.updates.len(), 1);
+    assert_eq!(result.updates[0].ref_name, "refs/tags/v1.0");
+    assert!(result.updates[0].rejection.is_none());
+    let remote_tag = bare.find_reference("refs/tags/v1.0").unwrap();
+    assert_eq!(remote_tag.target(), Some(tag_oid));
+    assert_eq!(remote_tag.peel_to_commit().unwrap().id(), head.id());
+}
+
+#[test]
+fn push_tag_unknown_tag() {
+    let (_local_dir, local, _bare_dir, _bare, _branch) = setup_local_and_bare();
+    let result = push_tag(&local, "origin", "no-such-tag");
+    assert!(matches!(result, Err(AppError::Git(_))));
+}
+
+#[test]
+fn delete_remote_tag_removes_it_from_remote_only() {
+    let (_local_dir, local, _bare_dir, bare, _branch) = setup_local_and_bare();
+    let head = local.head().unwrap().peel_to_commit().unwrap();
+    local
+        .tag_lightweight("v1.0", head.as_object(), false)
+        .unwrap();
+    push_tag(&local, "origin", "v1.0").unwrap();
+    assert!(bare.find_reference("refs/tags/v1.0").is_ok());
+
+    let result = delete_remote_tag(&local, "origin", "v1.0").unwrap();
+
+    assert_eq!(result.updates.len(), 1);
+    assert!(result.updates[0].rejection.is_none());
+    assert!(bare.find_reference("refs/tags/v1.0").is_err());
+    assert!(local.find_reference("refs/tags/v1.0").is_ok());
+}
+
+#[test]
+fn tag_push_and_delete_reject_refspec_syntax() {
+    let (_local_dir, local, _bare_dir, bare, _branch) = setup_local_and_bare();
+    let head = local.head().unwrap().peel_to_commit().unwrap();
+    local
+        .tag_lightweight("v1.0", head.as_object(), false)
+        .unwrap();
+    push_tag(&local, "origin", "v1.0").unwrap();
+
+    // As refspecs these would delete every remote tag or push onto a branch,
+    // so they're refused before anything reaches the remote.
+    assert!(matches!(
+        delete_remote_tag(&local, "origin", "*"),
+        Err(AppError::InvalidTagName(t)) if t == "*"
+    ));
+    assert!(matches!(
+        push_tag(&local, "origin", "v1.0:refs/heads/main"),
+        Err(AppError::InvalidTagName(t)) if t == "v1.0:refs/heads/main"
+    ));
+
+    assert!(bare.find_reference("refs/tags/v1.0").is_ok());
+}
+
+// ============================================================================
+// list_remotes
+// ============================================================================
+
+#[test]
+fn list_remotes_reports_fetch_and_push_urls() {
+    let (_dir, repo) = create_test_repo();
+    repo.remote("origin", "https://example.com/read.git")
+        .unwrap();
+    repo.remote_set_pushurl("origin", Some("git@example.com:write.git"))
+        .unwrap();
+    repo.remote("mirror", "https://mirror.example.com/repo.git")
+        .unwrap();
+
+    let mut remotes = list_remotes(&repo).unwrap();
+    remotes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    assert_eq!(remotes.len(), 2);
+    assert_eq!(remotes[0].name, "mirror");
+    assert_eq!(remotes[0].fetch_url, "https://mirror.example.com/repo.git");
+    assert_eq!(remotes[0].push_url, None);
+    assert_eq!(remotes[1].name, "origin");
+    assert_eq!(remotes[1].fetch_url, "https://example.com/read.git");
+    assert_eq!(
+        remotes[1].push_url.as_deref(),
+        Some("git@example.com:write.git")
+    );
+}
+
+#[test]
+fn list_remotes_empty() {
+    let (_dir, repo) = create_test_repo();
+
+    assert!(list_remotes(&repo).unwrap().is_empty());
+}
+
+// ============================================================================
+// add_remote / remove_remote / set_remote_url
+// ============================================================================
+
+#[test]
+fn add_remote_shows_up_in_list() {
+    let (_dir, repo) = create_test_repo();
+
+    let info = add_remote(&repo, "origin", "https://example.com/repo.git").unwrap();
+
+    assert_eq!(info.name, "origin");
+    let remotes = list_remotes(&repo).unwrap();
+    assert_eq!(remotes.len(), 1);
+    assert_eq!(remotes[0].fetch_url, "https://example.com/repo.git");
+}
+
+#[test]
+fn add_remote_rejects_empty_and_duplicate_names() {
+    let (_dir, repo) = create_test_repo();
+    add_remote(&repo, "origin", "https://example.com/a.git").unwrap();
+
+    for name in ["", "  ", "bad name", "a..b"] {
+        let result = add_remote(&repo, name, "https://example.com/b.git");
+        assert!(
+            matches!(&result, Err(AppError::InvalidRemoteName(n)) if n == name),
+            "{name:?}: {result:?}"
+        );
+    }
+    let dup = add_remote(&repo, "origin", "https://example.com/b.git").unwrap_err();
+    assert_eq!(dup.code(), "git_exists");
+    assert!(dup.to_string().contains("already exists"), "got: {dup}");
+    assert_eq!(
+        list_remotes(&repo).unwrap()[0].fetch_url,
+        "https://example.com/a.git"
+    );
+}
+
+#[test]
+fn remove_remote_drops_it_from_list() {
+    let (_dir, repo) = create_test_repo();
+    add_remote(&repo, "origin", "https://example.com/a.git").unwrap();
+    add_remote(&repo, "fork", "https://example.com/b.git").unwrap();
+
+    remove_remote(&repo, "fork").unwrap();
+
+    let names: Vec<String> = list_remotes(&repo)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.name)
+        .collect();
+    assert_eq!(names, vec!["origin"]);
+}
+
+#[test]
+fn remove_remote_missing_is_clear_error() {
+    let (_dir, repo) = create_test_repo();
+
+    let err = remove_remote(&repo, "ghost").unwrap_err();
+    assert_eq!(err.code(), "git_not_found");
+    assert!(err.to_string().contains("ghost"), "got: {err}");
+}
+
+#[test]
+fn set_remote_url_updates_fetch_url() {
+    let (_dir, repo) = create_test_repo();
+    add_remote(&repo, "origin", "https://example.com/old.git").unwrap();
+
+    let info = set_remote_url(&repo, "origin", "https://example.com/new.git").unwrap();
+
+    assert_eq!(info.fetch_url, "https://example.com/new.git");
+    assert_eq!(
+        list_remotes(&repo).unwrap()[0].fetch_url,
+        "https://example.com/new.git"
+    );
+    let err = set_remote_url(&repo, "ghost", "https://example.com/x.git").unwrap_err();
+    assert_eq!(err.code(), "git_not_found");
+}
@@ -7,10 +7,14 @@
 mod common;
 
 use common::create_test_repo;
-use git2::Repository;
+use git2::{Repository, Signature};
+use std::fs;
 use std::path::Path;
 use tempfile::TempDir;
-use yagg_lib::git::{self, get_repo_info, open_repo};
+use yagg_lib::error::AppError;
+use yagg_lib::git::{
+    self, discover_repo, get_repo_info, init_repo, open_repo, HistoryCache, RepoState, RepoStats,
+};
 
 // Local helper — git/repository.rs's tests had this inline because they
 // don't need `create_initial_commit` from common (which writes a file
@@ -48,6 +52,81 @@ fn open_repo_not_a_repo() {
     assert!(result.is_err());
 }
 
+// =============================================================================
+// discover_repo (3 tests)
+// =============================================================================
+
+#[test]
+fn discover_repo_from_nested_subdirectory() {
+    let (temp_dir, _repo) = create_test_repo();
+    let nested = temp_dir.path().join("a").join("b").join("c");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    let repo = discover_repo(&nested).unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    assert_eq!(repo.workdir().unwrap().canonicalize().unwrap(), root);
+
+    let info = get_repo_info(&repo).unwrap();
+    assert_eq!(Path::new(&info.path).canonicalize().unwrap(), root);
+}
+
+#[test]
+fn discover_repo_not_a_repo() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(discover_repo(temp_dir.path()).is_err());
+}
+
+#[test]
+fn open_repo_strict_rejects_subdirectory() {
+    let (temp_dir, _repo) = create_test_repo();
+    let nested = temp_dir.path().join("sub");
+    std::fs::create_dir(&nested).unwrap();
+
+    assert!(open_repo(&nested).is_err());
+}
+
+// =============================================================================
+// init_repo (3 tests)
+// =============================================================================
+
+#[test]
+fn init_repo_fresh_directory() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "hello\n").unwrap();
+
+    let repo = init_repo(temp_dir.path(), false).unwrap();
+    assert!(!repo.is_bare());
+
+    let info = get_repo_info(&repo).unwrap();
+    assert_eq!(info.head_hash, None);
+    assert!(!info.is_detached);
+    assert_eq!(info.repo_state, RepoState::Clean);
+    assert!(open_repo(temp_dir.path()).is_ok());
+}
+
+#[test]
+fn init_repo_bare() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let repo = init_repo(temp_dir.path(), true).unwrap();
+    assert!(repo.is_bare());
+    assert!(temp_dir.path().join("HEAD").exists());
+    assert!(get_repo_info(&repo).is_ok());
+}
+
+#[test]
+fn init_repo_rejects_existing_repo_and_missing_dir() {
+    let (temp_dir, _repo) = create_test_repo();
+    assert!(matches!(
+        init_repo(temp_dir.path(), false),
+        Err(AppError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        init_repo(&temp_dir.path().join("missing"), false),
+        Err(AppError::InvalidPath(_))
+    ));
+}
+
 // =============================================================================
 // get_repo_info (5 tests)
 // =============================================================================
@@ -62,7 +141,7 @@ fn get_repo_info_new_repo() {
     assert!(!info.is_detached);
     assert!(info.remotes.is_empty());
     assert!(info.head_hash.is_none());
-    assert_eq!(info.repo_state, "clean");
+    assert_eq!(info.repo_state, RepoState::Clean);
 }
 
 #[test]
@@ -118,6 +197,87 @@ fn get_repo_info_multiple_remotes() {
     assert!(info.remotes.contains(&"upstream".to_string()));
 }
 
+// =============================================================================
+// get_repo_info upstream tracking (3 tests)
+// =============================================================================
+
+/// Commit an empty tree on top of `parents` without moving any ref.
+fn dangling_commit(repo: &Repository, parents: &[git2::Oid], message: &str) -> git2::Oid {
+    let sig = repo.signature().unwrap();
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let parents: Vec<git2::Commit> = parents
+        .iter()
+        .map(|oid| repo.find_commit(*oid).unwrap())
+        .collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(None, &sig, &sig, message, &tree, &parent_refs)
+        .unwrap()
+}
+
+/// `origin/<current branch>` points at `remote_tip` and is set as upstream.
+fn track_origin(repo: &Repository, remote_tip: git2::Oid) {
+    let branch_name = repo.head().unwrap().shorthand().unwrap().to_string();
+    repo.remote("origin", "https://example.com/repo.git")
+        .unwrap();
+    repo.reference(
+        &format!("refs/remotes/origin/{branch_name}"),
+        remote_tip,
+        true,
+        "test",
+    )
+    .unwrap();
+    repo.find_branch(&branch_name, git2::BranchType::Local)
+        .unwrap()
+        .set_upstream(Some(&format!("origin/{branch_name}")))
+        .unwrap();
+}
+
+#[test]
+fn get_repo_info_without_upstream_has_no_tracking() {
+    let (_temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo);
+
+    let info = get_repo_info(&repo).unwrap();
+    assert!(info.upstream.is_none());
+    assert!(info.ahead.is_none());
+    assert!(info.behind.is_none());
+}
+
+#[test]
+fn get_repo_info_in_sync_with_upstream() {
+    let (_temp_dir, repo) = create_test_repo();
+    let base = create_initial_commit(&repo);
+    track_origin(&repo, base);
+
+    let info = get_repo_info(&repo).unwrap();
+    let branch = info.current_branch.clone().unwrap();
+    assert_eq!(info.upstream, Some(format!("origin/{branch}")));
+    assert_eq!(info.ahead, Some(0));
+    assert_eq!(info.behind, Some(0));
+}
+
+#[test]
+fn get_repo_info_counts_ahead_and_behind() {
+    let (_temp_dir, repo) = create_test_repo();
+    let base = create_initial_commit(&repo);
+    let remote_1 = dangling_commit(&repo, &[base], "remote 1");
+    let remote_2 = dangling_commit(&repo, &[remote_1], "remote 2");
+    track_origin(&repo, remote_2);
+    let local_1 = dangling_commit(&repo, &[base], "local 1");
+    let local_2 = dangling_commit(&repo, &[local_1], "local 2");
+    let local_3 = dangling_commit(&repo, &[local_2], "local 3");
+    repo.head()
+        .unwrap()
+        .set_target(local_3, "advance local")
+        .unwrap();
+
+    let info = get_repo_info(&repo).unwrap();
+    assert_eq!(info.ahead, Some(3));
+    assert_eq!(info.behind, Some(2));
+}
+
 // =============================================================================
 // branch/tag deletion (4 tests)
 // =============================================================================
@@ -205,7 +365,7 @@ fn repo_state_clean() {
     create_initial_commit(&repo);
 
     let info = get_repo_info(&repo).unwrap();
-    assert_eq!(info.repo_state, "clean");
+    assert_eq!(info.repo_state, RepoState::Clean);
 }
 
 #[test]
@@ -275,7 +435,98 @@ fn repo_state_merge() {
     let _ = repo.merge(&[&annotated], None, None);
 
     let info = get_repo_info(&repo).unwrap();
-    assert_eq!(info.repo_state, "merge");
+    assert_eq!(info.repo_state, RepoState::Merge);
+}
+
+// =============================================================================
+// get_repo_stats (2 tests)
+// =============================================================================
+
+#[test]
+fn get_repo_stats_counts_small_repo() {
+    let (temp_dir, repo) = create_test_repo();
+    let first = create_initial_commit(&repo);
+    fs::write(temp_dir.path().join("a.txt"), "a\n").unwrap();
+    fs::write(temp_dir.path().join("b.txt"), "b\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("a.txt")).unwrap();
+    index.add_path(Path::new("b.txt")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let other = Signature::now("Other", "OTHER@example.com").unwrap();
+    let parent = repo.find_commit(first).unwrap();
+    let second = repo
+        .commit(Some("HEAD"), &other, &other, "Add files", &tree, &[&parent])
+        .unwrap();
+
+    // A side branch and a tag add no commits of their own.
+    repo.branch("side", &parent, false).unwrap();
+    repo.tag_lightweight("v1", parent.as_object(), false)
+        .unwrap();
+
+    let stats = git::get_repo_stats(&repo, &mut HistoryCache::default()).unwrap();
+    assert_eq!(
+        stats,
+        RepoStats {
+            commit_count: 2,
+            contributor_count: 2,
+            branch_count: 2,
+            tag_count: 1,
+            file_count: 2,
+        }
+    );
+
+    // Commits only reachable from a tag still count.
+    let sig = repo.signature().unwrap();
+    let dangling = repo
+        .commit(
+            None,
+            &sig,
+            &sig,
+            "Tagged only",
+            &tree,
+            &[&repo.find_commit(second).unwrap()],
+        )
+        .unwrap();
+    let dangling = repo.find_object(dangling, None).unwrap();
+    repo.tag_lightweight("v2", &dangling, false).unwrap();
+    let stats = git::get_repo_stats(&repo, &mut HistoryCache::default()).unwrap();
+    assert_eq!(stats.commit_count, 3);
+    assert_eq!(stats.tag_count, 2);
+}
+
+#[test]
+fn get_repo_stats_reuses_walk_until_refs_move() {
+    let (temp_dir, repo) = create_test_repo();
+    let empty = git::get_repo_stats(&repo, &mut HistoryCache::default()).unwrap();
+    assert_eq!(empty.commit_count, 0);
+    assert_eq!(empty.contributor_count, 0);
+
+    let mut cache = HistoryCache::default();
+    create_initial_commit(&repo);
+    assert_eq!(
+        git::get_repo_stats(&repo, &mut cache).unwrap().commit_count,
+        1
+    );
+
+    // Cheap counts stay live while the walk is reused.
+    fs::write(temp_dir.path().join("a.txt"), "a\n").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("a.txt")).unwrap();
+    index.write().unwrap();
+    let stats = git::get_repo_stats(&repo, &mut cache).unwrap();
+    assert_eq!(stats.file_count, 1);
+    assert_eq!(stats.commit_count, 1);
+
+    let sig = repo.signature().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Second", &tree, &[&head])
+        .unwrap();
+    assert_eq!(
+        git::get_repo_stats(&repo, &mut cache).unwrap().commit_count,
+        2
+    );
 }
 
 // =============================================================================
@@ -0,0 +1,92 @@
+//! Integration tests for `reset_to_commit`. Each test builds two commits,
+//! dirties the working tree and index, then resets to the first commit and
+//! checks which of HEAD / index / working tree moved for the given mode.
+
+mod common;
+
+use common::{create_commit_with_file, create_initial_commit, create_test_repo};
+use git2::{Oid, Repository, Status};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use yagg_lib::error::AppError;
+use yagg_lib::git::{reset_to_commit, ResetMode};
+
+/// History: first (initial.txt) → second (+second.txt). On top of that,
+/// `initial.txt` has an unstaged edit. Returns (first, second).
+fn setup(repo: &Repository, temp_dir: &TempDir) -> (Oid, Oid) {
+    let first = create_initial_commit(repo, temp_dir);
+    let second = create_commit_with_file(repo, temp_dir, "second.txt", "two", "Second");
+    fs::write(temp_dir.path().join("initial.txt"), "dirty").unwrap();
+    (first, second)
+}
+
+fn status_of(repo: &Repository, path: &str) -> Status {
+    repo.status_file(Path::new(path)).unwrap()
+}
+
+#[test]
+fn reset_soft_moves_head_only() {
+    let (temp_dir, repo) = create_test_repo();
+    let (first, _second) = setup(&repo, &temp_dir);
+
+    reset_to_commit(&repo, &first.to_string(), ResetMode::Soft).unwrap();
+
+    assert_eq!(repo.head().unwrap().target(), Some(first));
+    // second.txt is still in the index, so it shows as staged-new.
+    assert_eq!(status_of(&repo, "second.txt"), Status::INDEX_NEW);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "dirty"
+    );
+}
+
+#[test]
+fn reset_mixed_resets_index_keeps_worktree() {
+    let (temp_dir, repo) = create_test_repo();
+    let (first, _second) = setup(&repo, &temp_dir);
+
+    reset_to_commit(&repo, &first.to_string(), ResetMode::Mixed).unwrap();
+
+    assert_eq!(repo.head().unwrap().target(), Some(first));
+    // Index matches `first`, so second.txt is now untracked on disk.
+    assert_eq!(status_of(&repo, "second.txt"), Status::WT_NEW);
+    assert_eq!(status_of(&repo, "initial.txt"), Status::WT_MODIFIED);
+    assert!(temp_dir.path().join("second.txt").exists());
+}
+
+#[test]
+fn reset_hard_overwrites_worktree() {
+    let (temp_dir, repo) = create_test_repo();
+    let (first, _second) = setup(&repo, &temp_dir);
+
+    reset_to_commit(&repo, &first.to_string(), ResetMode::Hard).unwrap();
+
+    assert_eq!(repo.head().unwrap().target(), Some(first));
+    assert_eq!(status_of(&repo, "initial.txt"), Status::CURRENT);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+    assert!(!temp_dir.path().join("second.txt").exists());
+}
+
+#[test]
+fn reset_invalid_hash_is_clear_error() {
+    let (temp_dir, repo) = create_test_repo();
+    let (_first, second) = setup(&repo, &temp_dir);
+
+    let err = reset_to_commit(&repo, "not-a-hash", ResetMode::Hard).unwrap_err();
+    assert!(matches!(err, AppError::InvalidArgument(_)));
+    assert!(err.to_string().contains("Invalid commit hash: not-a-hash"));
+    assert_eq!(repo.head().unwrap().target(), Some(second));
+}
+
+#[test]
+fn reset_unknown_commit_errors() {
+    let (temp_dir, repo) = create_test_repo();
+    setup(&repo, &temp_dir);
+
+    let missing = "0123456789abcdef0123456789abcdef01234567";
+    assert!(reset_to_commit(&repo, missing, ResetMode::Mixed).is_err());
+}
@@ -0,0 +1,185 @@
+//! Behavior tests for signed commits (`git::signing`). The GPG and SSH
+//! tests need the real tools and skip themselves when they're missing.
+
+mod common;
+
+use common::{create_initial_commit, create_test_repo};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use yagg_lib::error::AppError;
+use yagg_lib::git;
+
+fn tool_available(program: &str, arg: &str) -> bool {
+    Command::new(program)
+        .arg(arg)
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+fn stage_file(repo: &git2::Repository, dir: &Path, name: &str) {
+    fs::write(dir.join(name), "content").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new(name)).unwrap();
+    index.write().unwrap();
+}
+
+fn verify_commit(dir: &Path, hash: &str) -> std::process::Output {
+    Command::new("git")
+        .args(["verify-commit", hash])
+        .current_dir(dir)
+        .output()
+        .unwrap()
+}
+
+// ============================================================================
+// signing_config (3 tests)
+// ============================================================================
+
+#[test]
+fn test_signing_config_disabled_by_default() {
+    let (_temp_dir, repo) = create_test_repo();
+
+    assert!(git::signing_config(&repo, None).unwrap().is_none());
+}
+
+#[test]
+fn test_signing_config_override_beats_gpgsign() {
+    let (_temp_dir, repo) = create_test_repo();
+    repo.config()
+        .unwrap()
+        .set_bool("commit.gpgsign", true)
+        .unwrap();
+
+    assert!(git::signing_config(&repo, Some(false)).unwrap().is_none());
+    let config = git::signing_config(&repo, None).unwrap().unwrap();
+    assert_eq!(config.format, git::SigningFormat::OpenPgp);
+    assert_eq!(config.program, "gpg");
+}
+
+#[test]
+fn test_signing_config_ssh_requires_key() {
+    let (_temp_dir, repo) = create_test_repo();
+    repo.config().unwrap().set_str("gpg.format", "ssh").unwrap();
+
+    let result = git::signing_config(&repo, Some(true));
+    assert!(matches!(result, Err(AppError::SigningFailed(_))));
+}
+
+// ============================================================================
+// Signed commits (2 tests)
+// ============================================================================
+
+#[test]
+fn test_create_commit_signs_with_gpg() {
+    if !tool_available("gpg", "--version") {
+        eprintln!("gpg not installed, skipping");
+        return;
+    }
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    // Keep the throwaway key out of the user's keyring. git verify-commit
+    // and the signer both pick GNUPGHOME up from the environment.
+    let gnupg_home = tempfile::TempDir::new().unwrap();
+    std::env::set_var("GNUPGHOME", gnupg_home.path());
+    let status = Command::new("gpg")
+        .args(["--batch", "--passphrase", "", "--quick-gen-key"])
+        .args([
+            "Test User <test@example.com>",
+            "default",
+            "default",
+            "never",
+        ])
+        .output()
+        .unwrap()
+        .status;
+    assert!(status.success());
+    repo.config()
+        .unwrap()
+        .set_str("user.signingkey", "test@example.com")
+        .unwrap();
+
+    stage_file(&repo, temp_dir.path(), "signed.txt");
+    let hash = git::create_commit(
+        &repo,
+        "Signed",
+        &git::AuthorOverride::default(),
+        Some(true),
+        false,
+        false,
+    )
+    .unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id().to_string(), hash);
+    assert!(head.header_field_bytes("gpgsig").is_ok());
+    let output = verify_commit(temp_dir.path(), &hash);
+    let _ = Command::new("gpgconf")
+        .args(["--kill", "gpg-agent"])
+        .output();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_create_commit_signs_with_ssh_from_config() {
+    if !tool_available("which", "ssh-keygen") {
+        eprintln!("ssh-keygen not installed, skipping");
+        return;
+    }
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    let keys = tempfile::TempDir::new().unwrap();
+    let key_path = keys.path().join("id_ed25519");
+    let status = Command::new("ssh-keygen")
+        .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+        .arg(&key_path)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let public_key = fs::read_to_string(key_path.with_extension("pub")).unwrap();
+    let allowed_signers = keys.path().join("allowed_signers");
+    fs::write(&allowed_signers, format!("test@example.com {public_key}")).unwrap();
+
+    let mut config = repo.config().unwrap();
+    config.set_bool("commit.gpgsign", true).unwrap();
+    config.set_str("gpg.format", "ssh").unwrap();
+    config
+        .set_str("user.signingkey", key_path.to_str().unwrap())
+        .unwrap();
+    config
+        .set_str(
+            "gpg.ssh.allowedSignersFile",
+            allowed_signers.to_str().unwrap(),
+        )
+        .unwrap();
+
+    stage_file(&repo, temp_dir.path(), "signed.txt");
+    let hash = git::create_commit(
+        &repo,
+        "Signed",
+        &git::AuthorOverride::default(),
+        None,
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert_eq!(repo.head().unwrap().target().unwrap().to_string(), hash);
+    let head_log = repo.reflog("HEAD").unwrap();
+    let entry = head_log.get(0).unwrap();
+    assert_eq!(entry.id_new().to_string(), hash);
+    assert_eq!(entry.message(), Ok(Some("commit: Signed")));
+    let output = verify_commit(temp_dir.path(), &hash);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
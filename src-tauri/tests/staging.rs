@@ -16,9 +16,10 @@ use std::path::Path;
 use tempfile::TempDir;
 use yagg_lib::error::AppError;
 use yagg_lib::git::{
-    self, discard_hunk, get_file_diff, get_file_statuses, resolve_conflict, revert_commit,
-    revert_commit_file, revert_commit_file_lines, stage_file, stage_files, stage_hunk, stage_lines,
-    unstage_file, unstage_files, unstage_hunk, DiffHunk, DiffLine, FileStatusType, LineType,
+    self, discard_hunk, get_file_diff, get_file_diff_with_config, get_file_statuses,
+    get_path_status, revert_commit, revert_commit_file, revert_commit_file_lines, stage_file,
+    stage_files, stage_hunk, stage_lines, unstage_file, unstage_files, unstage_hunk, DiffConfig,
+    DiffHunk, DiffLine, FileStatusType, LineType,
 };
 
 // Local helpers used by the revert tests. Live alongside the tests that need
@@ -72,13 +73,13 @@ fn make_commit_delete(
 }
 
 // =============================================================================
-// File statuses (4 tests)
+// File statuses (6 tests)
 // =============================================================================
 
 #[test]
 fn get_file_statuses_empty_repo() {
     let (_temp_dir, repo) = create_test_repo();
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
     assert!(statuses.unstaged.is_empty());
     assert!(statuses.untracked.is_empty());
@@ -90,7 +91,7 @@ fn get_file_statuses_untracked_file() {
     let file_path = temp_dir.path().join("new_file.txt");
     fs::write(&file_path, "new content").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
     assert!(statuses.unstaged.is_empty());
     assert_eq!(statuses.untracked.len(), 1);
@@ -113,7 +114,7 @@ fn get_file_statuses_staged_new_file() {
     index.add_path(Path::new("staged.txt")).unwrap();
     index.write().unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
     assert_eq!(statuses.staged[0].path, "staged.txt");
     assert!(matches!(statuses.staged[0].status, FileStatusType::Added));
@@ -127,7 +128,7 @@ fn get_file_statuses_modified_unstaged() {
     let file_path = temp_dir.path().join("initial.txt");
     fs::write(&file_path, "modified content").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
     assert_eq!(statuses.unstaged.len(), 1);
     assert_eq!(statuses.unstaged[0].path, "initial.txt");
@@ -137,6 +138,112 @@ fn get_file_statuses_modified_unstaged() {
     ));
 }
 
+#[test]
+fn get_file_statuses_has_no_line_counts_by_default() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "modified content").unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert_eq!(statuses.unstaged[0].additions, None);
+    assert_eq!(statuses.unstaged[0].deletions, None);
+}
+
+#[test]
+fn add_line_counts_for_staged_unstaged_and_untracked() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    // Staged: one line replaced and two added; then one more unstaged line.
+    fs::write(
+        temp_dir.path().join("initial.txt"),
+        "changed\nsecond\nthird\n",
+    )
+    .unwrap();
+    stage_file(&repo, "initial.txt").unwrap();
+    fs::write(
+        temp_dir.path().join("initial.txt"),
+        "changed\nsecond\nthird\nfourth\n",
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("new.txt"), "a\nb\n").unwrap();
+
+    let mut statuses = get_file_statuses(&repo, false).unwrap();
+    git::add_line_counts(&repo, &mut statuses).unwrap();
+
+    let staged = &statuses.staged[0];
+    assert_eq!((staged.additions, staged.deletions), (Some(3), Some(1)));
+    let unstaged = &statuses.unstaged[0];
+    assert_eq!((unstaged.additions, unstaged.deletions), (Some(1), Some(0)));
+    let untracked = &statuses.untracked[0];
+    assert_eq!(untracked.path, "new.txt");
+    assert_eq!(
+        (untracked.additions, untracked.deletions),
+        (Some(2), Some(0))
+    );
+}
+
+// =============================================================================
+// get_path_status (5 tests)
+// =============================================================================
+
+#[test]
+fn get_path_status_modified_unstaged() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "modified content").unwrap();
+
+    let status = get_path_status(&repo, "initial.txt").unwrap().unwrap();
+    assert_eq!(status.path, "initial.txt");
+    assert!(status.staged.is_none());
+    assert!(matches!(status.unstaged, Some(FileStatusType::Modified)));
+}
+
+#[test]
+fn get_path_status_staged_and_modified_again() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "staged content").unwrap();
+    stage_file(&repo, "initial.txt").unwrap();
+
+    let status = get_path_status(&repo, "initial.txt").unwrap().unwrap();
+    assert!(matches!(status.staged, Some(FileStatusType::Modified)));
+    assert!(status.unstaged.is_none());
+
+    fs::write(temp_dir.path().join("initial.txt"), "edited after staging").unwrap();
+    let status = get_path_status(&repo, "initial.txt").unwrap().unwrap();
+    assert!(matches!(status.staged, Some(FileStatusType::Modified)));
+    assert!(matches!(status.unstaged, Some(FileStatusType::Modified)));
+}
+
+#[test]
+fn get_path_status_untracked() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+
+    let status = get_path_status(&repo, "new.txt").unwrap().unwrap();
+    assert!(status.staged.is_none());
+    assert!(matches!(status.unstaged, Some(FileStatusType::Untracked)));
+}
+
+#[test]
+fn get_path_status_clean_is_none() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(get_path_status(&repo, "initial.txt").unwrap().is_none());
+}
+
+#[test]
+fn get_path_status_missing_path_is_none() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(get_path_status(&repo, "does-not-exist.txt")
+        .unwrap()
+        .is_none());
+}
+
 // =============================================================================
 // stage_file / unstage_file (5 tests)
 // =============================================================================
@@ -151,7 +258,7 @@ fn stage_file_new_file() {
 
     stage_file(&repo, "new_file.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
     assert_eq!(statuses.staged[0].path, "new_file.txt");
     assert!(statuses.untracked.is_empty());
@@ -167,7 +274,7 @@ fn stage_file_modified_file() {
 
     stage_file(&repo, "initial.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
     assert_eq!(statuses.staged[0].path, "initial.txt");
     assert!(matches!(
@@ -187,7 +294,7 @@ fn stage_file_deleted_file() {
 
     stage_file(&repo, "initial.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
     assert_eq!(statuses.staged[0].path, "initial.txt");
     assert!(matches!(statuses.staged[0].status, FileStatusType::Deleted));
@@ -202,12 +309,12 @@ fn unstage_file_new_file() {
     fs::write(&file_path, "new content").unwrap();
     stage_file(&repo, "new_file.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
 
     unstage_file(&repo, "new_file.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
     assert_eq!(statuses.untracked.len(), 1);
 }
@@ -221,12 +328,12 @@ fn unstage_file_modified_file() {
     fs::write(&file_path, "modified content").unwrap();
     stage_file(&repo, "initial.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
 
     unstage_file(&repo, "initial.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
     assert_eq!(statuses.unstaged.len(), 1);
     assert_eq!(statuses.unstaged[0].path, "initial.txt");
@@ -247,14 +354,374 @@ fn mixed_statuses() {
     let untracked_path = temp_dir.path().join("untracked.txt");
     fs::write(&untracked_path, "untracked").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
     assert_eq!(statuses.unstaged.len(), 1);
     assert_eq!(statuses.untracked.len(), 1);
 }
 
 // =============================================================================
-// stage_hunk / unstage_hunk (4 tests)
+// stage_all / stage_untracked / unstage_all (5 tests)
+// =============================================================================
+
+/// initial.txt + gone.txt committed; then initial.txt modified, gone.txt
+/// deleted, and new.txt created.
+fn mixed_changes() -> (TempDir, Repository) {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    make_commit(&repo, &temp_dir, "gone.txt", "bye", "Add gone");
+    fs::write(temp_dir.path().join("initial.txt"), "modified").unwrap();
+    fs::remove_file(temp_dir.path().join("gone.txt")).unwrap();
+    fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+    (temp_dir, repo)
+}
+
+#[test]
+fn stage_all_stages_modified_new_and_deleted() {
+    let (_temp_dir, repo) = mixed_changes();
+
+    git::stage_all(&repo).unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.unstaged.is_empty());
+    assert!(statuses.untracked.is_empty());
+    assert_eq!(statuses.staged.len(), 3);
+    let status_of = |path: &str| {
+        &statuses
+            .staged
+            .iter()
+            .find(|f| f.path == path)
+            .unwrap()
+            .status
+    };
+    assert!(matches!(status_of("initial.txt"), FileStatusType::Modified));
+    assert!(matches!(status_of("gone.txt"), FileStatusType::Deleted));
+    assert!(matches!(status_of("new.txt"), FileStatusType::Added));
+}
+
+#[test]
+fn stage_untracked_leaves_tracked_changes_unstaged() {
+    let (temp_dir, repo) = mixed_changes();
+    fs::create_dir(temp_dir.path().join("docs")).unwrap();
+    fs::write(temp_dir.path().join("docs/readme.md"), "hi").unwrap();
+
+    let staged = git::stage_untracked(&repo).unwrap();
+
+    assert_eq!(staged, 2);
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.untracked.is_empty());
+    let mut staged_paths: Vec<&str> = statuses.staged.iter().map(|f| f.path.as_str()).collect();
+    staged_paths.sort();
+    assert_eq!(staged_paths, ["docs/readme.md", "new.txt"]);
+    assert!(statuses
+        .staged
+        .iter()
+        .all(|f| matches!(f.status, FileStatusType::Added)));
+    let mut unstaged: Vec<&str> = statuses.unstaged.iter().map(|f| f.path.as_str()).collect();
+    unstaged.sort();
+    assert_eq!(unstaged, ["gone.txt", "initial.txt"]);
+}
+
+#[test]
+fn unstage_all_restores_index_to_head() {
+    let (temp_dir, repo) = mixed_changes();
+    git::stage_all(&repo).unwrap();
+
+    git::unstage_all(&repo).unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert_eq!(statuses.unstaged.len(), 2);
+    assert_eq!(statuses.untracked.len(), 1);
+    assert_eq!(statuses.untracked[0].path, "new.txt");
+    // Working tree is left alone.
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "modified"
+    );
+}
+
+#[test]
+fn unstage_all_keeps_working_tree_edits_on_top_of_staged_ones() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "staged edit").unwrap();
+    fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+    fs::write(temp_dir.path().join("b.txt"), "b").unwrap();
+    stage_files(
+        &repo,
+        &["initial.txt".into(), "a.txt".into(), "b.txt".into()],
+    )
+    .unwrap();
+    fs::write(temp_dir.path().join("initial.txt"), "later edit").unwrap();
+
+    git::unstage_all(&repo).unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert_eq!(statuses.unstaged.len(), 1);
+    assert_eq!(statuses.untracked.len(), 2);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "later edit"
+    );
+}
+
+#[test]
+fn unstage_all_without_commits_empties_index() {
+    let (temp_dir, repo) = create_test_repo();
+    fs::write(temp_dir.path().join("a.txt"), "a").unwrap();
+    git::stage_all(&repo).unwrap();
+    assert_eq!(get_file_statuses(&repo, false).unwrap().staged.len(), 1);
+
+    git::unstage_all(&repo).unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert_eq!(statuses.untracked.len(), 1);
+}
+
+// =============================================================================
+// stage_directory (3 tests)
+// =============================================================================
+
+fn write_file(temp_dir: &TempDir, path: &str, content: &str) {
+    let full = temp_dir.path().join(path);
+    fs::create_dir_all(full.parent().unwrap()).unwrap();
+    fs::write(full, content).unwrap();
+}
+
+fn staged_paths(repo: &Repository) -> Vec<String> {
+    let mut paths: Vec<String> = get_file_statuses(repo, false)
+        .unwrap()
+        .staged
+        .into_iter()
+        .map(|f| f.path)
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn stage_directory_stages_only_nested_files_under_dir() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    write_file(&temp_dir, "src/a.txt", "a");
+    write_file(&temp_dir, "src/deep/b.txt", "b");
+    write_file(&temp_dir, "srcx/c.txt", "c");
+    write_file(&temp_dir, "other/d.txt", "d");
+
+    let count = git::stage_directory(&repo, "src/").unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(staged_paths(&repo), vec!["src/a.txt", "src/deep/b.txt"]);
+}
+
+#[test]
+fn stage_directory_stages_deletions_and_respects_gitignore() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::create_dir(temp_dir.path().join("lib")).unwrap();
+    make_commit(&repo, &temp_dir, "lib/old.txt", "old", "Add old");
+    make_commit(&repo, &temp_dir, ".gitignore", "*.log\n", "Ignore logs");
+    fs::remove_file(temp_dir.path().join("lib/old.txt")).unwrap();
+    write_file(&temp_dir, "lib/new.txt", "new");
+    write_file(&temp_dir, "lib/debug.log", "noise");
+
+    let count = git::stage_directory(&repo, "lib").unwrap();
+
+    assert_eq!(count, 2);
+    assert_eq!(staged_paths(&repo), vec!["lib/new.txt", "lib/old.txt"]);
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    let old = statuses.staged.iter().find(|f| f.path == "lib/old.txt");
+    assert!(matches!(old.unwrap().status, FileStatusType::Deleted));
+}
+
+#[test]
+fn stage_directory_rejects_escaping_paths() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    for dir in ["", "/", "../outside", "a/../../b"] {
+        assert!(
+            matches!(
+                git::stage_directory(&repo, dir),
+                Err(AppError::InvalidPath(_))
+            ),
+            "{dir:?} should be rejected"
+        );
+    }
+}
+
+// =============================================================================
+// discard_all_changes (4 tests)
+// =============================================================================
+
+#[test]
+fn discard_all_changes_reverts_staged_and_unstaged_work() {
+    let (temp_dir, repo) = mixed_changes();
+    stage_file(&repo, "initial.txt").unwrap();
+    fs::write(temp_dir.path().join("initial.txt"), "edited again").unwrap();
+
+    git::discard_all_changes(&repo, false).unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert!(statuses.unstaged.is_empty());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+    assert!(temp_dir.path().join("gone.txt").exists());
+}
+
+#[test]
+fn discard_all_changes_keeps_untracked_without_flag() {
+    let (temp_dir, repo) = mixed_changes();
+    stage_file(&repo, "new.txt").unwrap();
+    fs::write(temp_dir.path().join("loose.txt"), "loose").unwrap();
+
+    git::discard_all_changes(&repo, false).unwrap();
+
+    // The staged-new file drops back to untracked rather than being deleted.
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert_eq!(statuses.untracked.len(), 2);
+    assert!(temp_dir.path().join("new.txt").exists());
+    assert!(temp_dir.path().join("loose.txt").exists());
+}
+
+#[test]
+fn discard_all_changes_removes_untracked_with_flag() {
+    let (temp_dir, repo) = mixed_changes();
+    fs::create_dir(temp_dir.path().join("dir")).unwrap();
+    fs::write(temp_dir.path().join("dir/nested.txt"), "nested").unwrap();
+
+    git::discard_all_changes(&repo, true).unwrap();
+
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert!(statuses.unstaged.is_empty());
+    assert!(statuses.untracked.is_empty());
+    assert!(!temp_dir.path().join("new.txt").exists());
+    assert!(!temp_dir.path().join("dir/nested.txt").exists());
+}
+
+#[test]
+fn discard_all_changes_unborn_head_is_error() {
+    let (temp_dir, repo) = create_test_repo();
+    fs::write(temp_dir.path().join("new.txt"), "new").unwrap();
+    stage_file(&repo, "new.txt").unwrap();
+
+    let err = git::discard_all_changes(&repo, true).unwrap_err();
+    assert_eq!(err.code(), "git_unborn_branch");
+    assert!(temp_dir.path().join("new.txt").exists());
+}
+
+// =============================================================================
+// discard_file (8 tests)
+// =============================================================================
+
+#[test]
+fn discard_file_deletes_untracked() {
+    let (temp_dir, repo) = mixed_changes();
+
+    assert!(git::discard_file(&repo, "new.txt").unwrap().is_none());
+    assert!(!temp_dir.path().join("new.txt").exists());
+}
+
+#[test]
+fn discard_file_restores_tracked_from_head() {
+    let (temp_dir, repo) = mixed_changes();
+    stage_file(&repo, "initial.txt").unwrap();
+    fs::write(temp_dir.path().join("initial.txt"), "edited again").unwrap();
+
+    assert!(git::discard_file(&repo, "initial.txt").unwrap().is_none());
+    assert!(git::discard_file(&repo, "gone.txt").unwrap().is_none());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+    assert!(temp_dir.path().join("gone.txt").exists());
+}
+
+#[test]
+fn discard_file_unstages_and_deletes_staged_new() {
+    let (temp_dir, repo) = mixed_changes();
+    stage_file(&repo, "new.txt").unwrap();
+
+    assert!(git::discard_file(&repo, "new.txt").unwrap().is_none());
+    assert!(!temp_dir.path().join("new.txt").exists());
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(!statuses.staged.iter().any(|s| s.path == "new.txt"));
+    // Other changes are left alone.
+    assert!(statuses.unstaged.iter().any(|s| s.path == "initial.txt"));
+}
+
+#[test]
+fn discard_file_restores_file_removed_from_index() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    // `git rm --cached initial.txt` followed by an edit.
+    let mut index = repo.index().unwrap();
+    index.remove_path(Path::new("initial.txt")).unwrap();
+    index.write().unwrap();
+    fs::write(temp_dir.path().join("initial.txt"), "edited").unwrap();
+
+    assert!(git::discard_file(&repo, "initial.txt").unwrap().is_none());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+    assert_eq!(index_content(&repo, "initial.txt"), "initial content");
+}
+
+#[test]
+fn discard_file_unknown_path_is_error() {
+    let (_temp_dir, repo) = mixed_changes();
+    assert!(matches!(
+        git::discard_file(&repo, "no-such.txt"),
+        Err(AppError::InvalidArgument(msg)) if msg == "no-such.txt has no changes to discard"
+    ));
+}
+
+#[test]
+fn discard_file_clean_file_is_noop() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(git::discard_file(&repo, "initial.txt").unwrap().is_none());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+}
+
+#[test]
+fn discard_file_ignored_path_is_error() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join(".gitignore"), "*.log\n").unwrap();
+    fs::write(temp_dir.path().join("build.log"), "output").unwrap();
+
+    assert!(matches!(
+        git::discard_file(&repo, "build.log"),
+        Err(AppError::InvalidArgument(msg)) if msg.contains("is ignored")
+    ));
+    assert!(temp_dir.path().join("build.log").exists());
+}
+
+#[test]
+fn discard_file_rejects_escaping_path() {
+    let (_temp_dir, repo) = mixed_changes();
+    assert!(matches!(
+        git::discard_file(&repo, "../outside.txt"),
+        Err(AppError::InvalidPath(p)) if p == "../outside.txt"
+    ));
+}
+
+// =============================================================================
+// stage_hunk / unstage_hunk (15 tests)
 // =============================================================================
 
 #[test]
@@ -276,10 +743,10 @@ fn stage_hunk_first_hunk() {
 
     fs::write(&file_path, "modified1\nline2\nline3\n").unwrap();
 
-    let result = stage_hunk(&repo, "multi.txt", 0);
+    let result = stage_hunk(&repo, "multi.txt", 0, None, None, &DiffConfig::default());
     assert!(result.is_ok());
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
 }
 
@@ -302,8 +769,8 @@ fn stage_hunk_out_of_range() {
 
     fs::write(&file_path, "modified\n").unwrap();
 
-    let result = stage_hunk(&repo, "file.txt", 5);
-    assert!(result.is_err());
+    let result = stage_hunk(&repo, "file.txt", 5, None, None, &DiffConfig::default());
+    assert!(matches!(result, Err(AppError::HunkOutOfRange(5))));
 }
 
 #[test]
@@ -326,13 +793,13 @@ fn unstage_hunk_after_staging() {
     fs::write(&file_path, "modified\n").unwrap();
     stage_file(&repo, "file.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 1);
 
-    let result = unstage_hunk(&repo, "file.txt", 0);
+    let result = unstage_hunk(&repo, "file.txt", 0, None, &DiffConfig::default());
     assert!(result.is_ok());
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty() || !statuses.unstaged.is_empty());
 }
 
@@ -356,12 +823,253 @@ fn unstage_hunk_out_of_range() {
     fs::write(&file_path, "modified\n").unwrap();
     stage_file(&repo, "file.txt").unwrap();
 
-    let result = unstage_hunk(&repo, "file.txt", 5);
-    assert!(result.is_err());
+    let result = unstage_hunk(&repo, "file.txt", 5, None, &DiffConfig::default());
+    assert!(matches!(result, Err(AppError::HunkOutOfRange(5))));
+}
+
+/// Commit `content` as `path`, then overwrite the working copy with `modified`.
+fn commit_then_modify(
+    repo: &Repository,
+    temp_dir: &TempDir,
+    path: &str,
+    content: &str,
+    modified: &str,
+) {
+    make_commit(repo, temp_dir, path, content, "Add file");
+    fs::write(temp_dir.path().join(path), modified).unwrap();
+}
+
+fn index_content(repo: &Repository, path: &str) -> String {
+    let index = repo.index().unwrap();
+    let entry = index.get_path(Path::new(path), 0).unwrap();
+    let blob = repo.find_blob(entry.id).unwrap();
+    String::from_utf8(blob.content().to_vec()).unwrap()
+}
+
+#[test]
+fn stage_hunk_keeps_crlf_line_endings() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_then_modify(
+        &repo,
+        &temp_dir,
+        "win.txt",
+        "one\r\ntwo\r\nthree\r\n",
+        "one\r\nTWO\r\nthree\r\n",
+    );
+
+    stage_hunk(&repo, "win.txt", 0, None, None, &DiffConfig::default()).unwrap();
+
+    assert_eq!(index_content(&repo, "win.txt"), "one\r\nTWO\r\nthree\r\n");
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.unstaged.is_empty());
+}
+
+#[test]
+fn stage_hunk_of_new_crlf_file_keeps_crlf() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("new.txt"), "a\r\nb\r\n").unwrap();
+
+    stage_hunk(&repo, "new.txt", 0, None, None, &DiffConfig::default()).unwrap();
+
+    assert_eq!(index_content(&repo, "new.txt"), "a\r\nb\r\n");
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.unstaged.is_empty());
+}
+
+#[test]
+fn unstage_hunk_keeps_crlf_line_endings() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_then_modify(
+        &repo,
+        &temp_dir,
+        "win.txt",
+        "one\r\ntwo\r\nthree\r\n",
+        "one\r\nTWO\r\nthree\r\n",
+    );
+    stage_file(&repo, "win.txt").unwrap();
+
+    unstage_hunk(&repo, "win.txt", 0, None, &DiffConfig::default()).unwrap();
+
+    assert_eq!(index_content(&repo, "win.txt"), "one\r\ntwo\r\nthree\r\n");
+}
+
+#[test]
+fn stage_hunk_does_not_add_missing_trailing_newline() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_then_modify(&repo, &temp_dir, "f.txt", "a\nb", "a\nc");
+
+    stage_hunk(&repo, "f.txt", 0, None, None, &DiffConfig::default()).unwrap();
+
+    assert_eq!(index_content(&repo, "f.txt"), "a\nc");
+    assert!(get_file_statuses(&repo, false).unwrap().unstaged.is_empty());
+}
+
+#[test]
+fn stage_hunk_removing_trailing_newline() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_then_modify(&repo, &temp_dir, "f.txt", "a\nb\n", "a\nb");
+
+    let diff = get_file_diff(&repo, "f.txt", false).unwrap();
+    let flagged: Vec<&str> = diff.hunks[0]
+        .lines
+        .iter()
+        .filter(|l| l.no_newline)
+        .map(|l| l.content.as_str())
+        .collect();
+    assert_eq!(flagged, vec!["b"]);
+
+    stage_hunk(&repo, "f.txt", 0, None, None, &DiffConfig::default()).unwrap();
+
+    assert_eq!(index_content(&repo, "f.txt"), "a\nb");
+    assert!(get_file_statuses(&repo, false).unwrap().unstaged.is_empty());
+}
+
+#[test]
+fn unstage_hunk_restores_trailing_newline() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_then_modify(&repo, &temp_dir, "f.txt", "a\nb\n", "a\nb");
+    stage_file(&repo, "f.txt").unwrap();
+
+    unstage_hunk(&repo, "f.txt", 0, None, &DiffConfig::default()).unwrap();
+
+    assert_eq!(index_content(&repo, "f.txt"), "a\nb\n");
+}
+
+#[test]
+fn stage_hunk_with_matching_header() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_then_modify(&repo, &temp_dir, "f.txt", "a\nb\n", "a\nB\n");
+    let header = get_file_diff(&repo, "f.txt", false).unwrap().hunks[0]
+        .header
+        .clone();
+
+    stage_hunk(
+        &repo,
+        "f.txt",
+        0,
+        Some(&header),
+        None,
+        &DiffConfig::default(),
+    )
+    .unwrap();
+
+    assert_eq!(index_content(&repo, "f.txt"), "a\nB\n");
+}
+
+#[test]
+fn stage_hunk_rejects_stale_header() {
+    let (temp_dir, repo) = create_test_repo();
+    let original: String = (1..=20).map(|i| format!("l{i}\n")).collect();
+    commit_then_modify(
+        &repo,
+        &temp_dir,
+        "f.txt",
+        &original,
+        &original.replace("l18\n", "L18\n"),
+    );
+    // The UI shows one hunk, at the end of the file...
+    let header = get_file_diff(&repo, "f.txt", false).unwrap().hunks[0]
+        .header
+        .clone();
+    // ...then an edit near the top becomes hunk 0 before the user clicks.
+    fs::write(
+        temp_dir.path().join("f.txt"),
+        original.replace("l2\n", "L2\n").replace("l18\n", "L18\n"),
+    )
+    .unwrap();
+
+    let result = stage_hunk(
+        &repo,
+        "f.txt",
+        0,
+        Some(&header),
+        None,
+        &DiffConfig::default(),
+    );
+
+    assert!(matches!(result, Err(AppError::StaleHunk(0))));
+    assert_eq!(index_content(&repo, "f.txt"), original);
+}
+
+#[test]
+fn unstage_hunk_of_new_file_makes_it_untracked() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("new.txt"), "one\ntwo\nthree\n").unwrap();
+    stage_file(&repo, "new.txt").unwrap();
+
+    unstage_hunk(&repo, "new.txt", 0, None, &DiffConfig::default()).unwrap();
+
+    let index = repo.index().unwrap();
+    assert!(index.get_path(Path::new("new.txt"), 0).is_none());
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert_eq!(statuses.untracked.len(), 1);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("new.txt")).unwrap(),
+        "one\ntwo\nthree\n"
+    );
+}
+
+#[test]
+fn stage_hunk_from_zero_context_diff() {
+    let (temp_dir, repo) = create_test_repo();
+    let original: String = (1..=10).map(|i| format!("l{i}\n")).collect();
+    let modified = original
+        .replace("l2\n", "l2\nnew\n")
+        .replace("l8\n", "L8\n");
+    commit_then_modify(&repo, &temp_dir, "f.txt", &original, &modified);
+
+    // Without context the insertion is its own hunk, and its old side is
+    // empty: "-2,0" names the line it follows.
+    let diff = get_file_diff_with_config(
+        &repo,
+        "f.txt",
+        false,
+        &DiffConfig::default(),
+        false,
+        Some(0),
+    )
+    .unwrap();
+    assert_eq!(diff.hunks.len(), 2);
+    assert_eq!((diff.hunks[0].old_start, diff.hunks[0].old_lines), (2, 0));
+
+    stage_hunk(
+        &repo,
+        "f.txt",
+        0,
+        Some(&diff.hunks[0].header),
+        Some(0),
+        &DiffConfig::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        index_content(&repo, "f.txt"),
+        original.replace("l2\n", "l2\nnew\n")
+    );
+}
+
+#[test]
+fn stage_hunk_refuses_hunk_past_diff_budget() {
+    let (temp_dir, repo) = create_test_repo();
+    let original: String = (1..=20).map(|i| format!("l{i}\n")).collect();
+    let modified = original.replace("l2\n", "L2\n").replace("l18\n", "L18\n");
+    commit_then_modify(&repo, &temp_dir, "f.txt", &original, &modified);
+    let config = DiffConfig {
+        max_diff_bytes: 1,
+        ..DiffConfig::default()
+    };
+
+    let result = stage_hunk(&repo, "f.txt", 1, None, None, &config);
+
+    assert!(matches!(result, Err(AppError::InvalidArgument(_))));
+    assert_eq!(index_content(&repo, "f.txt"), original);
 }
 
 // =============================================================================
-// stage_lines (2 tests)
+// stage_lines / stage_selection (4 tests)
 // =============================================================================
 
 #[test]
@@ -383,7 +1091,7 @@ fn stage_lines_partial_hunk() {
 
     fs::write(&file_path, "modified1\nmodified2\nline3\n").unwrap();
 
-    let result = stage_lines(&repo, "file.txt", 0, vec![1]);
+    let result = stage_lines(&repo, "file.txt", 0, vec![1], None, &DiffConfig::default());
     assert!(result.is_ok());
 }
 
@@ -406,12 +1114,65 @@ fn stage_lines_out_of_range_hunk() {
 
     fs::write(&file_path, "modified\n").unwrap();
 
-    let result = stage_lines(&repo, "file.txt", 5, vec![0]);
+    let result = stage_lines(&repo, "file.txt", 5, vec![0], None, &DiffConfig::default());
     assert!(result.is_err());
 }
 
+#[test]
+fn stage_selection_spans_two_hunks() {
+    let (temp_dir, repo) = create_test_repo();
+    let original: String = (1..=20).map(|i| format!("l{i}\n")).collect();
+    let modified = original
+        .replace("l2\n", "L2\nnew-a\n")
+        .replace("l18\n", "L18\n");
+    commit_then_modify(&repo, &temp_dir, "f.txt", &original, &modified);
+
+    let diff = get_file_diff(&repo, "f.txt", false).unwrap();
+    assert_eq!(diff.hunks.len(), 2);
+    let line_of = |hunk: usize, text: &str| {
+        diff.hunks[hunk]
+            .lines
+            .iter()
+            .position(|l| l.content == text)
+            .unwrap()
+    };
+    // From the first hunk only the added line; from the second the whole
+    // l18 -> L18 change.
+    let selections = vec![
+        (1, vec![line_of(1, "l18\n"), line_of(1, "L18\n")]),
+        (0, vec![line_of(0, "new-a\n")]),
+    ];
+
+    git::stage_selection(&repo, "f.txt", &selections, None, &DiffConfig::default()).unwrap();
+
+    let expected = original
+        .replace("l2\n", "l2\nnew-a\n")
+        .replace("l18\n", "L18\n");
+    assert_eq!(index_content(&repo, "f.txt"), expected);
+    // The rest is still unstaged.
+    let remaining = get_file_diff(&repo, "f.txt", false).unwrap();
+    assert_eq!(remaining.hunks.len(), 1);
+}
+
+#[test]
+fn stage_selection_out_of_range_hunk_stages_nothing() {
+    let (temp_dir, repo) = create_test_repo();
+    commit_then_modify(&repo, &temp_dir, "f.txt", "a\nb\n", "a\nB\n");
+
+    let result = git::stage_selection(
+        &repo,
+        "f.txt",
+        &[(0, vec![1, 2]), (3, vec![0])],
+        None,
+        &DiffConfig::default(),
+    );
+
+    assert!(matches!(result, Err(AppError::HunkOutOfRange(3))));
+    assert_eq!(index_content(&repo, "f.txt"), "a\nb\n");
+}
+
 // =============================================================================
-// discard_hunk (3 tests)
+// discard_hunk (4 tests)
 // =============================================================================
 
 #[test]
@@ -433,7 +1194,7 @@ fn discard_hunk_whole_hunk() {
 
     fs::write(&file_path, "modified1\nline2\nline3\n").unwrap();
 
-    let result = discard_hunk(&repo, "file.txt", 0, None);
+    let result = discard_hunk(&repo, "file.txt", 0, None, None, &DiffConfig::default());
     assert!(result.is_ok());
 
     let content = fs::read_to_string(&file_path).unwrap();
@@ -478,6 +1239,8 @@ fn discard_hunk_selected_lines() {
         "file.txt",
         0,
         Some(vec![first_deletion_idx, first_addition_idx]),
+        None,
+        &DiffConfig::default(),
     );
     assert!(result.is_ok());
 
@@ -505,12 +1268,28 @@ fn discard_hunk_out_of_range() {
 
     fs::write(&file_path, "modified\n").unwrap();
 
-    let result = discard_hunk(&repo, "file.txt", 5, None);
+    let result = discard_hunk(&repo, "file.txt", 5, None, None, &DiffConfig::default());
     assert!(result.is_err());
 }
 
+#[test]
+fn discard_hunk_from_zero_context_diff() {
+    let (temp_dir, repo) = create_test_repo();
+    let original: String = (1..=10).map(|i| format!("l{i}\n")).collect();
+    let modified = original.replace("l3\n", "").replace("l8\n", "L8\n");
+    commit_then_modify(&repo, &temp_dir, "f.txt", &original, &modified);
+
+    // The deletion's new side is empty and follows line 2.
+    discard_hunk(&repo, "f.txt", 0, None, Some(0), &DiffConfig::default()).unwrap();
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("f.txt")).unwrap(),
+        original.replace("l8\n", "L8\n")
+    );
+}
+
 // =============================================================================
-// revert_commit / revert_commit_file / revert_commit_file_lines (8 tests)
+// revert_commit / revert_commit_file / revert_commit_file_lines (10 tests)
 // =============================================================================
 
 #[test]
@@ -558,6 +1337,46 @@ fn revert_commit_deleted_file() {
     assert_eq!(content, "content\n");
 }
 
+#[test]
+fn revert_commit_and_commit_creates_commit() {
+    let (temp_dir, repo) = create_test_repo();
+    make_commit(&repo, &temp_dir, "file.txt", "original\n", "initial");
+    let commit_oid = make_commit(&repo, &temp_dir, "file.txt", "modified\n", "modify file");
+
+    let new_hash = git::revert_commit_and_commit(&repo, &commit_oid.to_string())
+        .unwrap()
+        .unwrap();
+
+    let head = repo.head().unwrap().peel_to_commit().unwrap();
+    assert_eq!(head.id().to_string(), new_hash);
+    assert_eq!(head.parent_id(0).unwrap(), commit_oid);
+    assert_eq!(
+        head.message().unwrap(),
+        format!("Revert \"modify file\"\n\nThis reverts commit {commit_oid}.\n")
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+        "original\n"
+    );
+    assert_eq!(repo.state(), git2::RepositoryState::Clean);
+    assert!(repo.statuses(None).unwrap().is_empty());
+}
+
+#[test]
+fn revert_commit_and_commit_leaves_conflicts() {
+    let (temp_dir, repo) = create_test_repo();
+    make_commit(&repo, &temp_dir, "file.txt", "original\n", "initial");
+    let commit_oid = make_commit(&repo, &temp_dir, "file.txt", "modified\n", "modify file");
+    let head = make_commit(&repo, &temp_dir, "file.txt", "rewritten\n", "rewrite file");
+
+    let result = git::revert_commit_and_commit(&repo, &commit_oid.to_string()).unwrap();
+
+    assert_eq!(result, None);
+    assert_eq!(repo.head().unwrap().target(), Some(head));
+    assert!(repo.index().unwrap().has_conflicts());
+    assert_eq!(repo.state(), git2::RepositoryState::Revert);
+}
+
 #[test]
 fn revert_commit_file_single() {
     let (temp_dir, repo) = create_test_repo();
@@ -645,7 +1464,7 @@ fn revert_commit_stages_changes() {
 
     revert_commit(&repo, &commit_oid.to_string()).unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(
         !statuses.staged.is_empty(),
         "Revert should stage the changes"
@@ -708,7 +1527,7 @@ fn stage_file_empty_repo() {
     let result = stage_file(&repo, "new.txt");
     assert!(result.is_ok());
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.iter().any(|s| s.path == "new.txt"));
 }
 
@@ -721,51 +1540,19 @@ fn unstage_file_empty_repo() {
 
     stage_file(&repo, "new.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.iter().any(|s| s.path == "new.txt"));
 
     let result = unstage_file(&repo, "new.txt");
     assert!(result.is_ok());
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
     assert!(statuses.untracked.iter().any(|s| s.path == "new.txt"));
 }
 
 // =============================================================================
-// resolve_conflict — staging side-effect (1 test; pure-string variants stay inline)
-// =============================================================================
-
-#[test]
-fn resolve_conflict_stages_file() {
-    let (temp_dir, repo) = create_test_repo();
-    common::create_commit_with_file(
-        &repo,
-        &temp_dir,
-        "conflict.txt",
-        "base content\n",
-        "Initial",
-    );
-
-    let file_path = temp_dir.path().join("conflict.txt");
-    fs::write(
-        &file_path,
-        "<<<<<<< HEAD\nours\n=======\ntheirs\n>>>>>>> branch\n",
-    )
-    .unwrap();
-
-    resolve_conflict(&repo, "conflict.txt", "ours").unwrap();
-
-    let resolved = fs::read_to_string(&file_path).unwrap();
-    assert_eq!(resolved, "ours\n");
-
-    let statuses = get_file_statuses(&repo).unwrap();
-    assert!(statuses.staged.iter().any(|s| s.path == "conflict.txt"));
-    assert!(!statuses.unstaged.iter().any(|s| s.path == "conflict.txt"));
-}
-
-// =============================================================================
-// stage_files / unstage_files batch (4 tests)
+// stage_files / unstage_files batch (5 tests)
 // =============================================================================
 
 #[test]
@@ -784,7 +1571,7 @@ fn stage_files_batch_stages_multiple_files() {
     ];
     stage_files(&repo, &paths).unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.iter().any(|s| s.path == "a.txt"));
     assert!(statuses.staged.iter().any(|s| s.path == "b.txt"));
     assert!(statuses.staged.iter().any(|s| s.path == "c.txt"));
@@ -802,7 +1589,7 @@ fn stage_files_handles_deleted_files() {
     let paths = vec!["delete.txt".to_string()];
     stage_files(&repo, &paths).unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses
         .staged
         .iter()
@@ -820,16 +1607,44 @@ fn unstage_files_batch_unstages_multiple_files() {
     let paths = vec!["a.txt".to_string(), "b.txt".to_string()];
     stage_files(&repo, &paths).unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert_eq!(statuses.staged.len(), 2);
 
     unstage_files(&repo, &paths).unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
     assert_eq!(statuses.untracked.len(), 2);
 }
 
+#[test]
+fn stage_and_unstage_files_mixed_changes() {
+    let (temp_dir, repo) = create_test_repo();
+    common::create_commit_with_file(&repo, &temp_dir, "mod.txt", "old\n", "Add mod");
+    common::create_commit_with_file(&repo, &temp_dir, "gone.txt", "gone\n", "Add gone");
+
+    fs::write(temp_dir.path().join("mod.txt"), "new\n").unwrap();
+    fs::remove_file(temp_dir.path().join("gone.txt")).unwrap();
+    fs::write(temp_dir.path().join("new.txt"), "new\n").unwrap();
+
+    let paths = vec![
+        "mod.txt".to_string(),
+        "gone.txt".to_string(),
+        "new.txt".to_string(),
+    ];
+    stage_files(&repo, &paths).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert_eq!(statuses.staged.len(), 3);
+    assert!(statuses.unstaged.is_empty());
+
+    unstage_files(&repo, &paths).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.is_empty());
+    assert!(statuses.unstaged.iter().any(|s| s.path == "mod.txt"));
+    assert!(statuses.unstaged.iter().any(|s| s.path == "gone.txt"));
+    assert!(statuses.untracked.iter().any(|s| s.path == "new.txt"));
+}
+
 #[test]
 fn stage_files_empty_list_is_noop() {
     let (temp_dir, repo) = create_test_repo();
@@ -837,10 +1652,247 @@ fn stage_files_empty_list_is_noop() {
 
     stage_files(&repo, &[]).unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
 }
 
+// =============================================================================
+// Symlinks and file modes (5 tests, unix only)
+// =============================================================================
+
+#[cfg(unix)]
+fn index_mode(repo: &Repository, path: &str) -> u32 {
+    repo.index()
+        .unwrap()
+        .get_path(Path::new(path), 0)
+        .unwrap()
+        .mode
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_file_keeps_symlink_mode() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    std::os::unix::fs::symlink("initial.txt", temp_dir.path().join("link")).unwrap();
+
+    stage_file(&repo, "link").unwrap();
+    assert_eq!(index_mode(&repo, "link"), 0o120000);
+
+    // Retargeting and restaging keeps it a symlink.
+    fs::remove_file(temp_dir.path().join("link")).unwrap();
+    std::os::unix::fs::symlink("elsewhere.txt", temp_dir.path().join("link")).unwrap();
+    stage_file(&repo, "link").unwrap();
+    assert_eq!(index_mode(&repo, "link"), 0o120000);
+}
+
+#[cfg(unix)]
+#[test]
+fn partial_staging_refuses_symlinks() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    std::os::unix::fs::symlink("initial.txt", temp_dir.path().join("link")).unwrap();
+    stage_file(&repo, "link").unwrap();
+    let sig = repo.signature().unwrap();
+    let tree = repo
+        .find_tree(repo.index().unwrap().write_tree().unwrap())
+        .unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Add link", &tree, &[&parent])
+        .unwrap();
+
+    fs::remove_file(temp_dir.path().join("link")).unwrap();
+    std::os::unix::fs::symlink("elsewhere.txt", temp_dir.path().join("link")).unwrap();
+
+    assert!(matches!(
+        stage_hunk(&repo, "link", 0, None, None, &DiffConfig::default()),
+        Err(AppError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        stage_lines(&repo, "link", 0, vec![0], None, &DiffConfig::default()),
+        Err(AppError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        discard_hunk(&repo, "link", 0, None, None, &DiffConfig::default()),
+        Err(AppError::InvalidArgument(_))
+    ));
+    // Nothing was written: the link and its index entry are unchanged.
+    assert_eq!(
+        fs::read_link(temp_dir.path().join("link")).unwrap(),
+        Path::new("elsewhere.txt")
+    );
+    assert_eq!(index_mode(&repo, "link"), 0o120000);
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_hunk_keeps_executable_mode() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let script = temp_dir.path().join("run.sh");
+    fs::write(&script, "#!/bin/sh\necho hi\n").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+    // New file: the mode comes from the working tree.
+    stage_hunk(&repo, "run.sh", 0, None, None, &DiffConfig::default()).unwrap();
+    assert_eq!(index_mode(&repo, "run.sh"), 0o100755);
+
+    // Existing entry: the index mode is kept.
+    fs::write(&script, "#!/bin/sh\necho bye\n").unwrap();
+    stage_hunk(&repo, "run.sh", 0, None, None, &DiffConfig::default()).unwrap();
+    assert_eq!(index_mode(&repo, "run.sh"), 0o100755);
+}
+
+#[cfg(unix)]
+fn commit_executable(repo: &Repository, temp_dir: &TempDir) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let script = temp_dir.path().join("build.sh");
+    fs::write(&script, "#!/bin/sh\nstep one\nstep two\nstep three\n").unwrap();
+    fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+    make_commit(
+        repo,
+        temp_dir,
+        "build.sh",
+        "#!/bin/sh\nstep one\nstep two\nstep three\n",
+        "Add script",
+    );
+    script
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_hunk_committed_executable_keeps_mode() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let script = commit_executable(&repo, &temp_dir);
+    assert_eq!(index_mode(&repo, "build.sh"), 0o100755);
+
+    fs::write(&script, "#!/bin/sh\nstep one\nstep 2\nstep three\n").unwrap();
+    stage_hunk(&repo, "build.sh", 0, None, None, &DiffConfig::default()).unwrap();
+
+    assert_eq!(index_mode(&repo, "build.sh"), 0o100755);
+    let statuses = get_file_statuses(&repo, false).unwrap();
+    assert!(statuses.staged.iter().any(|s| s.path == "build.sh"));
+    assert!(statuses.unstaged.is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn stage_lines_committed_executable_keeps_mode() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let script = commit_executable(&repo, &temp_dir);
+
+    fs::write(&script, "#!/bin/sh\nstep one\nstep 2\nstep three\n").unwrap();
+    let hunk = &get_file_diff(&repo, "build.sh", false).unwrap().hunks[0];
+    let added = hunk
+        .lines
+        .iter()
+        .position(|l| matches!(l.line_type, LineType::Addition))
+        .unwrap();
+    stage_lines(
+        &repo,
+        "build.sh",
+        0,
+        vec![added],
+        None,
+        &DiffConfig::default(),
+    )
+    .unwrap();
+
+    assert_eq!(index_mode(&repo, "build.sh"), 0o100755);
+}
+
+// =============================================================================
+// apply_patch (3 tests)
+// =============================================================================
+
+#[test]
+fn apply_patch_to_working_tree_and_index() {
+    let (temp_dir, repo) = create_test_repo();
+    make_commit(
+        &repo,
+        &temp_dir,
+        "file.txt",
+        "one\ntwo\nthree\n",
+        "Add file",
+    );
+    fs::write(temp_dir.path().join("file.txt"), "one\n2\nthree\n").unwrap();
+    let patch = git::get_file_patch(&repo, "file.txt", false).unwrap();
+    git::discard_file(&repo, "file.txt").unwrap();
+
+    git::apply_patch(&repo, &patch, true).unwrap();
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+        "one\n2\nthree\n"
+    );
+    assert!(get_file_diff(&repo, "file.txt", true)
+        .unwrap()
+        .hunks
+        .is_empty());
+
+    // To the index only: staged, while the working tree keeps its own copy.
+    git::discard_file(&repo, "file.txt").unwrap();
+    git::apply_patch(&repo, &patch, false).unwrap();
+    assert_eq!(
+        get_file_diff(&repo, "file.txt", true).unwrap().hunks.len(),
+        1
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+        "one\ntwo\nthree\n"
+    );
+}
+
+#[test]
+fn apply_patch_rejects_stale_patch_without_writing() {
+    let (temp_dir, repo) = create_test_repo();
+    make_commit(&repo, &temp_dir, "a.txt", "a\n", "Add file");
+    make_commit(&repo, &temp_dir, "b.txt", "b\n", "Add file");
+    fs::write(temp_dir.path().join("a.txt"), "A\n").unwrap();
+    fs::write(temp_dir.path().join("b.txt"), "B\n").unwrap();
+    let patch = [
+        git::get_file_patch(&repo, "a.txt", false).unwrap(),
+        git::get_file_patch(&repo, "b.txt", false).unwrap(),
+    ]
+    .concat();
+
+    // a.txt still matches the patch's preimage; b.txt has moved on.
+    git::discard_file(&repo, "a.txt").unwrap();
+    fs::write(temp_dir.path().join("b.txt"), "something else\n").unwrap();
+
+    let result = git::apply_patch(&repo, &patch, true);
+    assert!(
+        matches!(result, Err(AppError::PatchFailed(_))),
+        "{result:?}"
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+        "a\n"
+    );
+}
+
+#[test]
+fn apply_patch_rejects_garbage() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    for text in ["", "just some text\n"] {
+        let result = git::apply_patch(&repo, text, true);
+        assert!(
+            matches!(result, Err(AppError::PatchFailed(_))),
+            "{result:?}"
+        );
+    }
+}
+
 // =============================================================================
 // commands/staging.rs integration tests — formerly mirrored "_logic" wrappers.
 // The Tauri command wrappers can't be invoked without a `tauri::State`, so
@@ -857,7 +1909,7 @@ fn commands_staging_get_file_statuses_logic() {
     let file_path = temp_dir.path().join("untracked.txt");
     fs::write(&file_path, "untracked").unwrap();
 
-    let result = get_file_statuses(&repo);
+    let result = get_file_statuses(&repo, false);
     assert!(result.is_ok());
     let statuses = result.unwrap();
     assert_eq!(statuses.untracked.len(), 1);
@@ -874,7 +1926,7 @@ fn commands_staging_stage_file_logic() {
     let result = stage_file(&repo, "new.txt");
     assert!(result.is_ok());
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.iter().any(|s| s.path == "new.txt"));
 }
 
@@ -888,13 +1940,13 @@ fn commands_staging_unstage_file_logic() {
 
     stage_file(&repo, "staged.txt").unwrap();
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.iter().any(|s| s.path == "staged.txt"));
 
     let result = unstage_file(&repo, "staged.txt");
     assert!(result.is_ok());
 
-    let statuses = get_file_statuses(&repo).unwrap();
+    let statuses = get_file_statuses(&repo, false).unwrap();
     assert!(statuses.staged.is_empty());
 }
 
@@ -1001,7 +2053,7 @@ fn commands_staging_revert_file_empty_repo_returns_descriptive_error() {
 fn commands_staging_discard_hunk_returns_error_for_nonexistent_file() {
     let (temp_dir, repo) = create_test_repo();
     create_initial_commit(&repo, &temp_dir);
-    let result = discard_hunk(&repo, "missing.txt", 0, None);
+    let result = discard_hunk(&repo, "missing.txt", 0, None, None, &DiffConfig::default());
     assert!(result.is_err());
 }
 
@@ -9,8 +9,10 @@ use common::{create_commit_with_file, create_initial_commit, create_test_repo};
 use git2::Repository;
 use std::fs;
 use tempfile::TempDir;
+use yagg_lib::error::AppError;
 use yagg_lib::git::{
-    apply_stash, drop_stash, get_stash_details, get_stash_file_diff, list_stashes,
+    self, apply_stash, apply_stash_with_progress, drop_stash, get_stash_details,
+    get_stash_file_diff, list_stashes,
 };
 
 fn create_stash(repo: &mut Repository, temp_dir: &TempDir) {
@@ -81,7 +83,7 @@ fn list_stashes_multiple() {
 }
 
 // =============================================================================
-// get_stash_details (2 tests)
+// get_stash_details (3 tests)
 // =============================================================================
 
 #[test]
@@ -102,6 +104,35 @@ fn get_stash_details_basic() {
     assert!(details.files_changed.iter().any(|f| f.path == "file.txt"));
 }
 
+#[test]
+fn get_stash_details_detects_rename() {
+    let (temp_dir, mut repo) = create_test_repo();
+    let content = "alpha\nbeta\ngamma\ndelta\n";
+    create_commit_with_file(&repo, &temp_dir, "old.txt", content, "Initial commit");
+
+    fs::rename(
+        temp_dir.path().join("old.txt"),
+        temp_dir.path().join("new.txt"),
+    )
+    .unwrap();
+    let mut index = repo.index().unwrap();
+    index.remove_path(std::path::Path::new("old.txt")).unwrap();
+    index.add_path(std::path::Path::new("new.txt")).unwrap();
+    index.write().unwrap();
+
+    let sig = repo.signature().unwrap();
+    repo.stash_save(&sig, "Rename stash", None).unwrap();
+
+    let details = get_stash_details(&mut repo, 0).unwrap();
+    assert_eq!(details.files_changed.len(), 1);
+    assert_eq!(details.files_changed[0].status, "renamed");
+    assert_eq!(details.files_changed[0].path, "new.txt");
+    assert_eq!(
+        details.files_changed[0].old_path.as_deref(),
+        Some("old.txt")
+    );
+}
+
 #[test]
 fn get_stash_details_not_found() {
     let (temp_dir, mut repo) = create_test_repo();
@@ -112,7 +143,7 @@ fn get_stash_details_not_found() {
 }
 
 // =============================================================================
-// apply / drop / file diff (3 tests)
+// apply / drop / file diff (6 tests)
 // =============================================================================
 
 #[test]
@@ -135,6 +166,72 @@ fn apply_stash_restores_changes() {
     assert_eq!(content, "modified content");
 }
 
+#[test]
+fn apply_stash_reports_conflicts() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "base\n", "Initial commit");
+
+    let file_path = temp_dir.path().join("file.txt");
+    fs::write(&file_path, "stashed\n").unwrap();
+    let sig = repo.signature().unwrap();
+    repo.stash_save(&sig, "Test stash", None).unwrap();
+
+    // HEAD moves on with a different edit to the same line.
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "committed\n", "Diverge");
+
+    match apply_stash(&mut repo, 0) {
+        Err(AppError::MergeConflict { paths, step }) => {
+            assert_eq!(paths, vec!["file.txt".to_string()]);
+            assert_eq!(step, None);
+        }
+        other => panic!("expected MergeConflict, got {other:?}"),
+    }
+    assert!(repo.index().unwrap().has_conflicts());
+    // The stash is kept so the user can retry.
+    assert_eq!(list_stashes(&mut repo).unwrap().len(), 1);
+}
+
+#[test]
+fn apply_stash_over_dirty_file_is_checkout_conflict() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "base\n", "Initial commit");
+    fs::write(temp_dir.path().join("file.txt"), "stashed\n").unwrap();
+    let sig = repo.signature().unwrap();
+    repo.stash_save(&sig, "Test stash", None).unwrap();
+    fs::write(temp_dir.path().join("file.txt"), "edited since\n").unwrap();
+
+    match apply_stash(&mut repo, 0) {
+        Err(AppError::CheckoutConflict(paths)) => {
+            assert_eq!(paths, vec!["file.txt".to_string()]);
+        }
+        other => panic!("expected CheckoutConflict, got {other:?}"),
+    }
+    // Nothing was applied over the user's edit.
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+        "edited since\n"
+    );
+    assert_eq!(list_stashes(&mut repo).unwrap().len(), 1);
+}
+
+#[test]
+fn apply_stash_with_progress_reports_stages() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_commit_with_file(&repo, &temp_dir, "file.txt", "content", "Initial commit");
+    fs::write(temp_dir.path().join("file.txt"), "modified content").unwrap();
+    let sig = repo.signature().unwrap();
+    repo.stash_save(&sig, "Test stash", None).unwrap();
+
+    let mut stages = Vec::new();
+    apply_stash_with_progress(&mut repo, 0, |stage| stages.push(stage)).unwrap();
+
+    assert_eq!(
+        stages.first(),
+        Some(&git2::StashApplyProgress::LoadingStash)
+    );
+    assert_eq!(stages.last(), Some(&git2::StashApplyProgress::Done));
+}
+
 #[test]
 fn drop_stash_removes_it() {
     let (temp_dir, mut repo) = create_test_repo();
@@ -263,3 +360,241 @@ fn commands_stash_file_diff_logic() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap().path, "initial.txt");
 }
+
+// =============================================================================
+// create_stash (4 tests)
+// =============================================================================
+
+#[test]
+fn create_stash_with_message() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "changed").unwrap();
+
+    let info = git::create_stash(&mut repo, Some("my work"), false, false).unwrap();
+
+    assert_eq!(info.index, 0);
+    assert!(info.message.contains("my work"));
+    assert!(!info.branch_name.is_empty());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+    assert_eq!(list_stashes(&mut repo).unwrap().len(), 1);
+}
+
+#[test]
+fn create_stash_include_untracked() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    let untracked = temp_dir.path().join("scratch.txt");
+    fs::write(&untracked, "notes").unwrap();
+
+    git::create_stash(&mut repo, None, true, false).unwrap();
+    assert!(!untracked.exists());
+
+    apply_stash(&mut repo, 0).unwrap();
+    assert_eq!(fs::read_to_string(&untracked).unwrap(), "notes");
+}
+
+#[test]
+fn create_stash_keep_index_leaves_staged_changes() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "staged").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("initial.txt")).unwrap();
+    index.write().unwrap();
+
+    git::create_stash(&mut repo, None, false, true).unwrap();
+
+    let status = repo
+        .status_file(std::path::Path::new("initial.txt"))
+        .unwrap();
+    assert!(status.contains(git2::Status::INDEX_MODIFIED));
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "staged"
+    );
+}
+
+#[test]
+fn create_stash_nothing_to_stash() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(matches!(
+        git::create_stash(&mut repo, None, false, false),
+        Err(AppError::NothingToStash)
+    ));
+    assert!(list_stashes(&mut repo).unwrap().is_empty());
+}
+
+// =============================================================================
+// clear_stashes (2 tests)
+// =============================================================================
+
+#[test]
+fn clear_stashes_drops_all() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    for i in 0..3 {
+        fs::write(temp_dir.path().join("initial.txt"), format!("change {i}")).unwrap();
+        git::create_stash(&mut repo, Some(&format!("stash {i}")), false, false).unwrap();
+    }
+    assert_eq!(list_stashes(&mut repo).unwrap().len(), 3);
+
+    let dropped = git::clear_stashes(&mut repo).unwrap();
+
+    assert_eq!(dropped, 3);
+    assert!(list_stashes(&mut repo).unwrap().is_empty());
+}
+
+#[test]
+fn clear_stashes_none() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert_eq!(git::clear_stashes(&mut repo).unwrap(), 0);
+}
+
+// =============================================================================
+// stash_paths (6 tests)
+// =============================================================================
+
+#[test]
+fn stash_paths_keeps_other_changes() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    create_commit_with_file(&repo, &temp_dir, "other.txt", "other", "Add other");
+    fs::write(temp_dir.path().join("initial.txt"), "stash me").unwrap();
+    fs::write(temp_dir.path().join("other.txt"), "keep me").unwrap();
+
+    let info = git::stash_paths(&mut repo, &["initial.txt".to_string()], Some("partial")).unwrap();
+
+    assert_eq!(info.index, 0);
+    assert!(info.message.contains("partial"));
+    assert!(!info.branch_name.is_empty());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "initial content"
+    );
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("other.txt")).unwrap(),
+        "keep me"
+    );
+    let details = get_stash_details(&mut repo, 0).unwrap();
+    let changed: Vec<_> = details
+        .files_changed
+        .iter()
+        .map(|f| f.path.as_str())
+        .collect();
+    assert_eq!(changed, ["initial.txt"]);
+
+    apply_stash(&mut repo, 0).unwrap();
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "stash me"
+    );
+}
+
+#[test]
+fn stash_paths_resets_staged_changes() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "staged").unwrap();
+    fs::write(temp_dir.path().join("new.txt"), "brand new").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(std::path::Path::new("initial.txt")).unwrap();
+    index.add_path(std::path::Path::new("new.txt")).unwrap();
+    index.write().unwrap();
+
+    git::stash_paths(
+        &mut repo,
+        &["initial.txt".to_string(), "new.txt".to_string()],
+        None,
+    )
+    .unwrap();
+
+    assert!(repo.statuses(None).unwrap().is_empty());
+    assert!(!temp_dir.path().join("new.txt").exists());
+
+    apply_stash(&mut repo, 0).unwrap();
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("new.txt")).unwrap(),
+        "brand new"
+    );
+}
+
+#[test]
+fn stash_paths_stacks_with_regular_stashes() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("initial.txt"), "first").unwrap();
+    git::create_stash(&mut repo, Some("regular"), false, false).unwrap();
+    fs::write(temp_dir.path().join("initial.txt"), "second").unwrap();
+
+    git::stash_paths(&mut repo, &["initial.txt".to_string()], Some("partial")).unwrap();
+
+    let stashes = list_stashes(&mut repo).unwrap();
+    assert_eq!(stashes.len(), 2);
+    assert!(stashes[0].message.contains("partial"));
+    assert!(stashes[1].message.contains("regular"));
+
+    drop_stash(&mut repo, 0).unwrap();
+    apply_stash(&mut repo, 0).unwrap();
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("initial.txt")).unwrap(),
+        "first"
+    );
+}
+
+#[test]
+fn stash_paths_nothing_to_stash() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::write(temp_dir.path().join("untracked.txt"), "notes").unwrap();
+
+    let result = git::stash_paths(
+        &mut repo,
+        &["initial.txt".to_string(), "untracked.txt".to_string()],
+        None,
+    );
+
+    assert!(matches!(result, Err(AppError::NothingToStash)));
+    assert!(list_stashes(&mut repo).unwrap().is_empty());
+    assert!(temp_dir.path().join("untracked.txt").exists());
+}
+
+#[test]
+fn stash_paths_rejects_empty_and_escaping_paths() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(matches!(
+        git::stash_paths(&mut repo, &[], None),
+        Err(AppError::InvalidPath(_))
+    ));
+    assert!(matches!(
+        git::stash_paths(&mut repo, &["../outside.txt".to_string()], None),
+        Err(AppError::InvalidPath(p)) if p == "../outside.txt"
+    ));
+}
+
+#[test]
+fn stash_paths_rejects_directory_without_stashing() {
+    let (temp_dir, mut repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    fs::create_dir(temp_dir.path().join("dir")).unwrap();
+    create_commit_with_file(&repo, &temp_dir, "dir/a.txt", "a", "Add dir");
+    fs::write(temp_dir.path().join("dir/a.txt"), "changed").unwrap();
+
+    let result = git::stash_paths(&mut repo, &["dir".to_string()], None);
+
+    assert!(matches!(result, Err(AppError::InvalidPath(_))));
+    assert!(git::list_stashes(&mut repo).unwrap().is_empty());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("dir/a.txt")).unwrap(),
+        "changed"
+    );
+}
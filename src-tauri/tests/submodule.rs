@@ -0,0 +1,100 @@
+//! Integration tests for submodule reporting (`git::list_submodules`).
+
+mod common;
+
+use common::{create_commit_with_file, create_initial_commit, create_test_repo};
+use std::path::Path;
+use tempfile::TempDir;
+use yagg_lib::git::{self, SubmoduleState};
+
+/// Add `upstream` as a submodule at `libs/sub` and commit it.
+fn add_submodule(repo: &git2::Repository, upstream: &TempDir) {
+    let url = upstream.path().to_str().unwrap();
+    let mut submodule = repo.submodule(url, Path::new("libs/sub"), true).unwrap();
+    submodule.clone(None).unwrap();
+    submodule.add_finalize().unwrap();
+
+    let mut index = repo.index().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = repo.signature().unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Add submodule", &tree, &[&parent])
+        .unwrap();
+}
+
+#[test]
+fn list_submodules_empty() {
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+
+    assert!(git::list_submodules(&repo).unwrap().is_empty());
+}
+
+#[test]
+fn list_submodules_clean_after_add() {
+    let (upstream_dir, upstream) = create_test_repo();
+    let upstream_head = create_initial_commit(&upstream, &upstream_dir);
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    add_submodule(&repo, &upstream_dir);
+
+    let submodules = git::list_submodules(&repo).unwrap();
+    assert_eq!(submodules.len(), 1);
+    let sub = &submodules[0];
+    assert_eq!(sub.name, "libs/sub");
+    assert_eq!(sub.path, "libs/sub");
+    assert_eq!(sub.url.as_deref(), upstream_dir.path().to_str());
+    assert_eq!(sub.head_oid, Some(upstream_head.to_string()));
+    assert_eq!(sub.workdir_oid, Some(upstream_head.to_string()));
+    assert_eq!(sub.state, SubmoduleState::Clean);
+}
+
+#[test]
+fn list_submodules_out_of_date_when_checkout_moves() {
+    let (upstream_dir, upstream) = create_test_repo();
+    let upstream_head = create_initial_commit(&upstream, &upstream_dir);
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    add_submodule(&repo, &upstream_dir);
+
+    // Commit inside the submodule so its HEAD drifts from the recorded one.
+    let sub_repo = git2::Repository::open(temp_dir.path().join("libs/sub")).unwrap();
+    let mut config = sub_repo.config().unwrap();
+    config.set_str("user.name", "Test User").unwrap();
+    config.set_str("user.email", "test@example.com").unwrap();
+    let moved = {
+        std::fs::write(temp_dir.path().join("libs/sub/new.txt"), "new").unwrap();
+        let mut index = sub_repo.index().unwrap();
+        index.add_path(Path::new("new.txt")).unwrap();
+        index.write().unwrap();
+        let tree = sub_repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = sub_repo.signature().unwrap();
+        let parent = sub_repo.head().unwrap().peel_to_commit().unwrap();
+        sub_repo
+            .commit(Some("HEAD"), &sig, &sig, "Move", &tree, &[&parent])
+            .unwrap()
+    };
+
+    let sub = &git::list_submodules(&repo).unwrap()[0];
+    assert_eq!(sub.state, SubmoduleState::OutOfDate);
+    assert_eq!(sub.head_oid, Some(upstream_head.to_string()));
+    assert_eq!(sub.workdir_oid, Some(moved.to_string()));
+}
+
+#[test]
+fn list_submodules_uninitialized_in_fresh_clone() {
+    let (upstream_dir, upstream) = create_test_repo();
+    create_commit_with_file(&upstream, &upstream_dir, "lib.txt", "lib", "Lib");
+    let (temp_dir, repo) = create_test_repo();
+    create_initial_commit(&repo, &temp_dir);
+    add_submodule(&repo, &upstream_dir);
+
+    let clone_dir = TempDir::new().unwrap();
+    let clone =
+        git2::Repository::clone(temp_dir.path().to_str().unwrap(), clone_dir.path()).unwrap();
+
+    let submodules = git::list_submodules(&clone).unwrap();
+    assert_eq!(submodules.len(), 1);
+    assert_eq!(submodules[0].state, SubmoduleState::Uninitialized);
+    assert_eq!(submodules[0].workdir_oid, None);
+}
@@ -7,8 +7,10 @@ mod common;
 
 use common::{create_initial_commit, create_test_repo};
 use tempfile::TempDir;
+use yagg_lib::error::AppError;
 use yagg_lib::git::{
-    add_worktree, list_worktrees, lock_worktree, move_worktree, remove_worktree, unlock_worktree,
+    add_worktree, list_worktrees, lock_worktree, move_worktree, prune_worktrees, remove_worktree,
+    unlock_worktree,
 };
 
 #[test]
@@ -208,3 +210,69 @@ fn dirty_count_reflects_workdir_changes() {
     let row = wts.iter().find(|w| w.name == "wt-dirty").unwrap();
     assert_eq!(row.dirty_count, 1);
 }
+
+#[test]
+fn add_worktree_into_empty_existing_dir() {
+    let (_td, repo) = create_test_repo();
+    create_initial_commit(&repo, &_td);
+
+    let wtdir = TempDir::new().unwrap();
+    let wt_path = wtdir.path().join("wt-empty");
+    std::fs::create_dir(&wt_path).unwrap();
+
+    let info = add_worktree(
+        &repo,
+        "wt-empty",
+        &wt_path,
+        None,
+        Some("feature-empty"),
+        None,
+    )
+    .unwrap();
+    assert_eq!(info.branch.as_deref(), Some("feature-empty"));
+    assert!(wt_path.join("initial.txt").exists());
+}
+
+#[test]
+fn add_worktree_rejects_non_empty_path() {
+    let (_td, repo) = create_test_repo();
+    create_initial_commit(&repo, &_td);
+
+    let wtdir = TempDir::new().unwrap();
+    let wt_path = wtdir.path().join("wt-busy");
+    std::fs::create_dir(&wt_path).unwrap();
+    std::fs::write(wt_path.join("keep.txt"), "mine").unwrap();
+
+    let result = add_worktree(&repo, "wt-busy", &wt_path, None, None, None);
+    assert!(matches!(result, Err(AppError::InvalidPath(_))));
+    assert_eq!(
+        std::fs::read_to_string(wt_path.join("keep.txt")).unwrap(),
+        "mine"
+    );
+    assert_eq!(list_worktrees(&repo).unwrap().len(), 1);
+}
+
+#[test]
+fn prune_worktrees_removes_only_missing_unlocked() {
+    let (_td, repo) = create_test_repo();
+    create_initial_commit(&repo, &_td);
+
+    let wtdir = TempDir::new().unwrap();
+    for name in ["wt-gone", "wt-gone-locked", "wt-present"] {
+        add_worktree(&repo, name, &wtdir.path().join(name), None, None, None).unwrap();
+    }
+    lock_worktree(&repo, "wt-gone-locked", None).unwrap();
+    std::fs::remove_dir_all(wtdir.path().join("wt-gone")).unwrap();
+    std::fs::remove_dir_all(wtdir.path().join("wt-gone-locked")).unwrap();
+
+    let pruned = prune_worktrees(&repo).unwrap();
+
+    assert_eq!(pruned, vec!["wt-gone".to_string()]);
+    let mut names: Vec<String> = list_worktrees(&repo)
+        .unwrap()
+        .into_iter()
+        .map(|w| w.name)
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["main", "wt-gone-locked", "wt-present"]);
+}
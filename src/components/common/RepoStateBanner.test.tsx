@@ -31,6 +31,9 @@ const makeRepoInfo = (repo_state: string): RepositoryInfo => ({
   remotes: ["origin"],
   head_hash: "abc123",
   repo_state,
+  upstream: null,
+  ahead: null,
+  behind: null,
 });
 
 const makeFileStatuses = (conflictedPaths: string[] = []): FileStatuses => ({
@@ -21,6 +21,7 @@ import { SegmentedToggle } from "./SegmentedToggle";
 import { openLogDir } from "../../services/logging";
 import { CliInstallDialog } from "./CliInstallDialog";
 import { logError } from "../../utils/logger";
+import { errorMessage } from "../../utils/errorMessages";
 
 // Delay before a hover-out closes the Help submenu. Lets the user travel from
 // the trigger row to the submenu without the panel snapping shut underneath.
@@ -131,7 +132,7 @@ export function SettingsMenu() {
       useNotificationStore.getState().showSuccess(result);
       setCliInstalled(true);
     } catch (error) {
-      useNotificationStore.getState().showError(String(error));
+      useNotificationStore.getState().showError(errorMessage(error));
     }
   };
 
@@ -142,7 +143,7 @@ export function SettingsMenu() {
       useNotificationStore.getState().showSuccess(result);
       setCliInstalled(false);
     } catch (error) {
-      useNotificationStore.getState().showError(String(error));
+      useNotificationStore.getState().showError(errorMessage(error));
     }
   };
 
@@ -158,8 +159,8 @@ export function SettingsMenu() {
     try {
       await openLogDir();
     } catch (e) {
-      logError("yagg::fe::ui", `open log dir failed: ${String(e)}`);
-      useNotificationStore.getState().showError(`Failed to open log folder: ${String(e)}`);
+      logError("yagg::fe::ui", `open log dir failed: ${errorMessage(e)}`);
+      useNotificationStore.getState().showError(`Failed to open log folder: ${errorMessage(e)}`);
     }
   };
 
@@ -197,7 +197,7 @@ describe("DiffViewPanel", () => {
     const stageButton = screen.getByText("Stage hunk");
     fireEvent.click(stageButton);
 
-    expect(mockStageHunk).toHaveBeenCalledWith("test.txt", 0);
+    expect(mockStageHunk).toHaveBeenCalledWith("test.txt", 0, "@@ -1,1 +1,1 @@");
   });
 
   it("calls unstageHunk when action clicked for staged diff", async () => {
@@ -164,7 +164,7 @@ export function DiffViewPanel({ diff, loading, staged }: DiffViewPanelProps) {
     if (staged) {
       await unstageHunk(currentDiffPath, hunkIndex);
     } else {
-      await stageHunk(currentDiffPath, hunkIndex);
+      await stageHunk(currentDiffPath, hunkIndex, diff.hunks[hunkIndex]?.header);
     }
   };
 
@@ -13,9 +13,11 @@ describe("BranchLines", () => {
     timestamp: 1234567890,
     parent_hashes: [],
     column: 0,
+    branch_id: 0,
     lines: [],
     refs: [],
     is_tip: false,
+    overflow: false,
     ...overrides,
   });
 
@@ -128,7 +130,15 @@ describe("BranchLines", () => {
   describe("line types", () => {
     it("renders pass_through line as vertical line", () => {
       const commit = createMockCommit({
-        lines: [{ from_column: 1, to_column: 1, is_merge: false, line_type: "pass_through" }],
+        lines: [
+          {
+            from_column: 1,
+            to_column: 1,
+            is_merge: false,
+            line_type: "pass_through",
+            branch_id: 1,
+          },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -147,7 +157,9 @@ describe("BranchLines", () => {
     it("renders from_above line from top to center", () => {
       const commit = createMockCommit({
         column: 0,
-        lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: "from_above" }],
+        lines: [
+          { from_column: 0, to_column: 0, is_merge: false, line_type: "from_above", branch_id: 0 },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -166,7 +178,9 @@ describe("BranchLines", () => {
     it("renders merge line as curved path", () => {
       const commit = createMockCommit({
         column: 0,
-        lines: [{ from_column: 0, to_column: 1, is_merge: true, line_type: "to_parent" }],
+        lines: [
+          { from_column: 0, to_column: 1, is_merge: true, line_type: "to_parent", branch_id: 1 },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -183,7 +197,10 @@ describe("BranchLines", () => {
     it("renders convergence line as curved path", () => {
       const commit = createMockCommit({
         column: 1,
-        lines: [{ from_column: 1, to_column: 0, is_merge: false, line_type: "to_parent" }],
+        branch_id: 1,
+        lines: [
+          { from_column: 1, to_column: 0, is_merge: false, line_type: "to_parent", branch_id: 1 },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -195,14 +212,16 @@ describe("BranchLines", () => {
       expect(path).toHaveAttribute("fill", "none");
       // Should have quadratic bezier curve
       expect(path.getAttribute("d")).toContain("Q");
-      // Should use the from_column color (column 1 = branch-2)
+      // Should use the line's branch color (branch 1 = branch-2)
       expect(path.getAttribute("stroke")).toContain("var(--color-branch-2)");
     });
 
     it("renders normal continuation line from node down", () => {
       const commit = createMockCommit({
         column: 0,
-        lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: "to_parent" }],
+        lines: [
+          { from_column: 0, to_column: 0, is_merge: false, line_type: "to_parent", branch_id: 0 },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -214,14 +233,41 @@ describe("BranchLines", () => {
       expect(line).toHaveAttribute("y1", "14"); // nodeY
       expect(line).toHaveAttribute("y2", "28"); // height
     });
+
+    it("renders to_boundary line as a short dashed stub", () => {
+      const commit = createMockCommit({
+        column: 0,
+        lines: [
+          { from_column: 0, to_column: 0, is_merge: false, line_type: "to_boundary", branch_id: 0 },
+        ],
+      });
+
+      const { container } = render(<BranchLines commit={commit} />);
+
+      const lines = container.querySelectorAll("line");
+      expect(lines.length).toBe(1);
+
+      const line = lines[0];
+      expect(line).toHaveAttribute("y1", "14"); // nodeY
+      expect(line).toHaveAttribute("y2", "21"); // halfway to the row bottom
+      expect(line).toHaveAttribute("stroke-dasharray", "2 2");
+    });
   });
 
   describe("colors", () => {
-    it("assigns color based on column index", () => {
+    it("assigns color based on branch id", () => {
       const commit = createMockCommit({
         is_tip: true,
         column: 0,
-        lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: "pass_through" }],
+        lines: [
+          {
+            from_column: 0,
+            to_column: 0,
+            is_merge: false,
+            line_type: "pass_through",
+            branch_id: 0,
+          },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -231,29 +277,57 @@ describe("BranchLines", () => {
       expect(circle?.getAttribute("fill")).toContain("var(--color-branch-1)");
     });
 
-    it("cycles through colors for different columns", () => {
+    it("keeps a branch's color when it moves to another column", () => {
       const commit = createMockCommit({
         is_tip: true,
-        column: 8, // Should wrap around (8 colors defined)
+        column: 2,
+        branch_id: 0,
+        lines: [
+          { from_column: 2, to_column: 2, is_merge: false, line_type: "from_above", branch_id: 0 },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
 
+      // Branch 0 uses branch-color-1 whatever its column
       const circle = container.querySelector("circle");
-      // Column 8 % 8 = 0, so should use branch-color-1
+      expect(circle?.getAttribute("fill")).toContain("var(--color-branch-1)");
+      const line = container.querySelector("line");
+      expect(line?.getAttribute("stroke")).toContain("var(--color-branch-1)");
+    });
+
+    it("cycles through colors for different branches", () => {
+      const commit = createMockCommit({
+        is_tip: true,
+        branch_id: 8, // Should wrap around (8 colors defined)
+      });
+
+      const { container } = render(<BranchLines commit={commit} />);
+
+      const circle = container.querySelector("circle");
+      // Branch 8 % 8 = 0, so should use branch-color-1
       expect(circle?.getAttribute("fill")).toContain("var(--color-branch-1)");
     });
 
     it("uses consistent color for line stroke", () => {
       const commit = createMockCommit({
         column: 2,
-        lines: [{ from_column: 2, to_column: 2, is_merge: false, line_type: "pass_through" }],
+        branch_id: 2,
+        lines: [
+          {
+            from_column: 2,
+            to_column: 2,
+            is_merge: false,
+            line_type: "pass_through",
+            branch_id: 2,
+          },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
 
       const line = container.querySelector("line");
-      // Column 2 uses branch-color-3
+      // Branch 2 uses branch-color-3
       expect(line?.getAttribute("stroke")).toContain("var(--color-branch-3)");
     });
   });
@@ -263,9 +337,15 @@ describe("BranchLines", () => {
       const commit = createMockCommit({
         column: 0,
         lines: [
-          { from_column: 0, to_column: 0, is_merge: false, line_type: "from_above" },
-          { from_column: 1, to_column: 1, is_merge: false, line_type: "pass_through" },
-          { from_column: 0, to_column: 1, is_merge: true, line_type: "to_parent" },
+          { from_column: 0, to_column: 0, is_merge: false, line_type: "from_above", branch_id: 0 },
+          {
+            from_column: 1,
+            to_column: 1,
+            is_merge: false,
+            line_type: "pass_through",
+            branch_id: 1,
+          },
+          { from_column: 0, to_column: 1, is_merge: true, line_type: "to_parent", branch_id: 1 },
         ],
       });
 
@@ -282,7 +362,15 @@ describe("BranchLines", () => {
   describe("line properties", () => {
     it("sets stroke width to 2", () => {
       const commit = createMockCommit({
-        lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: "pass_through" }],
+        lines: [
+          {
+            from_column: 0,
+            to_column: 0,
+            is_merge: false,
+            line_type: "pass_through",
+            branch_id: 0,
+          },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -294,7 +382,9 @@ describe("BranchLines", () => {
     it("sets stroke width to 2 for paths", () => {
       const commit = createMockCommit({
         column: 0,
-        lines: [{ from_column: 0, to_column: 1, is_merge: true, line_type: "to_parent" }],
+        lines: [
+          { from_column: 0, to_column: 1, is_merge: true, line_type: "to_parent", branch_id: 1 },
+        ],
       });
 
       const { container } = render(<BranchLines commit={commit} />);
@@ -324,7 +414,15 @@ describe("BranchLines", () => {
       // At spacious density (40px), the SVG and its lines must both be 40px tall
       // so adjacent rows' graph lines connect visually without gaps.
       const commit = createMockCommit({
-        lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: "pass_through" }],
+        lines: [
+          {
+            from_column: 0,
+            to_column: 0,
+            is_merge: false,
+            line_type: "pass_through",
+            branch_id: 0,
+          },
+        ],
       });
       const { container } = render(<BranchLines commit={commit} rowHeight={40} />);
 
@@ -338,7 +436,9 @@ describe("BranchLines", () => {
     it("keeps normal continuation lines reaching the row bottom at comfortable density", () => {
       const commit = createMockCommit({
         column: 0,
-        lines: [{ from_column: 0, to_column: 0, is_merge: false, line_type: "to_parent" }],
+        lines: [
+          { from_column: 0, to_column: 0, is_merge: false, line_type: "to_parent", branch_id: 0 },
+        ],
       });
       const { container } = render(<BranchLines commit={commit} rowHeight={30} />);
 
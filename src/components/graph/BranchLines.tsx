@@ -25,7 +25,9 @@ export const BranchLines = memo(({ commit, rowHeight }: BranchLinesProps) => {
   const nodeX = COLUMN_WIDTH + commit.column * COLUMN_WIDTH;
   const nodeY = height / 2;
 
-  const getColor = (column: number) => BRANCH_COLORS[column % BRANCH_COLORS.length];
+  // Color by branch id rather than column, so a branch keeps its color when
+  // it shifts columns.
+  const getColor = (branchId: number) => BRANCH_COLORS[branchId % BRANCH_COLORS.length];
 
   return (
     <svg width="100%" height={height} className="branch-lines-svg block">
@@ -43,7 +45,7 @@ export const BranchLines = memo(({ commit, rowHeight }: BranchLinesProps) => {
               y1={0}
               x2={fromX}
               y2={height}
-              stroke={getColor(line.from_column)}
+              stroke={getColor(line.branch_id)}
               strokeWidth={2}
             />
           );
@@ -56,10 +58,24 @@ export const BranchLines = memo(({ commit, rowHeight }: BranchLinesProps) => {
               y1={0}
               x2={fromX}
               y2={nodeY}
-              stroke={getColor(line.from_column)}
+              stroke={getColor(line.branch_id)}
               strokeWidth={2}
             />
           );
+        } else if (line.line_type === "to_boundary") {
+          // Parent isn't in the history (e.g. shallow clone) - short dashed stub
+          return (
+            <line
+              key={i}
+              x1={fromX}
+              y1={nodeY}
+              x2={fromX}
+              y2={(nodeY + height) / 2}
+              stroke={getColor(line.branch_id)}
+              strokeWidth={2}
+              strokeDasharray="2 2"
+            />
+          );
         } else if (line.is_merge) {
           // Merge line - curves from node to parent column
           return (
@@ -67,7 +83,7 @@ export const BranchLines = memo(({ commit, rowHeight }: BranchLinesProps) => {
               key={i}
               d={`M ${fromX} ${nodeY} Q ${fromX} ${height} ${toX} ${height}`}
               fill="none"
-              stroke={getColor(line.to_column)}
+              stroke={getColor(line.branch_id)}
               strokeWidth={2}
             />
           );
@@ -78,7 +94,7 @@ export const BranchLines = memo(({ commit, rowHeight }: BranchLinesProps) => {
               key={i}
               d={`M ${fromX} ${nodeY} Q ${fromX} ${height} ${toX} ${height}`}
               fill="none"
-              stroke={getColor(line.from_column)}
+              stroke={getColor(line.branch_id)}
               strokeWidth={2}
             />
           );
@@ -91,7 +107,7 @@ export const BranchLines = memo(({ commit, rowHeight }: BranchLinesProps) => {
               y1={nodeY}
               x2={toX}
               y2={height}
-              stroke={getColor(line.from_column)}
+              stroke={getColor(line.branch_id)}
               strokeWidth={2}
             />
           );
@@ -103,8 +119,8 @@ export const BranchLines = memo(({ commit, rowHeight }: BranchLinesProps) => {
         cx={nodeX}
         cy={nodeY}
         r={NODE_RADIUS}
-        fill={commit.is_tip ? getColor(commit.column) : "var(--color-bg-canvas)"}
-        stroke={getColor(commit.column)}
+        fill={commit.is_tip ? getColor(commit.branch_id) : "var(--color-bg-canvas)"}
+        stroke={getColor(commit.branch_id)}
         strokeWidth={commit.is_tip ? 1 : 2}
       />
     </svg>
@@ -120,9 +120,11 @@ const createMockCommit = (overrides: Partial<GraphCommit> = {}): GraphCommit =>
   timestamp: Math.floor(Date.now() / 1000),
   parent_hashes: [],
   column: 0,
+  branch_id: 0,
   lines: [],
   refs: [],
   is_tip: false,
+  overflow: false,
   ...overrides,
 });
 
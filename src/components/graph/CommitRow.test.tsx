@@ -105,9 +105,11 @@ describe("CommitRow", () => {
     timestamp: Math.floor(Date.now() / 1000) - 3600, // 1 hour ago
     parent_hashes: [],
     column: 0,
+    branch_id: 0,
     lines: [],
     refs: [],
     is_tip: false,
+    overflow: false,
     ...overrides,
   });
 
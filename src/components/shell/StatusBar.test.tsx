@@ -30,6 +30,9 @@ const defaultRepoInfo = {
   remotes: ["origin"],
   head_hash: "abc1234567890",
   repo_state: "clean",
+  upstream: null,
+  ahead: null,
+  behind: null,
 };
 
 const defaultFileStatuses = {
@@ -2,6 +2,7 @@ import { useState, useEffect } from "react";
 import { installCli, checkCliInstalled } from "../../services/system";
 import { CliInstallDialog } from "../common/CliInstallDialog";
 import { YaggButton } from "../common/YaggButton";
+import { errorMessage } from "../../utils/errorMessages";
 
 export function CliInstall() {
   const [isInstalled, setIsInstalled] = useState<boolean | null>(null);
@@ -25,7 +26,7 @@ export function CliInstall() {
       setMessage(result);
       setIsInstalled(true);
     } catch (error) {
-      setMessage(String(error));
+      setMessage(errorMessage(error));
     } finally {
       setInstalling(false);
     }
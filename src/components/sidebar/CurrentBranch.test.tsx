@@ -171,6 +171,9 @@ describe("CurrentBranch", () => {
           is_detached: false,
           remotes: [],
           repo_state: state,
+          upstream: null,
+          ahead: null,
+          behind: null,
         },
       });
     }
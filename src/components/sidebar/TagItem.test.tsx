@@ -317,20 +317,20 @@ describe("TagItem", () => {
         is_annotated: true,
         message: "Release",
         tagger_name: "Alice",
-        tagger_time: Math.floor(Date.now() / 1000) - 86400 * 7,
+        tagged_time: Math.floor(Date.now() / 1000) - 86400 * 7,
       };
       const { container } = render(<TagItem tag={tag} />);
       expect(container.querySelector(".tag-item-tagger")).toHaveTextContent("Alice");
       expect(container.querySelector(".tag-item-date")).toHaveTextContent(/ago/);
     });
 
-    it("shows just the tagger name when no tagger_time is present", () => {
+    it("shows just the tagger name when no tagged_time is present", () => {
       const tag: TagInfo = {
         name: "v1.0.0",
         target_hash: "abc",
         is_annotated: true,
         tagger_name: "Bob",
-        tagger_time: null,
+        tagged_time: null,
       };
       const { container } = render(<TagItem tag={tag} />);
       expect(container.querySelector(".tag-item-tagger")).toHaveTextContent("Bob");
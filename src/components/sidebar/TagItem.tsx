@@ -86,7 +86,7 @@ export function TagItem({ tag }: TagItemProps) {
 
   const taggerText = tag.is_annotated ? tag.tagger_name : null;
   const dateText =
-    tag.is_annotated && tag.tagger_time != null ? formatTimeAgo(tag.tagger_time) : null;
+    tag.is_annotated && tag.tagged_time != null ? formatTimeAgo(tag.tagged_time) : null;
 
   return (
     <SidebarRefItem
@@ -70,6 +70,9 @@ function setRepoPath(path: string | null) {
         remotes: [],
         head_hash: null,
         repo_state: "clean",
+        upstream: null,
+        ahead: null,
+        behind: null,
       }
     : null;
   mockStore(useRepositoryStore, { repositoryInfo: info });
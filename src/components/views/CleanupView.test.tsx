@@ -79,6 +79,9 @@ function resetStores() {
       remotes: ["origin"],
       head_hash: "abc",
       repo_state: "clean",
+      upstream: null,
+      ahead: null,
+      behind: null,
     },
   });
   useDialogStore.setState({ showConfirm: showConfirmMock });
@@ -134,6 +137,9 @@ describe("CleanupView", () => {
         remotes: [],
         head_hash: "abc",
         repo_state: "clean",
+        upstream: null,
+        ahead: null,
+        behind: null,
       },
     });
     render(<CleanupView />);
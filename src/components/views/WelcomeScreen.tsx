@@ -19,7 +19,7 @@ export function WelcomeScreen({ failedPath }: WelcomeScreenProps) {
     try {
       await openRepository(path.trim());
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   };
 
@@ -33,7 +33,7 @@ export function WelcomeScreen({ failedPath }: WelcomeScreenProps) {
         await openRepository(selected);
       } catch (err) {
         setPathInput(selected);
-        useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+        useNotificationStore.getState().showError(cleanErrorMessage(err));
       }
     }
   };
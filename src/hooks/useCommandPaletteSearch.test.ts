@@ -15,9 +15,11 @@ function makeCommit(overrides: Partial<GraphCommit> = {}): GraphCommit {
     timestamp: Date.now() / 1000,
     parent_hashes: [],
     column: 0,
+    branch_id: 0,
     lines: [],
     refs: [],
     is_tip: false,
+    overflow: false,
     ...overrides,
   };
 }
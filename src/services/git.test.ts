@@ -157,9 +157,17 @@ describe("git service", () => {
 
       const result = await git.getFileStatuses();
 
-      expect(invoke).toHaveBeenCalledWith("get_file_statuses");
+      expect(invoke).toHaveBeenCalledWith("get_file_statuses", { lineCounts: undefined });
       expect(result).toEqual(mockStatuses);
     });
+
+    it("passes lineCounts through", async () => {
+      vi.mocked(invoke).mockResolvedValue({ staged: [], unstaged: [], untracked: [] });
+
+      await git.getFileStatuses(true);
+
+      expect(invoke).toHaveBeenCalledWith("get_file_statuses", { lineCounts: true });
+    });
   });
 
   describe("stageFile", () => {
@@ -341,6 +349,15 @@ describe("git service", () => {
 
       expect(invoke).toHaveBeenCalledWith("revert_commit", { hash: "abc123" });
     });
+
+    it("passes commit through and returns the new hash", async () => {
+      vi.mocked(invoke).mockResolvedValue("def456");
+
+      const result = await git.revertCommit("abc123", true);
+
+      expect(invoke).toHaveBeenCalledWith("revert_commit", { hash: "abc123", commit: true });
+      expect(result).toBe("def456");
+    });
   });
 
   describe("revertCommitFile", () => {
@@ -1,14 +1,19 @@
 import { invoke } from "@tauri-apps/api/core";
 import { logDebug } from "../utils/logger";
+import { errorMessage } from "../utils/errorMessages";
 import type {
   RepositoryInfo,
+  RepoStats,
   GraphCommit,
   CommitDetails,
+  CommitFileChange,
   FileStatuses,
+  PathStatus,
   FileDiff,
   DiffHunk,
   BranchInfo,
   TagInfo,
+  RefInfo,
   StashInfo,
   StashDetails,
   BulkResult,
@@ -22,10 +27,18 @@ export async function openRepository(path: string): Promise<RepositoryInfo> {
   return invoke("open_repository", { path });
 }
 
+export async function initRepository(path: string, bare: boolean): Promise<RepositoryInfo> {
+  return invoke("init_repository", { path, bare });
+}
+
 export async function getRepositoryInfo(): Promise<RepositoryInfo> {
   return invoke("get_repository_info");
 }
 
+export async function getRepoStats(): Promise<RepoStats> {
+  return invoke("get_repo_stats");
+}
+
 export async function getAllCommitGraph(): Promise<GraphCommit[]> {
   return invoke("get_all_commit_graph");
 }
@@ -34,6 +47,18 @@ export async function getCommitDetails(hash: string): Promise<CommitDetails> {
   return invoke("get_commit_details", { hash });
 }
 
+export async function getBranchDiffFiles(base: string, head: string): Promise<CommitFileChange[]> {
+  return invoke("get_branch_diff_files", { base, head });
+}
+
+export async function refsAtCommit(hash: string): Promise<RefInfo[]> {
+  return invoke("refs_at_commit", { hash });
+}
+
+export async function refreshRefs(): Promise<void> {
+  return invoke("refresh_refs");
+}
+
 export async function listBranches(): Promise<BranchInfo[]> {
   return invoke("list_branches");
 }
@@ -42,12 +67,12 @@ export async function listTags(): Promise<TagInfo[]> {
   return invoke("list_tags");
 }
 
-export async function checkoutCommit(hash: string): Promise<void> {
-  return invoke("checkout_commit", { hash });
+export async function checkoutCommit(hash: string, force?: boolean): Promise<void> {
+  return invoke("checkout_commit", { hash, force });
 }
 
-export async function checkoutBranch(branchName: string): Promise<void> {
-  return invoke("checkout_branch", { branchName });
+export async function checkoutBranch(branchName: string, force?: boolean): Promise<void> {
+  return invoke("checkout_branch", { branchName, force });
 }
 
 export async function createBranch(name: string): Promise<void> {
@@ -61,7 +86,7 @@ export async function validateBranchName(
     await invoke("validate_branch_name", { name });
     return { ok: true };
   } catch (error) {
-    const reason = error instanceof Error ? error.message : String(error);
+    const reason = error instanceof Error ? error.message : errorMessage(error);
     // Validation failures are normal user input — log at debug, not error,
     // so they don't fill the log on every rejected keystroke.
     logDebug("yagg::fe::git", `validate_branch_name rejected: ${reason}`);
@@ -73,8 +98,13 @@ export async function getCommitFileDiff(hash: string, filePath: string): Promise
   return invoke("get_commit_file_diff", { hash, filePath });
 }
 
-export async function getFileStatuses(): Promise<FileStatuses> {
-  return invoke("get_file_statuses");
+export async function getCommitDiff(hash: string): Promise<FileDiff[]> {
+  return invoke("get_commit_diff", { hash });
+}
+
+/** `lineCounts` fills in each entry's additions/deletions at the cost of a full diff. */
+export async function getFileStatuses(lineCounts?: boolean): Promise<FileStatuses> {
+  return invoke("get_file_statuses", { lineCounts });
 }
 
 export async function stageFile(path: string): Promise<void> {
@@ -93,8 +123,18 @@ export async function unstageFiles(paths: string[]): Promise<void> {
   return invoke("unstage_files", { paths });
 }
 
-export async function stageHunk(path: string, hunkIndex: number): Promise<void> {
-  return invoke("stage_hunk", { path, hunkIndex });
+/** Stages new files only; returns how many were staged. */
+export async function stageUntracked(): Promise<number> {
+  return invoke("stage_untracked");
+}
+
+/** With `expectedHeader`, rejects with code "stale_hunk" if the hunk changed since it was shown. */
+export async function stageHunk(
+  path: string,
+  hunkIndex: number,
+  expectedHeader?: string
+): Promise<void> {
+  return invoke("stage_hunk", { path, hunkIndex, expectedHeader });
 }
 
 export async function unstageHunk(path: string, hunkIndex: number): Promise<void> {
@@ -109,6 +149,14 @@ export async function stageLines(
   return invoke("stage_lines", { path, hunkIndex, lineIndices });
 }
 
+/** Each selection is `[hunkIndex, lineIndices]`; all are staged against the same diff. */
+export async function stageSelection(
+  path: string,
+  selections: [number, number[]][]
+): Promise<void> {
+  return invoke("stage_selection", { path, selections });
+}
+
 export async function discardHunk(
   path: string,
   hunkIndex: number,
@@ -117,13 +165,22 @@ export async function discardHunk(
   return invoke("discard_hunk", { path, hunkIndex, lineIndices: lineIndices ?? null });
 }
 
+export async function applyPatch(patchText: string, toWorkingTree: boolean): Promise<void> {
+  return invoke("apply_patch", { patchText, toWorkingTree });
+}
+
 export async function getFileDiff(
   path: string,
   staged: boolean,
   isUntracked?: boolean,
-  isConflicted?: boolean
+  isConflicted?: boolean,
+  operationId?: string
 ): Promise<FileDiff> {
-  return invoke("get_file_diff", { path, staged, isUntracked, isConflicted });
+  return invoke("get_file_diff", { path, staged, isUntracked, isConflicted, operationId });
+}
+
+export async function getFilePatch(path: string, staged: boolean): Promise<string> {
+  return invoke("get_file_patch", { path, staged });
 }
 
 export async function createCommit(message: string): Promise<string> {
@@ -134,6 +191,10 @@ export async function revertFile(path: string): Promise<void> {
   return invoke("revert_file", { path });
 }
 
+export async function discardFile(path: string): Promise<PathStatus | null> {
+  return invoke("discard_file", { path });
+}
+
 export async function deleteFile(path: string): Promise<void> {
   return invoke("delete_file", { path });
 }
@@ -154,8 +215,14 @@ export async function continueOperation(): Promise<string> {
   return invoke("continue_operation");
 }
 
-export async function revertCommit(hash: string): Promise<void> {
-  return invoke("revert_commit", { hash });
+/** Abandons a diff or log load started with `operationId`; it rejects with code "cancelled". */
+export async function cancelOperation(id: string): Promise<boolean> {
+  return invoke("cancel_operation", { id });
+}
+
+/** With `commit`, a clean revert is committed and its hash returned. */
+export async function revertCommit(hash: string, commit?: boolean): Promise<string | null> {
+  return invoke("revert_commit", { hash, commit });
 }
 
 export async function revertCommitFile(hash: string, path: string): Promise<void> {
@@ -179,6 +246,16 @@ export async function deleteTag(tagName: string): Promise<void> {
   return invoke("delete_tag", { tagName });
 }
 
+/** Overwriting the tag requires `force`; without a message an annotated tag keeps its old one. */
+export async function moveTag(
+  name: string,
+  target: string,
+  force: boolean,
+  message?: string
+): Promise<TagInfo> {
+  return invoke("move_tag", { name, target, force, message });
+}
+
 export async function listStashes(): Promise<StashInfo[]> {
   return invoke("list_stashes");
 }
@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { check } from "@tauri-apps/plugin-updater";
 import { relaunch } from "@tauri-apps/plugin-process";
 import { logError } from "../utils/logger";
+import { errorMessage } from "../utils/errorMessages";
 
 export interface AppInfo {
   version: string;
@@ -37,7 +38,7 @@ export async function writeUpdateLog(message: string): Promise<void> {
   try {
     await invoke("write_update_log", { message });
   } catch (e) {
-    logError("yagg::fe::system", `write_update_log failed: ${String(e)}`);
+    logError("yagg::fe::system", `write_update_log failed: ${errorMessage(e)}`);
   }
 }
 
@@ -50,7 +51,7 @@ export async function checkForUpdate(): Promise<UpdateInfo> {
   try {
     appInfo = await getAppInfo();
   } catch (e) {
-    logError("yagg::fe::system", `get_app_info failed: ${String(e)}`);
+    logError("yagg::fe::system", `get_app_info failed: ${errorMessage(e)}`);
   }
   const infoStr = appInfo
     ? `current version: ${appInfo.version}, platform: ${appInfo.platform}, arch: ${appInfo.arch}`
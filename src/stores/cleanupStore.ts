@@ -112,7 +112,7 @@ export const useCleanupStore = create<CleanupState>((set, get) => ({
       set(
         (state) => ({ [category]: { ...state[category], loading: false } }) as Partial<CleanupState>
       );
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -231,7 +231,7 @@ export const useCleanupStore = create<CleanupState>((set, get) => ({
       set(
         (state) => ({ [category]: { ...state[category], loading: false } }) as Partial<CleanupState>
       );
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -252,7 +252,7 @@ export const useCleanupStore = create<CleanupState>((set, get) => ({
       // user sees the new state without a manual refresh click.
       await Promise.all([get().refreshCategory("gone"), get().refreshCategory("merged")]);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 }));
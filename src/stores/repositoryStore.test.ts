@@ -132,9 +132,11 @@ describe("repositoryStore", () => {
           timestamp: 1234567890,
           parent_hashes: [],
           column: 0,
+          branch_id: 0,
           lines: [],
           refs: [],
           is_tip: true,
+          overflow: false,
         },
       ]);
       vi.mocked(git.getFileStatuses).mockResolvedValue({
@@ -765,9 +767,11 @@ describe("repositoryStore", () => {
         timestamp: 1234567890 - i,
         parent_hashes: i < 499 ? [`hash${i + 1}`] : [],
         column: 0,
+        branch_id: 0,
         lines: [],
         refs: [],
         is_tip: i === 0,
+        overflow: false,
       }));
 
       vi.mocked(git.getAllCommitGraph).mockResolvedValue(mockCommits);
@@ -816,9 +820,11 @@ describe("repositoryStore", () => {
             timestamp: 1234567890,
             parent_hashes: [],
             column: 0,
+            branch_id: 0,
             lines: [],
             refs: [],
             is_tip: true,
+            overflow: false,
           },
         ],
         hasMoreCommits: false,
@@ -835,9 +841,11 @@ describe("repositoryStore", () => {
           timestamp: 1234567890,
           parent_hashes: ["new2"],
           column: 0,
+          branch_id: 0,
           lines: [],
           refs: [],
           is_tip: true,
+          overflow: false,
         },
         {
           hash: "new2",
@@ -848,9 +856,11 @@ describe("repositoryStore", () => {
           timestamp: 1234567880,
           parent_hashes: [],
           column: 0,
+          branch_id: 0,
           lines: [],
           refs: [],
           is_tip: false,
+          overflow: false,
         },
       ]);
 
@@ -1227,9 +1237,11 @@ describe("repositoryStore", () => {
             timestamp: 1234567890,
             parent_hashes: [],
             column: 0,
+            branch_id: 0,
             lines: [],
             refs: [],
             is_tip: true,
+            overflow: false,
           },
         ],
         hasMoreCommits: false,
@@ -1338,7 +1350,7 @@ describe("repositoryStore", () => {
       const { stageHunk } = useRepositoryStore.getState();
       await stageHunk("test.ts", 0);
 
-      expect(git.stageHunk).toHaveBeenCalledWith("test.ts", 0);
+      expect(git.stageHunk).toHaveBeenCalledWith("test.ts", 0, undefined);
       expect(git.getFileStatuses).toHaveBeenCalled();
       expect(git.getFileDiff).toHaveBeenCalledWith("test.ts", false, undefined, undefined);
     });
@@ -2160,6 +2172,9 @@ describe("repositoryStore", () => {
           remotes: [],
           head_hash: null,
           repo_state: "clean",
+          upstream: null,
+          ahead: null,
+          behind: null,
         },
         commits: [],
       });
@@ -2176,6 +2191,9 @@ describe("repositoryStore", () => {
           remotes: [],
           head_hash: "abc123",
           repo_state: "clean",
+          upstream: null,
+          ahead: null,
+          behind: null,
         },
         commits: [],
       });
@@ -2201,6 +2219,9 @@ describe("repositoryStore", () => {
           remotes: [],
           head_hash: null,
           repo_state: "clean",
+          upstream: null,
+          ahead: null,
+          behind: null,
         },
         commits: [],
       });
@@ -2407,6 +2428,9 @@ describe("repositoryStore", () => {
       remotes: [],
       head_hash: "abc123",
       repo_state: "rebase",
+      upstream: null,
+      ahead: null,
+      behind: null,
     };
 
     beforeEach(() => {
@@ -2476,6 +2500,9 @@ describe("repositoryStore", () => {
       remotes: [],
       head_hash: "abc123",
       repo_state: "cherry-pick",
+      upstream: null,
+      ahead: null,
+      behind: null,
     };
 
     beforeEach(() => {
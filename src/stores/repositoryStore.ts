@@ -89,7 +89,7 @@ interface RepositoryState {
   unstageFile: (path: string) => Promise<void>;
   stageFiles: (paths: string[]) => Promise<void>;
   unstageFiles: (paths: string[]) => Promise<void>;
-  stageHunk: (path: string, hunkIndex: number) => Promise<void>;
+  stageHunk: (path: string, hunkIndex: number, expectedHeader?: string) => Promise<void>;
   unstageHunk: (path: string, hunkIndex: number) => Promise<void>;
   stageLines: (path: string, hunkIndex: number, lineIndices: number[]) => Promise<void>;
   discardHunk: (path: string, hunkIndex: number) => Promise<void>;
@@ -165,7 +165,7 @@ async function withDiffRefresh(
       }
     }
   } catch (err) {
-    useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+    useNotificationStore.getState().showError(cleanErrorMessage(err));
   }
 }
 
@@ -226,7 +226,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       get().loadAllCommits();
       // Phase 3: loadBranchesAndTags fires via App.tsx useEffect on repositoryInfo
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
       set({
         isLoading: false,
         fileStatusesLoading: false,
@@ -252,7 +252,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
         await get().loadFileDiff(currentDiffPath, currentDiffStaged);
       }
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -271,7 +271,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       });
     } catch (err) {
       set({ commitsLoading: false });
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -282,7 +282,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       set({ fileStatuses: statuses, fileStatusesLoading: false });
     } catch (err) {
       set({ fileStatusesLoading: false });
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -309,7 +309,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       (diff) => set({ currentDiff: diff, diffLoading: false }),
       (err) => {
         set({ diffLoading: false });
-        useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+        useNotificationStore.getState().showError(cleanErrorMessage(err));
       }
     );
   },
@@ -321,7 +321,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       // Clear the conflict diff since the file is now resolved
       set({ currentDiff: null, currentDiffPath: null, currentDiffIsConflicted: false });
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -334,7 +334,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await get().refreshRepository();
       useNotificationStore.getState().showSuccess(`${stateLabel} aborted`);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -349,7 +349,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
         .getState()
         .showSuccess(`${stateLabel} continued (${newOid.slice(0, 7)})`);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -368,7 +368,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       newHunks[hunkIndex] = loadedHunk;
       set({ currentDiff: { ...currentDiff, hunks: newHunks } });
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -384,7 +384,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       newDiffs.set(filePath, { ...diff, hunks: newHunks });
       set({ commitFileDiffs: newDiffs });
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -425,8 +425,8 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
     await withDiffRefresh(() => git.unstageFiles(paths), get);
   },
 
-  stageHunk: async (path: string, hunkIndex: number) => {
-    await withDiffRefresh(() => git.stageHunk(path, hunkIndex), get, {
+  stageHunk: async (path: string, hunkIndex: number, expectedHeader?: string) => {
+    await withDiffRefresh(() => git.stageHunk(path, hunkIndex, expectedHeader), get, {
       path,
       staged: false,
     });
@@ -466,7 +466,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       get().clearDiff();
       await get().refreshRepository();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -475,7 +475,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await git.checkoutCommit(hash);
       await get().refreshRepository();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -485,7 +485,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await get().loadFileStatuses();
       get().clearDiffIfPathMatches(path);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -503,7 +503,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await get().loadFileStatuses();
       get().clearDiffIfPathMatches(path);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -531,7 +531,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
         set({ currentDiff: null, currentDiffPath: null });
       }
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -544,7 +544,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       (details) => set({ selectedCommitDetails: details, commitDetailsLoading: false }),
       (err) => {
         set({ commitDetailsLoading: false });
-        useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+        useNotificationStore.getState().showError(cleanErrorMessage(err));
       }
     );
   },
@@ -576,7 +576,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       newDiffs.set(filePath, diff);
       set({ commitFileDiffs: newDiffs });
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -586,7 +586,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await get().loadFileStatuses();
       useNotificationStore.getState().showSuccess(`Reverted commit ${hash.slice(0, 7)}`);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -596,7 +596,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await get().loadFileStatuses();
       useNotificationStore.getState().showSuccess(`Reverted ${path}`);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -613,7 +613,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await get().loadCommitFileDiff(hash, path);
       useNotificationStore.getState().showSuccess(`Reverted lines in ${path}`);
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -628,7 +628,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       set({ branches, tags, stashes, refsLoading: false });
     } catch (err) {
       set({ refsLoading: false });
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -637,7 +637,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await git.checkoutBranch(branchName);
       await get().refreshRepository();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -650,7 +650,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
     } catch (err) {
       useNotificationStore
         .getState()
-        .showError(`Failed to create branch: ${cleanErrorMessage(String(err))}`);
+        .showError(`Failed to create branch: ${cleanErrorMessage(err)}`);
     }
   },
 
@@ -662,7 +662,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       // it again here.
       await get().refreshRepository();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -671,7 +671,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await git.deleteTag(tagName);
       await get().refreshRepository();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -680,7 +680,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       const stashes = await git.listStashes();
       set({ stashes });
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -695,7 +695,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       set({ selectedStashDetails: details, stashDetailsLoading: false });
     } catch (err) {
       set({ stashDetailsLoading: false });
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -712,7 +712,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       await git.applyStash(index);
       await get().refreshRepository();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -731,7 +731,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
         });
       }
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -754,7 +754,7 @@ export const useRepositoryStore = create<RepositoryState>((set, get) => ({
       newDiffs.set(filePath, diff);
       set({ stashFileDiffs: newDiffs });
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 }));
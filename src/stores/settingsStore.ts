@@ -2,7 +2,7 @@ import { create } from "zustand";
 import { readSettings, writeSettings } from "../services/settings";
 import type { SettingsData } from "../services/settings";
 import { useNotificationStore } from "./notificationStore";
-import { cleanErrorMessage } from "../utils/errorMessages";
+import { cleanErrorMessage, errorMessage } from "../utils/errorMessages";
 import { setDebugLoggingEnabled as setDebugLoggingEnabledBackend } from "../services/logging";
 import { logError } from "../utils/logger";
 import { useSelectionStore } from "./selectionStore";
@@ -129,7 +129,7 @@ function persistDebounced(getState: () => SettingsState, immediate: boolean) {
       enabledTabs,
     };
     writeSettings(data).catch((err: unknown) => {
-      const raw = err instanceof Error ? err.message : String(err);
+      const raw = err instanceof Error ? err.message : err;
       notifyPersistError(cleanErrorMessage(raw));
     });
   }, delay);
@@ -172,7 +172,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
         loaded: true,
       });
     } catch (e) {
-      logError("yagg::fe::settings", `settings load failed: ${String(e)}`);
+      logError("yagg::fe::settings", `settings load failed: ${errorMessage(e)}`);
       // If reading fails, use defaults
       applyToDOM(DEFAULTS.density, DEFAULTS.textSize, DEFAULTS.theme);
       set({ ...DEFAULTS, loaded: true });
@@ -232,7 +232,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
     //      main.rs::read_debug_logging_from_disk.
     set({ debugLoggingEnabled });
     setDebugLoggingEnabledBackend(debugLoggingEnabled).catch((err: unknown) => {
-      logError("yagg::fe::settings", `set_debug_logging_enabled failed: ${String(err)}`);
+      logError("yagg::fe::settings", `set_debug_logging_enabled failed: ${errorMessage(err)}`);
     });
     persistDebounced(get, true);
   },
@@ -272,7 +272,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
       setDebugLoggingEnabledBackend(false).catch((err: unknown) => {
         logError(
           "yagg::fe::settings",
-          `set_debug_logging_enabled failed during reset: ${String(err)}`
+          `set_debug_logging_enabled failed during reset: ${errorMessage(err)}`
         );
       });
     }
@@ -293,7 +293,7 @@ export const useSettingsStore = create<SettingsState>((set, get) => ({
       enabledTabs: DEFAULTS.enabledTabs,
     };
     writeSettings(data).catch((err: unknown) => {
-      const raw = err instanceof Error ? err.message : String(err);
+      const raw = err instanceof Error ? err.message : err;
       notifyPersistError(cleanErrorMessage(raw));
     });
   },
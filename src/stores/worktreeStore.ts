@@ -47,7 +47,7 @@ export const useWorktreeStore = create<WorktreeState>((set, get) => ({
       set({ worktrees, loading: false });
     } catch (err) {
       set({ loading: false });
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -64,7 +64,7 @@ export const useWorktreeStore = create<WorktreeState>((set, get) => ({
       get().closeAddDialog();
       return true;
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
       return false;
     }
   },
@@ -86,7 +86,7 @@ export const useWorktreeStore = create<WorktreeState>((set, get) => ({
     } catch (err) {
       // If the user requested a non-force removal and the backend refused
       // because the tree is still valid, offer a force retry.
-      const msg = cleanErrorMessage(String(err));
+      const msg = cleanErrorMessage(err);
       if (!force && msg.includes("still valid")) {
         const forceConfirmed = await useDialogStore.getState().showConfirm({
           title: "Force remove?",
@@ -109,7 +109,7 @@ export const useWorktreeStore = create<WorktreeState>((set, get) => ({
       useNotificationStore.getState().showSuccess(`Moved worktree "${name}".`);
       await get().refresh();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -151,7 +151,7 @@ export const useWorktreeStore = create<WorktreeState>((set, get) => ({
       await worktreeService.lockWorktree(name, reason);
       await get().refresh();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 
@@ -160,7 +160,7 @@ export const useWorktreeStore = create<WorktreeState>((set, get) => ({
       await worktreeService.unlockWorktree(name);
       await get().refresh();
     } catch (err) {
-      useNotificationStore.getState().showError(cleanErrorMessage(String(err)));
+      useNotificationStore.getState().showError(cleanErrorMessage(err));
     }
   },
 }));
@@ -5,6 +5,17 @@ export interface RepositoryInfo {
   remotes: string[];
   head_hash: string | null;
   repo_state: string;
+  upstream: string | null;
+  ahead: number | null;
+  behind: number | null;
+}
+
+export interface RepoStats {
+  commit_count: number;
+  contributor_count: number;
+  branch_count: number;
+  tag_count: number;
+  file_count: number;
 }
 
 export interface CommitInfo {
@@ -13,22 +24,30 @@ export interface CommitInfo {
   message: string;
   author_name: string;
   author_email: string;
+  author_email_hash: string;
   timestamp: number;
+  author_time: number;
+  author_offset_minutes: number;
+  commit_time: number;
+  commit_offset_minutes: number;
   parent_hashes: string[];
 }
 
 export interface GraphCommit extends CommitInfo {
   column: number;
+  branch_id: number;
   lines: GraphLine[];
   refs: RefInfo[];
   is_tip: boolean;
+  overflow: boolean;
 }
 
 export interface GraphLine {
   from_column: number;
   to_column: number;
   is_merge: boolean;
-  line_type: "to_parent" | "from_above" | "pass_through";
+  line_type: "to_parent" | "from_above" | "pass_through" | "to_boundary";
+  branch_id: number;
 }
 
 export interface RefInfo {
@@ -51,7 +70,12 @@ export interface CommitDetails {
   committer_name: string;
   committer_email: string;
   timestamp: number;
+  author_time: number;
+  author_offset_minutes: number;
+  commit_time: number;
+  commit_offset_minutes: number;
   parent_hashes: string[];
+  parent_subjects: string[];
   files_changed: CommitFileChange[];
 }
 
@@ -59,6 +83,14 @@ export interface FileStatus {
   path: string;
   status: FileStatusType;
   is_staged: boolean;
+  additions?: number | null;
+  deletions?: number | null;
+}
+
+export interface PathStatus {
+  path: string;
+  staged: FileStatusType | null;
+  unstaged: FileStatusType | null;
 }
 
 export type FileStatusType =
@@ -82,6 +114,18 @@ export interface FileDiff {
   is_binary: boolean;
   total_lines: number;
   is_conflicted?: boolean;
+  is_lfs_pointer?: boolean;
+  old_lfs?: LfsPointer | null;
+  new_lfs?: LfsPointer | null;
+  detected_encoding?: string | null;
+  line_ending?: "lf" | "crlf" | "mixed";
+  is_non_utf8?: boolean;
+  old_path?: string | null;
+}
+
+export interface LfsPointer {
+  oid: string;
+  size: number;
 }
 
 export interface DiffHunk {
@@ -129,7 +173,8 @@ export interface TagInfo {
   is_annotated: boolean;
   message?: string | null;
   tagger_name?: string | null;
-  tagger_time?: number | null;
+  tagger_email?: string | null;
+  tagged_time?: number | null;
   last_commit_summary?: string | null;
 }
 
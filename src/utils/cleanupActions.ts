@@ -121,7 +121,7 @@ export async function runQuickCleanup<T>(spec: QuickCleanupSpec<T>): Promise<voi
     }
     await refresh();
   } catch (err) {
-    notif.showError(cleanErrorMessage(String(err)));
+    notif.showError(cleanErrorMessage(err));
   } finally {
     setRunning(false);
   }
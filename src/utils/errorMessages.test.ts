@@ -0,0 +1,40 @@
+import { describe, it, expect } from "vitest";
+import { cleanErrorMessage, errorCode, errorMessage } from "./errorMessages";
+
+describe("errorMessage", () => {
+  it("returns the message of a backend error", () => {
+    const err = { code: "no_workdir", message: "Repository has no working directory" };
+    expect(errorMessage(err)).toBe("Repository has no working directory");
+  });
+
+  it("stringifies plain strings and Errors as before", () => {
+    expect(errorMessage("boom")).toBe("boom");
+    expect(errorMessage(new Error("boom"))).toBe("Error: boom");
+  });
+});
+
+describe("errorCode", () => {
+  it("returns the code of a backend error", () => {
+    expect(errorCode({ code: "hunk_out_of_range", message: "Hunk index 5 out of range" })).toBe(
+      "hunk_out_of_range"
+    );
+  });
+
+  it("returns undefined for anything else", () => {
+    expect(errorCode("boom")).toBeUndefined();
+    expect(errorCode(new Error("boom"))).toBeUndefined();
+    expect(errorCode(null)).toBeUndefined();
+  });
+});
+
+describe("cleanErrorMessage with backend errors", () => {
+  it("cleans the message field", () => {
+    expect(
+      cleanErrorMessage({
+        code: "git_not_found",
+        message:
+          "Git error: could not find repository from '/tmp/test'; class=Repository (6); code=NotFound (-3)",
+      })
+    ).toBe("No git repository found at\n/tmp/test");
+  });
+});
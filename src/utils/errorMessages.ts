@@ -7,8 +7,8 @@
  * frontend log site in `notificationStore.showError`; logging here would
  * double-log every notification path (raw + cleaned).
  */
-export function cleanErrorMessage(raw: string): string {
-  let msg = raw;
+export function cleanErrorMessage(raw: unknown): string {
+  let msg = errorMessage(raw);
 
   // Strip "Git error: " prefix
   if (msg.startsWith("Git error: ")) {
@@ -26,3 +26,32 @@ export function cleanErrorMessage(raw: string): string {
 
   return msg.trim();
 }
+
+/** Shape of a rejected `invoke()` when the backend returns an `AppError`. */
+export interface BackendError {
+  code: string;
+  message: string;
+}
+
+function isBackendError(err: unknown): err is BackendError {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    typeof (err as BackendError).code === "string" &&
+    typeof (err as BackendError).message === "string"
+  );
+}
+
+/**
+ * Text of a caught error. Backend errors arrive as `{ code, message }`, so
+ * `String(err)` would give "[object Object]"; anything else is stringified
+ * as before.
+ */
+export function errorMessage(err: unknown): string {
+  return isBackendError(err) ? err.message : String(err);
+}
+
+/** Machine-readable backend error code (e.g. `"hunk_out_of_range"`), if any. */
+export function errorCode(err: unknown): string | undefined {
+  return isBackendError(err) ? err.code : undefined;
+}